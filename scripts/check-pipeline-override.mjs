@@ -0,0 +1,54 @@
+/**
+ * Pipeline Override 一致性检查
+ * 用 tests/fixtures/pipeline-override.json 中的用例运行前端的 generateTaskPipelineOverride，
+ * 后端无界面模式（src-tauri/src/headless/pipeline_override.rs）的单元测试运行同一份用例，
+ * 两边任意一处修改导致结果不一致时都会失败
+ *
+ * 用法：pnpm test:pipeline-override
+ */
+
+import { readFileSync } from 'node:fs';
+import { fileURLToPath } from 'node:url';
+import { isDeepStrictEqual } from 'node:util';
+import { createServer } from 'vite';
+
+const root = fileURLToPath(new URL('..', import.meta.url));
+const fixture = JSON.parse(
+  readFileSync(new URL('../tests/fixtures/pipeline-override.json', import.meta.url), 'utf-8'),
+);
+
+// 不加载项目的 vite.config.ts（React / Tailwind 插件与此无关），只保留路径别名
+const server = await createServer({
+  root,
+  configFile: false,
+  logLevel: 'error',
+  appType: 'custom',
+  resolve: { alias: { '@': `${root}src` } },
+  server: { middlewareMode: true, hmr: false },
+  optimizeDeps: { noDiscovery: true },
+});
+
+let failed = 0;
+try {
+  const { generateTaskPipelineOverride } = await server.ssrLoadModule(
+    '/src/utils/pipelineOverride.ts',
+  );
+  for (const { name, task, controller, resource, expected } of fixture.cases) {
+    const actual = JSON.parse(
+      generateTaskPipelineOverride(task, fixture.interface, controller, resource),
+    );
+    if (isDeepStrictEqual(actual, expected)) {
+      console.log(`ok   ${name}`);
+    } else {
+      failed++;
+      console.error(`FAIL ${name}`);
+      console.error(`  expected: ${JSON.stringify(expected)}`);
+      console.error(`  actual:   ${JSON.stringify(actual)}`);
+    }
+  }
+} finally {
+  await server.close();
+}
+
+console.log(`${fixture.cases.length - failed}/${fixture.cases.length} passed`);
+process.exitCode = failed > 0 ? 1 : 0;
@@ -0,0 +1,301 @@
+//! mxu-cli：无界面运行 MXU 实例
+//!
+//! 读取 exe 目录下的 interface.json 与 config/mxu-{项目名}.json，
+//! 连接实例保存的控制器、加载资源并依次执行启用的任务，回调事件输出到标准输出
+//!
+//! 退出码：0 全部任务成功；1 存在失败的任务；2 配置错误或连接/资源加载失败
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use mxu_lib::commands::event_bus::{
+    BusEvent, EventSink, FileRecorderSink, AGENT_OUTPUT_EVENT, MAA_CALLBACK_EVENT,
+};
+use mxu_lib::commands::maa_core::{destroy_instance, init_maa_library};
+use mxu_lib::commands::types::MaaState;
+use mxu_lib::commands::utils::{get_app_data_dir, get_exe_directory};
+use mxu_lib::headless::config::{config_file_path, load_config};
+use mxu_lib::headless::interface::load_interface;
+use mxu_lib::headless::runner::run_instance;
+use mxu_lib::history::stats::{self, RunStats};
+use mxu_lib::history::store::{self, RunQuery};
+
+const USAGE: &str = "\
+用法: mxu-cli [选项] [实例]
+
+参数:
+  [实例]                   要运行的实例 ID 或名称（默认：最后激活的实例）
+
+选项:
+  --interface <路径>       interface.json 路径（默认：exe 目录下的 interface.json）
+  --config <路径>          配置文件路径（默认：数据目录/config/mxu-{项目名}.json）
+  --maafw <目录>           MaaFramework 库目录（默认：exe 目录下的 maafw）
+  --record <路径>          同时将回调事件以 JSON Lines 格式记录到文件
+  --image-folder <目录>    用图片目录代替实例的控制器试运行（输入只记录日志，不实际执行）
+  --list                   列出配置文件中的实例
+  --stats                  输出运行历史统计（指定 [实例] 时只统计该实例）
+  --days <天数>            只统计最近若干天的运行（默认：全部）
+  --by-resource            按资源哈希拆分任务统计（对比资源更新前后的表现）
+  -v, --verbose            输出调试日志
+  -h, --help               显示帮助";
+
+/// 命令行参数
+#[derive(Default)]
+struct CliArgs {
+    instance: Option<String>,
+    interface: Option<PathBuf>,
+    config: Option<PathBuf>,
+    maafw: Option<String>,
+    record: Option<PathBuf>,
+    image_folder: Option<PathBuf>,
+    list: bool,
+    stats: bool,
+    days: Option<u32>,
+    by_resource: bool,
+    verbose: bool,
+}
+
+fn parse_args() -> Result<Option<CliArgs>, String> {
+    let mut args = CliArgs::default();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().ok_or(format!("{} 缺少参数值", name));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--interface" => args.interface = Some(PathBuf::from(value("--interface")?)),
+            "--config" => args.config = Some(PathBuf::from(value("--config")?)),
+            "--maafw" => args.maafw = Some(value("--maafw")?),
+            "--record" => args.record = Some(PathBuf::from(value("--record")?)),
+            "--image-folder" => {
+                args.image_folder = Some(PathBuf::from(value("--image-folder")?));
+            }
+            "--list" => args.list = true,
+            "--stats" => args.stats = true,
+            "--days" => {
+                let days = value("--days")?;
+                args.days = Some(days.parse().map_err(|_| format!("无效的天数: {}", days))?);
+            }
+            "--by-resource" => args.by_resource = true,
+            "-v" | "--verbose" => args.verbose = true,
+            s if s.starts_with('-') => return Err(format!("未知选项: {}", s)),
+            _ if args.instance.is_none() => args.instance = Some(arg),
+            _ => return Err(format!("多余的参数: {}", arg)),
+        }
+    }
+
+    Ok(Some(args))
+}
+
+/// 将回调事件输出到标准输出
+struct StdoutSink;
+
+impl EventSink for StdoutSink {
+    fn handle(&self, event: &BusEvent) {
+        let field = |key: &str| event.payload[key].as_str().unwrap_or_default().to_string();
+        match event.name.as_str() {
+            MAA_CALLBACK_EVENT => println!("[{}] {}", field("message"), field("details")),
+            AGENT_OUTPUT_EVENT => println!("[agent:{}] {}", field("stream"), field("line")),
+            _ => println!("[{}] {}", event.name, event.payload),
+        }
+    }
+}
+
+/// 输出到标准错误的简单日志实现（级别由 log::max_level 控制）
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "{} [{}] {}",
+                chrono::Local::now().format("%H:%M:%S%.3f"),
+                record.level(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let level = if args.verbose {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    };
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+
+    match run(args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            log::error!("{}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// 执行实例，返回是否所有任务都成功
+fn run(args: CliArgs) -> Result<bool, String> {
+    let interface_path = match args.interface {
+        Some(path) => path,
+        None => get_exe_directory()?.join("interface.json"),
+    };
+    let mut pi = load_interface(&interface_path)?;
+    let base_path = interface_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let config_path = match args.config {
+        Some(path) => path,
+        None => config_file_path(&get_app_data_dir()?, &pi.name),
+    };
+    let config = load_config(&config_path)?;
+
+    if args.list {
+        for instance in &config.instances {
+            let enabled = instance.tasks.iter().filter(|t| t.enabled).count();
+            println!(
+                "{}\t{}\t{} 个启用的任务",
+                instance.id, instance.name, enabled
+            );
+        }
+        return Ok(true);
+    }
+
+    if args.stats {
+        // 实例已从配置中删除时按 ID 统计
+        let instance_id = args.instance.as_deref().map(|key| {
+            config
+                .find_instance(Some(key))
+                .map_or(key.to_string(), |i| i.id.clone())
+        });
+        let since = args
+            .days
+            .map(|days| chrono::Local::now().timestamp_millis() - i64::from(days) * 86_400_000);
+        let query = RunQuery {
+            instance_id,
+            since,
+            ..Default::default()
+        };
+        let records = store::query(&get_app_data_dir()?.join("history"), &query)?;
+        print_stats(&stats::compute(&records, args.by_resource));
+        return Ok(true);
+    }
+
+    let instance = config
+        .find_instance(args.instance.as_deref())
+        .ok_or_else(|| match &args.instance {
+            Some(key) => format!("未找到实例: {}", key),
+            None => "配置文件中没有实例".to_string(),
+        })?;
+    log::info!("Running instance: {} ({})", instance.name, instance.id);
+
+    // 控制器名称保持不变，控制器相关的选项仍然生效
+    let mut instance = instance.clone();
+    if let Some(dir) = &args.image_folder {
+        let controller = match &instance.controller_name {
+            Some(name) => pi.controller.iter_mut().find(|c| &c.name == name),
+            None => pi.controller.first_mut(),
+        }
+        .ok_or("未找到控制器定义")?;
+        controller.controller_type = "ImageFolder".to_string();
+        instance
+            .saved_device
+            .get_or_insert_with(Default::default)
+            .image_folder_path = Some(dir.to_string_lossy().to_string());
+        log::info!("Using image folder: {}", dir.display());
+    }
+
+    let state = Arc::new(MaaState::default());
+    let version = init_maa_library(&state, args.maafw)?;
+    log::info!("MaaFramework {}", version);
+
+    state.history.set_dir(get_app_data_dir()?.join("history"));
+    state.event_bus.add_sink(Arc::new(StdoutSink));
+    if let Some(path) = &args.record {
+        state
+            .event_bus
+            .add_sink(Arc::new(FileRecorderSink::new(path)?));
+    }
+
+    let result = tauri::async_runtime::block_on(run_instance(
+        &state,
+        &pi,
+        &base_path,
+        &instance,
+        &config.settings,
+    ));
+    let _ = destroy_instance(&state, &instance.id);
+    let summary = result?;
+
+    for task in &summary.tasks {
+        log::info!("{}: {:?}", task.name, task.status);
+    }
+    Ok(summary.all_succeeded())
+}
+
+/// 输出运行历史统计
+fn print_stats(stats: &RunStats) {
+    println!("共 {} 次运行", stats.total_runs);
+    if stats.total_runs == 0 {
+        return;
+    }
+
+    let seconds =
+        |ms: Option<i64>| ms.map_or("-".to_string(), |ms| format!("{:.1}s", ms as f64 / 1000.0));
+    println!("\n任务\t成功率\t成功/结束\t中位耗时\tP95 耗时\t资源");
+    for entry in &stats.entries {
+        let rate = entry
+            .success_rate
+            .map_or("-".to_string(), |r| format!("{:.1}%", r * 100.0));
+        println!(
+            "{}\t{}\t{}/{}\t{}\t{}\t{}",
+            entry.entry,
+            rate,
+            entry.succeeded,
+            entry.finished,
+            seconds(entry.median_duration_ms),
+            seconds(entry.p95_duration_ms),
+            entry.resource_hash.as_deref().unwrap_or("-")
+        );
+    }
+
+    if !stats.failing_nodes.is_empty() {
+        println!("\n常见失败节点:");
+        for node in &stats.failing_nodes {
+            println!("{}\t{}\t{} 次", node.entry, node.node, node.count);
+        }
+    }
+
+    println!("\n每日运行:");
+    for day in &stats.daily {
+        println!(
+            "{}\t{}\t{} 次（失败 {} 次）",
+            day.date, day.instance_id, day.runs, day.failed_runs
+        );
+    }
+}
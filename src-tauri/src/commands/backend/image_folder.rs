@@ -0,0 +1,196 @@
+//! 图片目录调试控制器
+//!
+//! 以 MaaFramework 自定义控制器实现：每次截图依次返回目录中的下一张图片（按文件名排序，循环播放），
+//! 点击、滑动、按键等输入只写入日志而不实际执行。无需真实设备即可在任意机器上试运行 Pipeline。
+//! 路径也可以是会话录制生成的会话包（zip），此时按录制顺序回放其中的画面
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use log::{debug, info, warn};
+use maa_framework::common::ControllerFeature;
+use maa_framework::custom_controller::CustomControllerCallback;
+
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::recorder::bundle;
+
+/// 支持的图片扩展名（由 MaaFramework 解码）
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp"];
+
+/// 画面帧
+enum Frame {
+    /// 目录中的图片（截图时读取）
+    File(PathBuf),
+    /// 会话包中的图片（打开时读入内存）
+    Data(Vec<u8>),
+}
+
+/// 图片目录控制器
+pub struct ImageFolderController {
+    dir: PathBuf,
+    frames: Vec<Frame>,
+    /// 下一次截图返回的帧序号
+    next: AtomicUsize,
+}
+
+impl ImageFolderController {
+    /// 读取目录或会话包中的图片（无法读取或没有图片时返回错误）
+    pub fn open(dir: &Path) -> MxuResult<Self> {
+        let frames = if bundle::is_bundle(dir) {
+            bundle::read_frames(dir)?
+                .into_iter()
+                .map(Frame::Data)
+                .collect()
+        } else {
+            read_dir_frames(dir)?
+        };
+        if frames.is_empty() {
+            return Err(MxuError::new(
+                ErrorCode::NotFound,
+                Subsystem::Controller,
+                "No images found in image folder",
+            )
+            .with_path(dir));
+        }
+
+        info!(
+            "[ImageFolder] Loaded {} frame(s) from {}",
+            frames.len(),
+            dir.display()
+        );
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            frames,
+            next: AtomicUsize::new(0),
+        })
+    }
+}
+
+/// 读取目录中的图片（按文件名排序）
+fn read_dir_frames(dir: &Path) -> MxuResult<Vec<Frame>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        MxuError::new(
+            ErrorCode::InvalidPath,
+            Subsystem::Controller,
+            format!("Failed to read image folder: {}", e),
+        )
+        .with_path(dir)
+    })?;
+
+    let mut frames: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_image(path))
+        .collect();
+    frames.sort();
+    Ok(frames.into_iter().map(Frame::File).collect())
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+impl CustomControllerCallback for ImageFolderController {
+    fn connect(&self) -> bool {
+        true
+    }
+
+    fn request_uuid(&self) -> Option<String> {
+        Some(format!("image-folder:{}", self.dir.display()))
+    }
+
+    fn get_features(&self) -> ControllerFeature {
+        ControllerFeature::empty()
+    }
+
+    fn start_app(&self, intent: &str) -> bool {
+        info!("[ImageFolder] start_app {}", intent);
+        true
+    }
+
+    fn stop_app(&self, intent: &str) -> bool {
+        info!("[ImageFolder] stop_app {}", intent);
+        true
+    }
+
+    fn screencap(&self) -> Option<Vec<u8>> {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.frames.len();
+        match &self.frames[index] {
+            Frame::File(path) => match std::fs::read(path) {
+                Ok(data) => {
+                    debug!("[ImageFolder] Frame #{}: {}", index, path.display());
+                    Some(data)
+                }
+                Err(e) => {
+                    warn!("[ImageFolder] Failed to read {}: {}", path.display(), e);
+                    None
+                }
+            },
+            Frame::Data(data) => {
+                debug!("[ImageFolder] Frame #{}", index);
+                Some(data.clone())
+            }
+        }
+    }
+
+    fn click(&self, x: i32, y: i32) -> bool {
+        info!("[ImageFolder] click ({}, {})", x, y);
+        true
+    }
+
+    fn swipe(&self, x1: i32, y1: i32, x2: i32, y2: i32, duration: i32) -> bool {
+        info!(
+            "[ImageFolder] swipe ({}, {}) -> ({}, {}) in {}ms",
+            x1, y1, x2, y2, duration
+        );
+        true
+    }
+
+    fn touch_down(&self, contact: i32, x: i32, y: i32, pressure: i32) -> bool {
+        info!(
+            "[ImageFolder] touch_down #{} ({}, {}) pressure {}",
+            contact, x, y, pressure
+        );
+        true
+    }
+
+    fn touch_move(&self, contact: i32, x: i32, y: i32, pressure: i32) -> bool {
+        debug!(
+            "[ImageFolder] touch_move #{} ({}, {}) pressure {}",
+            contact, x, y, pressure
+        );
+        true
+    }
+
+    fn touch_up(&self, contact: i32) -> bool {
+        info!("[ImageFolder] touch_up #{}", contact);
+        true
+    }
+
+    fn click_key(&self, keycode: i32) -> bool {
+        info!("[ImageFolder] click_key {}", keycode);
+        true
+    }
+
+    fn input_text(&self, text: &str) -> bool {
+        info!("[ImageFolder] input_text {:?}", text);
+        true
+    }
+
+    fn key_down(&self, keycode: i32) -> bool {
+        info!("[ImageFolder] key_down {}", keycode);
+        true
+    }
+
+    fn key_up(&self, keycode: i32) -> bool {
+        info!("[ImageFolder] key_up {}", keycode);
+        true
+    }
+
+    fn scroll(&self, dx: i32, dy: i32) -> bool {
+        info!("[ImageFolder] scroll ({}, {})", dx, dy);
+        true
+    }
+}
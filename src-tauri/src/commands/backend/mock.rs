@@ -0,0 +1,904 @@
+//! 脚本化 mock 后端
+//!
+//! 在进程内模拟 MaaFramework 的异步行为：请求立即返回 ID，由后台线程推进状态并发送
+//! 与 MaaFramework 同名的回调消息（Resource.Loading.* / Controller.Action.* / Tasker.Task.* /
+//! Node.*），连接结果、任务耗时与结果、截图内容均由 `MockScript` 配置。每个任务执行一个与入口
+//! 同名的节点（DirectHit 识别 + DoNothing 动作，动作结果即任务结果）
+
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use maa_framework::MaaStatus;
+use serde_json::json;
+
+use super::{
+    ControllerBackend, MaaBackend, MaaCallback, RecognitionImages, ResourceBackend, TaskerBackend,
+};
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::types::{
+    ActionDetail, ControllerConfig, ControllerInput, NodeDetail, RecognitionDetail,
+    ScreenshotResolution, TaskDetail, TaskStatus,
+};
+
+/// 任务停止检查间隔
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// mock 行为配置
+#[derive(Debug, Clone)]
+pub struct MockScript {
+    /// 连接是否成功
+    pub connect_succeeds: bool,
+    /// 连接耗时
+    pub connect_delay: Duration,
+    /// 资源加载是否成功
+    pub resource_load_succeeds: bool,
+    /// 资源加载耗时
+    pub resource_load_delay: Duration,
+    /// 单个任务的默认耗时
+    pub task_duration: Duration,
+    /// 按 entry 指定任务耗时
+    pub task_durations: HashMap<String, Duration>,
+    /// 未在 task_outcomes 中指定的任务是否成功
+    pub default_task_succeeds: bool,
+    /// 按 entry 指定任务结果（true 成功，false 失败）
+    pub task_outcomes: HashMap<String, bool>,
+    /// 截图返回的 PNG 数据（None 时截图失败）
+    pub screenshot: Option<Vec<u8>>,
+    /// 资源中的节点名（None 时不提供节点列表，跳过 Pipeline 覆盖的节点存在性检查）
+    pub nodes: Option<Vec<String>>,
+}
+
+impl Default for MockScript {
+    fn default() -> Self {
+        Self {
+            connect_succeeds: true,
+            connect_delay: Duration::ZERO,
+            resource_load_succeeds: true,
+            resource_load_delay: Duration::ZERO,
+            task_duration: Duration::from_millis(100),
+            task_durations: HashMap::new(),
+            default_task_succeeds: true,
+            task_outcomes: HashMap::new(),
+            screenshot: None,
+            nodes: None,
+        }
+    }
+}
+
+impl MockScript {
+    fn task_succeeds(&self, entry: &str) -> bool {
+        self.task_outcomes
+            .get(entry)
+            .copied()
+            .unwrap_or(self.default_task_succeeds)
+    }
+
+    fn task_duration(&self, entry: &str) -> Duration {
+        self.task_durations
+            .get(entry)
+            .copied()
+            .unwrap_or(self.task_duration)
+    }
+}
+
+/// mock 后端
+pub struct MockBackend {
+    script: Arc<Mutex<MockScript>>,
+    /// 所有请求共享的 ID 计数器（与 MaaFramework 一致，ID 全局唯一）
+    next_id: Arc<AtomicI64>,
+    /// 已创建控制器的连接状态（用于模拟设备掉线）
+    connections: Mutex<Vec<Weak<AtomicBool>>>,
+}
+
+impl MockBackend {
+    pub fn new(script: MockScript) -> Self {
+        Self {
+            script: Arc::new(Mutex::new(script)),
+            next_id: Arc::new(AtomicI64::new(1)),
+            connections: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 模拟设备掉线（如模拟器重启）：已创建的控制器全部变为未连接
+    pub fn drop_connections(&self) {
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.retain(|c| match c.upgrade() {
+                Some(connected) => {
+                    connected.store(false, Ordering::SeqCst);
+                    true
+                }
+                None => false,
+            });
+        }
+    }
+
+    /// 修改脚本（对之后发起的请求生效，已创建的对象同样适用）
+    pub fn update_script(&self, f: impl FnOnce(&mut MockScript)) {
+        if let Ok(mut script) = self.script.lock() {
+            f(&mut script);
+        }
+    }
+
+    fn shared(&self, callback: MaaCallback) -> Shared {
+        Shared {
+            script: self.script.clone(),
+            next_id: self.next_id.clone(),
+            jobs: Arc::new(JobTable::default()),
+            callback,
+        }
+    }
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new(MockScript::default())
+    }
+}
+
+impl MaaBackend for MockBackend {
+    fn name(&self) -> &'static str {
+        "Mock"
+    }
+
+    fn create_resource(&self, callback: MaaCallback) -> MxuResult<Arc<dyn ResourceBackend>> {
+        Ok(Arc::new(MockResource {
+            shared: self.shared(callback),
+            loaded: Arc::new(AtomicBool::new(false)),
+        }))
+    }
+
+    fn create_controller(
+        &self,
+        _config: &ControllerConfig,
+        callback: MaaCallback,
+    ) -> MxuResult<Arc<dyn ControllerBackend>> {
+        let connected = Arc::new(AtomicBool::new(false));
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.push(Arc::downgrade(&connected));
+        }
+        Ok(Arc::new(MockController {
+            shared: self.shared(callback),
+            connected,
+            image: Arc::new(Mutex::new(None)),
+        }))
+    }
+
+    fn create_tasker(
+        &self,
+        resource: &Arc<dyn ResourceBackend>,
+        controller: &Arc<dyn ControllerBackend>,
+        callback: MaaCallback,
+    ) -> MxuResult<Arc<dyn TaskerBackend>> {
+        Ok(Arc::new(MockTasker {
+            inner: Arc::new(TaskerInner {
+                shared: self.shared(callback),
+                resource: resource.clone(),
+                controller: controller.clone(),
+                queue: Mutex::new(TaskQueue::default()),
+                details: Mutex::new(HashMap::new()),
+                stopping: AtomicBool::new(false),
+            }),
+        }))
+    }
+}
+
+// ============================================================================
+// 公共状态
+// ============================================================================
+
+/// 请求状态表（支持阻塞等待）
+#[derive(Default)]
+struct JobTable {
+    jobs: Mutex<HashMap<i64, MaaStatus>>,
+    changed: Condvar,
+}
+
+impl JobTable {
+    fn set(&self, id: i64, status: MaaStatus) {
+        if let Ok(mut jobs) = self.jobs.lock() {
+            jobs.insert(id, status);
+        }
+        self.changed.notify_all();
+    }
+
+    fn status(&self, id: i64) -> MaaStatus {
+        self.jobs
+            .lock()
+            .ok()
+            .and_then(|jobs| jobs.get(&id).copied())
+            .unwrap_or(MaaStatus::INVALID)
+    }
+
+    fn wait(&self, id: i64) -> MaaStatus {
+        let Ok(mut jobs) = self.jobs.lock() else {
+            return MaaStatus::INVALID;
+        };
+        loop {
+            match jobs.get(&id).copied() {
+                Some(status) if status.pending() || status.running() => {}
+                Some(status) => return status,
+                None => return MaaStatus::INVALID,
+            }
+            jobs = match self.changed.wait(jobs) {
+                Ok(jobs) => jobs,
+                Err(_) => return MaaStatus::INVALID,
+            };
+        }
+    }
+}
+
+/// 各 mock 对象共享的上下文
+#[derive(Clone)]
+struct Shared {
+    script: Arc<Mutex<MockScript>>,
+    next_id: Arc<AtomicI64>,
+    jobs: Arc<JobTable>,
+    callback: MaaCallback,
+}
+
+impl Shared {
+    fn script(&self) -> MockScript {
+        self.script.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    fn next_id(&self) -> i64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn emit(&self, message: &str, details: serde_json::Value) {
+        (self.callback)(message, &details.to_string());
+    }
+
+    /// 在后台线程中模拟异步请求：Starting → 等待 delay → Succeeded/Failed
+    fn run_job(
+        &self,
+        id: i64,
+        prefix: &'static str,
+        details: serde_json::Value,
+        delay: Duration,
+        succeeds: bool,
+        on_done: impl FnOnce(bool) + Send + 'static,
+    ) {
+        self.jobs.set(id, MaaStatus::PENDING);
+        let shared = self.clone();
+        thread::spawn(move || {
+            shared.jobs.set(id, MaaStatus::RUNNING);
+            shared.emit(&format!("{}.Starting", prefix), details.clone());
+            thread::sleep(delay);
+            on_done(succeeds);
+            if succeeds {
+                shared.jobs.set(id, MaaStatus::SUCCEEDED);
+                shared.emit(&format!("{}.Succeeded", prefix), details);
+            } else {
+                shared.jobs.set(id, MaaStatus::FAILED);
+                shared.emit(&format!("{}.Failed", prefix), details);
+            }
+        });
+    }
+}
+
+// ============================================================================
+// Resource
+// ============================================================================
+
+struct MockResource {
+    shared: Shared,
+    loaded: Arc<AtomicBool>,
+}
+
+impl ResourceBackend for MockResource {
+    fn post_bundle(&self, path: &str) -> MxuResult<i64> {
+        let script = self.shared.script();
+        let id = self.shared.next_id();
+        let loaded = self.loaded.clone();
+        self.shared.run_job(
+            id,
+            "Resource.Loading",
+            json!({ "res_id": id, "path": path, "hash": "" }),
+            script.resource_load_delay,
+            script.resource_load_succeeds,
+            move |succeeded| {
+                if succeeded {
+                    loaded.store(true, Ordering::SeqCst);
+                }
+            },
+        );
+        Ok(id)
+    }
+
+    fn status(&self, id: i64) -> MaaStatus {
+        self.shared.jobs.status(id)
+    }
+
+    fn wait(&self, id: i64) -> MaaStatus {
+        self.shared.jobs.wait(id)
+    }
+
+    fn loaded(&self) -> bool {
+        self.loaded.load(Ordering::SeqCst)
+    }
+
+    fn node_list(&self) -> MxuResult<Option<Vec<String>>> {
+        Ok(self.shared.script().nodes)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// Controller
+// ============================================================================
+
+struct MockController {
+    shared: Shared,
+    connected: Arc<AtomicBool>,
+    image: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+impl ControllerBackend for MockController {
+    fn post_connection(&self) -> MxuResult<i64> {
+        let script = self.shared.script();
+        let id = self.shared.next_id();
+        let connected = self.connected.clone();
+        self.shared.run_job(
+            id,
+            "Controller.Action",
+            json!({ "ctrl_id": id, "uuid": "mock", "action": "connect", "param": {} }),
+            script.connect_delay,
+            script.connect_succeeds,
+            move |succeeded| connected.store(succeeded, Ordering::SeqCst),
+        );
+        Ok(id)
+    }
+
+    fn post_screencap(&self) -> MxuResult<i64> {
+        let script = self.shared.script();
+        let id = self.shared.next_id();
+        let image = self.image.clone();
+        let screenshot = script.screenshot.filter(|_| self.connected());
+        let succeeds = screenshot.is_some();
+        self.shared.run_job(
+            id,
+            "Controller.Action",
+            json!({ "ctrl_id": id, "uuid": "mock", "action": "screencap", "param": {} }),
+            Duration::ZERO,
+            succeeds,
+            move |_| {
+                if let (Some(data), Ok(mut image)) = (screenshot, image.lock()) {
+                    *image = Some(data);
+                }
+            },
+        );
+        Ok(id)
+    }
+
+    fn post_input(&self, input: &ControllerInput) -> MxuResult<i64> {
+        let id = self.shared.next_id();
+        // 输入不做任何事，连接后总是成功
+        self.shared.run_job(
+            id,
+            "Controller.Action",
+            json!({ "ctrl_id": id, "uuid": "mock", "action": input.action(), "param": input }),
+            Duration::ZERO,
+            self.connected(),
+            |_| {},
+        );
+        Ok(id)
+    }
+
+    fn status(&self, id: i64) -> MaaStatus {
+        self.shared.jobs.status(id)
+    }
+
+    fn wait(&self, id: i64) -> MaaStatus {
+        self.shared.jobs.wait(id)
+    }
+
+    fn connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    fn cached_image(&self) -> MxuResult<Vec<u8>> {
+        self.image
+            .lock()?
+            .clone()
+            .ok_or_else(|| MxuError::maa(Subsystem::Controller, "No image data available"))
+    }
+
+    fn set_screenshot_resolution(&self, _resolution: ScreenshotResolution) -> MxuResult<()> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// Tasker
+// ============================================================================
+
+/// 排队中的任务
+struct QueuedTask {
+    id: i64,
+    entry: String,
+}
+
+#[derive(Default)]
+struct TaskQueue {
+    pending: VecDeque<QueuedTask>,
+    /// 正在执行的任务 ID
+    current: Option<i64>,
+    /// 工作线程是否存活
+    worker_alive: bool,
+}
+
+struct TaskerInner {
+    shared: Shared,
+    resource: Arc<dyn ResourceBackend>,
+    controller: Arc<dyn ControllerBackend>,
+    queue: Mutex<TaskQueue>,
+    /// 各任务的执行详情
+    details: Mutex<HashMap<i64, TaskDetail>>,
+    stopping: AtomicBool,
+}
+
+impl TaskerInner {
+    fn task_details(id: i64, entry: &str) -> serde_json::Value {
+        json!({ "task_id": id, "entry": entry, "uuid": "mock", "hash": "" })
+    }
+
+    fn update_detail(&self, task_id: i64, f: impl FnOnce(&mut TaskDetail)) {
+        if let Ok(mut details) = self.details.lock() {
+            if let Some(detail) = details.get_mut(&task_id) {
+                f(detail);
+            }
+        }
+    }
+
+    /// 开始执行任务的节点（识别直接命中），返回节点 ID 与动作 ID
+    fn start_node(&self, task: &QueuedTask) -> (i64, i64) {
+        let node_id = self.shared.next_id();
+        let reco_id = self.shared.next_id();
+        let action_id = self.shared.next_id();
+        let node = json!({ "task_id": task.id, "node_id": node_id, "name": task.entry });
+        let reco = json!({ "task_id": task.id, "reco_id": reco_id, "name": task.entry });
+        let action = json!({ "task_id": task.id, "action_id": action_id, "name": task.entry });
+
+        self.shared.emit("Node.PipelineNode.Starting", node);
+        self.shared.emit("Node.Recognition.Starting", reco.clone());
+        self.update_detail(task.id, |detail| {
+            detail.nodes.push(NodeDetail {
+                node_id,
+                name: task.entry.clone(),
+                completed: false,
+                timing: Default::default(),
+                recognition: Some(RecognitionDetail {
+                    reco_id,
+                    name: task.entry.clone(),
+                    algorithm: "DirectHit".to_string(),
+                    hit: true,
+                    box_rect: [0, 0, 0, 0],
+                    detail: serde_json::Value::Null,
+                    timing: Default::default(),
+                    sub_details: Vec::new(),
+                }),
+                action: None,
+            });
+        });
+        self.shared.emit("Node.Recognition.Succeeded", reco);
+        self.shared.emit("Node.Action.Starting", action);
+        (node_id, action_id)
+    }
+
+    /// 结束节点的动作
+    fn finish_node(&self, task: &QueuedTask, node_id: i64, action_id: i64, success: bool) {
+        self.update_detail(task.id, |detail| {
+            if let Some(node) = detail.nodes.iter_mut().find(|n| n.node_id == node_id) {
+                node.completed = success;
+                node.action = Some(ActionDetail {
+                    action_id,
+                    name: task.entry.clone(),
+                    action: "DoNothing".to_string(),
+                    success,
+                    box_rect: [0, 0, 0, 0],
+                    detail: serde_json::Value::Null,
+                    timing: Default::default(),
+                });
+            }
+        });
+        let (action_message, node_message) = if success {
+            ("Node.Action.Succeeded", "Node.PipelineNode.Succeeded")
+        } else {
+            ("Node.Action.Failed", "Node.PipelineNode.Failed")
+        };
+        self.shared.emit(
+            action_message,
+            json!({ "task_id": task.id, "action_id": action_id, "name": task.entry }),
+        );
+        self.shared.emit(
+            node_message,
+            json!({ "task_id": task.id, "node_id": node_id, "name": task.entry }),
+        );
+    }
+
+    fn set_status(&self, task_id: i64, status: MaaStatus) {
+        self.shared.jobs.set(task_id, status);
+        self.update_detail(task_id, |detail| detail.status = status.into());
+    }
+
+    /// 按提交顺序依次执行任务，队列为空时退出
+    fn work(self: Arc<Self>) {
+        loop {
+            let task = {
+                let Ok(mut queue) = self.queue.lock() else {
+                    return;
+                };
+                match queue.pending.pop_front() {
+                    Some(task) => {
+                        queue.current = Some(task.id);
+                        task
+                    }
+                    None => {
+                        queue.current = None;
+                        queue.worker_alive = false;
+                        self.stopping.store(false, Ordering::SeqCst);
+                        return;
+                    }
+                }
+            };
+
+            let details = Self::task_details(task.id, &task.entry);
+            if self.stopping.load(Ordering::SeqCst) {
+                self.set_status(task.id, MaaStatus::FAILED);
+                self.shared.emit("Tasker.Task.Failed", details);
+                continue;
+            }
+
+            let script = self.shared.script();
+            self.set_status(task.id, MaaStatus::RUNNING);
+            self.shared.emit("Tasker.Task.Starting", details.clone());
+            let (node_id, action_id) = self.start_node(&task);
+
+            let deadline = Instant::now() + script.task_duration(&task.entry);
+            while Instant::now() < deadline && !self.stopping.load(Ordering::SeqCst) {
+                thread::sleep(
+                    STOP_CHECK_INTERVAL.min(deadline.saturating_duration_since(Instant::now())),
+                );
+            }
+
+            // 执行期间设备掉线的任务失败
+            let succeeded = !self.stopping.load(Ordering::SeqCst)
+                && self.controller.connected()
+                && script.task_succeeds(&task.entry);
+            self.finish_node(&task, node_id, action_id, succeeded);
+            if succeeded {
+                self.set_status(task.id, MaaStatus::SUCCEEDED);
+                self.shared.emit("Tasker.Task.Succeeded", details);
+            } else {
+                self.set_status(task.id, MaaStatus::FAILED);
+                self.shared.emit("Tasker.Task.Failed", details);
+            }
+        }
+    }
+}
+
+struct MockTasker {
+    inner: Arc<TaskerInner>,
+}
+
+impl TaskerBackend for MockTasker {
+    fn inited(&self) -> bool {
+        self.inner.resource.loaded() && self.inner.controller.connected()
+    }
+
+    fn running(&self) -> bool {
+        self.inner
+            .queue
+            .lock()
+            .map(|q| q.worker_alive)
+            .unwrap_or(false)
+    }
+
+    fn post_task(&self, entry: &str, _pipeline_override: &str) -> MxuResult<i64> {
+        if !self.inited() {
+            return Err(MxuError::new(
+                ErrorCode::TaskerNotInitialized,
+                Subsystem::Tasker,
+                "Tasker not initialized",
+            ));
+        }
+
+        let id = self.inner.shared.next_id();
+        self.inner.shared.jobs.set(id, MaaStatus::PENDING);
+        self.inner.details.lock()?.insert(
+            id,
+            TaskDetail {
+                task_id: id,
+                entry: entry.to_string(),
+                status: TaskStatus::Pending,
+                timing: Default::default(),
+                nodes: Vec::new(),
+            },
+        );
+
+        let mut queue = self.inner.queue.lock()?;
+        queue.pending.push_back(QueuedTask {
+            id,
+            entry: entry.to_string(),
+        });
+        if !queue.worker_alive {
+            queue.worker_alive = true;
+            let inner = self.inner.clone();
+            thread::spawn(move || inner.work());
+        }
+        Ok(id)
+    }
+
+    fn task_status(&self, task_id: i64) -> MxuResult<MaaStatus> {
+        Ok(self.inner.shared.jobs.status(task_id))
+    }
+
+    fn task_detail(&self, task_id: i64) -> MxuResult<Option<TaskDetail>> {
+        Ok(self.inner.details.lock()?.get(&task_id).cloned())
+    }
+
+    /// 识别使用控制器当前的截图，没有标注图
+    fn recognition_images(&self, reco_id: i64) -> MxuResult<Option<RecognitionImages>> {
+        let known = self.inner.details.lock()?.values().any(|detail| {
+            detail.nodes.iter().any(|node| {
+                node.recognition
+                    .as_ref()
+                    .is_some_and(|r| r.reco_id == reco_id)
+            })
+        });
+        Ok(known.then(|| RecognitionImages {
+            raw: self.inner.controller.cached_image().ok(),
+            draws: Vec::new(),
+        }))
+    }
+
+    fn post_stop(&self) -> MxuResult<()> {
+        if self.running() {
+            self.inner.stopping.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    fn override_pipeline(&self, task_id: i64, _pipeline_override: &str) -> MxuResult<bool> {
+        let queue = self.inner.queue.lock()?;
+        Ok(queue.current == Some(task_id) || queue.pending.iter().any(|t| t.id == task_id))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Drop for MockTasker {
+    fn drop(&mut self) {
+        // 与 MaaFramework 一致，销毁 Tasker 时终止剩余任务
+        self.inner.stopping.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::event_bus::{CollectorSink, MAA_CALLBACK_EVENT};
+    use crate::commands::maa_agent::start_tasks;
+    use crate::commands::maa_core::{
+        connect_controller, create_instance, destroy_instance, load_resource, stop_task,
+    };
+    use crate::commands::types::{MaaState, RunOptions, TaskConfig};
+
+    /// 等待条件成立的最长时间
+    const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            thread::sleep(STOP_CHECK_INTERVAL);
+        }
+        condition()
+    }
+
+    fn task(entry: &str) -> TaskConfig {
+        TaskConfig {
+            entry: entry.to_string(),
+            pipeline_override: "{}".to_string(),
+            timeout_ms: None,
+            retry: None,
+        }
+    }
+
+    /// 创建实例并完成连接与资源加载
+    fn setup(script: MockScript) -> (Arc<MaaState>, Arc<CollectorSink>) {
+        let state = Arc::new(MaaState::with_backend(Arc::new(MockBackend::new(script))));
+        let collector = Arc::new(CollectorSink::default());
+        state.event_bus.add_sink(collector.clone());
+
+        create_instance(&state, "test").unwrap();
+        let config = ControllerConfig::PlayCover {
+            address: "127.0.0.1:1717".to_string(),
+            uuid: None,
+            screenshot_resolution: ScreenshotResolution::default(),
+        };
+        let conn_id = connect_controller(&state, "test", &config).unwrap();
+        let res_ids = load_resource(&state, "test", &["resource".to_string()]).unwrap();
+        let (controller, resource) = {
+            let instances = state.instances.lock().unwrap();
+            let instance = &instances["test"];
+            (
+                instance.controller.clone().unwrap(),
+                instance.resource.clone().unwrap(),
+            )
+        };
+        assert!(controller.wait(conn_id).succeeded());
+        assert!(res_ids.iter().all(|id| resource.wait(*id).succeeded()));
+        (state, collector)
+    }
+
+    fn start(state: &Arc<MaaState>, entries: &[&str]) -> Vec<i64> {
+        start_with_options(state, entries, &RunOptions::default())
+    }
+
+    fn start_with_options(
+        state: &Arc<MaaState>,
+        entries: &[&str],
+        run_options: &RunOptions,
+    ) -> Vec<i64> {
+        let tasks: Vec<_> = entries.iter().map(|e| task(e)).collect();
+        let result = tauri::async_runtime::block_on(start_tasks(
+            state,
+            "test",
+            &tasks,
+            None,
+            ".",
+            false,
+            run_options,
+        ))
+        .unwrap();
+        assert!(result.rejected.is_empty());
+        result.task_ids
+    }
+
+    fn tasker(state: &MaaState) -> Arc<dyn TaskerBackend> {
+        state.instances.lock().unwrap()["test"]
+            .tasker
+            .clone()
+            .unwrap()
+    }
+
+    /// 收集到的任务回调（消息名, task_id）
+    fn task_callbacks(collector: &CollectorSink) -> Vec<(String, i64)> {
+        collector
+            .events()
+            .into_iter()
+            .filter(|e| e.name == MAA_CALLBACK_EVENT && e.instance_id.as_deref() == Some("test"))
+            .filter_map(|e| {
+                let message = e.payload["message"].as_str()?.to_string();
+                if !message.starts_with("Tasker.Task.") {
+                    return None;
+                }
+                let details: serde_json::Value =
+                    serde_json::from_str(e.payload["details"].as_str()?).ok()?;
+                Some((message, details["task_id"].as_i64()?))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn start_tasks_runs_in_order() {
+        let mut script = MockScript::default();
+        script.task_outcomes.insert("B".to_string(), false);
+        let (state, collector) = setup(script);
+
+        let task_ids = start(&state, &["A", "B", "C"]);
+        assert_eq!(task_ids.len(), 3);
+        assert_eq!(state.instances.lock().unwrap()["test"].task_ids, task_ids);
+
+        let tasker = tasker(&state);
+        assert!(wait_until(|| !tasker.running()));
+        let statuses: Vec<_> = task_ids
+            .iter()
+            .map(|id| tasker.task_status(*id).unwrap())
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                MaaStatus::SUCCEEDED,
+                MaaStatus::FAILED,
+                MaaStatus::SUCCEEDED
+            ]
+        );
+
+        let expected: Vec<_> = [
+            ("Tasker.Task.Starting", task_ids[0]),
+            ("Tasker.Task.Succeeded", task_ids[0]),
+            ("Tasker.Task.Starting", task_ids[1]),
+            ("Tasker.Task.Failed", task_ids[1]),
+            ("Tasker.Task.Starting", task_ids[2]),
+            ("Tasker.Task.Succeeded", task_ids[2]),
+        ]
+        .into_iter()
+        .map(|(message, id)| (message.to_string(), id))
+        .collect();
+        assert_eq!(task_callbacks(&collector), expected);
+    }
+
+    #[test]
+    fn stop_task_aborts_remaining_tasks() {
+        let (state, collector) = setup(MockScript {
+            task_duration: Duration::from_secs(10),
+            ..Default::default()
+        });
+
+        let task_ids = start(&state, &["A", "B"]);
+        let tasker = tasker(&state);
+        assert!(wait_until(|| {
+            tasker.task_status(task_ids[0]).unwrap() == MaaStatus::RUNNING
+        }));
+
+        stop_task(&state, "test").unwrap();
+        assert!(state.instances.lock().unwrap()["test"].task_ids.is_empty());
+        assert!(wait_until(|| !tasker.running()));
+        for id in &task_ids {
+            assert_eq!(tasker.task_status(*id).unwrap(), MaaStatus::FAILED);
+        }
+        let callbacks = task_callbacks(&collector);
+        assert!(callbacks.contains(&("Tasker.Task.Failed".to_string(), task_ids[1])));
+        assert!(!callbacks.contains(&("Tasker.Task.Starting".to_string(), task_ids[1])));
+    }
+
+    #[test]
+    fn timeout_monitor_exits_after_run_finishes() {
+        let (state, _collector) = setup(MockScript::default());
+
+        let run_options = RunOptions {
+            timeout_ms: Some(60_000),
+            ..Default::default()
+        };
+        start_with_options(&state, &["A", "B"], &run_options);
+        assert!(state.instances.lock().unwrap()["test"]
+            .timeout_monitor
+            .is_some());
+
+        let tasker = tasker(&state);
+        assert!(wait_until(|| !tasker.running()));
+        assert!(wait_until(|| {
+            state.instances.lock().unwrap()["test"]
+                .timeout_monitor
+                .is_none()
+        }));
+    }
+
+    #[test]
+    fn start_tasks_requires_loaded_resource() {
+        let state = Arc::new(MaaState::with_backend(Arc::new(MockBackend::default())));
+        create_instance(&state, "test").unwrap();
+
+        let result = tauri::async_runtime::block_on(start_tasks(
+            &state,
+            "test",
+            &[task("A")],
+            None,
+            ".",
+            false,
+            &RunOptions::default(),
+        ));
+        assert_eq!(result.unwrap_err().code, ErrorCode::ResourceNotLoaded);
+
+        destroy_instance(&state, "test").unwrap();
+        assert!(state.instances.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+//! MaaFramework 后端抽象
+//!
+//! 将 Resource / Controller / Tasker 抽象为 trait，命令层与 `MaaState` 只持有 trait 对象。
+//! 默认使用 MaaFramework 原生实现；`mock` 提供进程内的脚本化实现，无需原生库即可运行完整流程
+//!
+//! 模块结构：
+//! - `native`: MaaFramework 原生实现
+//! - `image_folder`: 图片目录调试控制器（原生后端的自定义控制器）
+//! - `mock`: 脚本化 mock 实现（可配置连接结果、任务耗时与结果、截图）
+
+use std::any::Any;
+use std::sync::Arc;
+
+use maa_framework::MaaStatus;
+
+use super::error::MxuResult;
+use super::types::{ControllerConfig, ControllerInput, ScreenshotResolution, TaskDetail};
+
+pub mod image_folder;
+pub mod mock;
+pub mod native;
+
+/// 回调函数（message, details），由后端在 MaaFramework 回调线程或 mock 工作线程中调用
+pub type MaaCallback = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+/// 识别使用的截图与标注图（PNG 编码，仅在调试模式下保留）
+#[derive(Debug, Clone, Default)]
+pub struct RecognitionImages {
+    pub raw: Option<Vec<u8>>,
+    pub draws: Vec<Vec<u8>>,
+}
+
+/// 资源
+pub trait ResourceBackend: Send + Sync {
+    /// 提交资源包加载，返回请求 ID
+    fn post_bundle(&self, path: &str) -> MxuResult<i64>;
+    /// 查询加载请求状态
+    fn status(&self, id: i64) -> MaaStatus;
+    /// 阻塞等待加载请求结束
+    fn wait(&self, id: i64) -> MaaStatus;
+    /// 是否已成功加载过资源
+    fn loaded(&self) -> bool;
+    /// 已加载资源中的全部节点名（后端无法提供时返回 None）
+    fn node_list(&self) -> MxuResult<Option<Vec<String>>>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// 控制器
+pub trait ControllerBackend: Send + Sync {
+    /// 发起连接，返回请求 ID
+    fn post_connection(&self) -> MxuResult<i64>;
+    /// 发起截图，返回请求 ID
+    fn post_screencap(&self) -> MxuResult<i64>;
+    /// 发起点击、滑动、按键、输入文本、启动/停止应用等输入动作，返回请求 ID
+    fn post_input(&self, input: &ControllerInput) -> MxuResult<i64>;
+    /// 查询控制器请求状态
+    fn status(&self, id: i64) -> MaaStatus;
+    /// 阻塞等待控制器请求结束
+    fn wait(&self, id: i64) -> MaaStatus;
+    /// 是否已连接
+    fn connected(&self) -> bool;
+    /// 最近一次截图（PNG 编码）
+    fn cached_image(&self) -> MxuResult<Vec<u8>>;
+    /// 设置截图目标分辨率（可在连接后随时修改）
+    fn set_screenshot_resolution(&self, resolution: ScreenshotResolution) -> MxuResult<()>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// 任务执行器
+pub trait TaskerBackend: Send + Sync {
+    /// 资源与控制器是否均已就绪
+    fn inited(&self) -> bool;
+    /// 是否有任务正在运行或排队
+    fn running(&self) -> bool;
+    /// 提交任务，返回 task_id
+    fn post_task(&self, entry: &str, pipeline_override: &str) -> MxuResult<i64>;
+    /// 查询任务状态（未知任务返回 INVALID）
+    fn task_status(&self, task_id: i64) -> MxuResult<MaaStatus>;
+    /// 查询任务详情（已执行的节点及识别、动作结果，不含时间；未知任务返回 None）
+    fn task_detail(&self, task_id: i64) -> MxuResult<Option<TaskDetail>>;
+    /// 查询识别的截图与标注图（未知识别返回 None）
+    fn recognition_images(&self, reco_id: i64) -> MxuResult<Option<RecognitionImages>>;
+    /// 请求停止所有任务
+    fn post_stop(&self) -> MxuResult<()>;
+    /// 覆盖尚未执行完毕的任务的 Pipeline 配置
+    fn override_pipeline(&self, task_id: i64, pipeline_override: &str) -> MxuResult<bool>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// 后端工厂：创建资源、控制器和任务执行器
+pub trait MaaBackend: Send + Sync {
+    /// 后端名称（用于日志）
+    fn name(&self) -> &'static str;
+
+    fn create_resource(&self, callback: MaaCallback) -> MxuResult<Arc<dyn ResourceBackend>>;
+
+    fn create_controller(
+        &self,
+        config: &ControllerConfig,
+        callback: MaaCallback,
+    ) -> MxuResult<Arc<dyn ControllerBackend>>;
+
+    /// 创建任务执行器并绑定资源和控制器
+    fn create_tasker(
+        &self,
+        resource: &Arc<dyn ResourceBackend>,
+        controller: &Arc<dyn ControllerBackend>,
+        callback: MaaCallback,
+    ) -> MxuResult<Arc<dyn TaskerBackend>>;
+}
@@ -0,0 +1,436 @@
+//! MaaFramework 原生后端
+//!
+//! trait 直接实现在 maa_framework 的 Resource / Controller / Tasker 上，
+//! Agent 等需要原生句柄的场景通过 `native_handles` 取回具体类型
+
+use std::any::Any;
+use std::path::Path;
+use std::sync::Arc;
+
+use log::warn;
+use maa_framework::common::{self as maa_common, Rect};
+use maa_framework::controller::{AdbControllerBuilder, Controller};
+use maa_framework::resource::Resource;
+use maa_framework::tasker::Tasker;
+use maa_framework::MaaStatus;
+
+use super::image_folder::ImageFolderController;
+use super::{
+    ControllerBackend, MaaBackend, MaaCallback, RecognitionImages, ResourceBackend, TaskerBackend,
+};
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::types::{
+    ActionDetail, ControllerConfig, ControllerInput, NodeDetail, RecognitionDetail,
+    ScreenshotResolution, TaskDetail,
+};
+use crate::commands::utils::get_maafw_dir;
+
+/// MaaFramework 原生后端（需要先加载 MaaFramework 库）
+pub struct NativeBackend;
+
+impl MaaBackend for NativeBackend {
+    fn name(&self) -> &'static str {
+        "MaaFramework"
+    }
+
+    fn create_resource(&self, callback: MaaCallback) -> MxuResult<Arc<dyn ResourceBackend>> {
+        let res = Resource::new().map_err(|e| MxuError::maa(Subsystem::Resource, e))?;
+
+        // 注册回调
+        res.add_sink(move |msg, detail| callback(msg, detail))
+            .map_err(|e| MxuError::maa(Subsystem::Resource, e))?;
+
+        // 注册 MXU Custom Actions
+        if let Err(e) = crate::mxu_actions::register_all_mxu_actions(&res) {
+            warn!("Failed to register MXU custom actions: {}", e);
+        }
+
+        Ok(Arc::new(res))
+    }
+
+    fn create_controller(
+        &self,
+        config: &ControllerConfig,
+        callback: MaaCallback,
+    ) -> MxuResult<Arc<dyn ControllerBackend>> {
+        let controller = build_controller(config)?;
+
+        // 注册回调
+        controller
+            .add_sink(move |msg, detail| callback(msg, detail))
+            .map_err(|e| MxuError::maa(Subsystem::Controller, e))?;
+
+        Ok(Arc::new(controller))
+    }
+
+    fn create_tasker(
+        &self,
+        resource: &Arc<dyn ResourceBackend>,
+        controller: &Arc<dyn ControllerBackend>,
+        callback: MaaCallback,
+    ) -> MxuResult<Arc<dyn TaskerBackend>> {
+        let resource = resource
+            .as_any()
+            .downcast_ref::<Resource>()
+            .ok_or_else(|| {
+                MxuError::internal("Resource was not created by the MaaFramework backend")
+            })?;
+        let controller = controller
+            .as_any()
+            .downcast_ref::<Controller>()
+            .ok_or_else(|| {
+                MxuError::internal("Controller was not created by the MaaFramework backend")
+            })?;
+
+        let tasker = Tasker::new().map_err(|e| MxuError::maa(Subsystem::Tasker, e))?;
+
+        // 添加回调 Sink，用于接收任务状态通知
+        let task_callback = callback.clone();
+        tasker
+            .add_sink(move |msg, detail| task_callback(msg, detail))
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))?;
+
+        // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
+        tasker
+            .add_context_sink(move |msg, detail| callback(msg, detail))
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))?;
+
+        // 绑定资源和控制器
+        tasker
+            .bind(resource, controller)
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))?;
+
+        Ok(Arc::new(tasker))
+    }
+}
+
+/// 根据配置创建控制器（阻塞调用）
+fn build_controller(config: &ControllerConfig) -> MxuResult<Controller> {
+    let controller = match config {
+        ControllerConfig::Adb {
+            adb_path,
+            address,
+            screencap_methods,
+            input_methods,
+            config,
+            ..
+        } => {
+            // 将字符串解析为 u64
+            let screencap = screencap_methods.parse::<u64>().map_err(|e| {
+                MxuError::new(
+                    ErrorCode::InvalidArgument,
+                    Subsystem::Controller,
+                    format!("Invalid screencap_methods '{}': {}", screencap_methods, e),
+                )
+            })?;
+            let input = input_methods.parse::<u64>().map_err(|e| {
+                MxuError::new(
+                    ErrorCode::InvalidArgument,
+                    Subsystem::Controller,
+                    format!("Invalid input_methods '{}': {}", input_methods, e),
+                )
+            })?;
+            let agent_path = get_maafw_dir()
+                .map(|p| p.join("MaaAgentBinary").to_string_lossy().to_string())
+                .unwrap_or_else(|_| "./MaaAgentBinary".to_string());
+
+            AdbControllerBuilder::new(adb_path, address)
+                .screencap_methods(
+                    maa_framework::common::AdbScreencapMethod::from_bits_truncate(screencap).bits(),
+                )
+                .input_methods(
+                    maa_framework::common::AdbInputMethod::from_bits_truncate(input).bits(),
+                )
+                .config(config)
+                .agent_path(&agent_path)
+                .build()
+                .map_err(|e| MxuError::maa(Subsystem::Controller, e))?
+        }
+        ControllerConfig::Win32 {
+            handle,
+            screencap_method,
+            mouse_method,
+            keyboard_method,
+            ..
+        } => {
+            let hwnd = *handle as *mut std::ffi::c_void;
+            Controller::new_win32(
+                hwnd,
+                maa_framework::common::Win32ScreencapMethod::from_bits_truncate(*screencap_method)
+                    .bits(),
+                maa_framework::common::Win32InputMethod::from_bits_truncate(*mouse_method).bits(),
+                maa_framework::common::Win32InputMethod::from_bits_truncate(*keyboard_method)
+                    .bits(),
+            )
+            .map_err(|e| MxuError::maa(Subsystem::Controller, e))?
+        }
+        ControllerConfig::PlayCover { address, uuid, .. } => {
+            let uuid_str = uuid.as_deref().unwrap_or("");
+            Controller::new_playcover(address, uuid_str)
+                .map_err(|e| MxuError::maa(Subsystem::Controller, e))?
+        }
+        ControllerConfig::ImageFolder { path, .. } => {
+            let callback = ImageFolderController::open(Path::new(path))?;
+            Controller::new_custom(callback).map_err(|e| MxuError::maa(Subsystem::Controller, e))?
+        }
+        ControllerConfig::Gamepad {
+            handle,
+            gamepad_type,
+            screencap_method,
+            ..
+        } => {
+            let hwnd = *handle as *mut std::ffi::c_void;
+            let gp_type = match gamepad_type.as_deref() {
+                Some("DualShock4") | Some("DS4") => maa_framework::common::GamepadType::DualShock4,
+                _ => maa_framework::common::GamepadType::Xbox360,
+            };
+            // bitflags
+            let screencap = screencap_method
+                .map(|v| maa_framework::common::Win32ScreencapMethod::from_bits_truncate(v))
+                .unwrap_or(maa_framework::common::Win32ScreencapMethod::DXGI_DESKTOP_DUP);
+
+            Controller::new_gamepad(hwnd, gp_type, screencap)
+                .map_err(|e| MxuError::maa(Subsystem::Controller, e))?
+        }
+    };
+
+    Ok(controller)
+}
+
+/// 取回原生 MaaFramework 对象（Agent 需要绑定原生句柄），非原生后端返回 None
+pub fn native_handles(
+    resource: &dyn ResourceBackend,
+    controller: &dyn ControllerBackend,
+    tasker: &dyn TaskerBackend,
+) -> Option<(Resource, Controller, Tasker)> {
+    Some((
+        resource.as_any().downcast_ref::<Resource>()?.clone(),
+        controller.as_any().downcast_ref::<Controller>()?.clone(),
+        tasker.as_any().downcast_ref::<Tasker>()?.clone(),
+    ))
+}
+
+// ============================================================================
+// trait 实现
+// ============================================================================
+
+impl ResourceBackend for Resource {
+    fn post_bundle(&self, path: &str) -> MxuResult<i64> {
+        Resource::post_bundle(self, path)
+            .map(|job| job.id)
+            .map_err(|e| MxuError::maa(Subsystem::Resource, e))
+    }
+
+    fn status(&self, id: i64) -> MaaStatus {
+        Resource::status(self, id)
+    }
+
+    fn wait(&self, id: i64) -> MaaStatus {
+        Resource::wait(self, id)
+    }
+
+    fn loaded(&self) -> bool {
+        Resource::loaded(self)
+    }
+
+    fn node_list(&self) -> MxuResult<Option<Vec<String>>> {
+        Resource::node_list(self)
+            .map(Some)
+            .map_err(|e| MxuError::maa(Subsystem::Resource, e))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl ControllerBackend for Controller {
+    fn post_connection(&self) -> MxuResult<i64> {
+        Controller::post_connection(self).map_err(|e| MxuError::maa(Subsystem::Controller, e))
+    }
+
+    fn post_screencap(&self) -> MxuResult<i64> {
+        Controller::post_screencap(self).map_err(|e| MxuError::maa(Subsystem::Controller, e))
+    }
+
+    fn post_input(&self, input: &ControllerInput) -> MxuResult<i64> {
+        let result = match input {
+            ControllerInput::Click { x, y } => self.post_click(*x, *y),
+            ControllerInput::Swipe {
+                x1,
+                y1,
+                x2,
+                y2,
+                duration,
+            } => self.post_swipe(*x1, *y1, *x2, *y2, *duration),
+            ControllerInput::ClickKey { keycode } => self.post_click_key(*keycode),
+            ControllerInput::InputText { text } => self.post_input_text(text),
+            ControllerInput::StartApp { intent } => self.post_start_app(intent),
+            ControllerInput::StopApp { intent } => self.post_stop_app(intent),
+        };
+        result.map_err(|e| MxuError::maa(Subsystem::Controller, e))
+    }
+
+    fn status(&self, id: i64) -> MaaStatus {
+        Controller::status(self, id)
+    }
+
+    fn wait(&self, id: i64) -> MaaStatus {
+        Controller::wait(self, id)
+    }
+
+    fn connected(&self) -> bool {
+        Controller::connected(self)
+    }
+
+    fn cached_image(&self) -> MxuResult<Vec<u8>> {
+        let buffer =
+            Controller::cached_image(self).map_err(|e| MxuError::maa(Subsystem::Controller, e))?;
+        buffer
+            .to_vec()
+            .ok_or_else(|| MxuError::maa(Subsystem::Controller, "Failed to convert image buffer"))
+    }
+
+    fn set_screenshot_resolution(&self, resolution: ScreenshotResolution) -> MxuResult<()> {
+        // 原始分辨率选项优先于缩放目标，切回缩放时需先关闭
+        let result = match resolution {
+            ScreenshotResolution::ShortSide(short_side) => {
+                Controller::set_screenshot_use_raw_size(self, false)
+                    .and_then(|_| Controller::set_screenshot_target_short_side(self, short_side))
+            }
+            ScreenshotResolution::LongSide(long_side) => {
+                Controller::set_screenshot_use_raw_size(self, false)
+                    .and_then(|_| Controller::set_screenshot_target_long_side(self, long_side))
+            }
+            ScreenshotResolution::Raw => Controller::set_screenshot_use_raw_size(self, true),
+        };
+        result.map_err(|e| MxuError::maa(Subsystem::Controller, e))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TaskerBackend for Tasker {
+    fn inited(&self) -> bool {
+        Tasker::inited(self)
+    }
+
+    fn running(&self) -> bool {
+        Tasker::running(self)
+    }
+
+    fn post_task(&self, entry: &str, pipeline_override: &str) -> MxuResult<i64> {
+        Tasker::post_task(self, entry, pipeline_override)
+            .map(|job| job.id)
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))
+    }
+
+    fn task_status(&self, task_id: i64) -> MxuResult<MaaStatus> {
+        Ok(self
+            .get_task_detail(task_id)
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))?
+            .map(|d| d.status)
+            .unwrap_or(MaaStatus::INVALID))
+    }
+
+    fn task_detail(&self, task_id: i64) -> MxuResult<Option<TaskDetail>> {
+        let Some(detail) = self
+            .get_task_detail(task_id)
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))?
+        else {
+            return Ok(None);
+        };
+        let nodes = detail
+            .node_id_list
+            .iter()
+            .zip(detail.nodes)
+            .filter_map(|(&node_id, node)| node.map(|node| convert_node(node_id, node)))
+            .collect();
+        Ok(Some(TaskDetail {
+            task_id,
+            entry: detail.entry,
+            status: detail.status.into(),
+            timing: Default::default(),
+            nodes,
+        }))
+    }
+
+    fn recognition_images(&self, reco_id: i64) -> MxuResult<Option<RecognitionImages>> {
+        Ok(self
+            .get_recognition_detail(reco_id)
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))?
+            .map(|reco| RecognitionImages {
+                raw: reco.raw_image,
+                draws: reco.draw_images,
+            }))
+    }
+
+    fn post_stop(&self) -> MxuResult<()> {
+        Tasker::post_stop(self)
+            .map(|_| ())
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))
+    }
+
+    fn override_pipeline(&self, task_id: i64, pipeline_override: &str) -> MxuResult<bool> {
+        Tasker::override_pipeline(self, task_id, pipeline_override)
+            .map_err(|e| MxuError::maa(Subsystem::Tasker, e))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// 任务详情转换
+// ============================================================================
+
+fn rect_to_array(rect: &Rect) -> [i32; 4] {
+    [rect.x, rect.y, rect.width, rect.height]
+}
+
+fn convert_node(node_id: i64, node: maa_common::NodeDetail) -> NodeDetail {
+    let (reco_id, act_id) = (node.reco_id, node.act_id);
+    NodeDetail {
+        node_id,
+        name: node.node_name,
+        completed: node.completed,
+        timing: Default::default(),
+        recognition: node
+            .recognition
+            .map(|reco| convert_recognition(reco_id, reco)),
+        action: node.action.map(|action| convert_action(act_id, action)),
+    }
+}
+
+/// 子识别结果没有单独的 ID，reco_id 为 0
+fn convert_recognition(reco_id: i64, reco: maa_common::RecognitionDetail) -> RecognitionDetail {
+    RecognitionDetail {
+        reco_id,
+        name: reco.node_name,
+        algorithm: reco.algorithm.into(),
+        hit: reco.hit,
+        box_rect: rect_to_array(&reco.box_rect),
+        detail: reco.detail,
+        timing: Default::default(),
+        sub_details: reco
+            .sub_details
+            .into_iter()
+            .map(|sub| convert_recognition(0, sub))
+            .collect(),
+    }
+}
+
+fn convert_action(action_id: i64, action: maa_common::ActionDetail) -> ActionDetail {
+    ActionDetail {
+        action_id,
+        name: action.node_name,
+        action: action.action.into(),
+        success: action.success,
+        box_rect: rect_to_array(&action.box_rect),
+        detail: action.detail,
+        timing: Default::default(),
+    }
+}
@@ -0,0 +1,27 @@
+//! ADB 设备热插拔监听相关命令
+//!
+//! 监听线程在 Rust 端运行（见 `crate::device_monitor`），前端只负责同步设置
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::device_monitor::{AdbDeviceMonitor, DeviceMonitorOptions};
+
+/// 更新设备监听设置（前端加载配置与修改设置时调用）
+#[tauri::command]
+pub fn maa_device_monitor_configure(
+    monitor: State<Arc<AdbDeviceMonitor>>,
+    options: DeviceMonitorOptions,
+) {
+    log::info!("maa_device_monitor_configure called");
+    monitor.configure(options);
+}
+
+/// 获取当前的设备监听设置
+#[tauri::command]
+pub fn maa_device_monitor_get_options(
+    monitor: State<Arc<AdbDeviceMonitor>>,
+) -> DeviceMonitorOptions {
+    monitor.options()
+}
@@ -10,6 +10,7 @@ use tauri::Emitter;
 use super::types::GitHubRelease;
 use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
 
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
 use super::types::{DownloadProgressEvent, DownloadResult};
 use super::update::move_to_old_folder;
 use super::utils::build_user_agent;
@@ -30,7 +31,7 @@ pub async fn get_github_release_by_version(
     target_version: String,
     github_pat: Option<String>,
     proxy_url: Option<String>,
-) -> Result<Option<GitHubRelease>, String> {
+) -> MxuResult<Option<GitHubRelease>> {
     let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
 
     // 构造请求头
@@ -46,18 +47,27 @@ pub async fn get_github_release_by_version(
             info!("[检查更新] 目标: {}", url);
             let reqwest_proxy = reqwest::Proxy::all(proxy).map_err(|e| {
                 error!("代理配置失败: {} (代理地址: {})", e, proxy);
-                format!(
-                    "代理配置失败: {}。请检查代理格式是否正确（支持 http:// 或 socks5://）",
-                    e
+                MxuError::new(
+                    ErrorCode::InvalidProxy,
+                    Subsystem::Network,
+                    format!(
+                        "代理配置失败: {}。请检查代理格式是否正确（支持 http:// 或 socks5://）",
+                        e
+                    ),
                 )
+                .with_url(proxy)
             })?;
             client_builder = client_builder.proxy(reqwest_proxy);
         }
     }
 
-    let client = client_builder
-        .build()
-        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+    let client = client_builder.build().map_err(|e| {
+        MxuError::new(
+            ErrorCode::HttpClient,
+            Subsystem::Network,
+            format!("创建 HTTP 客户端失败: {}", e),
+        )
+    })?;
 
     let mut request = client
         .get(&url)
@@ -71,19 +81,33 @@ pub async fn get_github_release_by_version(
         }
     }
 
-    let response = request
-        .send()
-        .await
-        .map_err(|e| format!("请求失败: {}", e))?;
+    let response = request.send().await.map_err(|e| {
+        MxuError::new(
+            ErrorCode::RequestFailed,
+            Subsystem::Network,
+            format!("请求失败: {}", e),
+        )
+        .with_url(&url)
+    })?;
 
     if !response.status().is_success() {
-        return Err(format!("GitHub API 错误: {}", response.status()));
+        return Err(MxuError::new(
+            ErrorCode::HttpStatus,
+            Subsystem::Network,
+            format!("GitHub API 错误: {}", response.status()),
+        )
+        .with_http_status(response.status().as_u16())
+        .with_url(&url));
     }
 
-    let releases: Vec<GitHubRelease> = response
-        .json()
-        .await
-        .map_err(|e| format!("解析 JSON 失败: {}", e))?;
+    let releases: Vec<GitHubRelease> = response.json().await.map_err(|e| {
+        MxuError::new(
+            ErrorCode::ParseFailed,
+            Subsystem::Network,
+            format!("解析 JSON 失败: {}", e),
+        )
+        .with_url(&url)
+    })?;
 
     let normalize = |v: &str| {
         v.trim_start_matches(|c| c == 'v' || c == 'V')
@@ -119,7 +143,7 @@ pub async fn download_file(
     save_path: String,
     total_size: Option<u64>,
     proxy_url: Option<String>,
-) -> Result<DownloadResult, String> {
+) -> MxuResult<DownloadResult> {
     use futures_util::StreamExt;
     use std::io::Write;
 
@@ -136,7 +160,9 @@ pub async fn download_file(
 
     // 确保目录存在
     if let Some(parent) = save_path_obj.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| format!("无法创建目录: {}", e))?;
+        std::fs::create_dir_all(parent).map_err(|e| {
+            MxuError::io(Subsystem::Network, parent, format!("无法创建目录: {}", e))
+        })?;
     }
 
     // 构建 HTTP 客户端和请求
@@ -152,10 +178,15 @@ pub async fn download_file(
             info!("[下载] 目标: {}", url);
             let reqwest_proxy = reqwest::Proxy::all(proxy).map_err(|e| {
                 error!("代理配置失败: {} (代理地址: {})", e, proxy);
-                format!(
-                    "代理配置失败: {}。请检查代理格式是否正确（支持 http:// 或 socks5://）",
-                    e
+                MxuError::new(
+                    ErrorCode::InvalidProxy,
+                    Subsystem::Network,
+                    format!(
+                        "代理配置失败: {}。请检查代理格式是否正确（支持 http:// 或 socks5://）",
+                        e
+                    ),
                 )
+                .with_url(proxy)
             })?;
             client_builder = client_builder.proxy(reqwest_proxy);
         } else {
@@ -165,18 +196,31 @@ pub async fn download_file(
         info!("[下载] 直连（无代理）: {}", url);
     }
 
-    let client = client_builder
-        .build()
-        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
-
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("请求失败: {}", e))?;
+    let client = client_builder.build().map_err(|e| {
+        MxuError::new(
+            ErrorCode::HttpClient,
+            Subsystem::Network,
+            format!("创建 HTTP 客户端失败: {}", e),
+        )
+    })?;
+
+    let response = client.get(&url).send().await.map_err(|e| {
+        MxuError::new(
+            ErrorCode::RequestFailed,
+            Subsystem::Network,
+            format!("请求失败: {}", e),
+        )
+        .with_url(&url)
+    })?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP 错误: {}", response.status()));
+        return Err(MxuError::new(
+            ErrorCode::HttpStatus,
+            Subsystem::Network,
+            format!("HTTP 错误: {}", response.status()),
+        )
+        .with_http_status(response.status().as_u16())
+        .with_url(&url));
     }
 
     // 尝试从 Content-Disposition header 或最终 URL 提取文件名
@@ -207,7 +251,13 @@ pub async fn download_file(
     let total = total_size.or(content_length).unwrap_or(0);
 
     // 创建临时文件
-    let mut file = std::fs::File::create(&temp_path).map_err(|e| format!("无法创建文件: {}", e))?;
+    let mut file = std::fs::File::create(&temp_path).map_err(|e| {
+        MxuError::io(
+            Subsystem::Network,
+            &temp_path,
+            format!("无法创建文件: {}", e),
+        )
+    })?;
 
     // 流式下载
     let mut stream = response.bytes_stream();
@@ -227,18 +277,30 @@ pub async fn download_file(
             drop(file);
             // 清理临时文件
             let _ = std::fs::remove_file(&temp_path);
-            return Err("下载已取消".to_string());
+            return Err(download_cancelled(&url));
         }
 
-        let chunk = chunk.map_err(|e| format!("下载数据失败: {}", e))?;
+        let chunk = chunk.map_err(|e| {
+            MxuError::new(
+                ErrorCode::RequestFailed,
+                Subsystem::Network,
+                format!("下载数据失败: {}", e),
+            )
+            .with_url(&url)
+        })?;
 
         buffer.extend_from_slice(&chunk);
         downloaded += chunk.len() as u64;
 
         // 当缓冲区达到一定大小时写入磁盘
         if buffer.len() >= 256 * 1024 {
-            file.write_all(&buffer)
-                .map_err(|e| format!("写入文件失败: {}", e))?;
+            file.write_all(&buffer).map_err(|e| {
+                MxuError::io(
+                    Subsystem::Network,
+                    &temp_path,
+                    format!("写入文件失败: {}", e),
+                )
+            })?;
             buffer.clear();
         }
 
@@ -280,18 +342,28 @@ pub async fn download_file(
         );
         drop(file);
         let _ = std::fs::remove_file(&temp_path);
-        return Err("下载已取消".to_string());
+        return Err(download_cancelled(&url));
     }
 
     // 写入剩余缓冲区
     if !buffer.is_empty() {
-        file.write_all(&buffer)
-            .map_err(|e| format!("写入文件失败: {}", e))?;
+        file.write_all(&buffer).map_err(|e| {
+            MxuError::io(
+                Subsystem::Network,
+                &temp_path,
+                format!("写入文件失败: {}", e),
+            )
+        })?;
     }
 
     // 确保数据写入磁盘
-    file.sync_all()
-        .map_err(|e| format!("同步文件失败: {}", e))?;
+    file.sync_all().map_err(|e| {
+        MxuError::io(
+            Subsystem::Network,
+            &temp_path,
+            format!("同步文件失败: {}", e),
+        )
+    })?;
     drop(file);
 
     // 发送最终进度
@@ -312,7 +384,13 @@ pub async fn download_file(
     }
 
     // 重命名临时文件
-    std::fs::rename(&temp_path, &actual_save_path).map_err(|e| format!("重命名文件失败: {}", e))?;
+    std::fs::rename(&temp_path, &actual_save_path).map_err(|e| {
+        MxuError::io(
+            Subsystem::Network,
+            &actual_save_path,
+            format!("重命名文件失败: {}", e),
+        )
+    })?;
 
     info!(
         "download_file completed: {} bytes -> {} (session {})",
@@ -328,7 +406,7 @@ pub async fn download_file(
 
 /// 取消下载
 #[tauri::command]
-pub fn cancel_download(save_path: String) -> Result<(), String> {
+pub fn cancel_download(save_path: String) -> MxuResult<()> {
     info!("cancel_download called for: {}", save_path);
 
     // 设置取消标志，让下载循环退出
@@ -350,6 +428,16 @@ pub fn cancel_download(save_path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 下载被取消（用户取消或被新的下载会话替换）
+fn download_cancelled(url: &str) -> MxuError {
+    MxuError::new(
+        ErrorCode::DownloadCancelled,
+        Subsystem::Network,
+        "下载已取消",
+    )
+    .with_url(url)
+}
+
 /// 从 HTTP 响应中提取文件名
 ///
 /// 优先级：
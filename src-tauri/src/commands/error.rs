@@ -0,0 +1,248 @@
+//! 错误类型
+//!
+//! Tauri 命令统一返回 `MxuError`：稳定的错误码、出错的子系统、可读消息和结构化上下文。
+//! 前端按 `code` 本地化提示，脚本按 `code` 判断具体失败原因，`message` 仅用于日志和兜底显示
+
+use std::fmt;
+use std::path::Path;
+use std::sync::PoisonError;
+
+use serde::Serialize;
+
+/// 命令返回值
+pub type MxuResult<T> = Result<T, MxuError>;
+
+/// 出错的子系统
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    /// MaaFramework 库加载与版本
+    Maa,
+    Instance,
+    Controller,
+    Resource,
+    Tasker,
+    Agent,
+    /// 本地文件读写
+    File,
+    /// 压缩包解压
+    Archive,
+    /// 更新安装
+    Update,
+    /// HTTP 请求与下载
+    Network,
+    /// 进程、权限、自启动等系统功能
+    System,
+    Tray,
+    /// 定时执行
+    Scheduler,
+    /// 本地 HTTP / WebSocket 控制接口
+    RemoteApi,
+    /// 运行历史
+    History,
+    /// 会话录制
+    Recorder,
+    Internal,
+}
+
+/// 错误码（序列化为 SCREAMING_SNAKE_CASE，发布后不再修改含义）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // MaaFramework 库
+    LibraryNotFound,
+    LibraryLoadFailed,
+    LibraryNotLoaded,
+    VersionParseFailed,
+    /// MaaFramework API 调用失败
+    MaaCallFailed,
+
+    // 实例运行时
+    InstanceNotFound,
+    ControllerNotConnected,
+    ResourceNotLoaded,
+    TaskerNotCreated,
+    TaskerNotInitialized,
+    /// 实例正在运行任务
+    InstanceBusy,
+    /// Pipeline 覆盖校验失败
+    InvalidPipelineOverride,
+
+    // Agent
+    AgentStartFailed,
+    AgentUnsupported,
+
+    // 文件与数据
+    InvalidPath,
+    NotFound,
+    Io,
+    ParseFailed,
+    ArchiveInvalid,
+
+    // 网络
+    InvalidProxy,
+    HttpClient,
+    RequestFailed,
+    HttpStatus,
+    DownloadCancelled,
+    /// 监听端口失败（端口被占用等）
+    BindFailed,
+    /// 缺少或错误的访问令牌
+    Unauthorized,
+
+    // 系统
+    ProcessFailed,
+    PermissionDenied,
+    UnsupportedPlatform,
+    InvalidArgument,
+
+    // 定时执行
+    /// 定时表达式或排除时段无效
+    InvalidSchedule,
+
+    // 内部错误
+    LockPoisoned,
+    Internal,
+}
+
+/// 结构化上下文（未设置的字段不序列化）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ErrorContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ErrorContext {
+    pub fn is_empty(&self) -> bool {
+        self.path.is_none()
+            && self.instance_id.is_none()
+            && self.http_status.is_none()
+            && self.url.is_none()
+    }
+}
+
+/// MXU 命令错误
+#[derive(Debug, Clone, Serialize)]
+pub struct MxuError {
+    pub code: ErrorCode,
+    pub subsystem: Subsystem,
+    pub message: String,
+    #[serde(skip_serializing_if = "ErrorContext::is_empty")]
+    pub context: ErrorContext,
+}
+
+impl MxuError {
+    pub fn new(code: ErrorCode, subsystem: Subsystem, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            subsystem,
+            message: message.into(),
+            context: ErrorContext::default(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.context.path = Some(path.as_ref().to_string_lossy().to_string());
+        self
+    }
+
+    pub fn with_instance(mut self, instance_id: &str) -> Self {
+        self.context.instance_id = Some(instance_id.to_string());
+        self
+    }
+
+    pub fn with_http_status(mut self, status: u16) -> Self {
+        self.context.http_status = Some(status);
+        self
+    }
+
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.context.url = Some(url.to_string());
+        self
+    }
+
+    // ========================================================================
+    // 常用错误
+    // ========================================================================
+
+    /// 文件读写失败
+    pub fn io(subsystem: Subsystem, path: impl AsRef<Path>, message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Io, subsystem, message).with_path(path)
+    }
+
+    /// MaaFramework API 调用失败
+    pub fn maa(subsystem: Subsystem, err: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::MaaCallFailed, subsystem, err.to_string())
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, Subsystem::Internal, message)
+    }
+
+    pub fn unsupported_platform(subsystem: Subsystem, message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::UnsupportedPlatform, subsystem, message)
+    }
+
+    pub fn instance_not_found(instance_id: &str) -> Self {
+        Self::new(
+            ErrorCode::InstanceNotFound,
+            Subsystem::Instance,
+            "Instance not found",
+        )
+        .with_instance(instance_id)
+    }
+
+    pub fn controller_not_connected(instance_id: &str) -> Self {
+        Self::new(
+            ErrorCode::ControllerNotConnected,
+            Subsystem::Controller,
+            "Controller not connected",
+        )
+        .with_instance(instance_id)
+    }
+
+    pub fn resource_not_loaded(instance_id: &str) -> Self {
+        Self::new(
+            ErrorCode::ResourceNotLoaded,
+            Subsystem::Resource,
+            "Resource not loaded",
+        )
+        .with_instance(instance_id)
+    }
+
+    pub fn tasker_not_created(instance_id: &str) -> Self {
+        Self::new(
+            ErrorCode::TaskerNotCreated,
+            Subsystem::Tasker,
+            "Tasker not created",
+        )
+        .with_instance(instance_id)
+    }
+}
+
+impl fmt::Display for MxuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for MxuError {}
+
+impl<T> From<PoisonError<T>> for MxuError {
+    fn from(e: PoisonError<T>) -> Self {
+        Self::new(ErrorCode::LockPoisoned, Subsystem::Internal, e.to_string())
+    }
+}
+
+/// 供仍以 String 作为错误类型的调用方（mxu-cli、headless）使用 `?`
+impl From<MxuError> for String {
+    fn from(e: MxuError) -> Self {
+        e.message
+    }
+}
@@ -0,0 +1,273 @@
+//! 事件总线
+//!
+//! MaaFramework 回调与 Agent 输出统一经由事件总线分发，可同时投递给多个消费者
+//! （Tauri 前端、日志文件、命令行输出等），与 Tauri 运行时解耦
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use super::types::MaaCallbackEvent;
+
+/// MaaFramework 回调事件名
+pub const MAA_CALLBACK_EVENT: &str = "maa-callback";
+
+/// Agent 输出事件名
+pub const AGENT_OUTPUT_EVENT: &str = "maa-agent-output";
+
+/// 总线事件
+#[derive(Debug, Clone, Serialize)]
+pub struct BusEvent {
+    /// 事件名（与前端监听的 Tauri 事件名一致）
+    pub name: String,
+    /// 来源实例 ID（非实例相关事件为 None）
+    pub instance_id: Option<String>,
+    /// 事件载荷
+    pub payload: Value,
+    /// 事件产生时间（毫秒时间戳）
+    pub timestamp: i64,
+}
+
+impl BusEvent {
+    pub fn new<T: Serialize>(name: &str, instance_id: Option<&str>, payload: &T) -> Self {
+        Self {
+            name: name.to_string(),
+            instance_id: instance_id.map(str::to_string),
+            payload: serde_json::to_value(payload).unwrap_or(Value::Null),
+            timestamp: chrono::Local::now().timestamp_millis(),
+        }
+    }
+}
+
+/// 任务结束回调（Tasker.Task.Succeeded / Tasker.Task.Failed）
+#[derive(Debug, Clone, Copy)]
+pub struct TaskEnd<'a> {
+    pub instance_id: &'a str,
+    pub task_id: i64,
+    pub succeeded: bool,
+}
+
+impl BusEvent {
+    /// 解析实例的任务结束回调，其他事件返回 None
+    pub fn task_end(&self) -> Option<TaskEnd<'_>> {
+        if self.name != MAA_CALLBACK_EVENT {
+            return None;
+        }
+        let instance_id = self.instance_id.as_deref()?;
+        let succeeded = match self.payload["message"].as_str()? {
+            "Tasker.Task.Succeeded" => true,
+            "Tasker.Task.Failed" => false,
+            _ => return None,
+        };
+        let details: Value = serde_json::from_str(self.payload["details"].as_str()?).ok()?;
+        Some(TaskEnd {
+            instance_id,
+            task_id: details["task_id"].as_i64()?,
+            succeeded,
+        })
+    }
+}
+
+/// Agent 输出事件载荷
+#[derive(Clone, Serialize)]
+pub struct AgentOutputEvent {
+    pub instance_id: String,
+    pub stream: String,
+    pub line: String,
+}
+
+/// 事件消费者
+pub trait EventSink: Send + Sync {
+    fn handle(&self, event: &BusEvent);
+}
+
+/// 事件总线（线程安全，可在 MaaFramework 回调线程中直接调用）
+#[derive(Default)]
+pub struct EventBus {
+    sinks: RwLock<Vec<(u64, Arc<dyn EventSink>)>>,
+    next_id: AtomicU64,
+}
+
+impl EventBus {
+    /// 注册消费者，返回用于移除的 sink ID
+    pub fn add_sink(&self, sink: Arc<dyn EventSink>) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Ok(mut sinks) = self.sinks.write() {
+            sinks.push((id, sink));
+        }
+        id
+    }
+
+    /// 移除消费者
+    pub fn remove_sink(&self, id: u64) -> bool {
+        let Ok(mut sinks) = self.sinks.write() else {
+            return false;
+        };
+        let len = sinks.len();
+        sinks.retain(|(sink_id, _)| *sink_id != id);
+        sinks.len() != len
+    }
+
+    /// 分发事件到所有消费者
+    pub fn emit(&self, event: BusEvent) {
+        // 先复制列表再分发，避免消费者内部增删 sink 时死锁
+        let sinks: Vec<Arc<dyn EventSink>> = match self.sinks.read() {
+            Ok(sinks) => sinks.iter().map(|(_, s)| s.clone()).collect(),
+            Err(_) => return,
+        };
+        for sink in sinks {
+            sink.handle(&event);
+        }
+    }
+
+    /// 分发 MaaFramework 回调
+    pub fn emit_callback(&self, instance_id: Option<&str>, message: &str, details: &str) {
+        let payload = MaaCallbackEvent {
+            message: message.to_string(),
+            details: details.to_string(),
+        };
+        self.emit(BusEvent::new(MAA_CALLBACK_EVENT, instance_id, &payload));
+    }
+
+    /// 分发 Agent 输出（移除 ANSI 转义序列）
+    pub fn emit_agent_output(&self, instance_id: &str, stream: &str, line: &str) {
+        let payload = AgentOutputEvent {
+            instance_id: instance_id.to_string(),
+            stream: stream.to_string(),
+            line: super::maa_agent::strip_ansi_escapes(line),
+        };
+        self.emit(BusEvent::new(
+            AGENT_OUTPUT_EVENT,
+            Some(instance_id),
+            &payload,
+        ));
+    }
+
+    /// 生成转发到总线的 MaaFramework sink 回调
+    pub fn callback_sink(
+        self: &Arc<Self>,
+        instance_id: &str,
+    ) -> impl Fn(&str, &str) + Send + Sync + 'static {
+        let bus = self.clone();
+        let instance_id = instance_id.to_string();
+        move |msg, detail| bus.emit_callback(Some(&instance_id), msg, detail)
+    }
+}
+
+// ============================================================================
+// 消费者实现
+// ============================================================================
+
+/// 转发到 Tauri 前端（事件名与载荷保持原有格式）
+pub struct TauriSink {
+    app: AppHandle,
+}
+
+impl TauriSink {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl EventSink for TauriSink {
+    fn handle(&self, event: &BusEvent) {
+        if let Err(e) = self.app.emit(&event.name, &event.payload) {
+            log::error!("Failed to emit {}: {}", event.name, e);
+        }
+    }
+}
+
+/// 转发到 mpsc 通道（供后台线程消费）
+pub struct ChannelSink {
+    sender: Mutex<Sender<BusEvent>>,
+}
+
+impl ChannelSink {
+    /// 创建消费者及对应的接收端
+    pub fn new() -> (Self, Receiver<BusEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        (
+            Self {
+                sender: Mutex::new(sender),
+            },
+            receiver,
+        )
+    }
+}
+
+impl EventSink for ChannelSink {
+    fn handle(&self, event: &BusEvent) {
+        if let Ok(sender) = self.sender.lock() {
+            // 接收端已关闭时忽略
+            let _ = sender.send(event.clone());
+        }
+    }
+}
+
+/// 以 JSON Lines 格式追加写入文件
+pub struct FileRecorderSink {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl FileRecorderSink {
+    pub fn new(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("无法打开事件记录文件 {}: {}", path.display(), e))?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+}
+
+impl EventSink for FileRecorderSink {
+    fn handle(&self, event: &BusEvent) {
+        let Ok(line) = serde_json::to_string(event) else {
+            return;
+        };
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "{}", line);
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// 在内存中收集事件（用于测试和调试）
+#[derive(Default)]
+pub struct CollectorSink {
+    events: Mutex<Vec<BusEvent>>,
+}
+
+impl CollectorSink {
+    /// 已收集事件的快照
+    pub fn events(&self) -> Vec<BusEvent> {
+        self.events.lock().map(|e| e.clone()).unwrap_or_default()
+    }
+
+    /// 清空已收集事件
+    pub fn clear(&self) {
+        if let Ok(mut events) = self.events.lock() {
+            events.clear();
+        }
+    }
+}
+
+impl EventSink for CollectorSink {
+    fn handle(&self, event: &BusEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event.clone());
+        }
+    }
+}
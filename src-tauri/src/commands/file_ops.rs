@@ -5,52 +5,68 @@
 use log::debug;
 use std::path::PathBuf;
 
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
 use super::utils::{get_app_data_dir, get_exe_directory, normalize_path};
 
-fn resolve_local_file_path(filename: &str) -> Result<PathBuf, String> {
+fn resolve_local_file_path(filename: &str) -> MxuResult<PathBuf> {
     let exe_dir = get_exe_directory()?;
     let file_path = normalize_path(&exe_dir.join(filename).to_string_lossy());
     // 防止路径穿越，确保仍在 exe 目录下
     if !file_path.starts_with(&exe_dir) {
-        return Err(format!("非法文件路径: {}", filename));
+        return Err(MxuError::new(
+            ErrorCode::InvalidPath,
+            Subsystem::File,
+            format!("非法文件路径: {}", filename),
+        )
+        .with_path(filename));
     }
     Ok(file_path)
 }
 
 /// 读取 exe 同目录下的文本文件
 #[tauri::command]
-pub fn read_local_file(filename: String) -> Result<String, String> {
+pub fn read_local_file(filename: String) -> MxuResult<String> {
     let file_path = resolve_local_file_path(&filename)?;
     debug!("Reading local file: {:?}", file_path);
 
-    std::fs::read_to_string(&file_path)
-        .map_err(|e| format!("读取文件失败 [{}]: {}", file_path.display(), e))
+    std::fs::read_to_string(&file_path).map_err(|e| {
+        MxuError::io(
+            Subsystem::File,
+            &file_path,
+            format!("读取文件失败 [{}]: {}", file_path.display(), e),
+        )
+    })
 }
 
 /// 读取 exe 同目录下的二进制文件，返回 base64 编码
 #[tauri::command]
-pub fn read_local_file_base64(filename: String) -> Result<String, String> {
+pub fn read_local_file_base64(filename: String) -> MxuResult<String> {
     use base64::{engine::general_purpose::STANDARD, Engine as _};
 
     let file_path = resolve_local_file_path(&filename)?;
     debug!("Reading local file (base64): {:?}", file_path);
 
-    let data = std::fs::read(&file_path)
-        .map_err(|e| format!("读取文件失败 [{}]: {}", file_path.display(), e))?;
+    let data = std::fs::read(&file_path).map_err(|e| {
+        MxuError::io(
+            Subsystem::File,
+            &file_path,
+            format!("读取文件失败 [{}]: {}", file_path.display(), e),
+        )
+    })?;
 
     Ok(STANDARD.encode(&data))
 }
 
 /// 检查 exe 同目录下的文件是否存在
 #[tauri::command]
-pub fn local_file_exists(filename: String) -> Result<bool, String> {
+pub fn local_file_exists(filename: String) -> MxuResult<bool> {
     let file_path = resolve_local_file_path(&filename)?;
     Ok(file_path.exists())
 }
 
 /// 获取 exe 所在目录路径
 #[tauri::command]
-pub fn get_exe_dir() -> Result<String, String> {
+pub fn get_exe_dir() -> MxuResult<String> {
     let exe_dir = get_exe_directory()?;
     Ok(exe_dir.to_string_lossy().to_string())
 }
@@ -59,17 +75,23 @@ pub fn get_exe_dir() -> Result<String, String> {
 /// - macOS: ~/Library/Application Support/MXU/
 /// - Windows/Linux: exe 所在目录
 #[tauri::command]
-pub fn get_data_dir() -> Result<String, String> {
+pub fn get_data_dir() -> MxuResult<String> {
     let data_dir = get_app_data_dir()?;
     Ok(data_dir.to_string_lossy().to_string())
 }
 
 /// 获取当前工作目录
 #[tauri::command]
-pub fn get_cwd() -> Result<String, String> {
+pub fn get_cwd() -> MxuResult<String> {
     std::env::current_dir()
         .map(|p| p.to_string_lossy().to_string())
-        .map_err(|e| format!("Failed to get current directory: {}", e))
+        .map_err(|e| {
+            MxuError::new(
+                ErrorCode::Io,
+                Subsystem::System,
+                format!("Failed to get current directory: {}", e),
+            )
+        })
 }
 
 /// 检查 exe 路径是否存在问题
@@ -144,18 +166,28 @@ pub fn check_exe_path() -> Option<String> {
 /// 为文件设置可执行权限（仅 Unix 系统）
 /// Windows 上此命令不做任何操作
 #[tauri::command]
-pub fn set_executable(file_path: String) -> Result<(), String> {
+pub fn set_executable(file_path: String) -> MxuResult<()> {
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let metadata = std::fs::metadata(&file_path)
-            .map_err(|e| format!("无法获取文件元数据 [{}]: {}", file_path, e))?;
+        let metadata = std::fs::metadata(&file_path).map_err(|e| {
+            MxuError::io(
+                Subsystem::File,
+                &file_path,
+                format!("无法获取文件元数据 [{}]: {}", file_path, e),
+            )
+        })?;
         let mut permissions = metadata.permissions();
         // 添加可执行权限 (owner, group, others)
         let mode = permissions.mode() | 0o111;
         permissions.set_mode(mode);
-        std::fs::set_permissions(&file_path, permissions)
-            .map_err(|e| format!("无法设置执行权限 [{}]: {}", file_path, e))?;
+        std::fs::set_permissions(&file_path, permissions).map_err(|e| {
+            MxuError::io(
+                Subsystem::File,
+                &file_path,
+                format!("无法设置执行权限 [{}]: {}", file_path, e),
+            )
+        })?;
         log::info!("Set executable permission: {}", file_path);
     }
     #[cfg(not(unix))]
@@ -171,7 +203,7 @@ pub fn set_executable(file_path: String) -> Result<(), String> {
 pub fn export_logs(
     project_name: Option<String>,
     project_version: Option<String>,
-) -> Result<String, String> {
+) -> MxuResult<String> {
     use std::fs::File;
     use std::io::{Read, Write};
     use zip::write::SimpleFileOptions;
@@ -182,7 +214,10 @@ pub fn export_logs(
     let debug_dir = data_dir.join("debug");
 
     if !debug_dir.exists() {
-        return Err("日志目录不存在".to_string());
+        return Err(
+            MxuError::new(ErrorCode::NotFound, Subsystem::File, "日志目录不存在")
+                .with_path(&debug_dir),
+        );
     }
 
     // 生成带时间戳的文件名：项目名-版本号-日期.zip
@@ -197,7 +232,13 @@ pub fn export_logs(
     };
     let zip_path = debug_dir.join(&filename);
 
-    let file = File::create(&zip_path).map_err(|e| format!("创建压缩文件失败: {}", e))?;
+    let file = File::create(&zip_path).map_err(|e| {
+        MxuError::io(
+            Subsystem::Archive,
+            &zip_path,
+            format!("创建压缩文件失败: {}", e),
+        )
+    })?;
     let mut zip = ZipWriter::new(file);
     let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
@@ -249,7 +290,13 @@ pub fn export_logs(
     }
 
     // 遍历 debug 目录下的所有 .log 文件
-    let entries = std::fs::read_dir(&debug_dir).map_err(|e| format!("读取日志目录失败: {}", e))?;
+    let entries = std::fs::read_dir(&debug_dir).map_err(|e| {
+        MxuError::io(
+            Subsystem::File,
+            &debug_dir,
+            format!("读取日志目录失败: {}", e),
+        )
+    })?;
 
     for entry in entries.flatten() {
         let path = entry.path();
@@ -296,7 +343,22 @@ pub fn export_logs(
         }
     }
 
-    zip.finish().map_err(|e| format!("完成压缩失败: {}", e))?;
+    // 处理 sessions 文件夹（只包含最近3个会话包）
+    for path in crate::recorder::bundle::recent(&debug_dir.join("sessions"), 3) {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let archive_name = format!("sessions/{}", name.to_string_lossy());
+        add_file_to_zip(&mut zip, &path, &archive_name, options);
+    }
+
+    zip.finish().map_err(|e| {
+        MxuError::io(
+            Subsystem::Archive,
+            &zip_path,
+            format!("完成压缩失败: {}", e),
+        )
+    })?;
 
     Ok(zip_path.to_string_lossy().to_string())
 }
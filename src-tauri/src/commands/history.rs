@@ -0,0 +1,50 @@
+//! 运行历史相关命令
+//!
+//! 记录本身见 `crate::history`，运行结束后由后端自动写入
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use super::error::MxuResult;
+use super::types::MaaState;
+use crate::history::stats::{self, RunStats};
+use crate::history::store::RunQuery;
+use crate::history::RunRecord;
+
+/// 查询运行历史（按开始时间从新到旧）
+/// since / until 为毫秒时间戳，按运行开始时间筛选（包含 since，不包含 until）
+#[tauri::command]
+pub fn history_query_runs(
+    state: State<Arc<MaaState>>,
+    instance_id: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    limit: Option<usize>,
+) -> MxuResult<Vec<RunRecord>> {
+    state.history.query(&RunQuery {
+        instance_id,
+        since,
+        until,
+        limit,
+    })
+}
+
+/// 统计运行历史（筛选条件同 history_query_runs）
+/// by_resource 为 true 时按 入口 + 资源哈希 拆分入口统计，用于对比资源更新前后的表现
+#[tauri::command]
+pub fn history_stats(
+    state: State<Arc<MaaState>>,
+    instance_id: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    by_resource: Option<bool>,
+) -> MxuResult<RunStats> {
+    let records = state.history.query(&RunQuery {
+        instance_id,
+        since,
+        until,
+        limit: None,
+    })?;
+    Ok(stats::compute(&records, by_resource.unwrap_or(false)))
+}
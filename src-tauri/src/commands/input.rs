@@ -0,0 +1,111 @@
+//! 控制器输入命令
+//!
+//! 直接向实例的控制器发送点击、滑动、按键、输入文本、启动/停止应用等动作，供截图面板的
+//! 远程操控与本地控制接口的脚本化恢复使用。发送后立即返回请求 ID，可再等待动作完成
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::info;
+use maa_framework::MaaStatus;
+use tauri::State;
+
+use super::backend::ControllerBackend;
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::types::{ControllerInput, MaaState, TaskStatus};
+
+/// 等待动作完成的默认超时
+pub const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 等待期间查询请求状态的间隔
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 取出实例的控制器（require_connected 时要求已连接）
+fn instance_controller(
+    state: &MaaState,
+    instance_id: &str,
+    require_connected: bool,
+) -> MxuResult<Arc<dyn ControllerBackend>> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+    instance
+        .controller
+        .clone()
+        .filter(|c| !require_connected || c.connected())
+        .ok_or_else(|| MxuError::controller_not_connected(instance_id))
+}
+
+/// 发送输入动作，返回控制器请求 ID
+pub fn post_input(state: &MaaState, instance_id: &str, input: &ControllerInput) -> MxuResult<i64> {
+    let controller = instance_controller(state, instance_id, true)?;
+    let ctrl_id = controller.post_input(input)?;
+    info!(
+        "Controller input {} posted to {}, ctrl_id: {}",
+        input.action(),
+        instance_id,
+        ctrl_id
+    );
+    Ok(ctrl_id)
+}
+
+/// 等待控制器请求结束（阻塞调用），超时返回当前状态（Pending / Running）
+pub fn wait_input(
+    state: &MaaState,
+    instance_id: &str,
+    ctrl_id: i64,
+    timeout: Duration,
+) -> MxuResult<TaskStatus> {
+    let controller = instance_controller(state, instance_id, false)?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        let status = match controller.status(ctrl_id) {
+            MaaStatus::SUCCEEDED => return Ok(TaskStatus::Succeeded),
+            MaaStatus::FAILED => return Ok(TaskStatus::Failed),
+            MaaStatus::RUNNING => TaskStatus::Running,
+            MaaStatus::PENDING => TaskStatus::Pending,
+            _ => {
+                return Err(MxuError::new(
+                    ErrorCode::NotFound,
+                    Subsystem::Controller,
+                    format!("Unknown controller request: {}", ctrl_id),
+                )
+                .with_instance(instance_id))
+            }
+        };
+        if Instant::now() >= deadline {
+            return Ok(status);
+        }
+        thread::sleep(WAIT_POLL_INTERVAL);
+    }
+}
+
+/// 发送输入动作（点击、滑动、按键、输入文本、启动/停止应用），返回控制器请求 ID
+#[tauri::command]
+pub fn maa_post_input(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    input: ControllerInput,
+) -> MxuResult<i64> {
+    post_input(&state, &instance_id, &input)
+}
+
+/// 等待输入动作完成，超时（默认 10 秒）返回当前状态
+#[tauri::command]
+pub async fn maa_wait_input(
+    state: State<'_, Arc<MaaState>>,
+    instance_id: String,
+    ctrl_id: i64,
+    timeout_ms: Option<u64>,
+) -> MxuResult<TaskStatus> {
+    let state_arc = state.inner().clone();
+    let timeout = timeout_ms.map_or(DEFAULT_WAIT_TIMEOUT, Duration::from_millis);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        wait_input(&state_arc, &instance_id, ctrl_id, timeout)
+    })
+    .await
+    .map_err(|e| MxuError::internal(e.to_string()))?
+}
@@ -10,49 +10,42 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 
 use chrono::Local;
-use tauri::{Emitter, State};
+use tauri::State;
 
 use maa_framework::agent_client::AgentClient;
 use maa_framework::controller::Controller;
 use maa_framework::resource::Resource;
 use maa_framework::tasker::Tasker;
 
-use super::types::{AgentConfig, MaaState, TaskConfig};
-use super::utils::{emit_callback_event, get_logs_dir, normalize_path};
+use super::backend::native::native_handles;
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::event_bus::EventBus;
+use super::maa_core::create_tasker;
+use super::pipeline_check::{resource_nodes, validate_tasks};
+use super::task_timeout::TimeoutMonitor;
+use super::types::{
+    AgentConfig, InstanceRuntime, MaaState, OverrideError, PostedTask, RunOptions,
+    StartTasksResult, TaskConfig,
+};
+use super::utils::{get_logs_dir, normalize_path};
 use regex::Regex;
 use std::sync::LazyLock;
 
-/// Agent 输出事件载荷
-#[derive(Clone, serde::Serialize)]
-pub struct AgentOutputEvent {
-    pub instance_id: String,
-    pub stream: String,
-    pub line: String,
-}
-
-/// 发送 Agent 输出事件
-fn emit_agent_output(app: &tauri::AppHandle, instance_id: &str, stream: &str, line: &str) {
-    let event = AgentOutputEvent {
-        instance_id: instance_id.to_string(),
-        stream: stream.to_string(),
-        line: strip_ansi_escapes(line),
-    };
-    if let Err(e) = app.emit("maa-agent-output", event) {
-        log::error!("[agent_output] Failed to emit event: {}", e);
-    }
-}
-
 /// 移除 ANSI 转义序列
 static ANSI_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]|\x1b\][^\x07]*\x07?").unwrap());
 
-fn strip_ansi_escapes(s: &str) -> String {
+pub fn strip_ansi_escapes(s: &str) -> String {
     ANSI_RE.replace_all(s, "").into_owned()
 }
 
+fn agent_error(message: impl Into<String>) -> MxuError {
+    MxuError::new(ErrorCode::AgentStartFailed, Subsystem::Agent, message)
+}
+
 /// 启动单个 Agent 子进程并完成连接
 async fn start_single_agent(
-    app: tauri::AppHandle,
+    bus: Arc<EventBus>,
     agent: AgentConfig,
     agent_index: usize,
     instance_id: String,
@@ -61,7 +54,7 @@ async fn start_single_agent(
     resource: Resource,
     controller: Controller,
     tasker: Tasker,
-) -> Result<(AgentClient, std::process::Child), String> {
+) -> MxuResult<(AgentClient, std::process::Child)> {
     info!("[agent#{}] Starting agent: {:?}", agent_index, agent);
 
     // 将整个启动过程移入 spawn_blocking，避免阻塞 async runtime 线程
@@ -74,20 +67,20 @@ async fn start_single_agent(
                     agent_index, e
                 );
                 AgentClient::new(None)
-            }).map_err(|e| e.to_string())?
+            }).map_err(|e| agent_error(e.to_string()))?
         } else {
             debug!("[agent#{}] Creating default agent client...", agent_index);
-            AgentClient::new(None).map_err(|e| e.to_string())?
+            AgentClient::new(None).map_err(|e| agent_error(e.to_string()))?
         };
 
         if let Err(e) = client.bind(resource.clone()) {
             warn!("[agent#{}] Failed to bind resource: {}", agent_index, e);
-            return Err(e.to_string());
+            return Err(agent_error(e.to_string()));
         }
 
         let socket_id = client
             .identifier()
-            .ok_or_else(|| format!("Failed to get identifier for agent #{}", agent_index))?;
+            .ok_or_else(|| agent_error(format!("Failed to get identifier for agent #{}", agent_index)))?;
         info!("[agent#{}] Agent socket_id: {}", agent_index, socket_id);
 
         // 启动子进程
@@ -122,10 +115,11 @@ async fn start_single_agent(
             .stderr(Stdio::piped());
 
         let mut child = cmd.spawn().map_err(|e| {
-            format!(
+            agent_error(format!(
                 "Failed to spawn agent #{}: {} (path: {:?})",
                 agent_index, e, exec_path
-            )
+            ))
+            .with_path(&exec_path)
         })?;
 
         // 创建 agent 日志文件（多 agent、多实例时使用不同文件名，包含进程 PID）
@@ -143,7 +137,7 @@ async fn start_single_agent(
         // 在单独线程中读取 stdout
         if let Some(stdout) = child.stdout.take() {
             let lf = log_file.clone();
-            let app_handle = app.clone();
+            let bus = bus.clone();
             let inst_id = instance_id.clone();
             thread::spawn(move || {
                 let mut reader = BufReader::new(stdout);
@@ -162,7 +156,7 @@ async fn start_single_agent(
                                 }
                             }
                             info!(target: "agent", "[agent#{}][stdout] {}", agent_index, clean_line);
-                            emit_agent_output(&app_handle, &inst_id, "stdout", clean_line);
+                            bus.emit_agent_output(&inst_id, "stdout", clean_line);
                         }
                         Err(_) => break,
                     }
@@ -173,7 +167,7 @@ async fn start_single_agent(
         // Stderr thread
         if let Some(stderr) = child.stderr.take() {
             let lf = log_file.clone();
-            let app_handle = app.clone();
+            let bus = bus.clone();
             let inst_id = instance_id.clone();
             thread::spawn(move || {
                 let mut reader = BufReader::new(stderr);
@@ -192,7 +186,7 @@ async fn start_single_agent(
                                 }
                             }
                             warn!(target: "agent", "[agent#{}][stderr] {}", agent_index, clean_line);
-                            emit_agent_output(&app_handle, &inst_id, "stderr", clean_line);
+                            bus.emit_agent_output(&inst_id, "stderr", clean_line);
                         }
                         Err(_) => break,
                     }
@@ -212,7 +206,7 @@ async fn start_single_agent(
              error!("[agent#{}] Connection failed: {}", agent_index, e);
              let _ = child.kill();
              let _ = child.wait();
-             return Err(e.to_string());
+             return Err(agent_error(e.to_string()));
         }
 
         info!("[agent#{}] Connected successfully!", agent_index);
@@ -222,82 +216,82 @@ async fn start_single_agent(
             error!("[agent#{}] Failed to register sinks: {}", agent_index, e);
             let _ = child.kill();
             let _ = child.wait();
-            return Err(e.to_string());
+            return Err(agent_error(e.to_string()));
         }
 
         Ok((client, child))
-    }).await.map_err(|e| e.to_string())?
+    }).await.map_err(|e| MxuError::internal(e.to_string()))?
 }
 
 /// 启动任务（支持多个 Agent）
 #[tauri::command]
 pub async fn maa_start_tasks(
-    app: tauri::AppHandle,
     state: State<'_, Arc<MaaState>>,
     instance_id: String,
     tasks: Vec<TaskConfig>,
     agent_configs: Option<Vec<AgentConfig>>,
     cwd: String,
     tcp_compat_mode: bool,
-) -> Result<Vec<i64>, String> {
+    run_options: Option<RunOptions>,
+) -> MxuResult<StartTasksResult> {
     info!("maa_start_tasks called");
 
     info!("instance_id: {}", instance_id);
     info!("tasks: {:?}", tasks);
     info!("agent_configs: {:?}", agent_configs);
     info!("cwd: {}, tcp_compat_mode: {}", cwd, tcp_compat_mode);
+    info!("run_options: {:?}", run_options);
+
+    start_tasks(
+        state.inner(),
+        &instance_id,
+        &tasks,
+        agent_configs,
+        &cwd,
+        tcp_compat_mode,
+        &run_options.unwrap_or_default(),
+    )
+    .await
+}
 
+/// 启动 Agent 并提交任务，返回成功提交的 task_id 列表与未提交的任务（供 Tauri 命令和 mxu-cli 共用）
+/// 配置了超时时启动超时监视
+pub async fn start_tasks(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    tasks: &[TaskConfig],
+    agent_configs: Option<Vec<AgentConfig>>,
+    cwd: &str,
+    tcp_compat_mode: bool,
+    run_options: &RunOptions,
+) -> MxuResult<StartTasksResult> {
     let (resource, controller, tasker) = {
         debug!("[start_tasks] Acquiring instances lock...");
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let mut instances = state.instances.lock()?;
         debug!("[start_tasks] Instances lock acquired");
         let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
+            .get_mut(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
         debug!("[start_tasks] Instance found: {}", instance_id);
 
         let res = instance
             .resource
             .as_ref()
-            .ok_or("Resource not loaded")?
+            .ok_or_else(|| MxuError::resource_not_loaded(instance_id))?
             .clone();
         debug!("[start_tasks] Resource acquired");
 
         let ctrl = instance
             .controller
             .as_ref()
-            .ok_or("Controller not connected")?
+            .ok_or_else(|| MxuError::controller_not_connected(instance_id))?
             .clone();
         debug!("[start_tasks] Controller acquired");
 
         // 创建或获取 tasker
         if instance.tasker.is_none() {
             debug!("[start_tasks] Creating new tasker...");
-            let t = Tasker::new().map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Tasker created");
-
-            // 添加回调 Sink，用于接收任务状态通知
-            debug!("[start_tasks] Adding tasker sink...");
-            let app_handle = app.clone();
-            t.add_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Tasker sink added");
-
-            // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
-            debug!("[start_tasks] Adding tasker context sink...");
-            let app_handle = app.clone();
-            t.add_context_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Tasker context sink added");
-
-            debug!("[start_tasks] Binding resource and controller...");
-            t.bind(&res, &ctrl).map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Resource and controller bound");
-
+            let t = create_tasker(state, &res, &ctrl, instance_id)?;
             instance.tasker = Some(t);
             debug!("[start_tasks] Tasker created and stored");
         } else {
@@ -312,9 +306,35 @@ pub async fn maa_start_tasks(
     // 检查 Tasker 初始化状态
     if !tasker.inited() {
         error!("[start_tasks] Tasker not properly initialized");
-        return Err("Tasker not properly initialized".to_string());
+        return Err(MxuError::new(
+            ErrorCode::TaskerNotInitialized,
+            Subsystem::Tasker,
+            "Tasker not properly initialized",
+        )
+        .with_instance(instance_id));
+    }
+
+    // 校验 Pipeline 覆盖，未通过的任务不提交
+    let nodes = resource_nodes(resource.as_ref());
+    let mut validations = validate_tasks(tasks, nodes.as_ref());
+    for rejected in validations.iter().filter(|v| !v.errors.is_empty()) {
+        warn!(
+            "[start_tasks] Task {} ({}) rejected: {:?}",
+            rejected.index, rejected.entry, rejected.errors
+        );
+    }
+    for warned in validations
+        .iter()
+        .filter(|v| v.errors.is_empty() && !v.warnings.is_empty())
+    {
+        warn!(
+            "[start_tasks] Task {} ({}) override warnings: {:?}",
+            warned.index, warned.entry, warned.warnings
+        );
     }
 
+    let agent_count = agent_configs.as_ref().map_or(0, Vec::len);
+
     // 启动所有 Agent（如果配置了）
     debug!("[start_tasks] Checking agent configs...");
     if let Some(configs) = agent_configs {
@@ -323,6 +343,21 @@ pub async fn maa_start_tasks(
         } else {
             info!("[start_tasks] Starting {} agent(s)...", configs.len());
 
+            // Agent 需要绑定原生 MaaFramework 句柄
+            let (resource, controller, tasker) =
+                native_handles(resource.as_ref(), controller.as_ref(), tasker.as_ref())
+                    .ok_or_else(|| {
+                        MxuError::new(
+                            ErrorCode::AgentUnsupported,
+                            Subsystem::Agent,
+                            format!(
+                                "Agent is not supported by the {} backend",
+                                state.backend.name()
+                            ),
+                        )
+                        .with_instance(instance_id)
+                    })?;
+
             // 用于收集所有成功启动的 agent，失败时需要回滚清理
             let mut new_clients = Vec::new();
             let mut new_children = Vec::new();
@@ -331,12 +366,12 @@ pub async fn maa_start_tasks(
                 let res_clone = resource.clone();
                 let ctrl_clone = controller.clone();
                 let tasker_clone = tasker.clone();
-                let app_handle = app.clone();
-                let inst_id = instance_id.clone();
-                let cwd_clone = cwd.clone();
+                let bus = state.event_bus.clone();
+                let inst_id = instance_id.to_string();
+                let cwd_clone = cwd.to_string();
 
                 match start_single_agent(
-                    app_handle,
+                    bus,
                     config.clone(),
                     idx,
                     inst_id,
@@ -366,14 +401,15 @@ pub async fn maa_start_tasks(
                             let _ = child.kill();
                             let _ = child.wait();
                         }
-                        return Err(format!("Agent start failed: {}", e));
+                        return Err(agent_error(format!("Agent start failed: {}", e))
+                            .with_instance(instance_id));
                     }
                 }
             }
 
             // 保存所有 agent 状态到 instance
-            let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-            if let Some(instance) = instances.get_mut(&instance_id) {
+            let mut instances = state.instances.lock()?;
+            if let Some(instance) = instances.get_mut(instance_id) {
                 instance.agent_clients.extend(new_clients);
                 instance.agent_children.extend(new_children);
             }
@@ -389,9 +425,16 @@ pub async fn maa_start_tasks(
         debug!("[start_tasks] No agent configs, skipping agent setup");
     };
 
+    // 记录运行历史（Agent 全部启动后才算开始运行）
+    state.history.begin_run(instance_id, agent_count);
+
     debug!("[start_tasks] Submitting {} tasks...", tasks.len());
     let mut task_ids = Vec::new();
+    let mut posted_tasks = Vec::new();
     for (idx, task) in tasks.iter().enumerate() {
+        if !validations[idx].errors.is_empty() {
+            continue;
+        }
         debug!("[start_tasks] Preparing task {}: entry={}", idx, task.entry);
 
         info!(
@@ -399,16 +442,31 @@ pub async fn maa_start_tasks(
             task.entry, task.pipeline_override
         );
         match tasker.post_task(&task.entry, &task.pipeline_override) {
-            Ok(job) => {
-                info!("[start_tasks] post_task returned task_id: {}", job.id);
-                task_ids.push(job.id);
+            Ok(task_id) => {
+                info!("[start_tasks] post_task returned task_id: {}", task_id);
+                task_ids.push(task_id);
+                posted_tasks.push(PostedTask {
+                    task_id,
+                    config: task.clone(),
+                    retries: 0,
+                });
+                state.history.task_posted(
+                    instance_id,
+                    task_id,
+                    &task.entry,
+                    &task.pipeline_override,
+                );
                 debug!(
                     "[start_tasks] Task {} submitted successfully, task_id: {}",
-                    idx, job.id
+                    idx, task_id
                 );
             }
-            Err(_e) => {
-                warn!("[start_tasks] Failed to post task: {}", task.entry);
+            Err(e) => {
+                warn!("[start_tasks] Failed to post task: {}: {}", task.entry, e);
+                validations[idx].errors.push(OverrideError {
+                    path: String::new(),
+                    message: e.to_string(),
+                });
             }
         }
     }
@@ -417,35 +475,55 @@ pub async fn maa_start_tasks(
         "[start_tasks] All tasks submitted, total: {} task_ids",
         task_ids.len()
     );
+    state.history.seal_run(instance_id);
 
     // 缓存 task_ids，用于刷新后恢复状态
     debug!("[start_tasks] Caching task_ids...");
     {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        if let Some(instance) = instances.get_mut(&instance_id) {
+        let mut instances = state.instances.lock()?;
+        if let Some(instance) = instances.get_mut(instance_id) {
             instance.task_ids = task_ids.clone();
+            instance.timeout_monitor =
+                TimeoutMonitor::start(state, instance_id, run_options, &posted_tasks);
+            instance.posted_tasks = posted_tasks;
+            instance.clear_pause();
+            state.task_queue.remove_instance(instance_id);
+            state.task_retry.watch(state, instance_id, instance);
         }
     }
     debug!("[start_tasks] Task_ids cached");
 
+    let (rejected, warnings): (Vec<_>, Vec<_>) = validations
+        .into_iter()
+        .filter(|v| !v.errors.is_empty() || !v.warnings.is_empty())
+        .partition(|v| !v.errors.is_empty());
     info!(
-        "[start_tasks] maa_start_tasks completed successfully, returning {} task_ids",
-        task_ids.len()
+        "[start_tasks] maa_start_tasks completed successfully, returning {} task_ids, {} rejected",
+        task_ids.len(),
+        rejected.len()
     );
-    Ok(task_ids)
+    Ok(StartTasksResult {
+        task_ids,
+        rejected,
+        warnings,
+    })
 }
 
 /// 停止所有 Agent 并断开连接（异步执行，避免阻塞 UI）
 /// 不强制 kill 子进程，等待 MaaTaskerPostStop 触发子进程自行退出
 #[tauri::command]
-pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> Result<(), String> {
+pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> MxuResult<()> {
     info!("maa_stop_agent called for instance: {}", instance_id);
+    stop_agents(&state, &instance_id)
+}
 
+/// 断开实例的所有 Agent，并在后台线程等待子进程退出
+pub fn stop_agents(state: &MaaState, instance_id: &str) -> MxuResult<()> {
     let (clients, children) = {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let mut instances = state.instances.lock()?;
         let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
+            .get_mut(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
 
         // 取出所有 agent clients 和 children，准备在后台线程清理
         (
@@ -465,6 +543,8 @@ pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> R
         children.len()
     );
 
+    let history = state.history.clone();
+    let instance_id = instance_id.to_string();
     thread::spawn(move || {
         // 断开所有客户端连接
         for client in clients {
@@ -472,6 +552,7 @@ pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> R
         }
 
         // 等待子进程退出
+        let mut exit_codes = Vec::new();
         for (i, mut child) in children.into_iter().enumerate() {
             debug!("Waiting for agent process #{} to exit...", i);
 
@@ -482,8 +563,9 @@ pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> R
             // 同步轮询子进程状态
             while start.elapsed() < timeout {
                 match child.try_wait() {
-                    Ok(Some(_)) => {
+                    Ok(Some(status)) => {
                         exited = true;
+                        exit_codes.push(status.code());
                         break;
                     }
                     Ok(None) => {
@@ -500,12 +582,49 @@ pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> R
             if !exited {
                 warn!("Agent process #{} did not exit in time, killing it...", i);
                 let _ = child.kill();
-                let _ = child.wait();
+                exit_codes.push(child.wait().ok().and_then(|status| status.code()));
             } else {
                 info!("Background: Agent #{} child process exited", i);
             }
         }
+
+        history.agents_exited(&instance_id, exit_codes);
     });
 
     Ok(())
 }
+
+/// 控制器重新连接后，将实例 Agent 的控制器与任务执行器事件转发改绑到新的对象上
+/// （Agent 绑定的资源不变，自定义识别与动作无需重新注册）
+pub fn rebind_agent_sinks(instance: &mut InstanceRuntime) -> MxuResult<()> {
+    if instance.agent_clients.is_empty() {
+        return Ok(());
+    }
+    let (Some(resource), Some(controller), Some(tasker)) =
+        (&instance.resource, &instance.controller, &instance.tasker)
+    else {
+        return Ok(());
+    };
+    let (_, controller, tasker) =
+        native_handles(resource.as_ref(), controller.as_ref(), tasker.as_ref()).ok_or_else(
+            || {
+                MxuError::new(
+                    ErrorCode::AgentUnsupported,
+                    Subsystem::Agent,
+                    "Agent requires native MaaFramework handles",
+                )
+            },
+        )?;
+
+    for client in instance.agent_clients.iter_mut() {
+        client
+            .register_controller_sink(controller.clone())
+            .and_then(|_| client.register_tasker_sink(tasker.clone()))
+            .map_err(|e| agent_error(e.to_string()))?;
+    }
+    info!(
+        "[rebind_agent_sinks] Rebound {} agent(s) to the new controller",
+        instance.agent_clients.len()
+    );
+    Ok(())
+}
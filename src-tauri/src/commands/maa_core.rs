@@ -6,19 +6,20 @@ use log::{debug, error, info, warn};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use serde::Serialize;
 use tauri::State;
 
-use maa_framework::controller::{AdbControllerBuilder, Controller};
-use maa_framework::resource::Resource;
-use maa_framework::tasker::Tasker;
 use maa_framework::toolkit::Toolkit;
-use maa_framework::MaaStatus;
 
+use super::backend::{ControllerBackend, RecognitionImages, ResourceBackend, TaskerBackend};
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::pipeline_check::{resource_nodes, validate_task, validation_error};
+use super::screenshot::{cached_screenshot, encode, ScreenshotOptions};
 use super::types::{
-    AdbDevice, ConnectionStatus, ControllerConfig, MaaState, TaskStatus, VersionCheckResult,
-    Win32Window,
+    AdbDevice, ConnectionStatus, ControllerConfig, MaaState, PostedTask, ScreenshotResolution,
+    TaskConfig, TaskDetail, TaskStatus, VersionCheckResult, Win32Window,
 };
-use super::utils::{emit_callback_event, get_maafw_dir, normalize_path};
+use super::utils::{get_maafw_dir, normalize_path};
 
 /// MaaFramework 最小支持版本
 const MIN_MAAFW_VERSION: &str = "5.5.0-beta.1";
@@ -30,9 +31,13 @@ const MIN_MAAFW_VERSION: &str = "5.5.0-beta.1";
 /// 初始化 MaaFramework
 /// 如果提供 lib_dir 则使用该路径，否则自动从 exe 目录/maafw 加载
 #[tauri::command]
-pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<String, String> {
+pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> MxuResult<String> {
     info!("maa_init called, lib_dir: {:?}", lib_dir);
+    init_maa_library(&state, lib_dir)
+}
 
+/// 加载 MaaFramework 库并初始化 Toolkit（供 Tauri 命令和 mxu-cli 共用）
+pub fn init_maa_library(state: &MaaState, lib_dir: Option<String>) -> MxuResult<String> {
     let lib_path = match lib_dir {
         Some(dir) if !dir.is_empty() => std::path::PathBuf::from(&dir),
         _ => get_maafw_dir()?,
@@ -46,7 +51,9 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
             lib_path.display()
         );
         error!("{}", err);
-        return Err(err);
+        return Err(
+            MxuError::new(ErrorCode::LibraryNotFound, Subsystem::Maa, err).with_path(&lib_path),
+        );
     }
 
     // Windows: 将 lib_dir 添加到 DLL 搜索路径，确保依赖 DLL 能被找到
@@ -86,7 +93,7 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
     } else {
         lib_path.clone()
     };
-    *state.lib_dir.lock().map_err(|e| e.to_string())? = Some(effective_dir);
+    *state.lib_dir.lock()? = Some(effective_dir);
 
     // 加载库
     // 允许用户指定具体的文件路径，或者只指定目录
@@ -107,7 +114,11 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
         Err(e) if e.contains("already loaded") => {
             info!("maa_init library already loaded, skipping");
         }
-        Err(e) => return Err(e),
+        Err(e) => {
+            return Err(
+                MxuError::new(ErrorCode::LibraryLoadFailed, Subsystem::Maa, e).with_path(&dll_path),
+            )
+        }
     }
 
     // 初始化 Toolkit
@@ -130,36 +141,38 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
 
 /// 设置资源目录
 #[tauri::command]
-pub fn maa_set_resource_dir(
-    state: State<Arc<MaaState>>,
-    resource_dir: String,
-) -> Result<(), String> {
+pub fn maa_set_resource_dir(state: State<Arc<MaaState>>, resource_dir: String) -> MxuResult<()> {
     info!(
         "maa_set_resource_dir called, resource_dir: {}",
         resource_dir
     );
-    *state.resource_dir.lock().map_err(|e| e.to_string())? =
-        Some(std::path::PathBuf::from(&resource_dir));
+    *state.resource_dir.lock()? = Some(std::path::PathBuf::from(&resource_dir));
     info!("maa_set_resource_dir success");
     Ok(())
 }
 
 /// 获取 MaaFramework 版本
 #[tauri::command]
-pub fn maa_get_version() -> Result<String, String> {
+pub fn maa_get_version() -> MxuResult<String> {
     debug!("maa_get_version called");
-    let version = std::panic::catch_unwind(|| maa_framework::maa_version().to_string())
-        .map_err(|_| "MaaFramework library not loaded".to_string())?;
+    let version =
+        std::panic::catch_unwind(|| maa_framework::maa_version().to_string()).map_err(|_| {
+            MxuError::new(
+                ErrorCode::LibraryNotLoaded,
+                Subsystem::Maa,
+                "MaaFramework library not loaded",
+            )
+        })?;
     info!("maa_get_version result: {}", version);
     Ok(version)
 }
 
 /// 检查 MaaFramework 版本是否满足最小要求
 #[tauri::command]
-pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResult, String> {
+pub fn maa_check_version(state: State<Arc<MaaState>>) -> MxuResult<VersionCheckResult> {
     debug!("maa_check_version called");
 
-    let lib_dir = state.lib_dir.lock().map_err(|e| e.to_string())?.clone();
+    let lib_dir = state.lib_dir.lock()?.clone();
 
     if let Some(dir) = lib_dir {
         #[cfg(windows)]
@@ -175,16 +188,31 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
                     "Failed to load MaaFramework library from {:?}: {:?}",
                     dll_path, e
                 );
-                return Err(format!("MaaFramework library failed to load: {}", e));
+                return Err(MxuError::new(
+                    ErrorCode::LibraryLoadFailed,
+                    Subsystem::Maa,
+                    format!("MaaFramework library failed to load: {}", e),
+                )
+                .with_path(&dll_path));
             }
         }
     }
 
     let current_str = std::panic::catch_unwind(|| maa_framework::maa_version().to_string())
-        .map_err(|_| "MaaFramework library not loaded (panic in maa_version)".to_string())?;
+        .map_err(|_| {
+            MxuError::new(
+                ErrorCode::LibraryNotLoaded,
+                Subsystem::Maa,
+                "MaaFramework library not loaded (panic in maa_version)",
+            )
+        })?;
 
     if current_str == "unknown" || current_str.is_empty() {
-        return Err("MaaFramework not initialized".to_string());
+        return Err(MxuError::new(
+            ErrorCode::LibraryNotLoaded,
+            Subsystem::Maa,
+            "MaaFramework not initialized",
+        ));
     }
 
     // 去掉版本号前缀 'v'（如 "v5.5.0-beta.1" -> "5.5.0-beta.1"）
@@ -192,8 +220,13 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
     let min_clean = MIN_MAAFW_VERSION.trim_start_matches('v');
 
     // 解析最小版本（这个应该总是成功的）
-    let minimum = semver::Version::parse(min_clean)
-        .map_err(|e| format!("Failed to parse minimum version '{}': {}", min_clean, e))?;
+    let minimum = semver::Version::parse(min_clean).map_err(|e| {
+        MxuError::new(
+            ErrorCode::VersionParseFailed,
+            Subsystem::Maa,
+            format!("Failed to parse minimum version '{}': {}", min_clean, e),
+        )
+    })?;
 
     // 尝试解析当前版本，如果解析失败（如 "DEBUG_VERSION"），视为不兼容
     let is_compatible = semver::Version::parse(current_clean).is_ok_and(|v| v >= minimum);
@@ -211,38 +244,40 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
 
 /// 查找 ADB 设备（结果会缓存到 MaaState）
 #[tauri::command]
-pub async fn maa_find_adb_devices(
-    state: State<'_, Arc<MaaState>>,
-) -> Result<Vec<AdbDevice>, String> {
+pub async fn maa_find_adb_devices(state: State<'_, Arc<MaaState>>) -> MxuResult<Vec<AdbDevice>> {
     info!("maa_find_adb_devices called");
 
     let state_arc = state.inner().clone();
 
-    tauri::async_runtime::spawn_blocking(move || {
-        let devices = Toolkit::find_adb_devices().map_err(|e| e.to_string())?;
-
-        let result_devices: Vec<AdbDevice> = devices
-            .into_iter()
-            .map(|d| AdbDevice {
-                name: d.name,
-                adb_path: d.adb_path.to_string_lossy().to_string(),
-                address: d.address,
-                screencap_methods: d.screencap_methods,
-                input_methods: d.input_methods,
-                config: d.config.to_string(),
-            })
-            .collect();
+    tauri::async_runtime::spawn_blocking(move || search_adb_devices(&state_arc))
+        .await
+        .map_err(|e| MxuError::internal(e.to_string()))?
+}
 
-        // 缓存搜索结果
-        if let Ok(mut cached) = state_arc.cached_adb_devices.lock() {
-            *cached = result_devices.clone();
-        }
+/// 搜索 ADB 设备并缓存结果（阻塞调用）
+pub fn search_adb_devices(state: &MaaState) -> MxuResult<Vec<AdbDevice>> {
+    let devices =
+        Toolkit::find_adb_devices().map_err(|e| MxuError::maa(Subsystem::Controller, e))?;
+
+    let result_devices: Vec<AdbDevice> = devices
+        .into_iter()
+        .map(|d| AdbDevice {
+            name: d.name,
+            adb_path: d.adb_path.to_string_lossy().to_string(),
+            address: d.address,
+            screencap_methods: d.screencap_methods,
+            input_methods: d.input_methods,
+            config: d.config.to_string(),
+        })
+        .collect();
 
-        info!("Returning {} device(s)", result_devices.len());
-        Ok(result_devices)
-    })
-    .await
-    .map_err(|e| e.to_string())?
+    // 缓存搜索结果
+    if let Ok(mut cached) = state.cached_adb_devices.lock() {
+        *cached = result_devices.clone();
+    }
+
+    info!("Returning {} device(s)", result_devices.len());
+    Ok(result_devices)
 }
 
 /// 查找 Win32 窗口（结果会缓存到 MaaState）
@@ -251,59 +286,63 @@ pub async fn maa_find_win32_windows(
     state: State<'_, Arc<MaaState>>,
     class_regex: Option<String>,
     window_regex: Option<String>,
-) -> Result<Vec<Win32Window>, String> {
+) -> MxuResult<Vec<Win32Window>> {
     info!(
         "maa_find_win32_windows called, class_regex: {:?}, window_regex: {:?}",
         class_regex, window_regex
     );
 
     let state_arc = state.inner().clone();
-    let class_re_str = class_regex.clone();
-    let window_re_str = window_regex.clone();
 
     tauri::async_runtime::spawn_blocking(move || {
-        let windows = Toolkit::find_desktop_windows().map_err(|e| e.to_string())?;
-
-        // 编译正则表达式
-        let class_re = class_re_str
-            .as_ref()
-            .and_then(|r| regex::Regex::new(r).ok());
-        let window_re = window_re_str
-            .as_ref()
-            .and_then(|r| regex::Regex::new(r).ok());
-
-        let mut result_windows = Vec::new();
-
-        for w in windows {
-            // 过滤
-            if let Some(re) = &class_re {
-                if !re.is_match(&w.class_name) {
-                    continue;
-                }
+        search_win32_windows(&state_arc, class_regex.as_deref(), window_regex.as_deref())
+    })
+    .await
+    .map_err(|e| MxuError::internal(e.to_string()))?
+}
+
+/// 搜索并按正则过滤 Win32 窗口，缓存结果（阻塞调用）
+pub fn search_win32_windows(
+    state: &MaaState,
+    class_regex: Option<&str>,
+    window_regex: Option<&str>,
+) -> MxuResult<Vec<Win32Window>> {
+    let windows =
+        Toolkit::find_desktop_windows().map_err(|e| MxuError::maa(Subsystem::Controller, e))?;
+
+    // 编译正则表达式
+    let class_re = class_regex.and_then(|r| regex::Regex::new(r).ok());
+    let window_re = window_regex.and_then(|r| regex::Regex::new(r).ok());
+
+    let mut result_windows = Vec::new();
+
+    for w in windows {
+        // 过滤
+        if let Some(re) = &class_re {
+            if !re.is_match(&w.class_name) {
+                continue;
             }
-            if let Some(re) = &window_re {
-                if !re.is_match(&w.window_name) {
-                    continue;
-                }
+        }
+        if let Some(re) = &window_re {
+            if !re.is_match(&w.window_name) {
+                continue;
             }
-
-            result_windows.push(Win32Window {
-                handle: w.hwnd as u64,
-                class_name: w.class_name,
-                window_name: w.window_name,
-            });
         }
 
-        // 缓存搜索结果
-        if let Ok(mut cached) = state_arc.cached_win32_windows.lock() {
-            *cached = result_windows.clone();
-        }
+        result_windows.push(Win32Window {
+            handle: w.hwnd as u64,
+            class_name: w.class_name,
+            window_name: w.window_name,
+        });
+    }
 
-        info!("Returning {} filtered window(s)", result_windows.len());
-        Ok(result_windows)
-    })
-    .await
-    .map_err(|e| e.to_string())?
+    // 缓存搜索结果
+    if let Ok(mut cached) = state.cached_win32_windows.lock() {
+        *cached = result_windows.clone();
+    }
+
+    info!("Returning {} filtered window(s)", result_windows.len());
+    Ok(result_windows)
 }
 
 // ============================================================================
@@ -312,18 +351,22 @@ pub async fn maa_find_win32_windows(
 
 /// 创建实例（幂等操作，实例已存在时直接返回成功）
 #[tauri::command]
-pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
+pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<()> {
     info!("maa_create_instance called, instance_id: {}", instance_id);
+    create_instance(&state, &instance_id)
+}
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+/// 创建实例运行时（幂等）
+pub fn create_instance(state: &MaaState, instance_id: &str) -> MxuResult<()> {
+    let mut instances = state.instances.lock()?;
 
-    if instances.contains_key(&instance_id) {
+    if instances.contains_key(instance_id) {
         debug!("maa_create_instance: instance already exists, returning success");
         return Ok(());
     }
 
     instances.insert(
-        instance_id.clone(),
+        instance_id.to_string(),
         super::types::InstanceRuntime::default(),
     );
     info!("maa_create_instance success, instance_id: {}", instance_id);
@@ -332,16 +375,24 @@ pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) ->
 
 /// 销毁实例
 #[tauri::command]
-pub fn maa_destroy_instance(
-    state: State<Arc<MaaState>>,
-    instance_id: String,
-) -> Result<(), String> {
+pub fn maa_destroy_instance(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<()> {
     info!("maa_destroy_instance called, instance_id: {}", instance_id);
+    destroy_instance(&state, &instance_id)
+}
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let removed = instances.remove(&instance_id).is_some();
+/// 销毁实例运行时（断开 Agent 并释放所有 MaaFramework 对象）
+pub fn destroy_instance(state: &MaaState, instance_id: &str) -> MxuResult<()> {
+    let mut instances = state.instances.lock()?;
+    let removed = instances.remove(instance_id).is_some();
 
     if removed {
+        state.history.finish_run(instance_id);
+        state.recorder.finish_instance(instance_id);
+        state.frame_buffer.remove_instance(instance_id);
+        state.task_timings.remove_instance(instance_id);
+        state.task_pause.remove_instance(instance_id);
+        state.task_retry.remove_instance(instance_id);
+        state.task_queue.remove_instance(instance_id);
         info!("maa_destroy_instance success, instance_id: {}", instance_id);
     } else {
         warn!(
@@ -360,130 +411,63 @@ pub fn maa_destroy_instance(
 /// 返回连接请求 ID，前端通过监听 maa-callback 事件获取完成状态
 #[tauri::command]
 pub async fn maa_connect_controller(
-    app: tauri::AppHandle,
     state: State<'_, Arc<MaaState>>,
     instance_id: String,
     config: ControllerConfig,
-) -> Result<i64, String> {
+) -> MxuResult<i64> {
     info!(
         "maa_connect_controller called, instance_id: {}",
         instance_id
     );
 
     let state_arc = state.inner().clone();
-    let app_handle = app.clone();
 
     // Move blocking controller creation and connection to spawn_blocking
     tauri::async_runtime::spawn_blocking(move || {
-        let controller = match &config {
-            ControllerConfig::Adb {
-                adb_path,
-                address,
-                screencap_methods,
-                input_methods,
-                config,
-            } => {
-                // 将字符串解析为 u64
-                let screencap = screencap_methods.parse::<u64>().map_err(|e| {
-                    format!("Invalid screencap_methods '{}': {}", screencap_methods, e)
-                })?;
-                let input = input_methods
-                    .parse::<u64>()
-                    .map_err(|e| format!("Invalid input_methods '{}': {}", input_methods, e))?;
-                let agent_path = get_maafw_dir()
-                    .map(|p| p.join("MaaAgentBinary").to_string_lossy().to_string())
-                    .unwrap_or_else(|_| "./MaaAgentBinary".to_string());
-
-                AdbControllerBuilder::new(adb_path, address)
-                    .screencap_methods(
-                        maa_framework::common::AdbScreencapMethod::from_bits_truncate(screencap)
-                            .bits(),
-                    )
-                    .input_methods(
-                        maa_framework::common::AdbInputMethod::from_bits_truncate(input).bits(),
-                    )
-                    .config(config)
-                    .agent_path(&agent_path)
-                    .build()
-                    .map_err(|e| e.to_string())?
-            }
-            ControllerConfig::Win32 {
-                handle,
-                screencap_method,
-                mouse_method,
-                keyboard_method,
-            } => {
-                let hwnd = *handle as *mut std::ffi::c_void;
-                Controller::new_win32(
-                    hwnd,
-                    maa_framework::common::Win32ScreencapMethod::from_bits_truncate(
-                        *screencap_method,
-                    )
-                    .bits(),
-                    maa_framework::common::Win32InputMethod::from_bits_truncate(*mouse_method)
-                        .bits(),
-                    maa_framework::common::Win32InputMethod::from_bits_truncate(*keyboard_method)
-                        .bits(),
-                )
-                .map_err(|e| e.to_string())?
-            }
-            ControllerConfig::PlayCover { address, uuid } => {
-                let uuid_str = uuid.as_deref().unwrap_or("");
-                Controller::new_playcover(address, uuid_str).map_err(|e| e.to_string())?
-            }
-            ControllerConfig::Gamepad {
-                handle,
-                gamepad_type,
-                screencap_method,
-            } => {
-                let hwnd = *handle as *mut std::ffi::c_void;
-                let gp_type = match gamepad_type.as_deref() {
-                    Some("DualShock4") | Some("DS4") => {
-                        maa_framework::common::GamepadType::DualShock4
-                    }
-                    _ => maa_framework::common::GamepadType::Xbox360,
-                };
-                // bitflags
-                let screencap = screencap_method
-                    .map(|v| maa_framework::common::Win32ScreencapMethod::from_bits_truncate(v))
-                    .unwrap_or(maa_framework::common::Win32ScreencapMethod::DXGI_DESKTOP_DUP);
-
-                Controller::new_gamepad(hwnd, gp_type, screencap).map_err(|e| e.to_string())?
-            }
-        };
-
-        // 注册回调
-        let app_handle_clone = app_handle.clone();
-        controller
-            .add_sink(move |msg, detail| {
-                emit_callback_event(&app_handle_clone, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-
-        // 设置默认参数
-        if let Err(e) = controller.set_screenshot_target_short_side(720) {
-            warn!("Failed to set screenshot target short side to 720: {}", e);
-        }
+        connect_controller(&state_arc, &instance_id, &config)
+    })
+    .await
+    .map_err(|e| MxuError::internal(e.to_string()))?
+}
 
-        // 发起连接
-        let conn_id = controller.post_connection().map_err(|e| e.to_string())?;
+/// 创建控制器、注册回调并发起连接，返回连接请求 ID（阻塞调用）
+pub fn connect_controller(
+    state: &MaaState,
+    instance_id: &str,
+    config: &ControllerConfig,
+) -> MxuResult<i64> {
+    let controller = state
+        .backend
+        .create_controller(config, Arc::new(state.event_bus.callback_sink(instance_id)))?;
+
+    // 设置截图目标分辨率
+    let resolution = config.screenshot_resolution();
+    if let Err(e) = controller.set_screenshot_resolution(resolution) {
+        warn!(
+            "Failed to set screenshot resolution to {:?}: {}",
+            resolution, e
+        );
+    }
 
-        // 更新实例状态
-        debug!("Updating instance state...");
-        {
-            let mut instances = state_arc.instances.lock().map_err(|e| e.to_string())?;
-            let instance = instances
-                .get_mut(&instance_id)
-                .ok_or("Instance not found")?;
+    // 发起连接
+    let conn_id = controller.post_connection()?;
 
-            instance.controller = Some(controller);
-            instance.tasker = None;
-        }
+    // 更新实例状态
+    debug!("Updating instance state...");
+    {
+        let mut instances = state.instances.lock()?;
+        let instance = instances
+            .get_mut(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+
+        state.recorder.set_controller(instance_id, &controller);
+        state.frame_buffer.set_controller(instance_id, &controller);
+        instance.controller = Some(controller);
+        instance.controller_config = Some(config.clone());
+        instance.tasker = None;
+    }
 
-        Ok(conn_id)
-    })
-    .await
-    .map_err(|e| e.to_string())?
+    Ok(conn_id)
 }
 
 /// 获取连接状态（通过 MaaControllerConnected API 查询）
@@ -491,9 +475,11 @@ pub async fn maa_connect_controller(
 pub fn maa_get_connection_status(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<ConnectionStatus, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+) -> MxuResult<ConnectionStatus> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
 
     if instance.controller.as_ref().is_some_and(|c| c.connected()) {
         Ok(ConnectionStatus::Connected)
@@ -502,6 +488,43 @@ pub fn maa_get_connection_status(
     }
 }
 
+/// 修改控制器的截图目标分辨率（对之后的截图生效，掉线重连时沿用）
+#[tauri::command]
+pub fn maa_set_screenshot_resolution(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    resolution: ScreenshotResolution,
+) -> MxuResult<()> {
+    info!(
+        "maa_set_screenshot_resolution called, instance_id: {}, resolution: {:?}",
+        instance_id, resolution
+    );
+
+    set_screenshot_resolution(&state, &instance_id, resolution)
+}
+
+/// 修改实例控制器的截图目标分辨率，并记录到连接配置中
+pub fn set_screenshot_resolution(
+    state: &MaaState,
+    instance_id: &str,
+    resolution: ScreenshotResolution,
+) -> MxuResult<()> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+    let controller = instance
+        .controller
+        .as_ref()
+        .ok_or_else(|| MxuError::controller_not_connected(instance_id))?;
+
+    controller.set_screenshot_resolution(resolution)?;
+    if let Some(config) = instance.controller_config.as_mut() {
+        config.set_screenshot_resolution(resolution);
+    }
+    Ok(())
+}
+
 // ============================================================================
 // 资源命令
 // ============================================================================
@@ -510,37 +533,30 @@ pub fn maa_get_connection_status(
 /// 返回资源加载请求 ID 列表，前端通过监听 maa-callback 事件获取完成状态
 #[tauri::command]
 pub fn maa_load_resource(
-    app: tauri::AppHandle,
     state: State<Arc<MaaState>>,
     instance_id: String,
     paths: Vec<String>,
-) -> Result<Vec<i64>, String> {
+) -> MxuResult<Vec<i64>> {
     info!(
         "maa_load_resource called, instance: {}, paths: {:?}",
         instance_id, paths
     );
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    load_resource(&state, &instance_id, &paths)
+}
+
+/// 创建（或复用）实例资源并提交资源包加载，返回资源加载请求 ID 列表
+pub fn load_resource(state: &MaaState, instance_id: &str, paths: &[String]) -> MxuResult<Vec<i64>> {
+    let mut instances = state.instances.lock()?;
     let instance = instances
-        .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
 
     // 创建或获取资源
     if instance.resource.is_none() {
-        let res = Resource::new().map_err(|e| e.to_string())?;
-
-        // 注册回调
-        let app_handle = app.clone();
-        res.add_sink(move |msg, detail| {
-            emit_callback_event(&app_handle, msg, detail);
-        })
-        .map_err(|e| e.to_string())?;
-
-        // 注册 MXU Custom Actions
-        if let Err(e) = crate::mxu_actions::register_all_mxu_actions(&res) {
-            warn!("Failed to register MXU custom actions: {}", e);
-        }
-
+        let res = state
+            .backend
+            .create_resource(Arc::new(state.event_bus.callback_sink(instance_id)))?;
         instance.resource = Some(res);
     }
 
@@ -548,11 +564,11 @@ pub fn maa_load_resource(
     let mut res_ids = Vec::new();
 
     for path in paths {
-        let normalized = normalize_path(&path).to_string_lossy().to_string();
+        let normalized = normalize_path(path).to_string_lossy().to_string();
         match resource.post_bundle(&normalized) {
-            Ok(job) => {
-                info!("Posted resource bundle: {} -> id: {}", normalized, job.id);
-                res_ids.push(job.id);
+            Ok(res_id) => {
+                info!("Posted resource bundle: {} -> id: {}", normalized, res_id);
+                res_ids.push(res_id);
             }
             Err(e) => {
                 warn!("Failed to post resource bundle {}: {}", normalized, e);
@@ -565,26 +581,22 @@ pub fn maa_load_resource(
 
 /// 检查资源是否已加载（通过 MaaResourceLoaded API 查询）
 #[tauri::command]
-pub fn maa_is_resource_loaded(
-    state: State<Arc<MaaState>>,
-    instance_id: String,
-) -> Result<bool, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+pub fn maa_is_resource_loaded(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<bool> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
 
     Ok(instance.resource.as_ref().is_some_and(|r| r.loaded()))
 }
 
 /// 销毁资源（用于切换资源时重新创建）
 #[tauri::command]
-pub fn maa_destroy_resource(
-    state: State<Arc<MaaState>>,
-    instance_id: String,
-) -> Result<(), String> {
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+pub fn maa_destroy_resource(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<()> {
+    let mut instances = state.instances.lock()?;
     let instance = instances
         .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
 
     // 销毁旧的资源
     instance.resource = None;
@@ -601,50 +613,30 @@ pub fn maa_destroy_resource(
 /// 返回任务 ID，前端通过监听 maa-callback 事件获取完成状态
 #[tauri::command]
 pub fn maa_run_task(
-    app: tauri::AppHandle,
     state: State<Arc<MaaState>>,
     instance_id: String,
     entry: String,
     pipeline_override: String,
-) -> Result<i64, String> {
+) -> MxuResult<i64> {
     info!("maa_run_task called, entry: {}", entry);
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut instances = state.instances.lock()?;
     let instance = instances
         .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
 
-    let resource = instance.resource.as_ref().ok_or("Resource not loaded")?;
+    let resource = instance
+        .resource
+        .as_ref()
+        .ok_or_else(|| MxuError::resource_not_loaded(&instance_id))?;
     let controller = instance
         .controller
         .as_ref()
-        .ok_or("Controller not connected")?;
+        .ok_or_else(|| MxuError::controller_not_connected(&instance_id))?;
 
     // 创建或获取 tasker
     if instance.tasker.is_none() {
-        let tasker = Tasker::new().map_err(|e| e.to_string())?;
-
-        // 添加回调 Sink，用于接收任务状态通知
-        let app_handle = app.clone();
-        tasker
-            .add_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-
-        // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
-        let app_handle = app.clone();
-        tasker
-            .add_context_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-
-        // 绑定资源和控制器
-        tasker
-            .bind(resource, controller)
-            .map_err(|e| e.to_string())?;
-
+        let tasker = create_tasker(&state, resource, controller, &instance_id)?;
         instance.tasker = Some(tasker);
     }
 
@@ -652,54 +644,218 @@ pub fn maa_run_task(
 
     // 检查初始化状态
     if !tasker.inited() {
-        return Err("Tasker not initialized".to_string());
+        return Err(MxuError::new(
+            ErrorCode::TaskerNotInitialized,
+            Subsystem::Tasker,
+            "Tasker not initialized",
+        )
+        .with_instance(&instance_id));
     }
 
-    let job = tasker
-        .post_task(&entry, &pipeline_override)
-        .map_err(|e| e.to_string())?;
-    let task_id = job.id;
+    // 校验 Pipeline 覆盖
+    let task = TaskConfig {
+        entry: entry.clone(),
+        pipeline_override: pipeline_override.clone(),
+        timeout_ms: None,
+        retry: None,
+    };
+    let nodes = resource_nodes(resource.as_ref());
+    let check = validate_task(&task, nodes.as_ref());
+    if !check.errors.is_empty() {
+        warn!("maa_run_task rejected {}: {:?}", entry, check.errors);
+        return Err(validation_error(&instance_id, &entry, &check.errors));
+    }
+    if !check.warnings.is_empty() {
+        warn!(
+            "maa_run_task {} override warnings: {:?}",
+            entry, check.warnings
+        );
+    }
+
+    let task_id = tasker.post_task(&entry, &pipeline_override)?;
 
     instance.task_ids.push(task_id);
+    instance.posted_tasks.push(PostedTask {
+        task_id,
+        config: task,
+        retries: 0,
+    });
+    state
+        .history
+        .task_posted(&instance_id, task_id, &entry, &pipeline_override);
 
     Ok(task_id)
 }
 
+/// 通过后端创建 Tasker（注册回调并绑定资源和控制器）
+pub fn create_tasker(
+    state: &MaaState,
+    resource: &Arc<dyn ResourceBackend>,
+    controller: &Arc<dyn ControllerBackend>,
+    instance_id: &str,
+) -> MxuResult<Arc<dyn TaskerBackend>> {
+    state.backend.create_tasker(
+        resource,
+        controller,
+        Arc::new(state.event_bus.callback_sink(instance_id)),
+    )
+}
+
 /// 获取任务状态
 #[tauri::command]
 pub fn maa_get_task_status(
     state: State<Arc<MaaState>>,
     instance_id: String,
     task_id: i64,
-) -> Result<TaskStatus, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let tasker = instance.tasker.as_ref().ok_or("Tasker not created")?;
-
-    let status = tasker
-        .get_task_detail(task_id)
-        .map_err(|e| e.to_string())?
-        .map(|d| d.status)
-        .unwrap_or(MaaStatus::INVALID);
-
-    let result = match status {
-        MaaStatus::PENDING => TaskStatus::Pending,
-        MaaStatus::RUNNING => TaskStatus::Running,
-        MaaStatus::SUCCEEDED => TaskStatus::Succeeded,
-        _ => TaskStatus::Failed,
+) -> MxuResult<TaskStatus> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
+    let tasker = instance
+        .tasker
+        .as_ref()
+        .ok_or_else(|| MxuError::tasker_not_created(&instance_id))?;
+
+    Ok(tasker.task_status(task_id)?.into())
+}
+
+/// 获取任务详情（已执行的节点、识别与动作结果及各自的开始/结束时间）
+#[tauri::command]
+pub fn maa_get_task_detail(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    task_id: i64,
+) -> MxuResult<Option<TaskDetail>> {
+    get_task_detail(&state, &instance_id, task_id)
+}
+
+/// 获取任务详情（未知任务返回 None）
+pub fn get_task_detail(
+    state: &MaaState,
+    instance_id: &str,
+    task_id: i64,
+) -> MxuResult<Option<TaskDetail>> {
+    let tasker = {
+        let instances = state.instances.lock()?;
+        let instance = instances
+            .get(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+        instance
+            .tasker
+            .clone()
+            .ok_or_else(|| MxuError::tasker_not_created(instance_id))?
+    };
+
+    let mut detail = tasker.task_detail(task_id)?;
+    if let Some(detail) = detail.as_mut() {
+        state.task_timings.apply(instance_id, detail);
+    }
+    Ok(detail)
+}
+
+/// 识别的截图与标注图（data URL）
+#[derive(Debug, Serialize)]
+pub struct RecognitionImageUrls {
+    /// 识别使用的截图
+    pub raw: Option<String>,
+    /// MaaFramework 绘制的标注图（命中区域、候选结果等）
+    pub draws: Vec<String>,
+}
+
+/// 获取识别的截图与标注图（返回 base64 编码的 data URL）
+/// 图像仅在 MaaFramework 调试模式下保留，未开启时 raw 为 null、draws 为空
+#[tauri::command]
+pub fn maa_get_recognition_images(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    reco_id: i64,
+    options: Option<ScreenshotOptions>,
+) -> MxuResult<RecognitionImageUrls> {
+    let images = recognition_images(&state, &instance_id, reco_id)?;
+    let options = options.unwrap_or_default();
+    let raw = match images.raw {
+        Some(png) => Some(encode(png, &options)?.to_data_url()),
+        None => None,
+    };
+    let draws = images
+        .draws
+        .into_iter()
+        .map(|png| encode(png, &options).map(|image| image.to_data_url()))
+        .collect::<MxuResult<_>>()?;
+    Ok(RecognitionImageUrls { raw, draws })
+}
+
+/// 获取识别的单张图像（以二进制返回编码后的图像）
+/// draw_index 省略时返回识别使用的截图，否则返回对应的标注图
+#[tauri::command]
+pub fn maa_get_recognition_image_raw(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    reco_id: i64,
+    draw_index: Option<usize>,
+    options: Option<ScreenshotOptions>,
+) -> MxuResult<tauri::ipc::Response> {
+    let images = recognition_images(&state, &instance_id, reco_id)?;
+    let png = match draw_index {
+        Some(index) => images.draws.into_iter().nth(index),
+        None => images.raw,
+    }
+    .ok_or_else(|| {
+        MxuError::new(
+            ErrorCode::NotFound,
+            Subsystem::Tasker,
+            format!("Recognition image not available: {}", reco_id),
+        )
+        .with_instance(&instance_id)
+    })?;
+    let image = encode(png, &options.unwrap_or_default())?;
+    Ok(tauri::ipc::Response::new(image.data))
+}
+
+/// 获取识别的截图与标注图（PNG）
+pub fn recognition_images(
+    state: &MaaState,
+    instance_id: &str,
+    reco_id: i64,
+) -> MxuResult<RecognitionImages> {
+    let tasker = {
+        let instances = state.instances.lock()?;
+        let instance = instances
+            .get(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+        instance
+            .tasker
+            .clone()
+            .ok_or_else(|| MxuError::tasker_not_created(instance_id))?
     };
 
-    Ok(result)
+    tasker.recognition_images(reco_id)?.ok_or_else(|| {
+        MxuError::new(
+            ErrorCode::NotFound,
+            Subsystem::Tasker,
+            format!("Recognition not found: {}", reco_id),
+        )
+        .with_instance(instance_id)
+    })
 }
 
 /// 停止任务
 #[tauri::command]
-pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<()> {
+    stop_task(&state, &instance_id)
+}
+
+/// 停止实例的任务并清除提交记录（500ms 内重复调用时忽略）
+pub fn stop_task(state: &MaaState, instance_id: &str) -> MxuResult<()> {
+    let mut instances = state.instances.lock()?;
     let instance = instances
-        .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
-    let tasker = instance.tasker.as_ref().ok_or("Tasker not created")?;
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+    let tasker = instance
+        .tasker
+        .clone()
+        .ok_or_else(|| MxuError::tasker_not_created(instance_id))?;
 
     if instance.stop_in_progress {
         if !tasker.running() {
@@ -718,11 +874,17 @@ pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result
 
     instance.stop_in_progress = true;
     instance.stop_started_at = Some(Instant::now());
-    // 清空缓存的 task_ids
+    // 清空缓存的 task_ids 与暂停时保留的任务
     instance.task_ids.clear();
-
-    tasker.post_stop().map_err(|e| e.to_string())?;
-    Ok(())
+    instance.posted_tasks.clear();
+    instance.clear_pause();
+    state.task_pause.remove_instance(instance_id);
+    state.task_retry.remove_instance(instance_id);
+    state.task_queue.remove_instance(instance_id);
+    instance.timeout_monitor = None;
+    state.frame_buffer.ignore_failures(instance_id);
+
+    tasker.post_stop()
 }
 
 /// 覆盖已提交任务的 Pipeline 配置（用于运行中修改尚未执行的任务选项）
@@ -732,21 +894,53 @@ pub fn maa_override_pipeline(
     instance_id: String,
     task_id: i64,
     pipeline_override: String,
-) -> Result<bool, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let tasker = instance.tasker.as_ref().ok_or("Tasker not created")?;
+) -> MxuResult<bool> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
+
+    // 暂停时保留的任务在恢复时重新提交，只需修改保留的配置
+    if let Some(task) = instance
+        .held_tasks
+        .iter_mut()
+        .find(|t| t.task_id == task_id)
+    {
+        task.config.pipeline_override = pipeline_override;
+        return Ok(true);
+    }
+    // 同步修改队列中的任务，重新提交（重试、超时、调整队列等）时使用新的配置
+    let queued = match instance
+        .posted_tasks
+        .iter_mut()
+        .find(|t| t.task_id == task_id)
+    {
+        Some(task) => {
+            task.config.pipeline_override = pipeline_override.clone();
+            true
+        }
+        None => false,
+    };
+    // 运行中插入、尚未提交的任务只有临时 task_id
+    if task_id < 0 {
+        return Ok(queued);
+    }
+
+    let tasker = instance
+        .tasker
+        .as_ref()
+        .ok_or_else(|| MxuError::tasker_not_created(&instance_id))?;
 
-    tasker
-        .override_pipeline(task_id, &pipeline_override)
-        .map_err(|e| e.to_string())
+    tasker.override_pipeline(task_id, &pipeline_override)
 }
 
 /// 检查是否正在运行
 #[tauri::command]
-pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> Result<bool, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<bool> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
 
     Ok(instance.tasker.as_ref().is_some_and(|t| t.running()))
 }
@@ -757,43 +951,40 @@ pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> Resul
 
 /// 发起截图请求
 #[tauri::command]
-pub fn maa_post_screencap(state: State<Arc<MaaState>>, instance_id: String) -> Result<i64, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+pub fn maa_post_screencap(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<i64> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
     let controller = instance
         .controller
         .as_ref()
-        .ok_or("Controller not connected")?;
+        .ok_or_else(|| MxuError::controller_not_connected(&instance_id))?;
 
-    controller.post_screencap().map_err(|e| e.to_string())
+    controller.post_screencap()
 }
 
-/// 获取缓存的截图（返回 base64 编码的 PNG 图像）
+/// 获取缓存的截图（返回 base64 编码的 data URL）
+/// options 省略时返回原始尺寸的 PNG
 #[tauri::command]
 pub fn maa_get_cached_image(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<String, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let controller = instance
-        .controller
-        .as_ref()
-        .ok_or("Controller not connected")?;
-
-    let buffer = controller.cached_image().map_err(|e| e.to_string())?;
-    let data = buffer
-        .to_vec()
-        .ok_or("Failed to convert image buffer".to_string())?;
-
-    if data.is_empty() {
-        return Err("No image data available".to_string());
-    }
-
-    // 复制数据并转换为 base64
-    use base64::{engine::general_purpose::STANDARD, Engine as _};
-    let base64_str = STANDARD.encode(&data);
+    options: Option<ScreenshotOptions>,
+) -> MxuResult<String> {
+    let image = cached_screenshot(&state, &instance_id, &options.unwrap_or_default())?;
 
     // 返回带 data URL 前缀的 base64 字符串
-    Ok(format!("data:image/png;base64,{}", base64_str))
+    Ok(image.to_data_url())
+}
+
+/// 获取缓存的截图（以二进制返回编码后的图像，前端收到 ArrayBuffer，省去 base64 开销）
+#[tauri::command]
+pub fn maa_get_cached_image_raw(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    options: Option<ScreenshotOptions>,
+) -> MxuResult<tauri::ipc::Response> {
+    let image = cached_screenshot(&state, &instance_id, &options.unwrap_or_default())?;
+    Ok(tauri::ipc::Response::new(image.data))
 }
@@ -4,27 +4,61 @@
 //!
 //! 模块结构：
 //! - `types`: 数据类型定义
+//! - `error`: 命令错误类型（错误码、子系统、结构化上下文）
 //! - `utils`: 辅助函数
+//! - `event_bus`: 回调事件总线
+//! - `backend`: Resource / Controller / Tasker 后端抽象（原生实现与 mock 实现）
+//! - `screenshot`: 截图编码（格式、质量与缩放）
+//! - `input`: 控制器输入命令（点击、滑动、按键、输入文本、启动/停止应用）
 //! - `maa_core`: Maa 核心命令（初始化、设备搜索、控制器、资源、任务）
 //! - `maa_agent`: Agent 相关命令
+//! - `pipeline_check`: Pipeline 覆盖校验
+//! - `task_pause`: 任务队列暂停与恢复
+//! - `task_timeout`: 任务与整次运行的超时
+//! - `task_retry`: 任务失败重试
+//! - `task_queue`: 运行中调整任务队列
 //! - `state`: 状态查询命令
 //! - `file_ops`: 文件操作命令
 //! - `update`: 更新安装相关命令
 //! - `download`: 下载相关命令
 //! - `system`: 系统相关命令
 //! - `tray`: 托盘相关命令
+//! - `scheduler`: 定时执行相关命令
+//! - `remote_api`: 本地控制接口相关命令
+//! - `history`: 运行历史相关命令
+//! - `recorder`: 会话录制相关命令
+//! - `screen_stream`: 实时截图推流相关命令
+//! - `watchdog`: 控制器掉线重连相关命令
+//! - `device_monitor`: ADB 设备热插拔监听相关命令
 
+pub mod backend;
+pub mod error;
+pub mod event_bus;
+pub mod screenshot;
 pub mod types;
 pub mod utils;
 
+pub mod device_monitor;
 pub mod download;
 pub mod file_ops;
+pub mod history;
+pub mod input;
 pub mod maa_agent;
 pub mod maa_core;
+pub mod pipeline_check;
+pub mod recorder;
+pub mod remote_api;
+pub mod scheduler;
+pub mod screen_stream;
 pub mod state;
 pub mod system;
+pub mod task_pause;
+pub mod task_queue;
+pub mod task_retry;
+pub mod task_timeout;
 pub mod tray;
 pub mod update;
+pub mod watchdog;
 
 // 重新导出类型（供 lib.rs 使用）
 pub use types::MaaState;
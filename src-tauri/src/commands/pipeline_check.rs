@@ -0,0 +1,520 @@
+//! Pipeline 覆盖校验
+//!
+//! 提交任务前检查 pipeline_override：能否解析为 JSON、引用的节点是否存在于已加载的资源
+//! （或在覆盖中定义）、常用字段的类型是否正确。结构错误与引用不存在的节点会拒绝提交；字段类型
+//! 不符只作为警告（MaaFramework 可能兼容其他写法），未列出的字段不做检查，由 MaaFramework 自行解析
+
+use log::{info, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+use tauri::State;
+
+use super::backend::ResourceBackend;
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::types::{MaaState, OverrideError, TaskConfig, TaskValidation};
+
+// ============================================================================
+// 字段类型
+// ============================================================================
+
+/// 字段允许的 JSON 类型
+#[derive(Clone, Copy)]
+enum Kind {
+    Bool,
+    /// 非负整数
+    UInt,
+    Int,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Kind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Kind::Bool => value.is_boolean(),
+            Kind::UInt => value.is_u64(),
+            Kind::Int => value.is_i64() || value.is_u64(),
+            Kind::Number => value.is_number(),
+            Kind::String => value.is_string(),
+            Kind::Array => value.is_array(),
+            Kind::Object => value.is_object(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Bool => "boolean",
+            Kind::UInt => "non-negative integer",
+            Kind::Int => "integer",
+            Kind::Number => "number",
+            Kind::String => "string",
+            Kind::Array => "array",
+            Kind::Object => "object",
+        }
+    }
+}
+
+/// 引用其他节点的字段
+const NODE_LIST_FIELDS: &[&str] = &["next", "interrupt", "on_error"];
+
+/// 已知字段的类型（节点属性与常用的识别、动作参数），类型不符时给出警告
+const FIELD_KINDS: &[(&str, &[Kind])] = &[
+    ("recognition", &[Kind::String, Kind::Object]),
+    ("action", &[Kind::String, Kind::Object]),
+    ("next", &[Kind::String, Kind::Array]),
+    ("interrupt", &[Kind::String, Kind::Array]),
+    ("on_error", &[Kind::String, Kind::Array]),
+    ("enabled", &[Kind::Bool]),
+    ("inverse", &[Kind::Bool]),
+    ("is_sub", &[Kind::Bool]),
+    ("max_hit", &[Kind::UInt]),
+    ("rate_limit", &[Kind::UInt]),
+    ("timeout", &[Kind::Int]),
+    ("pre_delay", &[Kind::UInt]),
+    ("post_delay", &[Kind::UInt]),
+    ("pre_wait_freezes", &[Kind::UInt, Kind::Object]),
+    ("post_wait_freezes", &[Kind::UInt, Kind::Object]),
+    ("repeat", &[Kind::UInt]),
+    ("repeat_delay", &[Kind::UInt]),
+    ("repeat_wait_freezes", &[Kind::UInt, Kind::Object]),
+    ("roi", &[Kind::Array, Kind::String]),
+    ("roi_offset", &[Kind::Array]),
+    ("target", &[Kind::Bool, Kind::String, Kind::Array]),
+    ("target_offset", &[Kind::Array]),
+    ("template", &[Kind::String, Kind::Array]),
+    ("threshold", &[Kind::Number, Kind::Array]),
+    ("expected", &[Kind::String, Kind::Number, Kind::Array]),
+    ("order_by", &[Kind::String]),
+    ("index", &[Kind::Int]),
+    ("green_mask", &[Kind::Bool]),
+    ("only_rec", &[Kind::Bool]),
+    ("count", &[Kind::UInt]),
+    ("connected", &[Kind::Bool]),
+];
+
+fn check_fields(path: &str, fields: &Map<String, Value>, warnings: &mut Vec<OverrideError>) {
+    for (field, value) in fields {
+        let Some((_, kinds)) = FIELD_KINDS.iter().find(|(name, _)| name == field) else {
+            continue;
+        };
+        if !kinds.iter().any(|kind| kind.matches(value)) {
+            let expected: Vec<_> = kinds.iter().map(|kind| kind.name()).collect();
+            warnings.push(OverrideError {
+                path: format!("{}.{}", path, field),
+                message: format!("Expected {}", expected.join(" or ")),
+            });
+        }
+    }
+
+    // v2 格式：{ "recognition": { "type": ..., "param": { ... } } }
+    for field in ["recognition", "action"] {
+        let param = fields.get(field).and_then(|value| value.get("param"));
+        if let Some(param) = param.and_then(Value::as_object) {
+            check_fields(&format!("{}.{}.param", path, field), param, warnings);
+        }
+    }
+}
+
+// ============================================================================
+// 节点引用
+// ============================================================================
+
+/// 取出 next / interrupt / on_error 中引用的节点名（跳过锚点引用）
+fn referenced_nodes(value: &Value) -> Vec<(usize, String)> {
+    let items = match value {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    items
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            let name = match item {
+                Value::String(s) => {
+                    if s.starts_with("[Anchor]") {
+                        return None;
+                    }
+                    s.strip_prefix("[JumpBack]").unwrap_or(s)
+                }
+                Value::Object(obj) => {
+                    if obj.get("anchor").and_then(Value::as_bool) == Some(true) {
+                        return None;
+                    }
+                    obj.get("name")?.as_str()?
+                }
+                _ => return None,
+            };
+            Some((i, name.to_string()))
+        })
+        .collect()
+}
+
+// ============================================================================
+// 校验
+// ============================================================================
+
+/// 单个任务的校验结果
+#[derive(Debug, Default)]
+pub struct OverrideCheck {
+    /// 结构错误与引用不存在的节点（不为空时拒绝提交）
+    pub errors: Vec<OverrideError>,
+    /// 字段类型不符（仍然提交）
+    pub warnings: Vec<OverrideError>,
+}
+
+/// 校验单个任务
+/// nodes 为 None 时跳过节点存在性检查
+pub fn validate_task(task: &TaskConfig, nodes: Option<&HashSet<String>>) -> OverrideCheck {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let error = |path: String, message: String| OverrideError { path, message };
+
+    // 解析覆盖：对象，或按顺序依次覆盖的对象数组
+    let mut layers: Vec<(String, &Map<String, Value>)> = Vec::new();
+    let parsed = if task.pipeline_override.trim().is_empty() {
+        None
+    } else {
+        match serde_json::from_str::<Value>(&task.pipeline_override) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                errors.push(error(String::new(), format!("Invalid JSON: {}", e)));
+                None
+            }
+        }
+    };
+    match &parsed {
+        None => {}
+        Some(Value::Object(map)) => layers.push((String::new(), map)),
+        Some(Value::Array(items)) => {
+            for (i, item) in items.iter().enumerate() {
+                match item.as_object() {
+                    Some(map) => layers.push((format!("[{}].", i), map)),
+                    None => errors.push(error(format!("[{}]", i), "Expected an object".into())),
+                }
+            }
+        }
+        Some(_) => errors.push(error(
+            String::new(),
+            "Expected an object or an array of objects".into(),
+        )),
+    }
+
+    // 覆盖中定义的节点同样可以被引用
+    let defined: HashSet<&str> = layers
+        .iter()
+        .flat_map(|(_, map)| map.keys().map(String::as_str))
+        .collect();
+    let exists =
+        |name: &str| nodes.is_none_or(|nodes| nodes.contains(name)) || defined.contains(name);
+
+    if !exists(&task.entry) {
+        errors.push(error(
+            "entry".into(),
+            format!("Node not found: {}", task.entry),
+        ));
+    }
+
+    for (prefix, map) in &layers {
+        for (name, node) in *map {
+            let path = format!("{}{}", prefix, name);
+            let Some(fields) = node.as_object() else {
+                errors.push(error(path, "Expected an object".into()));
+                continue;
+            };
+            check_fields(&path, fields, &mut warnings);
+
+            for field in NODE_LIST_FIELDS {
+                let Some(value) = fields.get(*field) else {
+                    continue;
+                };
+                for (i, target) in referenced_nodes(value) {
+                    if !exists(&target) {
+                        errors.push(error(
+                            format!("{}.{}[{}]", path, field, i),
+                            format!("Node not found: {}", target),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    OverrideCheck { errors, warnings }
+}
+
+/// 校验任务列表，每个任务返回一项结果
+pub fn validate_tasks(
+    tasks: &[TaskConfig],
+    nodes: Option<&HashSet<String>>,
+) -> Vec<TaskValidation> {
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(index, task)| {
+            let check = validate_task(task, nodes);
+            TaskValidation {
+                index,
+                entry: task.entry.clone(),
+                errors: check.errors,
+                warnings: check.warnings,
+            }
+        })
+        .collect()
+}
+
+/// 读取资源中的节点名（后端不支持或读取失败时返回 None，跳过节点存在性检查）
+pub fn resource_nodes(resource: &dyn ResourceBackend) -> Option<HashSet<String>> {
+    match resource.node_list() {
+        Ok(nodes) => nodes.map(|nodes| nodes.into_iter().collect()),
+        Err(e) => {
+            warn!("[PipelineCheck] 读取节点列表失败，跳过节点检查: {}", e);
+            None
+        }
+    }
+}
+
+/// 将校验错误合并为一条命令错误
+pub fn validation_error(instance_id: &str, entry: &str, errors: &[OverrideError]) -> MxuError {
+    let details: Vec<_> = errors
+        .iter()
+        .map(|e| {
+            if e.path.is_empty() {
+                e.message.clone()
+            } else {
+                format!("{}: {}", e.path, e.message)
+            }
+        })
+        .collect();
+    MxuError::new(
+        ErrorCode::InvalidPipelineOverride,
+        Subsystem::Tasker,
+        format!(
+            "Invalid pipeline override for {}: {}",
+            entry,
+            details.join("; ")
+        ),
+    )
+    .with_instance(instance_id)
+}
+
+// ============================================================================
+// 命令
+// ============================================================================
+
+/// 校验任务的 Pipeline 覆盖（需已加载资源），每个任务返回一项结果
+#[tauri::command]
+pub fn maa_validate_tasks(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    tasks: Vec<TaskConfig>,
+) -> MxuResult<Vec<TaskValidation>> {
+    let results = validate_instance_tasks(&state, &instance_id, &tasks)?;
+    let invalid = results.iter().filter(|r| !r.errors.is_empty()).count();
+    info!(
+        "[PipelineCheck] 校验 {} 个任务，{} 个未通过",
+        results.len(),
+        invalid
+    );
+    Ok(results)
+}
+
+/// 使用实例已加载的资源校验任务
+pub fn validate_instance_tasks(
+    state: &MaaState,
+    instance_id: &str,
+    tasks: &[TaskConfig],
+) -> MxuResult<Vec<TaskValidation>> {
+    let resource = {
+        let instances = state.instances.lock()?;
+        let instance = instances
+            .get(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+        instance
+            .resource
+            .clone()
+            .ok_or_else(|| MxuError::resource_not_loaded(instance_id))?
+    };
+
+    let nodes = resource_nodes(resource.as_ref());
+    Ok(validate_tasks(tasks, nodes.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn task(entry: &str, pipeline_override: Value) -> TaskConfig {
+        TaskConfig {
+            entry: entry.to_string(),
+            pipeline_override: pipeline_override.to_string(),
+            timeout_ms: None,
+            retry: None,
+        }
+    }
+
+    fn nodes(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    /// 出错位置列表
+    fn paths(errors: &[OverrideError]) -> Vec<&str> {
+        errors.iter().map(|e| e.path.as_str()).collect()
+    }
+
+    #[test]
+    fn structural_errors_are_rejected() {
+        let resource = nodes(&["Start"]);
+        let raw = |pipeline_override: &str| TaskConfig {
+            pipeline_override: pipeline_override.to_string(),
+            ..task("Start", json!({}))
+        };
+
+        let cases = [
+            (raw("{"), vec![""]),
+            (raw("42"), vec![""]),
+            (raw(r#"[{}, 1]"#), vec!["[1]"]),
+            (raw(r#"{"Start": []}"#), vec!["Start"]),
+            (raw("  "), vec![]),
+        ];
+        for (task, expected) in cases {
+            let check = validate_task(&task, Some(&resource));
+            assert_eq!(paths(&check.errors), expected, "{}", task.pipeline_override);
+            assert!(check.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn unknown_nodes_are_rejected() {
+        let resource = nodes(&["Start", "Next"]);
+
+        let check = validate_task(&task("Missing", json!({})), Some(&resource));
+        assert_eq!(paths(&check.errors), ["entry"]);
+
+        let check = validate_task(
+            &task(
+                "Start",
+                json!({
+                    "Start": {
+                        "next": ["Next", "Gone", {"name": "Lost"}],
+                        "on_error": "Absent",
+                    }
+                }),
+            ),
+            Some(&resource),
+        );
+        let mut errors = paths(&check.errors);
+        errors.sort();
+        assert_eq!(
+            errors,
+            ["Start.next[1]", "Start.next[2]", "Start.on_error[0]"]
+        );
+        assert_eq!(check.errors[0].message, "Node not found: Gone");
+
+        // 没有节点列表时跳过存在性检查
+        let check = validate_task(&task("Missing", json!({"Missing": {"next": "Gone"}})), None);
+        assert!(check.errors.is_empty());
+    }
+
+    #[test]
+    fn anchor_and_jump_back_references() {
+        let resource = nodes(&["Start", "Back"]);
+        let check = validate_task(
+            &task(
+                "Start",
+                json!({
+                    "Start": {
+                        "next": [
+                            "[Anchor]Somewhere",
+                            {"name": "Elsewhere", "anchor": true},
+                            "[JumpBack]Back",
+                            {"name": "Back", "jump_back": true},
+                        ],
+                        "interrupt": ["[JumpBack]Gone"],
+                    }
+                }),
+            ),
+            Some(&resource),
+        );
+        assert_eq!(paths(&check.errors), ["Start.interrupt[0]"]);
+        assert_eq!(check.errors[0].message, "Node not found: Gone");
+    }
+
+    #[test]
+    fn nodes_defined_in_any_layer_can_be_referenced() {
+        let resource = nodes(&["Start"]);
+        let check = validate_task(
+            &task(
+                "Added",
+                json!([
+                    {"Start": {"next": ["Added", "Later"]}},
+                    {"Added": {"enabled": true}},
+                    {"Later": {"next": "Start", "timeout": "soon"}},
+                ]),
+            ),
+            Some(&resource),
+        );
+        assert!(check.errors.is_empty(), "{:?}", check.errors);
+        assert_eq!(paths(&check.warnings), ["[2].Later.timeout"]);
+
+        // 对象形式的覆盖没有层级前缀
+        let check = validate_task(
+            &task("Start", json!({"Start": {"next": "Later"}})),
+            Some(&resource),
+        );
+        assert_eq!(paths(&check.errors), ["Start.next[0]"]);
+    }
+
+    #[test]
+    fn type_mismatches_are_warnings() {
+        let resource = nodes(&["Start"]);
+        let check = validate_task(
+            &task(
+                "Start",
+                json!({
+                    "Start": {
+                        "enabled": "yes",
+                        "max_hit": -1,
+                        "timeout": -1,
+                        "roi": [0, 0, 100, 100],
+                        "custom_field": 1,
+                        "recognition": {
+                            "type": "TemplateMatch",
+                            "param": {"threshold": "high", "template": "a.png"},
+                        },
+                        "action": {"type": "Click", "param": {"target": 1}},
+                    }
+                }),
+            ),
+            Some(&resource),
+        );
+        assert!(check.errors.is_empty(), "{:?}", check.errors);
+        let mut warnings = paths(&check.warnings);
+        warnings.sort();
+        assert_eq!(
+            warnings,
+            [
+                "Start.action.param.target",
+                "Start.enabled",
+                "Start.max_hit",
+                "Start.recognition.param.threshold",
+            ]
+        );
+
+        let validations = validate_tasks(
+            &[
+                task("Start", json!({"Start": {"enabled": 1}})),
+                task("Missing", json!({})),
+            ],
+            Some(&resource),
+        );
+        assert_eq!(validations[0].index, 0);
+        assert!(validations[0].errors.is_empty());
+        assert_eq!(paths(&validations[0].warnings), ["Start.enabled"]);
+        assert_eq!(paths(&validations[1].errors), ["entry"]);
+    }
+}
@@ -0,0 +1,68 @@
+//! 会话录制相关命令
+//!
+//! 录制本身见 `crate::recorder`，停止后生成的会话包位于 debug/sessions，
+//! 最近画面导出到 debug/frames
+
+use std::sync::Arc;
+
+use log::info;
+use tauri::State;
+
+use super::error::{MxuError, MxuResult};
+use super::types::MaaState;
+use crate::recorder::animation::FrameExportFormat;
+use crate::recorder::RecordingStatus;
+
+/// 开始录制实例的会话（需已连接控制器；已在录制时返回当前状态）
+#[tauri::command]
+pub fn recorder_start(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> MxuResult<RecordingStatus> {
+    info!("recorder_start called, instance_id: {}", instance_id);
+    let controller = {
+        let instances = state.instances.lock()?;
+        let instance = instances
+            .get(&instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
+        instance
+            .controller
+            .clone()
+            .ok_or_else(|| MxuError::controller_not_connected(&instance_id))?
+    };
+    state.recorder.start(&instance_id, controller)
+}
+
+/// 停止录制并打包，返回会话包路径
+#[tauri::command]
+pub fn recorder_stop(state: State<Arc<MaaState>>, instance_id: String) -> MxuResult<String> {
+    info!("recorder_stop called, instance_id: {}", instance_id);
+    let path = state.recorder.stop(&instance_id)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 查询录制状态（未在录制时返回 null）
+#[tauri::command]
+pub fn recorder_status(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Option<RecordingStatus> {
+    state.recorder.status(&instance_id)
+}
+
+/// 导出实例最近缓存的画面（默认 APNG），返回写入的文件或目录
+#[tauri::command]
+pub fn recorder_export_frames(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    format: Option<FrameExportFormat>,
+) -> MxuResult<String> {
+    info!(
+        "recorder_export_frames called, instance_id: {}, format: {:?}",
+        instance_id, format
+    );
+    let path = state
+        .frame_buffer
+        .export(&instance_id, format.unwrap_or_default())?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,34 @@
+//! 本地控制接口相关命令
+//!
+//! 服务本身见 `crate::remote_api`，前端根据设置启动或停止
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use super::error::MxuResult;
+use crate::remote_api::{RemoteApi, RemoteApiStatus};
+
+/// 启动本地控制接口（已启动时按新设置重启）
+#[tauri::command]
+pub async fn remote_api_start(
+    api: State<'_, Arc<RemoteApi>>,
+    port: u16,
+    token: String,
+) -> MxuResult<RemoteApiStatus> {
+    log::info!("remote_api_start called, port: {}", port);
+    api.start(port, token).await
+}
+
+/// 停止本地控制接口
+#[tauri::command]
+pub fn remote_api_stop(api: State<Arc<RemoteApi>>) {
+    log::info!("remote_api_stop called");
+    api.stop();
+}
+
+/// 获取本地控制接口运行状态
+#[tauri::command]
+pub fn remote_api_status(api: State<Arc<RemoteApi>>) -> RemoteApiStatus {
+    api.status()
+}
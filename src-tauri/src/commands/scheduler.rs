@@ -0,0 +1,53 @@
+//! 定时执行相关命令
+//!
+//! 调度本身在 Rust 端运行（见 `crate::scheduler`），前端只负责查询与通知重新加载
+
+use std::sync::Arc;
+
+use chrono::Local;
+use tauri::State;
+
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::scheduler::expr::Schedule;
+use crate::scheduler::{ScheduleEntry, Scheduler};
+
+/// 预览默认返回的触发次数
+const DEFAULT_PREVIEW_COUNT: usize = 5;
+
+/// 预览最多返回的触发次数
+const MAX_PREVIEW_COUNT: usize = 100;
+
+/// 获取所有启用策略的下次触发时间
+#[tauri::command]
+pub fn scheduler_get_entries(scheduler: State<Arc<Scheduler>>) -> MxuResult<Vec<ScheduleEntry>> {
+    scheduler
+        .entries()
+        .map_err(|e| MxuError::new(ErrorCode::ParseFailed, Subsystem::Scheduler, e))
+}
+
+/// 通知调度器重新加载配置（前端保存定时策略后调用）
+#[tauri::command]
+pub fn scheduler_reload(scheduler: State<Arc<Scheduler>>) {
+    log::info!("scheduler_reload called");
+    scheduler.reload();
+}
+
+/// 预览定时表达式接下来的触发时间（RFC 3339），用于编辑策略时校验表达式
+#[tauri::command]
+pub fn scheduler_preview(
+    expression: String,
+    exclusions: Option<Vec<String>>,
+    count: Option<usize>,
+) -> MxuResult<Vec<String>> {
+    let schedule = Schedule::parse(&expression, &exclusions.unwrap_or_default())
+        .map_err(|e| MxuError::new(ErrorCode::InvalidSchedule, Subsystem::Scheduler, e))?;
+    let count = count
+        .unwrap_or(DEFAULT_PREVIEW_COUNT)
+        .min(MAX_PREVIEW_COUNT);
+
+    Ok(schedule
+        .preview(Local::now(), count)
+        .iter()
+        .map(|t| t.to_rfc3339())
+        .collect())
+}
@@ -0,0 +1,47 @@
+//! 实时截图推流相关命令
+//!
+//! 截图循环在 Rust 端运行（见 `crate::screen_stream`），前端订阅后监听 maa-screen-frame 事件，
+//! 每收到一帧回复确认
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::State;
+
+use super::error::MxuResult;
+use super::screenshot::ScreenshotOptions;
+use crate::screen_stream::ScreenStreamer;
+
+/// 订阅实例的截图流
+/// interval_ms 为期望的帧间隔（0 表示不限帧率），options 省略时推送原始尺寸的 PNG
+#[tauri::command]
+pub fn maa_screen_stream_start(
+    streamer: State<Arc<ScreenStreamer>>,
+    instance_id: String,
+    viewer_id: String,
+    interval_ms: u64,
+    options: Option<ScreenshotOptions>,
+) -> MxuResult<()> {
+    streamer.subscribe(
+        &instance_id,
+        &viewer_id,
+        Duration::from_millis(interval_ms),
+        options.unwrap_or_default(),
+    )
+}
+
+/// 取消订阅实例的截图流
+#[tauri::command]
+pub fn maa_screen_stream_stop(
+    streamer: State<Arc<ScreenStreamer>>,
+    instance_id: String,
+    viewer_id: String,
+) {
+    streamer.unsubscribe(&instance_id, &viewer_id);
+}
+
+/// 确认已收到截图帧
+#[tauri::command]
+pub fn maa_screen_stream_ack(streamer: State<Arc<ScreenStreamer>>, instance_id: String, seq: u64) {
+    streamer.ack(&instance_id, seq);
+}
@@ -0,0 +1,170 @@
+//! 截图编码
+//!
+//! 控制器缓存的截图为 PNG，按调用方请求的格式、质量与最大宽度重新编码。
+//! 请求原始 PNG 且无需缩放时直接返回缓存数据，不做解码
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::types::MaaState;
+
+/// JPEG 默认质量
+const DEFAULT_JPEG_QUALITY: u8 = 80;
+
+/// 截图输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotFormat {
+    #[default]
+    Png,
+    Jpeg,
+    /// 无损 WebP
+    Webp,
+}
+
+impl ScreenshotFormat {
+    pub fn mime(self) -> &'static str {
+        match self {
+            ScreenshotFormat::Png => "image/png",
+            ScreenshotFormat::Jpeg => "image/jpeg",
+            ScreenshotFormat::Webp => "image/webp",
+        }
+    }
+}
+
+/// 截图编码选项（字段均可省略，默认输出原始尺寸的 PNG）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ScreenshotOptions {
+    #[serde(default)]
+    pub format: ScreenshotFormat,
+    /// JPEG 质量（1-100，默认 80；PNG 与 WebP 为无损编码，忽略此项）
+    pub quality: Option<u8>,
+    /// 最大宽度（超过时按比例缩小）
+    pub max_width: Option<u32>,
+}
+
+/// 编码后的截图
+pub struct EncodedImage {
+    pub data: Vec<u8>,
+    pub mime: &'static str,
+}
+
+impl EncodedImage {
+    /// base64 编码的 data URL
+    pub fn to_data_url(&self) -> String {
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        format!("data:{};base64,{}", self.mime, STANDARD.encode(&self.data))
+    }
+}
+
+/// 获取实例控制器缓存的截图并按选项编码
+pub fn cached_screenshot(
+    state: &MaaState,
+    instance_id: &str,
+    options: &ScreenshotOptions,
+) -> MxuResult<EncodedImage> {
+    // 编码可能较慢，取出控制器后立即释放实例锁
+    let controller = {
+        let instances = state.instances.lock()?;
+        let instance = instances
+            .get(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+        Arc::clone(
+            instance
+                .controller
+                .as_ref()
+                .ok_or_else(|| MxuError::controller_not_connected(instance_id))?,
+        )
+    };
+
+    let data = controller.cached_image()?;
+    if data.is_empty() {
+        return Err(MxuError::new(
+            ErrorCode::NotFound,
+            Subsystem::Controller,
+            "No image data available",
+        )
+        .with_instance(instance_id));
+    }
+    encode(data, options)
+}
+
+/// 按选项重新编码 PNG 截图
+pub fn encode(png: Vec<u8>, options: &ScreenshotOptions) -> MxuResult<EncodedImage> {
+    let format = options.format;
+    let max_width = options.max_width.filter(|&w| w > 0);
+    if format == ScreenshotFormat::Png && max_width.is_none() {
+        return Ok(EncodedImage {
+            data: png,
+            mime: format.mime(),
+        });
+    }
+
+    let mut image = image::load_from_memory(&png).map_err(|e| {
+        MxuError::new(
+            ErrorCode::ParseFailed,
+            Subsystem::Controller,
+            format!("Failed to decode screenshot: {}", e),
+        )
+    })?;
+    let resized = match max_width {
+        Some(max_width) if image.width() > max_width => {
+            image = downscale(&image, max_width);
+            true
+        }
+        _ => false,
+    };
+    if format == ScreenshotFormat::Png && !resized {
+        return Ok(EncodedImage {
+            data: png,
+            mime: format.mime(),
+        });
+    }
+
+    let mut data = Vec::new();
+    let result = match format {
+        ScreenshotFormat::Png => image.write_with_encoder(PngEncoder::new(Cursor::new(&mut data))),
+        ScreenshotFormat::Jpeg => {
+            let quality = options
+                .quality
+                .unwrap_or(DEFAULT_JPEG_QUALITY)
+                .clamp(1, 100);
+            // JPEG 不支持透明通道
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(
+                JpegEncoder::new_with_quality(Cursor::new(&mut data), quality),
+            )
+        }
+        ScreenshotFormat::Webp => {
+            image.write_with_encoder(WebPEncoder::new_lossless(Cursor::new(&mut data)))
+        }
+    };
+    result.map_err(|e| MxuError::internal(format!("Failed to encode screenshot: {}", e)))?;
+
+    Ok(EncodedImage {
+        data,
+        mime: format.mime(),
+    })
+}
+
+/// 读取图像尺寸（只解析文件头）
+pub fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    image::ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// 按比例缩小到指定宽度
+fn downscale(image: &DynamicImage, width: u32) -> DynamicImage {
+    let height = (u64::from(image.height()) * u64::from(width) / u64::from(image.width())).max(1);
+    image.resize_exact(width, height as u32, FilterType::Triangle)
+}
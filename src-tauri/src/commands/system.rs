@@ -5,6 +5,7 @@
 use log::info;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
 use super::types::SystemInfo;
 use super::types::WebView2DirInfo;
 use super::utils::get_maafw_dir;
@@ -66,7 +67,7 @@ pub fn is_elevated() -> bool {
 
 /// 以管理员权限重启应用
 #[tauri::command]
-pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
+pub fn restart_as_admin(app_handle: tauri::AppHandle) -> MxuResult<()> {
     #[cfg(windows)]
     {
         use std::ffi::OsStr;
@@ -76,7 +77,13 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
         use windows::Win32::UI::Shell::ShellExecuteW;
         use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
 
-        let exe_path = std::env::current_exe().map_err(|e| format!("获取程序路径失败: {}", e))?;
+        let exe_path = std::env::current_exe().map_err(|e| {
+            MxuError::new(
+                ErrorCode::Io,
+                Subsystem::System,
+                format!("获取程序路径失败: {}", e),
+            )
+        })?;
 
         let exe_path_str = exe_path.to_string_lossy().to_string();
 
@@ -107,9 +114,10 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
                 app_handle.exit(0);
                 Ok(())
             } else {
-                Err(format!(
-                    "以管理员身份启动失败: 错误码 {}",
-                    result.0 as usize
+                Err(MxuError::new(
+                    ErrorCode::PermissionDenied,
+                    Subsystem::System,
+                    format!("以管理员身份启动失败: 错误码 {}", result.0 as usize),
                 ))
             }
         }
@@ -118,24 +126,38 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
     #[cfg(not(windows))]
     {
         let _ = app_handle;
-        Err("此功能仅在 Windows 上可用".to_string())
+        Err(MxuError::unsupported_platform(
+            Subsystem::System,
+            "此功能仅在 Windows 上可用",
+        ))
     }
 }
 
 /// 设置全局选项 - 保存调试图像
 #[tauri::command]
-pub fn maa_set_save_draw(enabled: bool) -> Result<bool, String> {
+pub fn maa_set_save_draw(enabled: bool) -> MxuResult<bool> {
     maa_framework::set_save_draw(enabled)
         .map(|_| {
             info!("保存调试图像: {}", if enabled { "启用" } else { "禁用" });
             true
         })
-        .map_err(|e| format!("设置保存调试图像失败: {}", e))
+        .map_err(|e| MxuError::maa(Subsystem::Maa, format!("设置保存调试图像失败: {}", e)))
+}
+
+/// 设置 MaaFramework 调试模式（保留识别使用的截图与标注图，供任务详情查看）
+#[tauri::command]
+pub fn maa_set_debug_mode(enabled: bool) -> MxuResult<bool> {
+    maa_framework::set_debug_mode(enabled)
+        .map(|_| {
+            info!("调试模式: {}", if enabled { "启用" } else { "禁用" });
+            true
+        })
+        .map_err(|e| MxuError::maa(Subsystem::Maa, format!("设置调试模式失败: {}", e)))
 }
 
 /// 打开文件（使用系统默认程序）
 #[tauri::command]
-pub async fn open_file(file_path: String) -> Result<(), String> {
+pub async fn open_file(file_path: String) -> MxuResult<()> {
     info!("open_file: {}", file_path);
 
     #[cfg(windows)]
@@ -145,16 +167,27 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
         Command::new("cmd")
             .args(["/c", "start", "", &file_path])
             .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+            .map_err(|e| {
+                MxuError::new(
+                    ErrorCode::ProcessFailed,
+                    Subsystem::System,
+                    format!("Failed to open file: {}", e),
+                )
+                .with_path(&file_path)
+            })?;
     }
 
     #[cfg(target_os = "macos")]
     {
         use std::process::Command;
-        Command::new("open")
-            .arg(&file_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+        Command::new("open").arg(&file_path).spawn().map_err(|e| {
+            MxuError::new(
+                ErrorCode::ProcessFailed,
+                Subsystem::System,
+                format!("Failed to open file: {}", e),
+            )
+            .with_path(&file_path)
+        })?;
     }
 
     #[cfg(target_os = "linux")]
@@ -163,7 +196,14 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
         Command::new("xdg-open")
             .arg(&file_path)
             .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+            .map_err(|e| {
+                MxuError::new(
+                    ErrorCode::ProcessFailed,
+                    Subsystem::System,
+                    format!("Failed to open file: {}", e),
+                )
+                .with_path(&file_path)
+            })?;
     }
 
     Ok(())
@@ -171,15 +211,20 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
 
 /// 运行程序并等待其退出
 #[tauri::command]
-pub async fn run_and_wait(file_path: String) -> Result<i32, String> {
+pub async fn run_and_wait(file_path: String) -> MxuResult<i32> {
     info!("run_and_wait: {}", file_path);
 
     #[cfg(windows)]
     {
         use std::process::Command;
-        let status = Command::new(&file_path)
-            .status()
-            .map_err(|e| format!("Failed to run file: {}", e))?;
+        let status = Command::new(&file_path).status().map_err(|e| {
+            MxuError::new(
+                ErrorCode::ProcessFailed,
+                Subsystem::System,
+                format!("Failed to run file: {}", e),
+            )
+            .with_path(&file_path)
+        })?;
 
         let exit_code = status.code().unwrap_or(-1);
         info!("run_and_wait finished with exit code: {}", exit_code);
@@ -189,7 +234,10 @@ pub async fn run_and_wait(file_path: String) -> Result<i32, String> {
     #[cfg(not(windows))]
     {
         let _ = file_path;
-        Err("run_and_wait is only supported on Windows".to_string())
+        Err(MxuError::unsupported_platform(
+            Subsystem::System,
+            "run_and_wait is only supported on Windows",
+        ))
     }
 }
 
@@ -440,7 +488,7 @@ pub async fn run_action(
     args: String,
     cwd: Option<String>,
     wait_for_exit: bool,
-) -> Result<i32, String> {
+) -> MxuResult<i32> {
     use std::process::Command;
 
     info!(
@@ -452,8 +500,13 @@ pub async fn run_action(
     let args_vec: Vec<String> = if args.trim().is_empty() {
         vec![]
     } else {
-        shell_words::split(&args)
-            .map_err(|e| format!("Failed to parse args: {}", e))?
+        shell_words::split(&args).map_err(|e| {
+            MxuError::new(
+                ErrorCode::InvalidArgument,
+                Subsystem::System,
+                format!("Failed to parse args: {}", e),
+            )
+        })?
     };
 
     let mut cmd = Command::new(&program);
@@ -477,17 +530,28 @@ pub async fn run_action(
 
     if wait_for_exit {
         // 等待进程退出
-        let status = cmd
-            .status()
-            .map_err(|e| format!("Failed to run action: {} - {}", program, e))?;
+        let status = cmd.status().map_err(|e| {
+            MxuError::new(
+                ErrorCode::ProcessFailed,
+                Subsystem::System,
+                format!("Failed to run action: {} - {}", program, e),
+            )
+            .with_path(&program)
+        })?;
 
         let exit_code = status.code().unwrap_or(-1);
         info!("run_action finished with exit code: {}", exit_code);
         Ok(exit_code)
     } else {
         // 不等待，启动后立即返回
-        cmd.spawn()
-            .map_err(|e| format!("Failed to spawn action: {} - {}", program, e))?;
+        cmd.spawn().map_err(|e| {
+            MxuError::new(
+                ErrorCode::ProcessFailed,
+                Subsystem::System,
+                format!("Failed to spawn action: {} - {}", program, e),
+            )
+            .with_path(&program)
+        })?;
 
         info!("run_action spawned (not waiting)");
         Ok(0) // 不等待时返回 0
@@ -496,12 +560,17 @@ pub async fn run_action(
 
 /// 重新尝试加载 MaaFramework 库
 #[tauri::command]
-pub async fn retry_load_maa_library() -> Result<String, String> {
+pub async fn retry_load_maa_library() -> MxuResult<String> {
     info!("retry_load_maa_library");
 
     let maafw_dir = get_maafw_dir()?;
     if !maafw_dir.exists() {
-        return Err("MaaFramework directory not found".to_string());
+        return Err(MxuError::new(
+            ErrorCode::LibraryNotFound,
+            Subsystem::Maa,
+            "MaaFramework directory not found",
+        )
+        .with_path(&maafw_dir));
     }
 
     // Load library
@@ -512,7 +581,9 @@ pub async fn retry_load_maa_library() -> Result<String, String> {
     #[cfg(target_os = "linux")]
     let dll_path = maafw_dir.join("libMaaFramework.so");
 
-    maa_framework::load_library(&dll_path).map_err(|e| e.to_string())?;
+    maa_framework::load_library(&dll_path).map_err(|e| {
+        MxuError::new(ErrorCode::LibraryLoadFailed, Subsystem::Maa, e).with_path(&dll_path)
+    })?;
 
     let version = maa_framework::maa_version().to_string();
     info!("MaaFramework loaded successfully, version: {}", version);
@@ -554,8 +625,14 @@ fn to_wide(s: &str) -> Vec<u16> {
 }
 
 #[cfg(windows)]
-fn create_schtask_autostart() -> Result<(), String> {
-    let exe_path = std::env::current_exe().map_err(|e| format!("获取程序路径失败: {}", e))?;
+fn create_schtask_autostart() -> MxuResult<()> {
+    let exe_path = std::env::current_exe().map_err(|e| {
+        MxuError::new(
+            ErrorCode::Io,
+            Subsystem::System,
+            format!("获取程序路径失败: {}", e),
+        )
+    })?;
     let exe = exe_path.to_string_lossy();
     let output = std::process::Command::new("schtasks")
         .args([
@@ -573,10 +650,20 @@ fn create_schtask_autostart() -> Result<(), String> {
             "/f",
         ])
         .output()
-        .map_err(|e| format!("执行 schtasks 失败: {}", e))?;
+        .map_err(|e| {
+            MxuError::new(
+                ErrorCode::ProcessFailed,
+                Subsystem::System,
+                format!("执行 schtasks 失败: {}", e),
+            )
+        })?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("创建计划任务失败: {}", stderr));
+        return Err(MxuError::new(
+            ErrorCode::ProcessFailed,
+            Subsystem::System,
+            format!("创建计划任务失败: {}", stderr),
+        ));
     }
     Ok(())
 }
@@ -639,7 +726,7 @@ fn has_legacy_registry_autostart() -> bool {
 
 /// 通过 Windows 任务计划程序启用开机自启动（以最高权限运行，避免 UAC 弹窗）
 #[tauri::command]
-pub fn autostart_enable() -> Result<(), String> {
+pub fn autostart_enable() -> MxuResult<()> {
     #[cfg(windows)]
     {
         create_schtask_autostart()?;
@@ -648,13 +735,16 @@ pub fn autostart_enable() -> Result<(), String> {
     }
     #[cfg(not(windows))]
     {
-        Err("此功能仅在 Windows 上可用".to_string())
+        Err(MxuError::unsupported_platform(
+            Subsystem::System,
+            "此功能仅在 Windows 上可用",
+        ))
     }
 }
 
 /// 通过 Windows 任务计划程序禁用开机自启动
 #[tauri::command]
-pub fn autostart_disable() -> Result<(), String> {
+pub fn autostart_disable() -> MxuResult<()> {
     #[cfg(windows)]
     {
         // 删除计划任务（不存在时忽略错误）
@@ -667,7 +757,10 @@ pub fn autostart_disable() -> Result<(), String> {
     }
     #[cfg(not(windows))]
     {
-        Err("此功能仅在 Windows 上可用".to_string())
+        Err(MxuError::unsupported_platform(
+            Subsystem::System,
+            "此功能仅在 Windows 上可用",
+        ))
     }
 }
 
@@ -0,0 +1,309 @@
+//! 任务队列暂停与恢复
+//!
+//! MaaFramework 只提供停止全部任务。暂停时记录尚未结束的任务：默认等当前任务执行完毕，
+//! 在其结束回调中停止 Tasker，剩余任务保留在实例中；立即暂停则马上停止，当前任务在下一个
+//! 节点边界中止，并随剩余任务一起保留（恢复时从入口重新执行）。恢复时按顺序重新提交保留的任务
+//!
+//! 结束回调在 MaaFramework 回调线程中同步分发，消费者只操作自身的暂停请求，不获取实例锁
+
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+use maa_framework::MaaStatus;
+use tauri::State;
+
+use super::backend::TaskerBackend;
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::event_bus::{BusEvent, EventSink, TaskEnd};
+use super::types::{repost_tasks, resumed_count, MaaState, PauseState, PostedTask, ResumedTask};
+
+// ============================================================================
+// 暂停请求
+// ============================================================================
+
+/// 停止位置
+#[derive(Clone, Copy)]
+enum StopPoint {
+    /// 尚未停止（等待当前任务结束）
+    Pending,
+    /// 请求时立即停止
+    Immediate,
+    /// 在该任务结束后停止
+    After(i64),
+}
+
+/// 暂停请求（实例与事件总线消费者共享）
+pub struct PauseRequest {
+    state: Weak<MaaState>,
+    instance_id: String,
+    tasker: Weak<dyn TaskerBackend>,
+    /// 请求暂停时尚未结束的任务 ID（按提交顺序）
+    queue: Vec<i64>,
+    stop: Mutex<StopPoint>,
+}
+
+impl PauseRequest {
+    fn new(
+        state: &Arc<MaaState>,
+        instance_id: &str,
+        tasker: &Arc<dyn TaskerBackend>,
+        queue: Vec<i64>,
+    ) -> Self {
+        Self {
+            state: Arc::downgrade(state),
+            instance_id: instance_id.to_string(),
+            tasker: Arc::downgrade(tasker),
+            queue,
+            stop: Mutex::new(StopPoint::Pending),
+        }
+    }
+
+    /// 停止 Tasker，之后的任务均保留
+    fn stop(&self, point: StopPoint) {
+        if let Ok(mut stop) = self.stop.lock() {
+            if !matches!(*stop, StopPoint::Pending) {
+                return;
+            }
+            *stop = point;
+        }
+        // 暂停中止的任务的失败不导出画面
+        if let Some(state) = self.state.upgrade() {
+            state.frame_buffer.ignore_failures(&self.instance_id);
+        }
+        if let Some(tasker) = self.tasker.upgrade() {
+            if let Err(e) = tasker.post_stop() {
+                warn!("[TaskPause] 停止任务失败: {}", e);
+            }
+        }
+    }
+
+    /// 是否已停止
+    pub fn stopped(&self) -> bool {
+        self.stop
+            .lock()
+            .is_ok_and(|stop| !matches!(*stop, StopPoint::Pending))
+    }
+
+    /// 停止后保留的任务（按提交顺序）
+    pub fn held_tasks(&self, posted: &[PostedTask]) -> Vec<PostedTask> {
+        let Ok(stop) = self.stop.lock() else {
+            return Vec::new();
+        };
+        let held_ids = match *stop {
+            StopPoint::Pending => return Vec::new(),
+            StopPoint::Immediate => &self.queue[..],
+            StopPoint::After(task_id) => match self.queue.iter().position(|&id| id == task_id) {
+                Some(index) => &self.queue[index + 1..],
+                None => &[],
+            },
+        };
+        held_ids
+            .iter()
+            .filter_map(|id| posted.iter().find(|t| t.task_id == *id).cloned())
+            .collect()
+    }
+}
+
+/// 等待当前任务结束的暂停请求（注册为事件总线消费者）
+#[derive(Default)]
+pub struct TaskPause {
+    requests: Mutex<HashMap<String, Arc<PauseRequest>>>,
+}
+
+impl TaskPause {
+    fn register(&self, instance_id: &str, request: Arc<PauseRequest>) {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.insert(instance_id.to_string(), request);
+        }
+    }
+
+    /// 实例停止任务或被销毁时丢弃暂停请求
+    pub fn remove_instance(&self, instance_id: &str) {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.remove(instance_id);
+        }
+    }
+}
+
+impl EventSink for TaskPause {
+    fn handle(&self, event: &BusEvent) {
+        let Some(TaskEnd {
+            instance_id,
+            task_id,
+            ..
+        }) = event.task_end()
+        else {
+            return;
+        };
+
+        let request = {
+            let Ok(mut requests) = self.requests.lock() else {
+                return;
+            };
+            match requests.get(instance_id) {
+                Some(request) if request.queue.contains(&task_id) => requests.remove(instance_id),
+                _ => None,
+            }
+        };
+        if let Some(request) = request {
+            info!(
+                "[TaskPause] 实例 {} 的任务 {} 已结束，暂停剩余任务",
+                instance_id, task_id
+            );
+            request.stop(StopPoint::After(task_id));
+        }
+    }
+}
+
+// ============================================================================
+// 命令
+// ============================================================================
+
+/// 暂停任务队列
+/// immediate 为 false 时等待当前任务执行完毕；为 true 时立即停止，当前任务随剩余任务一起保留
+#[tauri::command]
+pub fn maa_pause_tasks(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    immediate: Option<bool>,
+) -> MxuResult<PauseState> {
+    info!("maa_pause_tasks called, instance_id: {}", instance_id);
+    pause_tasks(&state, &instance_id, immediate.unwrap_or(false))
+}
+
+/// 恢复暂停的任务队列，返回重新提交的任务（原 task_id 与新 task_id 的对应关系）
+#[tauri::command]
+pub fn maa_resume_tasks(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> MxuResult<Vec<ResumedTask>> {
+    info!("maa_resume_tasks called, instance_id: {}", instance_id);
+    resume_tasks(&state, &instance_id)
+}
+
+/// 暂停任务队列，返回暂停状态（已在暂停中或已暂停时直接返回当前状态）
+pub fn pause_tasks(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    immediate: bool,
+) -> MxuResult<PauseState> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+    instance.settle_pause();
+    if instance.pause_state != PauseState::None {
+        return Ok(instance.pause_state);
+    }
+    if state.task_queue.is_applying(instance_id) {
+        return Err(MxuError::new(
+            ErrorCode::InstanceBusy,
+            Subsystem::Tasker,
+            "Task queue is being resubmitted",
+        )
+        .with_instance(instance_id));
+    }
+
+    let not_running = || {
+        MxuError::new(
+            ErrorCode::InvalidArgument,
+            Subsystem::Tasker,
+            "No running tasks to pause",
+        )
+        .with_instance(instance_id)
+    };
+    let tasker = instance
+        .tasker
+        .clone()
+        .filter(|t| t.running())
+        .ok_or_else(not_running)?;
+    // 运行中插入、尚未提交的任务（临时 task_id 为负数）同样保留
+    let queue: Vec<i64> = instance
+        .posted_tasks
+        .iter()
+        .map(|t| t.task_id)
+        .filter(|id| {
+            *id < 0
+                || tasker
+                    .task_status(*id)
+                    .is_ok_and(|s| s == MaaStatus::PENDING || s == MaaStatus::RUNNING)
+        })
+        .collect();
+    if queue.is_empty() {
+        return Err(not_running());
+    }
+
+    let mode = if immediate {
+        "立即"
+    } else {
+        "当前任务结束后"
+    };
+    info!(
+        "[TaskPause] 暂停实例 {}（{}），未结束的任务: {:?}",
+        instance_id, mode, queue
+    );
+    // 暂停停止任务导致的失败不触发重试，队列调整随保留的任务生效
+    state.task_retry.remove_instance(instance_id);
+    state.task_queue.remove_instance(instance_id);
+    let request = Arc::new(PauseRequest::new(state, instance_id, &tasker, queue));
+    if immediate {
+        request.stop(StopPoint::Immediate);
+    } else {
+        state.task_pause.register(instance_id, request.clone());
+    }
+    instance.pause_request = Some(request);
+    instance.pause_state = PauseState::Pausing;
+    Ok(PauseState::Pausing)
+}
+
+/// 按顺序重新提交暂停时保留的任务
+pub fn resume_tasks(state: &Arc<MaaState>, instance_id: &str) -> MxuResult<Vec<ResumedTask>> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+    instance.settle_pause();
+    match instance.pause_state {
+        PauseState::Paused => {}
+        PauseState::Pausing => {
+            return Err(MxuError::new(
+                ErrorCode::InstanceBusy,
+                Subsystem::Tasker,
+                "Waiting for the current task to finish",
+            )
+            .with_instance(instance_id));
+        }
+        PauseState::None => {
+            return Err(MxuError::new(
+                ErrorCode::InvalidArgument,
+                Subsystem::Tasker,
+                "Task queue is not paused",
+            )
+            .with_instance(instance_id));
+        }
+    }
+
+    let tasker = instance
+        .tasker
+        .clone()
+        .ok_or_else(|| MxuError::tasker_not_created(instance_id))?;
+    if !tasker.inited() {
+        return Err(MxuError::new(
+            ErrorCode::TaskerNotInitialized,
+            Subsystem::Tasker,
+            "Tasker not initialized",
+        )
+        .with_instance(instance_id));
+    }
+
+    let held = std::mem::take(&mut instance.held_tasks);
+    let resumed = repost_tasks(state, instance_id, instance, held);
+    info!(
+        "[TaskPause] 实例 {} 已恢复，重新提交 {} 个任务",
+        instance_id,
+        resumed_count(&resumed)
+    );
+    instance.pause_state = PauseState::None;
+    Ok(resumed)
+}
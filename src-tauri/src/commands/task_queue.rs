@@ -0,0 +1,613 @@
+//! 运行中调整任务队列
+//!
+//! MaaFramework 只能追加任务，不能移除或调整已提交的任务。实例的 posted_tasks 按执行顺序记录
+//! 运行中的任务队列：追加任务时直接提交；插入、取消或调整顺序时先只修改队列（插入的任务使用
+//! 负数的临时 task_id），在当前任务结束的回调中停止 Tasker，随后按调整后的顺序重新提交剩余任务。
+//! 期间经事件总线发出 maa-task-queue 事件（applying / applied），applied 带上原 task_id 与
+//! 新 task_id 的对应关系。调整生效前因暂停、超时、重试或掉线重连重新提交任务时，同样按调整后的
+//! 队列提交
+//!
+//! 已暂停时直接修改保留的任务，恢复时按修改后的顺序提交
+
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+
+use maa_framework::MaaStatus;
+use serde::Serialize;
+use tauri::State;
+
+use super::backend::TaskerBackend;
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use super::event_bus::{BusEvent, EventSink, TaskEnd};
+use super::pipeline_check::{resource_nodes, validate_task, validation_error};
+use super::types::{
+    repost_tasks, resumed_count, stop_tasks, InstanceRuntime, MaaState, PauseState, PostedTask,
+    ResumedTask, TaskConfig,
+};
+
+/// 队列调整事件名
+pub const TASK_QUEUE_EVENT: &str = "maa-task-queue";
+
+// ============================================================================
+// 类型定义
+// ============================================================================
+
+/// 队列调整生效的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueStage {
+    /// 当前任务已结束，正在停止 Tasker（之后被中止的任务的回调应忽略）
+    Applying,
+    /// 已按调整后的顺序重新提交剩余任务（没有剩余任务时运行结束）
+    Applied,
+}
+
+/// 队列调整事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskQueueEvent {
+    pub instance_id: String,
+    pub stage: QueueStage,
+    /// 调整生效前正在执行的任务
+    pub task_id: i64,
+    /// 重新提交的任务（仅 applied）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resumed_tasks: Vec<ResumedTask>,
+}
+
+impl TaskQueueEvent {
+    fn new(instance_id: &str, stage: QueueStage, task_id: i64) -> Self {
+        Self {
+            instance_id: instance_id.to_string(),
+            stage,
+            task_id,
+            resumed_tasks: Vec::new(),
+        }
+    }
+}
+
+/// 队列中的任务
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedTask {
+    /// task_id（尚未提交的任务为负数的临时 ID）
+    pub task_id: i64,
+    pub entry: String,
+}
+
+impl From<&PostedTask> for QueuedTask {
+    fn from(task: &PostedTask) -> Self {
+        Self {
+            task_id: task.task_id,
+            entry: task.config.entry.clone(),
+        }
+    }
+}
+
+/// 实例的任务队列
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TaskQueueState {
+    /// 正在执行的任务（已暂停或未在运行时为 None）
+    pub running: Option<QueuedTask>,
+    /// 等待执行的任务（按执行顺序；已暂停时为保留的任务）
+    pub pending: Vec<QueuedTask>,
+    /// 是否有等待当前任务结束后生效的调整
+    pub deferred: bool,
+}
+
+/// 对等待中的任务的调整
+pub enum QueueEdit {
+    /// 追加到队尾
+    Append(TaskConfig),
+    /// 插入到指定位置（0 为下一个执行，超出范围时追加到队尾）
+    Insert(usize, TaskConfig),
+    /// 取消任务
+    Cancel(i64),
+    /// 移动到指定位置（超出范围时移动到队尾）
+    Move(i64, usize),
+}
+
+// ============================================================================
+// 延后生效的调整
+// ============================================================================
+
+/// 一个实例等待当前任务结束后生效的调整
+struct DeferredEdit {
+    state: Weak<MaaState>,
+    tasker: Weak<dyn TaskerBackend>,
+    /// 调整时正在执行的任务
+    running: i64,
+    /// 已停止 Tasker，等待重新提交
+    applying: bool,
+}
+
+/// 在当前任务结束后应用队列调整（注册为事件总线消费者）
+#[derive(Default)]
+pub struct TaskQueue {
+    edits: Mutex<HashMap<String, DeferredEdit>>,
+}
+
+impl TaskQueue {
+    fn defer(
+        &self,
+        state: &Arc<MaaState>,
+        instance_id: &str,
+        tasker: &Arc<dyn TaskerBackend>,
+        running: i64,
+    ) {
+        if let Ok(mut edits) = self.edits.lock() {
+            edits.insert(
+                instance_id.to_string(),
+                DeferredEdit {
+                    state: Arc::downgrade(state),
+                    tasker: Arc::downgrade(tasker),
+                    running,
+                    applying: false,
+                },
+            );
+        }
+    }
+
+    /// 是否有等待当前任务结束后生效的调整
+    pub fn is_deferred(&self, instance_id: &str) -> bool {
+        self.edits
+            .lock()
+            .is_ok_and(|edits| edits.contains_key(instance_id))
+    }
+
+    /// 是否正在按调整后的顺序重新提交（期间不能暂停或再次调整）
+    pub fn is_applying(&self, instance_id: &str) -> bool {
+        self.edits
+            .lock()
+            .is_ok_and(|edits| edits.get(instance_id).is_some_and(|e| e.applying))
+    }
+
+    /// 结束重新提交，返回调整是否仍然有效（期间未被停止、暂停或开始新的运行）
+    fn finish_applying(&self, instance_id: &str, running: i64) -> bool {
+        let Ok(mut edits) = self.edits.lock() else {
+            return false;
+        };
+        match edits.get(instance_id) {
+            Some(edit) if edit.applying && edit.running == running => {
+                edits.remove(instance_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 停止、暂停、超时停止任务、掉线或实例被销毁时丢弃延后的调整
+    /// （之后重新提交的任务已按调整后的队列）
+    pub fn remove_instance(&self, instance_id: &str) {
+        if let Ok(mut edits) = self.edits.lock() {
+            edits.remove(instance_id);
+        }
+    }
+}
+
+impl TaskQueue {
+    /// 调整时正在执行的任务已结束：停止 Tasker，随后在后台按调整后的队列重新提交
+    ///
+    /// 结束回调与调整后的状态检查都会调用，只有第一次生效
+    fn running_finished(&self, instance_id: &str, task_id: i64) {
+        let (state, tasker) = {
+            let Ok(mut edits) = self.edits.lock() else {
+                return;
+            };
+            let Some(edit) = edits.get_mut(instance_id) else {
+                return;
+            };
+            if edit.running != task_id || edit.applying {
+                return;
+            }
+            edit.applying = true;
+            (edit.state.upgrade(), edit.tasker.upgrade())
+        };
+        let (Some(state), Some(tasker)) = (state, tasker) else {
+            self.remove_instance(instance_id);
+            return;
+        };
+        // 失败重试先于此处理，重试按调整后的队列重新提交
+        if state.task_retry.is_retrying(instance_id) {
+            self.remove_instance(instance_id);
+            return;
+        }
+
+        info!(
+            "[TaskQueue] 实例 {} 的任务 {} 已结束，按调整后的队列重新提交",
+            instance_id, task_id
+        );
+        // 先通知再停止任务，前端收到随后被中止任务的回调时已知道队列将重新提交
+        emit(
+            &state,
+            &TaskQueueEvent::new(instance_id, QueueStage::Applying, task_id),
+        );
+        // 停止导致的失败不触发重试
+        state.task_retry.remove_instance(instance_id);
+        let instance_id = instance_id.to_string();
+        thread::spawn(move || {
+            stop_tasks(&state, &instance_id, &tasker);
+            apply(&state, &instance_id, task_id);
+        });
+    }
+}
+
+impl EventSink for TaskQueue {
+    fn handle(&self, event: &BusEvent) {
+        if let Some(TaskEnd {
+            instance_id,
+            task_id,
+            ..
+        }) = event.task_end()
+        {
+            self.running_finished(instance_id, task_id);
+        }
+    }
+}
+
+/// Tasker 停止后重新提交结束的任务之后的任务
+fn apply(state: &Arc<MaaState>, instance_id: &str, finished: i64) {
+    let resumed = {
+        let Ok(mut instances) = state.instances.lock() else {
+            return;
+        };
+        // 等待停止期间手动停止了任务、暂停、开始了新的运行或实例被销毁
+        if !state.task_queue.finish_applying(instance_id, finished) {
+            return;
+        }
+        let Some(instance) = instances.get_mut(instance_id) else {
+            return;
+        };
+        repost_after(state, instance_id, instance, finished)
+    };
+
+    info!(
+        "[TaskQueue] 实例 {} 的队列调整已生效，重新提交 {} 个任务",
+        instance_id,
+        resumed_count(&resumed)
+    );
+    let mut event = TaskQueueEvent::new(instance_id, QueueStage::Applied, finished);
+    event.resumed_tasks = resumed;
+    emit(state, &event);
+}
+
+/// 按队列顺序重新提交指定任务之后的任务
+fn repost_after(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    instance: &mut InstanceRuntime,
+    finished: i64,
+) -> Vec<ResumedTask> {
+    let Some(index) = instance
+        .posted_tasks
+        .iter()
+        .position(|t| t.task_id == finished)
+    else {
+        return Vec::new();
+    };
+    if !instance.tasker.as_ref().is_some_and(|t| t.inited()) {
+        return Vec::new();
+    }
+    let remaining = instance.posted_tasks.split_off(index + 1);
+    repost_tasks(state, instance_id, instance, remaining)
+}
+
+fn emit(state: &MaaState, event: &TaskQueueEvent) {
+    state.event_bus.emit(BusEvent::new(
+        TASK_QUEUE_EVENT,
+        Some(&event.instance_id),
+        event,
+    ));
+}
+
+// ============================================================================
+// 命令
+// ============================================================================
+
+/// 获取实例的任务队列（正在执行与等待执行的任务）
+#[tauri::command]
+pub fn maa_get_task_queue(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> MxuResult<TaskQueueState> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(&instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(&instance_id))?;
+    instance.settle_pause();
+    Ok(queue_state(&state, &instance_id, instance))
+}
+
+/// 追加任务到运行中的队列末尾
+#[tauri::command]
+pub fn maa_append_task(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    task: TaskConfig,
+) -> MxuResult<TaskQueueState> {
+    info!(
+        "maa_append_task called, instance_id: {}, entry: {}",
+        instance_id, task.entry
+    );
+    edit_queue(&state, &instance_id, QueueEdit::Append(task))
+}
+
+/// 插入任务到运行中的队列（index 为在等待中的任务中的位置，0 为下一个执行）
+#[tauri::command]
+pub fn maa_insert_task(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    index: usize,
+    task: TaskConfig,
+) -> MxuResult<TaskQueueState> {
+    info!(
+        "maa_insert_task called, instance_id: {}, index: {}, entry: {}",
+        instance_id, index, task.entry
+    );
+    edit_queue(&state, &instance_id, QueueEdit::Insert(index, task))
+}
+
+/// 取消尚未执行的任务
+#[tauri::command]
+pub fn maa_cancel_task(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    task_id: i64,
+) -> MxuResult<TaskQueueState> {
+    info!(
+        "maa_cancel_task called, instance_id: {}, task_id: {}",
+        instance_id, task_id
+    );
+    edit_queue(&state, &instance_id, QueueEdit::Cancel(task_id))
+}
+
+/// 调整尚未执行的任务的顺序（index 为在等待中的任务中的位置）
+#[tauri::command]
+pub fn maa_move_task(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    task_id: i64,
+    index: usize,
+) -> MxuResult<TaskQueueState> {
+    info!(
+        "maa_move_task called, instance_id: {}, task_id: {}, index: {}",
+        instance_id, task_id, index
+    );
+    edit_queue(&state, &instance_id, QueueEdit::Move(task_id, index))
+}
+
+// ============================================================================
+// 队列调整
+// ============================================================================
+
+/// 调整实例的任务队列，返回调整后的队列
+///
+/// 运行中只能调整当前任务之后的任务；只追加任务且没有延后的调整时直接提交，否则在当前任务结束后生效
+pub fn edit_queue(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    edit: QueueEdit,
+) -> MxuResult<TaskQueueState> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+    instance.settle_pause();
+
+    let busy = |message: &str| {
+        MxuError::new(ErrorCode::InstanceBusy, Subsystem::Tasker, message)
+            .with_instance(instance_id)
+    };
+    if state.task_queue.is_applying(instance_id) || state.task_retry.is_retrying(instance_id) {
+        return Err(busy("Task queue is being resubmitted"));
+    }
+    if let QueueEdit::Append(task) | QueueEdit::Insert(_, task) = &edit {
+        let nodes = instance
+            .resource
+            .as_ref()
+            .and_then(|r| resource_nodes(r.as_ref()));
+        let check = validate_task(task, nodes.as_ref());
+        if !check.errors.is_empty() {
+            warn!(
+                "[TaskQueue] 拒绝添加任务 {}: {:?}",
+                task.entry, check.errors
+            );
+            return Err(validation_error(instance_id, &task.entry, &check.errors));
+        }
+        if !check.warnings.is_empty() {
+            warn!(
+                "[TaskQueue] 任务 {} 的覆盖有警告: {:?}",
+                task.entry, check.warnings
+            );
+        }
+    }
+    // 临时 task_id 取比队列中所有 task_id 更小的负数
+    let temporary_id = instance
+        .posted_tasks
+        .iter()
+        .chain(&instance.held_tasks)
+        .map(|t| t.task_id)
+        .min()
+        .unwrap_or(0)
+        .min(0)
+        - 1;
+
+    match instance.pause_state {
+        PauseState::None => {}
+        PauseState::Pausing => return Err(busy("Waiting for the current task to finish")),
+        PauseState::Paused => {
+            edit_pending(instance_id, &mut instance.held_tasks, edit, temporary_id)?;
+            info!(
+                "[TaskQueue] 已调整实例 {} 暂停时保留的任务: {:?}",
+                instance_id,
+                task_ids(&instance.held_tasks)
+            );
+            return Ok(queue_state(state, instance_id, instance));
+        }
+    }
+
+    let not_running = || {
+        MxuError::new(
+            ErrorCode::InvalidArgument,
+            Subsystem::Tasker,
+            "No running tasks to edit",
+        )
+        .with_instance(instance_id)
+    };
+    let tasker = instance
+        .tasker
+        .clone()
+        .filter(|t| t.running())
+        .ok_or_else(not_running)?;
+    let running = running_index(&tasker, &instance.posted_tasks).ok_or_else(not_running)?;
+    let running_id = instance.posted_tasks[running].task_id;
+    let deferred = state.task_queue.is_deferred(instance_id);
+    let append = match &edit {
+        QueueEdit::Append(_) => true,
+        QueueEdit::Insert(index, _) => *index >= instance.posted_tasks.len() - running - 1,
+        _ => false,
+    };
+
+    match edit {
+        // 追加到队尾且没有延后的调整（MaaFramework 中的顺序与队列一致）时直接提交
+        QueueEdit::Append(config) | QueueEdit::Insert(_, config) if append && !deferred => {
+            let task_id = tasker.post_task(&config.entry, &config.pipeline_override)?;
+            state.history.task_posted(
+                instance_id,
+                task_id,
+                &config.entry,
+                &config.pipeline_override,
+            );
+            instance.posted_tasks.push(PostedTask {
+                task_id,
+                config,
+                retries: 0,
+            });
+        }
+        QueueEdit::Cancel(task_id) if task_id == running_id => {
+            return Err(MxuError::new(
+                ErrorCode::InvalidArgument,
+                Subsystem::Tasker,
+                "Cannot cancel the running task",
+            )
+            .with_instance(instance_id));
+        }
+        edit => {
+            let mut pending = instance.posted_tasks.split_off(running + 1);
+            let result = edit_pending(instance_id, &mut pending, edit, temporary_id);
+            instance.posted_tasks.append(&mut pending);
+            if result? {
+                state
+                    .task_queue
+                    .defer(state, instance_id, &tasker, running_id);
+            }
+        }
+    }
+    // 尚未提交的任务没有 task_id，只记录已提交的任务
+    instance.task_ids = task_ids(&instance.posted_tasks)
+        .into_iter()
+        .filter(|id| *id >= 0)
+        .collect();
+    state.task_retry.watch(state, instance_id, instance);
+    info!(
+        "[TaskQueue] 已调整实例 {} 的任务队列: {:?}",
+        instance_id,
+        task_ids(&instance.posted_tasks)
+    );
+    // 当前任务在登记调整前已结束（错过了结束回调）时立即生效
+    let running_ended = !tasker
+        .task_status(running_id)
+        .is_ok_and(|s| s == MaaStatus::PENDING || s == MaaStatus::RUNNING);
+    if running_ended && state.task_queue.is_deferred(instance_id) {
+        state.task_queue.running_finished(instance_id, running_id);
+    }
+    Ok(queue_state(state, instance_id, instance))
+}
+
+/// 修改等待中的任务，返回是否需要在当前任务结束后重新提交
+fn edit_pending(
+    instance_id: &str,
+    pending: &mut Vec<PostedTask>,
+    edit: QueueEdit,
+    temporary_id: i64,
+) -> MxuResult<bool> {
+    let position = |pending: &[PostedTask], task_id: i64| {
+        pending
+            .iter()
+            .position(|t| t.task_id == task_id)
+            .ok_or_else(|| {
+                MxuError::new(
+                    ErrorCode::NotFound,
+                    Subsystem::Tasker,
+                    format!("Task {} is not queued", task_id),
+                )
+                .with_instance(instance_id)
+            })
+    };
+    let queued = |config| PostedTask {
+        task_id: temporary_id,
+        config,
+        retries: 0,
+    };
+
+    match edit {
+        QueueEdit::Append(config) => {
+            pending.push(queued(config));
+            Ok(true)
+        }
+        QueueEdit::Insert(index, config) => {
+            pending.insert(index.min(pending.len()), queued(config));
+            Ok(true)
+        }
+        QueueEdit::Cancel(task_id) => {
+            let task = pending.remove(position(pending, task_id)?);
+            // 尚未提交的任务直接移除即可
+            Ok(task.task_id >= 0)
+        }
+        QueueEdit::Move(task_id, index) => {
+            let from = position(pending, task_id)?;
+            let task = pending.remove(from);
+            let to = index.min(pending.len());
+            pending.insert(to, task);
+            Ok(from != to)
+        }
+    }
+}
+
+/// 正在执行的任务在队列中的位置（按队列顺序第一个未结束的任务，尚未提交的任务没有状态）
+fn running_index(tasker: &Arc<dyn TaskerBackend>, posted: &[PostedTask]) -> Option<usize> {
+    posted.iter().position(|t| {
+        tasker
+            .task_status(t.task_id)
+            .is_ok_and(|s| s == MaaStatus::PENDING || s == MaaStatus::RUNNING)
+    })
+}
+
+fn task_ids(tasks: &[PostedTask]) -> Vec<i64> {
+    tasks.iter().map(|t| t.task_id).collect()
+}
+
+/// 实例当前的任务队列
+fn queue_state(state: &MaaState, instance_id: &str, instance: &InstanceRuntime) -> TaskQueueState {
+    if instance.pause_state == PauseState::Paused {
+        return TaskQueueState {
+            running: None,
+            pending: instance.held_tasks.iter().map(QueuedTask::from).collect(),
+            deferred: false,
+        };
+    }
+    let Some(tasker) = instance.tasker.as_ref().filter(|t| t.running()) else {
+        return TaskQueueState::default();
+    };
+    let Some(running) = running_index(tasker, &instance.posted_tasks) else {
+        return TaskQueueState::default();
+    };
+    TaskQueueState {
+        running: Some(QueuedTask::from(&instance.posted_tasks[running])),
+        pending: instance.posted_tasks[running + 1..]
+            .iter()
+            .map(QueuedTask::from)
+            .collect(),
+        deferred: state.task_queue.is_deferred(instance_id),
+    }
+}
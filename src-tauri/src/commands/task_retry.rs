@@ -0,0 +1,385 @@
+//! 任务失败重试
+//!
+//! TaskConfig.retry 为单个任务设置重试策略。任务失败时在后台线程中立即停止 Tasker，中止已开始
+//! 执行的后续任务，随后依次执行恢复动作（如重启应用）、等待，再从失败的任务开始重新提交剩余
+//! 任务。超时触发的重试由超时监视在停止任务后调用。每次重试经事件总线发出
+//! maa-task-retry 事件，重新提交后带上原 task_id 与新 task_id 的对应关系
+//!
+//! 只监视提交时配置了重试策略的任务；停止、暂停、超时停止导致的失败以及控制器掉线时不重试
+
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::backend::{ControllerBackend, TaskerBackend};
+use super::event_bus::{BusEvent, EventSink, TaskEnd};
+use super::input::{post_input, wait_input, DEFAULT_WAIT_TIMEOUT};
+use super::types::{
+    repost_tasks, resumed_count, stop_tasks, InstanceRuntime, MaaState, PostedTask, ResumedTask,
+    RetryCondition, TaskStatus,
+};
+
+/// 重试事件名
+pub const TASK_RETRY_EVENT: &str = "maa-task-retry";
+
+// ============================================================================
+// 事件类型
+// ============================================================================
+
+/// 重试阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryStage {
+    /// 开始重试（已停止任务，即将执行恢复动作）
+    Retrying,
+    /// 已重新提交失败的任务及其后的任务
+    Resumed,
+    /// 未能重新提交（已手动停止、开始了新的运行或提交失败），运行结束
+    Aborted,
+    /// 重试次数已用完，任务按失败处理
+    Exhausted,
+}
+
+/// 重试事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskRetryEvent {
+    pub instance_id: String,
+    pub stage: RetryStage,
+    pub condition: RetryCondition,
+    /// 失败的任务
+    pub task_id: i64,
+    pub entry: String,
+    /// 第几次重试（从 1 开始；exhausted 为已重试次数）
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// 重新提交的任务（resumed；aborted 时为提交失败的任务）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resumed_tasks: Vec<ResumedTask>,
+}
+
+impl TaskRetryEvent {
+    fn new(
+        instance_id: &str,
+        stage: RetryStage,
+        task: &PostedTask,
+        condition: RetryCondition,
+    ) -> Self {
+        let max_attempts = task.config.retry.as_ref().map_or(0, |p| p.attempts);
+        let attempt = match stage {
+            RetryStage::Exhausted => task.retries,
+            _ => task.retries + 1,
+        };
+        Self {
+            instance_id: instance_id.to_string(),
+            stage,
+            condition,
+            task_id: task.task_id,
+            entry: task.config.entry.clone(),
+            attempt,
+            max_attempts,
+            resumed_tasks: Vec::new(),
+        }
+    }
+}
+
+/// 任务结束后是否重试
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// 未配置该条件的重试
+    Skip,
+    Retry,
+    /// 重试次数已用完
+    Exhausted,
+}
+
+/// 按任务的重试策略与已重试次数判断是否重试
+pub fn decide(task: &PostedTask, condition: RetryCondition) -> RetryDecision {
+    match &task.config.retry {
+        Some(policy) if policy.attempts > 0 && policy.on.contains(&condition) => {
+            if task.retries < policy.attempts {
+                RetryDecision::Retry
+            } else {
+                RetryDecision::Exhausted
+            }
+        }
+        _ => RetryDecision::Skip,
+    }
+}
+
+// ============================================================================
+// 失败监视
+// ============================================================================
+
+/// 一个实例当前运行中配置了重试策略的任务
+struct RetryWatch {
+    state: Weak<MaaState>,
+    tasker: Weak<dyn TaskerBackend>,
+    controller: Option<Weak<dyn ControllerBackend>>,
+    tasks: Vec<PostedTask>,
+    /// 已开始重试（之后被中止的任务的失败回调不再处理）
+    retrying: bool,
+}
+
+/// 监视任务失败回调（注册为事件总线消费者）
+#[derive(Default)]
+pub struct TaskRetry {
+    watches: Mutex<HashMap<String, RetryWatch>>,
+    /// 正在重试的实例（从停止任务到重新提交或中止）
+    pending: Mutex<HashSet<String>>,
+}
+
+impl TaskRetry {
+    /// 按实例当前提交的任务开始监视（替换之前的监视；没有配置重试策略的任务时不监视）
+    pub fn watch(&self, state: &Arc<MaaState>, instance_id: &str, instance: &InstanceRuntime) {
+        let tasks: Vec<PostedTask> = instance
+            .posted_tasks
+            .iter()
+            .filter(|t| t.config.retry.is_some())
+            .cloned()
+            .collect();
+        let Ok(mut watches) = self.watches.lock() else {
+            return;
+        };
+        let Some(tasker) = instance.tasker.as_ref().filter(|_| !tasks.is_empty()) else {
+            watches.remove(instance_id);
+            return;
+        };
+        watches.insert(
+            instance_id.to_string(),
+            RetryWatch {
+                state: Arc::downgrade(state),
+                tasker: Arc::downgrade(tasker),
+                controller: instance.controller.as_ref().map(Arc::downgrade),
+                tasks,
+                retrying: false,
+            },
+        );
+    }
+
+    /// 是否正在重试（已停止任务，等待执行恢复动作或重新提交）
+    pub fn is_retrying(&self, instance_id: &str) -> bool {
+        self.pending
+            .lock()
+            .is_ok_and(|pending| pending.contains(instance_id))
+    }
+
+    /// 标记实例开始重试（失败回调或超时监视决定重试时）
+    pub fn begin_retry(&self, instance_id: &str) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(instance_id.to_string());
+        }
+    }
+
+    fn end_retry(&self, instance_id: &str) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(instance_id);
+        }
+    }
+
+    /// 停止、暂停、超时停止任务或实例被销毁时停止监视
+    pub fn remove_instance(&self, instance_id: &str) {
+        if let Ok(mut watches) = self.watches.lock() {
+            watches.remove(instance_id);
+        }
+    }
+}
+
+impl EventSink for TaskRetry {
+    fn handle(&self, event: &BusEvent) {
+        let Some(TaskEnd {
+            instance_id,
+            task_id,
+            succeeded: false,
+        }) = event.task_end()
+        else {
+            return;
+        };
+
+        let (state, tasker, task, decision) = {
+            let Ok(mut watches) = self.watches.lock() else {
+                return;
+            };
+            let Some(watch) = watches.get_mut(instance_id) else {
+                return;
+            };
+            let Some(task) = watch.tasks.iter().find(|t| t.task_id == task_id) else {
+                return;
+            };
+            if watch.retrying {
+                return;
+            }
+            // 控制器掉线导致的失败交由掉线重连处理
+            let connected = watch
+                .controller
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .is_some_and(|c| c.connected());
+            if !connected {
+                return;
+            }
+            let decision = decide(task, RetryCondition::Failed);
+            if decision == RetryDecision::Retry {
+                watch.retrying = true;
+                self.begin_retry(instance_id);
+            }
+            (
+                watch.state.upgrade(),
+                watch.tasker.upgrade(),
+                task.clone(),
+                decision,
+            )
+        };
+        let Some(state) = state else {
+            return;
+        };
+
+        match decision {
+            RetryDecision::Skip => {}
+            RetryDecision::Exhausted => {
+                info!(
+                    "[TaskRetry] 实例 {} 的任务 {} ({}) 已重试 {} 次，不再重试",
+                    instance_id, task.task_id, task.config.entry, task.retries
+                );
+                notify(
+                    &state,
+                    instance_id,
+                    RetryStage::Exhausted,
+                    &task,
+                    RetryCondition::Failed,
+                );
+            }
+            RetryDecision::Retry => {
+                let Some(tasker) = tasker else {
+                    self.end_retry(instance_id);
+                    return;
+                };
+                // 先通知重试再停止任务，前端收到随后的失败回调时已知道会重试
+                notify(
+                    &state,
+                    instance_id,
+                    RetryStage::Retrying,
+                    &task,
+                    RetryCondition::Failed,
+                );
+                let instance_id = instance_id.to_string();
+                thread::spawn(move || {
+                    stop_tasks(&state, &instance_id, &tasker);
+                    retry_task(&state, &instance_id, &task, RetryCondition::Failed);
+                });
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 重试
+// ============================================================================
+
+/// 任务停止后执行恢复动作并等待，再从该任务开始重新提交剩余任务，返回重新提交的任务
+///
+/// 调用前需先 begin_retry，结束后清除重试标记。期间手动停止了任务或开始了新的运行（提交记录中
+/// 已没有该任务）时不再提交
+pub fn retry_task(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    task: &PostedTask,
+    condition: RetryCondition,
+) -> Vec<ResumedTask> {
+    let policy = task.config.retry.clone().unwrap_or_default();
+    info!(
+        "[TaskRetry] 实例 {} 第 {}/{} 次重试任务 {}（{:?}）",
+        instance_id,
+        task.retries + 1,
+        policy.attempts,
+        task.config.entry,
+        condition
+    );
+
+    for input in &policy.recovery {
+        let result = post_input(state, instance_id, input)
+            .and_then(|ctrl_id| wait_input(state, instance_id, ctrl_id, DEFAULT_WAIT_TIMEOUT));
+        match result {
+            Ok(TaskStatus::Succeeded) => {}
+            Ok(status) => warn!(
+                "[TaskRetry] 实例 {} 的恢复动作 {} 未完成: {:?}",
+                instance_id,
+                input.action(),
+                status
+            ),
+            Err(e) => warn!(
+                "[TaskRetry] 实例 {} 的恢复动作 {} 失败: {}",
+                instance_id,
+                input.action(),
+                e
+            ),
+        }
+    }
+    if policy.delay_ms > 0 {
+        thread::sleep(Duration::from_millis(policy.delay_ms));
+    }
+
+    let resumed = repost_from(state, instance_id, task.task_id);
+    let stage = if resumed_count(&resumed) == 0 {
+        warn!(
+            "[TaskRetry] 实例 {} 未能重新提交任务，运行结束",
+            instance_id
+        );
+        RetryStage::Aborted
+    } else {
+        RetryStage::Resumed
+    };
+    let mut event = TaskRetryEvent::new(instance_id, stage, task, condition);
+    event.resumed_tasks = resumed.clone();
+    emit(state, &event);
+    state.task_retry.end_retry(instance_id);
+    resumed
+}
+
+/// 从失败的任务开始按顺序重新提交（失败的任务记一次重试）
+fn repost_from(state: &Arc<MaaState>, instance_id: &str, failed: i64) -> Vec<ResumedTask> {
+    let Ok(mut instances) = state.instances.lock() else {
+        return Vec::new();
+    };
+    let Some(instance) = instances.get_mut(instance_id) else {
+        return Vec::new();
+    };
+    let Some(index) = instance
+        .posted_tasks
+        .iter()
+        .position(|t| t.task_id == failed)
+    else {
+        return Vec::new();
+    };
+    if !instance.tasker.as_ref().is_some_and(|t| t.inited()) {
+        return Vec::new();
+    }
+    let mut remaining = instance.posted_tasks.split_off(index);
+    remaining[0].retries += 1;
+    repost_tasks(state, instance_id, instance, remaining)
+}
+
+/// 发出不带重新提交结果的重试事件（retrying / exhausted）
+pub fn notify(
+    state: &MaaState,
+    instance_id: &str,
+    stage: RetryStage,
+    task: &PostedTask,
+    condition: RetryCondition,
+) {
+    emit(
+        state,
+        &TaskRetryEvent::new(instance_id, stage, task, condition),
+    );
+}
+
+fn emit(state: &MaaState, event: &TaskRetryEvent) {
+    state.event_bus.emit(BusEvent::new(
+        TASK_RETRY_EVENT,
+        Some(&event.instance_id),
+        event,
+    ));
+}
@@ -0,0 +1,336 @@
+//! 任务超时
+//!
+//! 卡住的 Pipeline 会让实例一直处于运行中，挡住之后的定时执行。提交任务时可为单个任务
+//! （TaskConfig.timeout_ms）和整次运行（RunOptions.timeout_ms）设置超时，监视线程按提交顺序
+//! 跟踪当前任务，超时后先发出 maa-task-timeout 事件（timed_out）再停止 Tasker，停止后再发出
+//! stopped 事件。单个任务超时且允许继续时，重新提交其后的任务，stopped 事件中带上原 task_id 与
+//! 新 task_id 的对应关系。任务的重试策略包含超时条件时，改为从超时的任务开始重新提交
+//!
+//! 整次运行的超时只计算有任务正在执行的时间（暂停中、已暂停、等待重试或重连期间不计时）；
+//! 暂停中与已暂停时不计算单个任务的耗时，恢复后重新计时。全部任务结束且 Tasker 空闲后监视线程
+//! 退出并清除实例的监视，之后通过 maa_run_task 追加的任务不再受本次运行的超时限制
+
+use log::{info, warn};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use maa_framework::MaaStatus;
+use serde::Serialize;
+
+use super::backend::TaskerBackend;
+use super::event_bus::BusEvent;
+use super::task_retry::{self, RetryDecision, RetryStage};
+use super::types::{
+    repost_tasks, resumed_count, stop_tasks, MaaState, PauseState, PostedTask, ResumedTask,
+    RetryCondition, RunOptions,
+};
+
+/// 超时事件名
+pub const TASK_TIMEOUT_EVENT: &str = "maa-task-timeout";
+
+/// 检查任务耗时的间隔
+const CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// 事件类型
+// ============================================================================
+
+/// 超时范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutScope {
+    /// 单个任务超时
+    Task,
+    /// 整次运行超时
+    Run,
+}
+
+/// 超时处理阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutStage {
+    /// 检测到超时，即将停止任务
+    TimedOut,
+    /// 任务已停止（继续执行时已重新提交后续任务）
+    Stopped,
+}
+
+/// 超时事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTimeoutEvent {
+    pub instance_id: String,
+    pub stage: TimeoutStage,
+    pub scope: TimeoutScope,
+    /// 超时时正在执行的任务
+    pub task_id: i64,
+    pub entry: String,
+    /// 已耗时（毫秒，单个任务超时为任务耗时，整次运行超时为运行耗时）
+    pub elapsed_ms: u64,
+    /// 是否继续执行后续任务
+    pub continue_run: bool,
+    /// 重新提交的后续任务（仅 stopped）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resumed_tasks: Vec<ResumedTask>,
+}
+
+// ============================================================================
+// 监视线程
+// ============================================================================
+
+/// 一次检查的结果
+enum Check {
+    /// 监视已被替换或清除（开始了新的运行、手动停止或实例被销毁）
+    Replaced,
+    /// 全部任务已结束且没有等待中的重试、队列调整或重连（已清除实例的监视）
+    Finished,
+    /// 暂时没有正在执行的任务（暂停中、已暂停、等待重试或重连），不计入整次运行的耗时
+    Idle,
+    Running(Arc<dyn TaskerBackend>, PostedTask),
+}
+
+/// 运行超时监视（由实例持有）
+pub struct TimeoutMonitor {
+    options: RunOptions,
+}
+
+impl TimeoutMonitor {
+    /// 运行或任务配置了超时时启动监视线程
+    pub fn start(
+        state: &Arc<MaaState>,
+        instance_id: &str,
+        options: &RunOptions,
+        tasks: &[PostedTask],
+    ) -> Option<Arc<Self>> {
+        if options.timeout_ms.is_none() && tasks.iter().all(|t| t.config.timeout_ms.is_none()) {
+            return None;
+        }
+        info!(
+            "[TaskTimeout] 监视实例 {} 的任务超时，运行超时: {:?} ms",
+            instance_id, options.timeout_ms
+        );
+
+        let monitor = Arc::new(Self {
+            options: options.clone(),
+        });
+        let state = state.clone();
+        let instance_id = instance_id.to_string();
+        let worker = monitor.clone();
+        thread::spawn(move || worker.run(&state, &instance_id));
+        Some(monitor)
+    }
+
+    fn run(self: Arc<Self>, state: &Arc<MaaState>, instance_id: &str) {
+        // 当前任务及其开始执行的时间
+        let mut current: Option<(i64, Instant)> = None;
+        // 整次运行已耗时（只计算有任务正在执行的时间）
+        let mut run_elapsed = Duration::ZERO;
+        let mut last_check = Instant::now();
+        loop {
+            thread::sleep(CHECK_INTERVAL);
+            let check = self.check(state, instance_id);
+            let now = Instant::now();
+            if matches!(check, Check::Running(..)) {
+                run_elapsed += now - last_check;
+            }
+            last_check = now;
+            let (tasker, task) = match check {
+                Check::Replaced => return,
+                Check::Finished => {
+                    info!(
+                        "[TaskTimeout] 实例 {} 的任务已全部结束，停止监视",
+                        instance_id
+                    );
+                    return;
+                }
+                Check::Idle => {
+                    current = None;
+                    continue;
+                }
+                Check::Running(tasker, task) => (tasker, task),
+            };
+
+            let task_started = match current {
+                Some((task_id, started)) if task_id == task.task_id => started,
+                _ => {
+                    current = Some((task.task_id, now));
+                    now
+                }
+            };
+            let exceeded = |timeout_ms: Option<u64>, elapsed: Duration| {
+                timeout_ms.is_some_and(|ms| elapsed >= Duration::from_millis(ms))
+            };
+            let (scope, elapsed) = if exceeded(self.options.timeout_ms, run_elapsed) {
+                (TimeoutScope::Run, run_elapsed)
+            } else if exceeded(task.config.timeout_ms, task_started.elapsed()) {
+                (TimeoutScope::Task, task_started.elapsed())
+            } else {
+                continue;
+            };
+
+            let retry = match scope {
+                TimeoutScope::Task => task_retry::decide(&task, RetryCondition::Timeout),
+                TimeoutScope::Run => RetryDecision::Skip,
+            };
+            let continue_run = scope == TimeoutScope::Task
+                && (self.options.continue_on_timeout || retry == RetryDecision::Retry);
+            warn!(
+                "[TaskTimeout] 实例 {} 的任务 {} ({}) 超时（{:?}，已耗时 {} ms）",
+                instance_id,
+                task.task_id,
+                task.config.entry,
+                scope,
+                elapsed.as_millis()
+            );
+            // 先通知超时再停止任务，前端收到随后的任务失败回调时已知道原因
+            let mut event = TaskTimeoutEvent {
+                instance_id: instance_id.to_string(),
+                stage: TimeoutStage::TimedOut,
+                scope,
+                task_id: task.task_id,
+                entry: task.config.entry.clone(),
+                elapsed_ms: elapsed.as_millis() as u64,
+                continue_run,
+                resumed_tasks: Vec::new(),
+            };
+            emit(state, &event);
+            // 超时停止导致的失败不触发失败重试，队列调整随重新提交的任务生效
+            state.task_retry.remove_instance(instance_id);
+            state.task_queue.remove_instance(instance_id);
+            match retry {
+                RetryDecision::Retry => {
+                    state.task_retry.begin_retry(instance_id);
+                    task_retry::notify(
+                        state,
+                        instance_id,
+                        RetryStage::Retrying,
+                        &task,
+                        RetryCondition::Timeout,
+                    )
+                }
+                RetryDecision::Exhausted => task_retry::notify(
+                    state,
+                    instance_id,
+                    RetryStage::Exhausted,
+                    &task,
+                    RetryCondition::Timeout,
+                ),
+                RetryDecision::Skip => {}
+            }
+            stop_tasks(state, instance_id, &tasker);
+
+            if retry == RetryDecision::Retry {
+                event.resumed_tasks =
+                    task_retry::retry_task(state, instance_id, &task, RetryCondition::Timeout);
+            } else if continue_run {
+                event.resumed_tasks = self.repost_remaining(state, instance_id, task.task_id);
+            }
+            let resumed = resumed_count(&event.resumed_tasks);
+            event.stage = TimeoutStage::Stopped;
+            emit(state, &event);
+            if resumed == 0 {
+                info!("[TaskTimeout] 实例 {} 的运行已因超时结束", instance_id);
+                self.detach(state, instance_id);
+                return;
+            }
+            info!(
+                "[TaskTimeout] 实例 {} 继续执行，重新提交 {} 个任务",
+                instance_id, resumed
+            );
+            current = None;
+        }
+    }
+
+    fn is_current(&self, monitor: Option<&Arc<TimeoutMonitor>>) -> bool {
+        monitor.is_some_and(|m| std::ptr::eq(Arc::as_ptr(m), self))
+    }
+
+    /// 监视线程退出时清除实例的监视（未被替换时）
+    fn detach(&self, state: &MaaState, instance_id: &str) {
+        let Ok(mut instances) = state.instances.lock() else {
+            return;
+        };
+        if let Some(instance) = instances.get_mut(instance_id) {
+            if self.is_current(instance.timeout_monitor.as_ref()) {
+                instance.timeout_monitor = None;
+            }
+        }
+    }
+
+    /// 按提交顺序找出正在执行的任务
+    fn check(&self, state: &MaaState, instance_id: &str) -> Check {
+        let Ok(mut instances) = state.instances.lock() else {
+            return Check::Replaced;
+        };
+        let Some(instance) = instances.get_mut(instance_id) else {
+            return Check::Replaced;
+        };
+        if !self.is_current(instance.timeout_monitor.as_ref()) {
+            return Check::Replaced;
+        }
+        instance.settle_pause();
+        match instance.pause_state {
+            PauseState::None => {}
+            // 等待当前任务结束后暂停时不再检查单个任务
+            PauseState::Pausing | PauseState::Paused => return Check::Idle,
+        }
+        let Some(tasker) = instance.tasker.clone() else {
+            return Check::Idle;
+        };
+        let task = instance.posted_tasks.iter().find(|t| {
+            tasker
+                .task_status(t.task_id)
+                .is_ok_and(|s| s == MaaStatus::PENDING || s == MaaStatus::RUNNING)
+        });
+        if let Some(task) = task {
+            return Check::Running(tasker, task.clone());
+        }
+
+        // 失败重试、队列调整与掉线重连会在任务结束后重新提交，期间保持监视
+        let waiting = tasker.running()
+            || state.task_retry.is_retrying(instance_id)
+            || state.task_queue.is_deferred(instance_id)
+            || instance.controller.as_ref().is_some_and(|c| !c.connected());
+        if waiting {
+            return Check::Idle;
+        }
+        instance.timeout_monitor = None;
+        Check::Finished
+    }
+
+    /// 重新提交超时任务之后的任务
+    fn repost_remaining(
+        &self,
+        state: &Arc<MaaState>,
+        instance_id: &str,
+        timed_out: i64,
+    ) -> Vec<ResumedTask> {
+        let Ok(mut instances) = state.instances.lock() else {
+            return Vec::new();
+        };
+        let Some(instance) = instances.get_mut(instance_id) else {
+            return Vec::new();
+        };
+        // 等待停止期间用户手动停止了任务或开始了新的运行
+        if !self.is_current(instance.timeout_monitor.as_ref()) {
+            return Vec::new();
+        }
+        let Some(index) = instance
+            .posted_tasks
+            .iter()
+            .position(|t| t.task_id == timed_out)
+        else {
+            return Vec::new();
+        };
+        let remaining = instance.posted_tasks.split_off(index + 1);
+        repost_tasks(state, instance_id, instance, remaining)
+    }
+}
+
+fn emit(state: &MaaState, event: &TaskTimeoutEvent) {
+    state.event_bus.emit(BusEvent::new(
+        TASK_TIMEOUT_EVENT,
+        Some(&event.instance_id),
+        event,
+    ));
+}
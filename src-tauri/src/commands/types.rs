@@ -5,15 +5,33 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Child;
-use std::sync::Mutex;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use log::warn;
 use serde::{Deserialize, Serialize};
 
 use maa_framework::agent_client::AgentClient;
-use maa_framework::controller::Controller;
-use maa_framework::resource::Resource;
-use maa_framework::tasker::Tasker;
+use maa_framework::MaaStatus;
+
+use super::backend::native::NativeBackend;
+use super::backend::{ControllerBackend, MaaBackend, ResourceBackend, TaskerBackend};
+use super::event_bus::EventBus;
+use super::task_pause::{PauseRequest, TaskPause};
+use super::task_queue::TaskQueue;
+use super::task_retry::TaskRetry;
+use super::task_timeout::TimeoutMonitor;
+use crate::history::timings::TaskTimings;
+use crate::history::RunHistory;
+use crate::recorder::frame_buffer::FrameBuffer;
+use crate::recorder::SessionRecorder;
+
+/// 停止任务后等待 Tasker 停止的最长时间
+const TASKER_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 等待任务停止时的轮询间隔
+const TASKER_STOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 // ============================================================================
 // 数据类型定义
@@ -60,6 +78,24 @@ pub struct Win32Window {
     pub window_name: String,
 }
 
+/// 截图目标分辨率（MaaFramework 按此缩放截图后再识别，Pipeline 坐标也以缩放后的尺寸为准）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "value", rename_all = "snake_case")]
+pub enum ScreenshotResolution {
+    /// 按短边缩放到指定像素
+    ShortSide(i32),
+    /// 按长边缩放到指定像素
+    LongSide(i32),
+    /// 不缩放，使用设备原始分辨率
+    Raw,
+}
+
+impl Default for ScreenshotResolution {
+    fn default() -> Self {
+        ScreenshotResolution::ShortSide(720)
+    }
+}
+
 /// 控制器类型
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -70,12 +106,16 @@ pub enum ControllerConfig {
         screencap_methods: String, // u64 作为字符串传递，避免 JS 精度丢失
         input_methods: String,     // u64 作为字符串传递
         config: String,
+        #[serde(default)]
+        screenshot_resolution: ScreenshotResolution,
     },
     Win32 {
         handle: u64,
         screencap_method: u64,
         mouse_method: u64,
         keyboard_method: u64,
+        #[serde(default)]
+        screenshot_resolution: ScreenshotResolution,
     },
     Gamepad {
         handle: u64,
@@ -83,14 +123,124 @@ pub enum ControllerConfig {
         gamepad_type: Option<String>,
         #[serde(default)]
         screencap_method: Option<u64>,
+        #[serde(default)]
+        screenshot_resolution: ScreenshotResolution,
     },
     PlayCover {
         address: String,
         #[serde(default)]
         uuid: Option<String>,
+        #[serde(default)]
+        screenshot_resolution: ScreenshotResolution,
+    },
+    /// 图片目录调试控制器（截图依次返回目录或会话包中的图片，输入只记录日志）
+    ImageFolder {
+        path: String,
+        #[serde(default)]
+        screenshot_resolution: ScreenshotResolution,
+    },
+}
+
+impl ControllerConfig {
+    /// 截图目标分辨率
+    pub fn screenshot_resolution(&self) -> ScreenshotResolution {
+        match self {
+            ControllerConfig::Adb {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::Win32 {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::Gamepad {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::PlayCover {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::ImageFolder {
+                screenshot_resolution,
+                ..
+            } => *screenshot_resolution,
+        }
+    }
+
+    /// 修改截图目标分辨率
+    pub fn set_screenshot_resolution(&mut self, resolution: ScreenshotResolution) {
+        match self {
+            ControllerConfig::Adb {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::Win32 {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::Gamepad {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::PlayCover {
+                screenshot_resolution,
+                ..
+            }
+            | ControllerConfig::ImageFolder {
+                screenshot_resolution,
+                ..
+            } => *screenshot_resolution = resolution,
+        }
+    }
+}
+
+/// 控制器输入动作（坐标为截图坐标系，与 Pipeline 中的坐标一致）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ControllerInput {
+    Click {
+        x: i32,
+        y: i32,
+    },
+    Swipe {
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        /// 滑动耗时（毫秒）
+        duration: i32,
+    },
+    /// 按键（ADB 为 Android keycode，Win32 为虚拟键码）
+    ClickKey {
+        keycode: i32,
+    },
+    InputText {
+        text: String,
+    },
+    /// 启动应用（ADB 为包名或 Activity）
+    StartApp {
+        intent: String,
+    },
+    StopApp {
+        intent: String,
     },
 }
 
+impl ControllerInput {
+    /// 对应的控制器动作名（与 Controller.Action 回调中的 action 一致）
+    pub fn action(&self) -> &'static str {
+        match self {
+            ControllerInput::Click { .. } => "click",
+            ControllerInput::Swipe { .. } => "swipe",
+            ControllerInput::ClickKey { .. } => "click_key",
+            ControllerInput::InputText { .. } => "input_text",
+            ControllerInput::StartApp { .. } => "start_app",
+            ControllerInput::StopApp { .. } => "stop_app",
+        }
+    }
+}
+
 /// 连接状态
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConnectionStatus {
@@ -101,7 +251,7 @@ pub enum ConnectionStatus {
 }
 
 /// 任务状态
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TaskStatus {
     Pending,
     Running,
@@ -109,6 +259,84 @@ pub enum TaskStatus {
     Failed,
 }
 
+impl From<MaaStatus> for TaskStatus {
+    /// 未知任务（INVALID）视为失败
+    fn from(status: MaaStatus) -> Self {
+        match status {
+            MaaStatus::PENDING => TaskStatus::Pending,
+            MaaStatus::RUNNING => TaskStatus::Running,
+            MaaStatus::SUCCEEDED => TaskStatus::Succeeded,
+            _ => TaskStatus::Failed,
+        }
+    }
+}
+
+/// 开始与结束时间（毫秒时间戳，取自 MaaFramework 回调，未收到回调时为 None）
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Timing {
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+}
+
+/// 任务详情：按执行顺序排列的节点及各节点的识别、动作结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskDetail {
+    pub task_id: i64,
+    pub entry: String,
+    pub status: TaskStatus,
+    #[serde(flatten)]
+    pub timing: Timing,
+    pub nodes: Vec<NodeDetail>,
+}
+
+/// 已执行的节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDetail {
+    pub node_id: i64,
+    pub name: String,
+    /// 节点是否执行完毕（识别命中且动作执行结束）
+    pub completed: bool,
+    #[serde(flatten)]
+    pub timing: Timing,
+    pub recognition: Option<RecognitionDetail>,
+    pub action: Option<ActionDetail>,
+}
+
+/// 识别结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecognitionDetail {
+    pub reco_id: i64,
+    pub name: String,
+    /// 识别算法（TemplateMatch、OCR 等）
+    pub algorithm: String,
+    pub hit: bool,
+    /// 命中区域 [x, y, w, h]
+    pub box_rect: [i32; 4],
+    /// 算法相关的原始结果
+    pub detail: serde_json::Value,
+    #[serde(flatten)]
+    pub timing: Timing,
+    /// And / Or 组合识别的子识别结果
+    #[serde(default)]
+    pub sub_details: Vec<RecognitionDetail>,
+}
+
+/// 动作结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionDetail {
+    pub action_id: i64,
+    pub name: String,
+    /// 动作类型（Click、Swipe 等）
+    pub action: String,
+    pub success: bool,
+    /// 动作目标区域 [x, y, w, h]
+    pub box_rect: [i32; 4],
+    /// 动作相关的原始结果
+    pub detail: serde_json::Value,
+    #[serde(flatten)]
+    pub timing: Timing,
+}
+
 /// 实例运行时状态（用于前端查询）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceState {
@@ -122,6 +350,25 @@ pub struct InstanceState {
     pub is_running: bool,
     /// 当前运行的任务 ID 列表
     pub task_ids: Vec<i64>,
+    /// 任务队列暂停状态
+    #[serde(default)]
+    pub pause_state: PauseState,
+    /// 暂停后保留、等待恢复提交的任务 ID
+    #[serde(default)]
+    pub held_task_ids: Vec<i64>,
+}
+
+/// 任务队列暂停状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseState {
+    /// 未暂停
+    #[default]
+    None,
+    /// 已请求暂停，等待当前任务结束（或到达下一个节点边界）
+    Pausing,
+    /// 已暂停，剩余任务保留在实例中等待恢复
+    Paused,
 }
 
 /// 所有实例状态的快照
@@ -132,20 +379,92 @@ pub struct AllInstanceStates {
     pub cached_win32_windows: Vec<Win32Window>,
 }
 
-/// 实例运行时状态（持有 MaaFramework 对象句柄）
+/// 实例运行时状态（持有后端对象句柄）
 #[derive(Default)]
 pub struct InstanceRuntime {
-    pub resource: Option<Resource>,
-    pub controller: Option<Controller>,
-    pub tasker: Option<Tasker>,
+    pub resource: Option<Arc<dyn ResourceBackend>>,
+    pub controller: Option<Arc<dyn ControllerBackend>>,
+    /// 最近一次连接使用的控制器配置（掉线重连时复用）
+    pub controller_config: Option<ControllerConfig>,
+    pub tasker: Option<Arc<dyn TaskerBackend>>,
     pub agent_clients: Vec<AgentClient>,
     pub agent_children: Vec<Child>,
     /// 当前运行的任务 ID 列表（用于刷新后恢复状态）
     pub task_ids: Vec<i64>,
+    /// 当前运行的任务队列（按执行顺序，包含运行中插入、尚未提交的任务；掉线重连、暂停、超时与重试
+    /// 时据此重新提交未完成的任务）
+    pub posted_tasks: Vec<PostedTask>,
     /// 是否正在停止任务（用于防重复 stop）
     pub stop_in_progress: bool,
     /// stop 请求的起始时间（用于节流/重试）
     pub stop_started_at: Option<Instant>,
+    /// 任务队列暂停状态
+    pub pause_state: PauseState,
+    /// 进行中的暂停请求（停止后用于确定保留的任务）
+    pub pause_request: Option<Arc<PauseRequest>>,
+    /// 暂停后保留、等待恢复提交的任务
+    pub held_tasks: Vec<PostedTask>,
+    /// 当前运行的超时监视（替换或清除后监视线程退出）
+    pub timeout_monitor: Option<Arc<TimeoutMonitor>>,
+}
+
+impl InstanceRuntime {
+    /// 查询实例当前状态（任务已结束时顺带清除停止中标记）
+    pub fn snapshot(&mut self) -> InstanceState {
+        // 通过 Maa API 查询真实状态
+        let is_running = self.tasker.as_ref().is_some_and(|t| t.running());
+
+        if !is_running && self.stop_in_progress {
+            self.stop_in_progress = false;
+            self.stop_started_at = None;
+        }
+        self.settle_pause();
+
+        InstanceState {
+            connected: self.controller.as_ref().is_some_and(|c| c.connected()),
+            resource_loaded: self.resource.as_ref().is_some_and(|r| r.loaded()),
+            tasker_inited: self.tasker.as_ref().is_some_and(|t| t.inited()),
+            is_running,
+            task_ids: self.task_ids.clone(),
+            pause_state: self.pause_state,
+            held_task_ids: self.held_tasks.iter().map(|t| t.task_id).collect(),
+        }
+    }
+
+    /// 暂停中的任务停止后转为已暂停，保留剩余任务（没有剩余任务时视为运行结束）
+    pub fn settle_pause(&mut self) {
+        if self.pause_state != PauseState::Pausing {
+            return;
+        }
+        let Some(request) = self.pause_request.clone() else {
+            self.pause_state = PauseState::None;
+            return;
+        };
+        if self.tasker.as_ref().is_some_and(|t| t.running()) {
+            return;
+        }
+
+        self.held_tasks = if request.stopped() {
+            request.held_tasks(&self.posted_tasks)
+        } else {
+            Vec::new()
+        };
+        self.pause_request = None;
+        self.task_ids.clear();
+        self.posted_tasks.clear();
+        self.pause_state = if self.held_tasks.is_empty() {
+            PauseState::None
+        } else {
+            PauseState::Paused
+        };
+    }
+
+    /// 清除暂停状态与保留的任务（停止任务或开始新的运行时）
+    pub fn clear_pause(&mut self) {
+        self.pause_state = PauseState::None;
+        self.pause_request = None;
+        self.held_tasks.clear();
+    }
 }
 
 impl Drop for InstanceRuntime {
@@ -175,7 +494,6 @@ impl Drop for InstanceRuntime {
 }
 
 /// MaaFramework 运行时状态
-#[derive(Default)]
 pub struct MaaState {
     pub lib_dir: Mutex<Option<PathBuf>>,
     pub resource_dir: Mutex<Option<PathBuf>>,
@@ -184,9 +502,70 @@ pub struct MaaState {
     pub cached_adb_devices: Mutex<Vec<AdbDevice>>,
     /// 缓存的 Win32 窗口列表（全局共享）
     pub cached_win32_windows: Mutex<Vec<Win32Window>>,
+    /// 回调事件总线（MaaFramework 回调与 Agent 输出经由此分发）
+    pub event_bus: Arc<EventBus>,
+    /// 创建 Resource / Controller / Tasker 的后端（默认 MaaFramework 原生实现）
+    pub backend: Arc<dyn MaaBackend>,
+    /// 运行历史（接收事件总线上的任务回调）
+    pub history: Arc<RunHistory>,
+    /// 会话录制（接收事件总线上的回调，保存截图与控制器动作）
+    pub recorder: Arc<SessionRecorder>,
+    /// 最近画面的环形缓冲（任务失败时自动导出）
+    pub frame_buffer: Arc<FrameBuffer>,
+    /// 任务执行计时（查询任务详情时填入）
+    pub task_timings: Arc<TaskTimings>,
+    /// 等待当前任务结束的暂停请求
+    pub task_pause: Arc<TaskPause>,
+    /// 失败后自动重试的任务
+    pub task_retry: Arc<TaskRetry>,
+    /// 等待当前任务结束后生效的队列调整
+    pub task_queue: Arc<TaskQueue>,
+}
+
+impl Default for MaaState {
+    fn default() -> Self {
+        Self::with_backend(Arc::new(NativeBackend))
+    }
 }
 
 impl MaaState {
+    /// 使用指定后端创建状态（如 mock 后端）
+    pub fn with_backend(backend: Arc<dyn MaaBackend>) -> Self {
+        let event_bus = Arc::new(EventBus::default());
+        let history = Arc::new(RunHistory::default());
+        event_bus.add_sink(history.clone());
+        let recorder = Arc::new(SessionRecorder::default());
+        event_bus.add_sink(recorder.clone());
+        let frame_buffer = Arc::new(FrameBuffer::default());
+        event_bus.add_sink(frame_buffer.clone());
+        let task_timings = Arc::new(TaskTimings::default());
+        event_bus.add_sink(task_timings.clone());
+        let task_pause = Arc::new(TaskPause::default());
+        event_bus.add_sink(task_pause.clone());
+        let task_retry = Arc::new(TaskRetry::default());
+        event_bus.add_sink(task_retry.clone());
+        // 在重试之后处理，任务失败将重试时由重试重新提交
+        let task_queue = Arc::new(TaskQueue::default());
+        event_bus.add_sink(task_queue.clone());
+
+        Self {
+            lib_dir: Mutex::new(None),
+            resource_dir: Mutex::new(None),
+            instances: Mutex::new(HashMap::new()),
+            cached_adb_devices: Mutex::new(Vec::new()),
+            cached_win32_windows: Mutex::new(Vec::new()),
+            event_bus,
+            backend,
+            history,
+            recorder,
+            frame_buffer,
+            task_timings,
+            task_pause,
+            task_retry,
+            task_queue,
+        }
+    }
+
     /// 清理所有实例的 agent 子进程
     pub fn cleanup_all_agent_children(&self) {
         if let Ok(mut instances) = self.instances.lock() {
@@ -230,6 +609,183 @@ pub struct AgentConfig {
 pub struct TaskConfig {
     pub entry: String,
     pub pipeline_override: String,
+    /// 任务超时时间（毫秒），超时后停止该任务
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// 失败或超时后的重试策略
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+/// 重试条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryCondition {
+    /// 任务执行失败
+    Failed,
+    /// 任务超时（需同时设置 timeout_ms）
+    Timeout,
+}
+
+/// 任务重试策略（字段均可省略）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// 最多重试次数
+    pub attempts: u32,
+    /// 重新提交前的等待时间（毫秒，在恢复动作之后）
+    #[serde(alias = "delay")]
+    pub delay_ms: u64,
+    /// 触发重试的条件
+    pub on: Vec<RetryCondition>,
+    /// 重新提交前依次执行的控制器动作（如停止并重新启动应用）
+    pub recovery: Vec<ControllerInput>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 1,
+            delay_ms: 0,
+            on: vec![RetryCondition::Failed],
+            recovery: Vec::new(),
+        }
+    }
+}
+
+/// 运行选项（字段均可省略）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunOptions {
+    /// 整次运行的超时时间（毫秒，只计算有任务正在执行的时间），超时后停止所有任务
+    pub timeout_ms: Option<u64>,
+    /// 单个任务超时后是否继续执行后续任务
+    pub continue_on_timeout: bool,
+}
+
+/// Pipeline 覆盖中的一处错误
+#[derive(Debug, Clone, Serialize)]
+pub struct OverrideError {
+    /// 出错位置（如 `MyNode.timeout`，数组形式的覆盖以 `[序号]` 开头；整体错误为空）
+    pub path: String,
+    pub message: String,
+}
+
+/// 单个任务的校验结果
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskValidation {
+    /// 任务在提交列表中的序号
+    pub index: usize,
+    pub entry: String,
+    /// 为空表示校验通过
+    pub errors: Vec<OverrideError>,
+    /// 不影响提交的问题（常用字段的类型与预期不符）
+    pub warnings: Vec<OverrideError>,
+}
+
+/// 启动任务结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StartTasksResult {
+    /// 成功提交的任务 ID（按任务列表顺序）
+    pub task_ids: Vec<i64>,
+    /// 未提交的任务（校验失败或提交失败）及原因
+    pub rejected: Vec<TaskValidation>,
+    /// 已提交但校验有警告的任务
+    pub warnings: Vec<TaskValidation>,
+}
+
+/// 已提交的任务
+#[derive(Debug, Clone)]
+pub struct PostedTask {
+    pub task_id: i64,
+    pub config: TaskConfig,
+    /// 已重试次数
+    pub retries: u32,
+}
+
+/// 重新提交的任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumedTask {
+    /// 重新提交前的 task_id
+    pub previous_task_id: i64,
+    /// 重新提交后的 task_id（提交失败时为 None）
+    pub task_id: Option<i64>,
+    /// 提交失败的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 按顺序重新提交任务，替换实例当前的任务队列并重新开始失败重试监视
+///
+/// 返回每个任务的提交结果（原 task_id 与新 task_id 的对应关系，提交失败的任务带上原因）
+pub fn repost_tasks(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    instance: &mut InstanceRuntime,
+    tasks: Vec<PostedTask>,
+) -> Vec<ResumedTask> {
+    let tasker = instance.tasker.clone();
+    let mut resumed = Vec::new();
+    let mut posted = Vec::new();
+    for task in tasks {
+        let entry = &task.config.entry;
+        let result = match &tasker {
+            Some(tasker) => tasker
+                .post_task(entry, &task.config.pipeline_override)
+                .map_err(|e| e.to_string()),
+            None => Err("Tasker not created".to_string()),
+        };
+        match result {
+            Ok(task_id) => {
+                state.history.task_posted(
+                    instance_id,
+                    task_id,
+                    entry,
+                    &task.config.pipeline_override,
+                );
+                resumed.push(ResumedTask {
+                    previous_task_id: task.task_id,
+                    task_id: Some(task_id),
+                    error: None,
+                });
+                posted.push(PostedTask { task_id, ..task });
+            }
+            Err(e) => {
+                warn!(
+                    "[Tasker] 实例 {} 重新提交任务 {} 失败: {}",
+                    instance_id, entry, e
+                );
+                resumed.push(ResumedTask {
+                    previous_task_id: task.task_id,
+                    task_id: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+    instance.task_ids = posted.iter().map(|t| t.task_id).collect();
+    instance.posted_tasks = posted;
+    state.task_retry.watch(state, instance_id, instance);
+    resumed
+}
+
+/// 重新提交结果中成功提交的任务数
+pub fn resumed_count(resumed: &[ResumedTask]) -> usize {
+    resumed.iter().filter(|t| t.task_id.is_some()).count()
+}
+
+/// 停止实例的任务并等待 Tasker 停止（最多等待 TASKER_STOP_TIMEOUT）
+///
+/// 被中止的任务的失败不自动导出画面
+pub fn stop_tasks(state: &MaaState, instance_id: &str, tasker: &Arc<dyn TaskerBackend>) {
+    state.frame_buffer.ignore_failures(instance_id);
+    if let Err(e) = tasker.post_stop() {
+        warn!("[Tasker] 停止实例 {} 的任务失败: {}", instance_id, e);
+    }
+    let deadline = Instant::now() + TASKER_STOP_TIMEOUT;
+    while tasker.running() && Instant::now() < deadline {
+        thread::sleep(TASKER_STOP_POLL_INTERVAL);
+    }
 }
 
 /// 版本检查结果
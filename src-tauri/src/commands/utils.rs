@@ -2,28 +2,23 @@
 //!
 //! 提供路径处理和其他通用工具函数
 
-use super::types::MaaCallbackEvent;
 use std::path::PathBuf;
-use tauri::{AppHandle, Emitter};
 
-/// 发送回调事件到前端
-pub fn emit_callback_event<S: Into<String>>(app: &AppHandle, message: S, details: S) {
-    let event = MaaCallbackEvent {
-        message: message.into(),
-        details: details.into(),
-    };
-    if let Err(e) = app.emit("maa-callback", event) {
-        log::error!("Failed to emit maa-callback: {}", e);
-    }
-}
+use super::error::{ErrorCode, MxuError, MxuResult, Subsystem};
 
 /// 获取应用数据目录
 /// - macOS: ~/Library/Application Support/MXU/
 /// - Windows/Linux: exe 所在目录（保持便携式部署）
-pub fn get_app_data_dir() -> Result<PathBuf, String> {
+pub fn get_app_data_dir() -> MxuResult<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
+        let home = std::env::var("HOME").map_err(|_| {
+            MxuError::new(
+                ErrorCode::NotFound,
+                Subsystem::System,
+                "无法获取 HOME 环境变量",
+            )
+        })?;
         let path = PathBuf::from(home)
             .join("Library")
             .join("Application Support")
@@ -82,16 +77,26 @@ pub fn get_logs_dir() -> PathBuf {
 }
 
 /// 获取 exe 所在目录路径（内部使用）
-pub fn get_exe_directory() -> Result<PathBuf, String> {
-    let exe_path = std::env::current_exe().map_err(|e| format!("获取 exe 路径失败: {}", e))?;
-    exe_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .ok_or_else(|| "无法获取 exe 所在目录".to_string())
+pub fn get_exe_directory() -> MxuResult<PathBuf> {
+    let exe_path = std::env::current_exe().map_err(|e| {
+        MxuError::new(
+            ErrorCode::Io,
+            Subsystem::System,
+            format!("获取 exe 路径失败: {}", e),
+        )
+    })?;
+    exe_path.parent().map(|p| p.to_path_buf()).ok_or_else(|| {
+        MxuError::new(
+            ErrorCode::InvalidPath,
+            Subsystem::System,
+            "无法获取 exe 所在目录",
+        )
+        .with_path(&exe_path)
+    })
 }
 
 /// 获取可执行文件所在目录下的 maafw 子目录
-pub fn get_maafw_dir() -> Result<PathBuf, String> {
+pub fn get_maafw_dir() -> MxuResult<PathBuf> {
     Ok(get_exe_directory()?.join("maafw"))
 }
 
@@ -0,0 +1,22 @@
+//! 控制器掉线重连相关命令
+//!
+//! 看门狗本身在 Rust 端运行（见 `crate::watchdog`），前端只负责同步设置
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::watchdog::{ControllerWatchdog, WatchdogOptions};
+
+/// 更新掉线重连设置（前端加载配置与修改设置时调用）
+#[tauri::command]
+pub fn maa_watchdog_configure(watchdog: State<Arc<ControllerWatchdog>>, options: WatchdogOptions) {
+    log::info!("maa_watchdog_configure called");
+    watchdog.configure(options);
+}
+
+/// 获取当前的掉线重连设置
+#[tauri::command]
+pub fn maa_watchdog_get_options(watchdog: State<Arc<ControllerWatchdog>>) -> WatchdogOptions {
+    watchdog.options()
+}
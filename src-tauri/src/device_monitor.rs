@@ -0,0 +1,213 @@
+//! ADB 设备热插拔监听
+//!
+//! maa_find_adb_devices 只在用户手动刷新时搜索一次。启用监听后后台线程定期重新搜索 ADB 设备，
+//! 与 MaaState 中缓存的设备列表比较，经事件总线发出 adb-device-added / adb-device-removed 事件，
+//! 连接面板据此更新设备列表，并在之前使用的设备重新出现时自动连接
+//!
+//! 手动搜索同样会更新缓存，监听只报告两次搜索之间的变化
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::event_bus::BusEvent;
+use crate::commands::maa_core::search_adb_devices;
+use crate::commands::types::{AdbDevice, MaaState};
+
+/// 新设备出现的事件名
+pub const ADB_DEVICE_ADDED_EVENT: &str = "adb-device-added";
+
+/// 设备消失的事件名
+pub const ADB_DEVICE_REMOVED_EVENT: &str = "adb-device-removed";
+
+/// 最短搜索间隔（搜索 ADB 设备会启动 adb 进程，不宜过于频繁）
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 监听配置（字段均可省略）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeviceMonitorOptions {
+    /// 是否启用监听
+    pub enabled: bool,
+    /// 搜索间隔（毫秒，最短 1 秒）
+    pub interval_ms: u64,
+}
+
+impl Default for DeviceMonitorOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 5000,
+        }
+    }
+}
+
+impl DeviceMonitorOptions {
+    fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms).max(MIN_INTERVAL)
+    }
+}
+
+/// 两次搜索之间的设备变化
+#[derive(Debug, Default)]
+pub struct DeviceChanges {
+    pub added: Vec<AdbDevice>,
+    pub removed: Vec<AdbDevice>,
+}
+
+impl DeviceChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// 比较两次搜索结果（名称、adb 路径与地址均相同视为同一设备；模拟器重启后端口变化会报告为
+/// 先移除再新增）
+pub fn diff_devices(previous: &[AdbDevice], current: &[AdbDevice]) -> DeviceChanges {
+    let same = |a: &AdbDevice, b: &AdbDevice| {
+        a.name == b.name && a.adb_path == b.adb_path && a.address == b.address
+    };
+    DeviceChanges {
+        added: current
+            .iter()
+            .filter(|d| !previous.iter().any(|p| same(p, d)))
+            .cloned()
+            .collect(),
+        removed: previous
+            .iter()
+            .filter(|p| !current.iter().any(|d| same(p, d)))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[derive(Default)]
+struct MonitorInner {
+    options: DeviceMonitorOptions,
+    /// 搜索线程是否在运行
+    running: bool,
+}
+
+/// ADB 设备热插拔监听
+pub struct AdbDeviceMonitor {
+    state: Arc<MaaState>,
+    inner: Mutex<MonitorInner>,
+    wakeup: Condvar,
+}
+
+impl AdbDeviceMonitor {
+    pub fn new(state: Arc<MaaState>) -> Arc<Self> {
+        Arc::new(Self {
+            state,
+            inner: Mutex::new(MonitorInner::default()),
+            wakeup: Condvar::new(),
+        })
+    }
+
+    /// 更新监听配置（启用时启动搜索线程，禁用后线程在当前搜索结束后退出）
+    pub fn configure(self: &Arc<Self>, options: DeviceMonitorOptions) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        info!("[DeviceMonitor] Options updated: {:?}", options);
+        let start = options.enabled && !inner.running;
+        if start {
+            inner.running = true;
+        }
+        inner.options = options;
+        drop(inner);
+        self.wakeup.notify_all();
+
+        if start {
+            let monitor = self.clone();
+            thread::spawn(move || monitor.run_loop());
+        }
+    }
+
+    /// 当前监听配置
+    pub fn options(&self) -> DeviceMonitorOptions {
+        self.inner
+            .lock()
+            .map(|inner| inner.options.clone())
+            .unwrap_or_default()
+    }
+
+    fn run_loop(self: Arc<Self>) {
+        info!("[DeviceMonitor] Started");
+        // 启用后立即搜索一次，报告启用前缓存之后的变化
+        loop {
+            self.scan();
+            if !self.sleep() {
+                break;
+            }
+        }
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.running = false;
+        }
+        info!("[DeviceMonitor] Stopped");
+    }
+
+    /// 等待一个搜索间隔（间隔在等待期间修改时按新间隔计算），期间被禁用时提前返回 false
+    fn sleep(&self) -> bool {
+        let started = Instant::now();
+        let Ok(mut inner) = self.inner.lock() else {
+            return false;
+        };
+        loop {
+            if !inner.options.enabled {
+                return false;
+            }
+            let deadline = started + inner.options.interval();
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            match self.wakeup.wait_timeout(inner, deadline - now) {
+                Ok((guard, _)) => inner = guard,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    /// 重新搜索设备并报告与缓存相比的变化
+    fn scan(&self) {
+        let previous = match self.state.cached_adb_devices.lock() {
+            Ok(cached) => cached.clone(),
+            Err(_) => return,
+        };
+        let current = match search_adb_devices(&self.state) {
+            Ok(devices) => devices,
+            Err(e) => {
+                warn!("[DeviceMonitor] Failed to search ADB devices: {}", e);
+                return;
+            }
+        };
+
+        let changes = diff_devices(&previous, &current);
+        if changes.is_empty() {
+            debug!("[DeviceMonitor] No device changes");
+            return;
+        }
+        for device in &changes.removed {
+            info!(
+                "[DeviceMonitor] Device removed: {} ({})",
+                device.name, device.address
+            );
+            self.state
+                .event_bus
+                .emit(BusEvent::new(ADB_DEVICE_REMOVED_EVENT, None, device));
+        }
+        for device in &changes.added {
+            info!(
+                "[DeviceMonitor] Device added: {} ({})",
+                device.name, device.address
+            );
+            self.state
+                .event_bus
+                .emit(BusEvent::new(ADB_DEVICE_ADDED_EVENT, None, device));
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! mxu 配置文件解析
+//!
+//! 读取前端保存的 config/mxu-{项目名}.json（无项目名时为 mxu.json）
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::interface::parse_jsonc;
+use crate::commands::types::{ControllerInput, RetryCondition, RetryPolicy, ScreenshotResolution};
+
+/// 配置文件子目录
+const CONFIG_DIR: &str = "config";
+
+/// MXU 配置文件（仅包含无界面运行需要的字段）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MxuConfig {
+    #[serde(default)]
+    pub instances: Vec<SavedInstance>,
+    #[serde(default)]
+    pub settings: AppSettings,
+    /// 最后激活的实例 ID
+    #[serde(default)]
+    pub last_active_instance_id: Option<String>,
+}
+
+/// 应用设置
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    /// 通信兼容模式，强制使用 TCP 而非 IPC
+    #[serde(default)]
+    pub tcp_compat_mode: bool,
+    /// 任务超时
+    #[serde(default)]
+    pub task_timeout: TaskTimeoutSettings,
+    /// 任务失败重试
+    #[serde(default)]
+    pub task_retry: TaskRetrySettings,
+}
+
+/// 任务超时设置（与前端 TaskTimeoutSettings 一致）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TaskTimeoutSettings {
+    pub enabled: bool,
+    /// 单个任务的超时时间（分钟，0 表示不限制）
+    pub task_timeout_minutes: u64,
+    /// 整次运行的超时时间（分钟，0 表示不限制）
+    pub run_timeout_minutes: u64,
+    /// 单个任务超时后是否继续执行后续任务
+    pub continue_on_timeout: bool,
+}
+
+impl TaskTimeoutSettings {
+    /// 单个任务的超时时间（毫秒）
+    pub fn task_timeout_ms(&self) -> Option<u64> {
+        Self::minutes_to_ms(self.enabled, self.task_timeout_minutes)
+    }
+
+    /// 整次运行的超时时间（毫秒）
+    pub fn run_timeout_ms(&self) -> Option<u64> {
+        Self::minutes_to_ms(self.enabled, self.run_timeout_minutes)
+    }
+
+    fn minutes_to_ms(enabled: bool, minutes: u64) -> Option<u64> {
+        (enabled && minutes > 0).then(|| minutes.saturating_mul(60_000))
+    }
+}
+
+/// 任务失败重试设置（与前端 TaskRetrySettings 一致）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TaskRetrySettings {
+    pub enabled: bool,
+    /// 最多重试次数
+    pub attempts: u32,
+    /// 重试前的等待时间（秒）
+    pub delay_seconds: u64,
+    /// 任务超时后是否也重试
+    pub retry_on_timeout: bool,
+}
+
+impl TaskRetrySettings {
+    /// 应用到任务的重试策略：任务单独设置了重试时以其为准，否则使用全局设置（未启用时为 None）
+    pub fn policy(&self, task_retry: Option<&TaskRetryOverride>) -> Option<RetryPolicy> {
+        match task_retry {
+            Some(retry) => retry_policy(
+                retry.attempts,
+                retry.delay_seconds,
+                retry.retry_on_timeout,
+                retry.restart_app.as_deref(),
+            ),
+            None if self.enabled => retry_policy(
+                self.attempts,
+                self.delay_seconds,
+                self.retry_on_timeout,
+                None,
+            ),
+            None => None,
+        }
+    }
+}
+
+/// 单个任务的失败重试设置（与前端 TaskRetryOverride 一致，覆盖全局设置）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TaskRetryOverride {
+    /// 最多重试次数（0 表示该任务不重试）
+    pub attempts: u32,
+    /// 重试前的等待时间（秒）
+    pub delay_seconds: u64,
+    /// 任务超时后是否也重试
+    pub retry_on_timeout: bool,
+    /// 重试前重新启动的应用（ADB 为包名或 Activity，为空时不重启）
+    pub restart_app: Option<String>,
+}
+
+/// 根据设置生成重试策略，重启应用时先停止再启动
+fn retry_policy(
+    attempts: u32,
+    delay_seconds: u64,
+    retry_on_timeout: bool,
+    restart_app: Option<&str>,
+) -> Option<RetryPolicy> {
+    if attempts == 0 {
+        return None;
+    }
+    let mut on = vec![RetryCondition::Failed];
+    if retry_on_timeout {
+        on.push(RetryCondition::Timeout);
+    }
+    let recovery = match restart_app.map(str::trim).filter(|app| !app.is_empty()) {
+        Some(app) => vec![
+            ControllerInput::StopApp {
+                intent: app.to_string(),
+            },
+            ControllerInput::StartApp {
+                intent: app.to_string(),
+            },
+        ],
+        None => Vec::new(),
+    };
+    Some(RetryPolicy {
+        attempts,
+        delay_ms: delay_seconds.saturating_mul(1000),
+        on,
+        recovery,
+    })
+}
+
+/// 保存的实例配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedInstance {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub controller_name: Option<String>,
+    #[serde(default)]
+    pub resource_name: Option<String>,
+    #[serde(default)]
+    pub saved_device: Option<SavedDeviceInfo>,
+    #[serde(default)]
+    pub tasks: Vec<SavedTask>,
+    /// 定时执行策略
+    #[serde(default)]
+    pub schedule_policies: Vec<SchedulePolicy>,
+    /// 实例单独设置的截图分辨率（覆盖控制器定义）
+    #[serde(default)]
+    pub screenshot_resolution: Option<ScreenshotResolution>,
+}
+
+/// 定时执行策略（与前端 SchedulePolicy 一致）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePolicy {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    /// 重复日期（0-6，0 = 周日）
+    #[serde(default)]
+    pub weekdays: Vec<u32>,
+    /// 开始时间（0-23 点）
+    #[serde(default)]
+    pub hours: Vec<u32>,
+    /// 定时表达式（cron / @every / @at），设置后代替 weekdays 与 hours
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    /// 排除时段，命中的触发时间被跳过
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<String>,
+}
+
+/// 保存的设备信息
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedDeviceInfo {
+    /// ADB 设备：保存设备名称
+    pub adb_device_name: Option<String>,
+    /// Win32/Gamepad：保存窗口名称
+    pub window_name: Option<String>,
+    /// PlayCover：保存地址
+    pub playcover_address: Option<String>,
+    /// ImageFolder：保存图片目录
+    pub image_folder_path: Option<String>,
+}
+
+/// 保存的任务配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedTask {
+    pub id: String,
+    pub task_name: String,
+    #[serde(default)]
+    pub custom_name: Option<String>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub option_values: HashMap<String, OptionValue>,
+    /// 单独设置的失败重试（未设置时使用全局设置）
+    #[serde(default)]
+    pub retry: Option<TaskRetryOverride>,
+}
+
+/// 选项值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OptionValue {
+    Select {
+        #[serde(rename = "caseName")]
+        case_name: String,
+    },
+    Checkbox {
+        #[serde(rename = "caseNames")]
+        case_names: Vec<String>,
+    },
+    Switch {
+        value: bool,
+    },
+    Input {
+        values: HashMap<String, String>,
+    },
+}
+
+/// 获取配置文件路径
+pub fn config_file_path(data_dir: &Path, project_name: &str) -> PathBuf {
+    let file_name = if project_name.is_empty() {
+        "mxu.json".to_string()
+    } else {
+        format!("mxu-{}.json", project_name)
+    };
+    data_dir.join(CONFIG_DIR).join(file_name)
+}
+
+/// 读取配置文件
+pub fn load_config(path: &Path) -> Result<MxuConfig, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("读取配置文件 {} 失败: {}", path.display(), e))?;
+    parse_jsonc(&content, &path.display().to_string())
+}
+
+impl MxuConfig {
+    /// 按 ID 或名称查找实例；未指定时依次使用最后激活的实例、第一个实例
+    pub fn find_instance(&self, key: Option<&str>) -> Option<&SavedInstance> {
+        match key {
+            Some(key) => self
+                .instances
+                .iter()
+                .find(|i| i.id == key)
+                .or_else(|| self.instances.iter().find(|i| i.name == key)),
+            None => self
+                .last_active_instance_id
+                .as_deref()
+                .and_then(|id| self.instances.iter().find(|i| i.id == id))
+                .or_else(|| self.instances.first()),
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! ProjectInterface 解析
+//!
+//! 读取 interface.json（PI V2，支持 JSONC 注释和尾逗号），处理 import 字段并计算资源路径
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::commands::types::{AgentConfig, ScreenshotResolution};
+
+// ============================================================================
+// 数据类型定义
+// ============================================================================
+
+/// ProjectInterface（仅包含无界面运行需要的字段）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectInterface {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub agent: Option<AgentField>,
+    #[serde(default)]
+    pub controller: Vec<ControllerItem>,
+    #[serde(default)]
+    pub resource: Vec<ResourceItem>,
+    #[serde(default)]
+    pub task: Vec<TaskItem>,
+    #[serde(default)]
+    pub option: HashMap<String, OptionDefinition>,
+    /// v2.3.0: 全局选项配置
+    #[serde(default)]
+    pub global_option: Vec<String>,
+    /// v2.2.0: 导入其他 PI 文件的路径数组
+    #[serde(default)]
+    pub import: Vec<String>,
+}
+
+/// agent 字段（单对象或数组）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AgentField {
+    Single(AgentConfig),
+    Multiple(Vec<AgentConfig>),
+}
+
+impl ProjectInterface {
+    /// 将 agent 字段标准化为数组
+    pub fn agent_configs(&self) -> Option<Vec<AgentConfig>> {
+        match &self.agent {
+            Some(AgentField::Single(agent)) => Some(vec![agent.clone()]),
+            Some(AgentField::Multiple(agents)) => Some(agents.clone()),
+            None => None,
+        }
+    }
+}
+
+/// 控制器定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct ControllerItem {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub controller_type: String,
+    /// v2.2.0: 额外的资源路径数组，在 resource.path 加载完成后加载
+    #[serde(default)]
+    pub attach_resource_path: Vec<String>,
+    /// v2.3.0: 控制器级的选项配置
+    #[serde(default)]
+    pub option: Vec<String>,
+    #[serde(default)]
+    pub win32: Option<Win32Config>,
+    #[serde(default)]
+    pub playcover: Option<PlayCoverConfig>,
+    #[serde(default)]
+    pub gamepad: Option<GamepadConfig>,
+    /// 截图目标短边
+    #[serde(default)]
+    pub display_short_side: Option<i32>,
+    /// 截图目标长边（优先于短边）
+    #[serde(default)]
+    pub display_long_side: Option<i32>,
+    /// 使用原始分辨率截图（优先于短边与长边）
+    #[serde(default)]
+    pub display_raw: bool,
+}
+
+impl ControllerItem {
+    /// 控制器定义的截图目标分辨率（均未设置时为默认的短边 720）
+    pub fn screenshot_resolution(&self) -> ScreenshotResolution {
+        if self.display_raw {
+            ScreenshotResolution::Raw
+        } else if let Some(long_side) = self.display_long_side {
+            ScreenshotResolution::LongSide(long_side)
+        } else if let Some(short_side) = self.display_short_side {
+            ScreenshotResolution::ShortSide(short_side)
+        } else {
+            ScreenshotResolution::default()
+        }
+    }
+}
+
+/// Win32 控制器配置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Win32Config {
+    pub class_regex: Option<String>,
+    pub window_regex: Option<String>,
+    pub mouse: Option<String>,
+    pub keyboard: Option<String>,
+    pub screencap: Option<String>,
+}
+
+/// PlayCover 控制器配置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PlayCoverConfig {
+    pub uuid: Option<String>,
+}
+
+/// Gamepad 控制器配置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GamepadConfig {
+    pub class_regex: Option<String>,
+    pub window_regex: Option<String>,
+    pub gamepad_type: Option<String>,
+    pub screencap: Option<String>,
+}
+
+/// 资源定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResourceItem {
+    pub name: String,
+    #[serde(default)]
+    pub path: Vec<String>,
+    #[serde(default)]
+    pub option: Vec<String>,
+}
+
+/// 任务定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskItem {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub entry: String,
+    #[serde(default)]
+    pub pipeline_override: Option<Value>,
+    #[serde(default)]
+    pub option: Vec<String>,
+}
+
+/// 选项 case 定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaseItem {
+    pub name: String,
+    #[serde(default)]
+    pub option: Vec<String>,
+    #[serde(default)]
+    pub pipeline_override: Option<Value>,
+}
+
+/// 输入项定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputItem {
+    pub name: String,
+    #[serde(default)]
+    pub default: Option<String>,
+    /// 'string' | 'int' | 'bool'，默认 string
+    #[serde(default)]
+    pub pipeline_type: Option<String>,
+}
+
+/// 选项定义（select / checkbox / switch / input，未指定 type 时视为 select）
+#[derive(Debug, Clone, Deserialize)]
+pub struct OptionDefinition {
+    #[serde(rename = "type", default)]
+    pub option_type: Option<String>,
+    #[serde(default)]
+    pub cases: Vec<CaseItem>,
+    /// select/switch 为字符串，checkbox 为字符串数组
+    #[serde(default)]
+    pub default_case: Option<Value>,
+    #[serde(default)]
+    pub inputs: Vec<InputItem>,
+    #[serde(default)]
+    pub pipeline_override: Option<Value>,
+}
+
+impl OptionDefinition {
+    /// 选项类型（未指定时为 select）
+    pub fn kind(&self) -> &str {
+        self.option_type.as_deref().unwrap_or("select")
+    }
+}
+
+/// 可被 import 的 PI 片段
+#[derive(Debug, Default, Deserialize)]
+struct ImportableInterface {
+    #[serde(default)]
+    task: Vec<TaskItem>,
+    #[serde(default)]
+    option: HashMap<String, OptionDefinition>,
+}
+
+// ============================================================================
+// JSONC 解析
+// ============================================================================
+
+/// 解析 JSONC 格式的字符串（支持注释和尾逗号）
+pub fn parse_jsonc<T: DeserializeOwned>(content: &str, source_name: &str) -> Result<T, String> {
+    let cleaned = strip_jsonc(content.trim_start_matches('\u{feff}'));
+    serde_json::from_str(&cleaned).map_err(|e| format!("解析 {} 失败: {}", source_name, e))
+}
+
+/// 移除 JSONC 中的注释和尾逗号，字符串内容保持不变
+fn strip_jsonc(content: &str) -> String {
+    remove_trailing_commas(&remove_comments(content))
+}
+
+/// 逐字符扫描，回调返回 false 时丢弃当前字符（字符串内的内容原样保留）
+fn scan_outside_strings(
+    content: &str,
+    mut keep: impl FnMut(&[char], &mut usize) -> bool,
+) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if in_string {
+            out.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                out.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        if keep(&chars, &mut i) {
+            if c == '"' {
+                in_string = true;
+            }
+            out.push(c);
+        }
+        // 回调未推进位置时前进一个字符
+        if i == start {
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// 移除 // 行注释和 /* */ 块注释
+fn remove_comments(content: &str) -> String {
+    scan_outside_strings(content, |chars, i| match (chars[*i], chars.get(*i + 1)) {
+        ('/', Some('/')) => {
+            while *i < chars.len() && chars[*i] != '\n' {
+                *i += 1;
+            }
+            false
+        }
+        ('/', Some('*')) => {
+            *i += 2;
+            while *i < chars.len() && !(chars[*i] == '*' && chars.get(*i + 1) == Some(&'/')) {
+                *i += 1;
+            }
+            *i = (*i + 2).min(chars.len());
+            false
+        }
+        _ => true,
+    })
+}
+
+/// 移除对象和数组的尾逗号（后续第一个非空白字符是 } 或 ] 的逗号）
+fn remove_trailing_commas(content: &str) -> String {
+    scan_outside_strings(content, |chars, i| {
+        if chars[*i] != ',' {
+            return true;
+        }
+        let next = chars[*i + 1..].iter().find(|ch| !ch.is_whitespace());
+        !matches!(next, Some('}') | Some(']'))
+    })
+}
+
+// ============================================================================
+// 加载与路径计算
+// ============================================================================
+
+/// 读取 interface.json 并合并 import 的文件
+pub fn load_interface(path: &Path) -> Result<ProjectInterface, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+    let mut pi: ProjectInterface = parse_jsonc(&content, &path.display().to_string())?;
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    for import_path in pi.import.clone() {
+        let full_path = base_dir.join(&import_path);
+        let imported = std::fs::read_to_string(&full_path)
+            .map_err(|e| format!("读取 {} 失败: {}", full_path.display(), e))
+            .and_then(|c| parse_jsonc::<ImportableInterface>(&c, &full_path.display().to_string()));
+
+        match imported {
+            Ok(imported) => {
+                if !imported.task.is_empty() {
+                    info!("合并了 {} 个导入的 task", imported.task.len());
+                    pi.task.extend(imported.task);
+                }
+                if !imported.option.is_empty() {
+                    info!("合并了 {} 个导入的 option", imported.option.len());
+                    pi.option.extend(imported.option);
+                }
+            }
+            Err(e) => warn!("加载导入文件失败 [{}]: {}", import_path, e),
+        }
+    }
+
+    Ok(pi)
+}
+
+/// 清理相对路径前缀 "./" 或 ".\"
+fn clean_relative_path(path: &str) -> &str {
+    path.strip_prefix("./")
+        .or_else(|| path.strip_prefix(".\\"))
+        .unwrap_or(path)
+}
+
+/// 计算完整的资源路径列表（resource.path 之后追加 controller.attach_resource_path）
+pub fn compute_resource_paths(
+    resource: &ResourceItem,
+    controller: Option<&ControllerItem>,
+    base_path: &Path,
+) -> Vec<String> {
+    let attach = controller
+        .map(|c| c.attach_resource_path.as_slice())
+        .unwrap_or_default();
+
+    resource
+        .path
+        .iter()
+        .chain(attach)
+        .map(|p| {
+            base_path
+                .join(clean_relative_path(p))
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
@@ -0,0 +1,16 @@
+//! 无界面运行支持
+//!
+//! 供 mxu-cli 使用：读取 interface.json 与 mxu 配置文件，复用 maa_core / maa_agent 的逻辑运行实例任务
+//!
+//! 模块结构：
+//! - `interface`: ProjectInterface 解析（JSONC、import 合并、资源路径计算）
+//! - `config`: mxu 配置文件解析
+//! - `special_tasks`: MXU 内置特殊任务定义（与前端 specialTasks.ts 保持一致）
+//! - `pipeline_override`: 任务 pipeline_override 生成（与前端 pipelineOverride.ts 保持一致）
+//! - `runner`: 连接控制器、加载资源、执行任务的完整流程
+
+pub mod config;
+pub mod interface;
+pub mod pipeline_override;
+pub mod runner;
+pub mod special_tasks;
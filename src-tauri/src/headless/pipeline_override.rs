@@ -0,0 +1,308 @@
+//! Pipeline Override 生成
+//!
+//! 与前端 pipelineOverride.ts 保持一致：生成数组格式的 pipeline_override，
+//! MaaFramework 会按顺序依次覆盖（同名字段完整替换，非深合并）
+//! 两边运行 tests/fixtures/pipeline-override.json 中的同一份用例检查结果一致
+
+use std::collections::HashMap;
+
+use log::warn;
+use serde_json::{Map, Value};
+
+use super::config::{OptionValue, SavedTask};
+use super::interface::{OptionDefinition, ProjectInterface};
+use super::special_tasks::get_special_task;
+
+const YES_CASE_NAMES: [&str; 4] = ["Yes", "yes", "Y", "y"];
+const NO_CASE_NAMES: [&str; 4] = ["No", "no", "N", "n"];
+
+/// 根据选项定义生成默认选项值
+fn default_option_value(option_def: &OptionDefinition) -> OptionValue {
+    let default_case = option_def.default_case.as_ref();
+    let first_case = option_def.cases.first().map(|c| c.name.clone());
+
+    match option_def.kind() {
+        "input" => OptionValue::Input {
+            values: option_def
+                .inputs
+                .iter()
+                .map(|i| (i.name.clone(), i.default.clone().unwrap_or_default()))
+                .collect(),
+        },
+        "switch" => {
+            let case_name = default_case
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .or(first_case)
+                .unwrap_or_else(|| "Yes".to_string());
+            OptionValue::Switch {
+                value: YES_CASE_NAMES.contains(&case_name.as_str()),
+            }
+        }
+        "checkbox" => OptionValue::Checkbox {
+            case_names: default_case
+                .and_then(Value::as_array)
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        },
+        _ => OptionValue::Select {
+            case_name: default_case
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .or(first_case)
+                .unwrap_or_default(),
+        },
+    }
+}
+
+/// 替换输入选项 pipeline_override 中的 {name} 占位符
+fn apply_input_values(
+    option_def: &OptionDefinition,
+    values: &HashMap<String, String>,
+) -> Option<Value> {
+    let mut override_str = serde_json::to_string(option_def.pipeline_override.as_ref()?).ok()?;
+
+    for input in &option_def.inputs {
+        let input_val = values
+            .get(&input.name)
+            .cloned()
+            .or_else(|| input.default.clone())
+            .unwrap_or_default();
+        let placeholder = format!("{{{}}}", input.name);
+        let quoted_placeholder = format!("\"{}\"", placeholder);
+
+        match input.pipeline_type.as_deref().unwrap_or("string") {
+            "int" => {
+                let val = if input_val.is_empty() {
+                    "0"
+                } else {
+                    input_val.as_str()
+                };
+                override_str = override_str
+                    .replace(&quoted_placeholder, val)
+                    .replace(&placeholder, val);
+            }
+            "bool" => {
+                let val = if ["true", "1", "yes", "y"].contains(&input_val.to_lowercase().as_str())
+                {
+                    "true"
+                } else {
+                    "false"
+                };
+                override_str = override_str
+                    .replace(&quoted_placeholder, val)
+                    .replace(&placeholder, val);
+            }
+            _ => {
+                // 转义为合法的 JSON 字符串内容（如 Windows 路径中的反斜杠）
+                let escaped = serde_json::to_string(&input_val).unwrap_or_default();
+                let escaped = &escaped[1..escaped.len() - 1];
+                override_str = override_str.replace(&placeholder, escaped);
+            }
+        }
+    }
+
+    match serde_json::from_str(&override_str) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("解析选项覆盖失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 递归处理选项的 pipeline_override，收集到数组中
+fn collect_option_overrides(
+    option_key: &str,
+    option_values: &HashMap<String, OptionValue>,
+    overrides: &mut Vec<Value>,
+    all_options: &HashMap<String, OptionDefinition>,
+) {
+    let Some(option_def) = all_options.get(option_key) else {
+        return;
+    };
+    let option_value = option_values
+        .get(option_key)
+        .cloned()
+        .unwrap_or_else(|| default_option_value(option_def));
+
+    match (&option_value, option_def.kind()) {
+        (OptionValue::Checkbox { case_names }, "checkbox") => {
+            // v2.3.0: checkbox 多选类型，按 cases 定义顺序合并所有选中的 case
+            for case_def in &option_def.cases {
+                if case_names.contains(&case_def.name) {
+                    if let Some(po) = &case_def.pipeline_override {
+                        overrides.push(po.clone());
+                    }
+                }
+            }
+        }
+        (OptionValue::Select { .. } | OptionValue::Switch { .. }, _)
+            if !option_def.cases.is_empty() =>
+        {
+            let case_name = match &option_value {
+                OptionValue::Switch { value } => {
+                    let target_names = if *value {
+                        YES_CASE_NAMES
+                    } else {
+                        NO_CASE_NAMES
+                    };
+                    option_def
+                        .cases
+                        .iter()
+                        .find(|c| target_names.contains(&c.name.as_str()))
+                        .map(|c| c.name.clone())
+                        .unwrap_or_else(|| if *value { "Yes" } else { "No" }.to_string())
+                }
+                OptionValue::Select { case_name } => case_name.clone(),
+                _ => unreachable!(),
+            };
+
+            let Some(case_def) = option_def.cases.iter().find(|c| c.name == case_name) else {
+                return;
+            };
+
+            if let Some(po) = &case_def.pipeline_override {
+                overrides.push(po.clone());
+            }
+
+            for nested_key in &case_def.option {
+                collect_option_overrides(nested_key, option_values, overrides, all_options);
+            }
+        }
+        (OptionValue::Input { values }, _) => {
+            if let Some(value) = apply_input_values(option_def, values) {
+                overrides.push(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 为单个任务生成 pipeline override JSON
+///
+/// 覆盖顺序：task.pipeline_override < global_option < resource.option < controller.option < task.option
+pub fn generate_task_pipeline_override(
+    task: &SavedTask,
+    pi: &ProjectInterface,
+    controller_name: Option<&str>,
+    resource_name: Option<&str>,
+) -> String {
+    // 处理 MXU 内置特殊任务
+    if get_special_task(&task.task_name).is_some() {
+        return generate_special_task_override(task);
+    }
+
+    let Some(task_def) = pi.task.iter().find(|t| t.name == task.task_name) else {
+        return "[]".to_string();
+    };
+
+    let mut overrides = Vec::new();
+
+    // 添加任务自身的 pipeline_override
+    if let Some(po) = &task_def.pipeline_override {
+        overrides.push(po.clone());
+    }
+
+    let resource_options = resource_name
+        .and_then(|name| pi.resource.iter().find(|r| r.name == name))
+        .map(|r| r.option.as_slice())
+        .unwrap_or_default();
+    let controller_options = controller_name
+        .and_then(|name| pi.controller.iter().find(|c| c.name == name))
+        .map(|c| c.option.as_slice())
+        .unwrap_or_default();
+
+    let option_keys = pi
+        .global_option
+        .iter()
+        .chain(resource_options)
+        .chain(controller_options)
+        .chain(&task_def.option);
+    for option_key in option_keys {
+        collect_option_overrides(option_key, &task.option_values, &mut overrides, &pi.option);
+    }
+
+    Value::Array(overrides).to_string()
+}
+
+/// 深合并多个对象（递归合并嵌套对象，非对象值后者覆盖前者）
+fn deep_merge(target: &mut Map<String, Value>, source: &Map<String, Value>) {
+    for (key, value) in source {
+        match (target.get_mut(key), value) {
+            (Some(Value::Object(existing)), Value::Object(incoming)) => {
+                deep_merge(existing, incoming);
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// 生成 MXU 内置特殊任务的 pipeline override
+/// 所有 override 先深合并为单个对象，避免 MaaFramework 浅替换丢失 custom_action_param 字段
+fn generate_special_task_override(task: &SavedTask) -> String {
+    let Some(special) = get_special_task(&task.task_name) else {
+        warn!("未找到特殊任务定义: {}", task.task_name);
+        return "[]".to_string();
+    };
+
+    let mut overrides = Vec::new();
+    if let Some(po) = &special.task_def.pipeline_override {
+        overrides.push(po.clone());
+    }
+    for option_key in &special.task_def.option {
+        collect_option_overrides(
+            option_key,
+            &task.option_values,
+            &mut overrides,
+            &special.option_defs,
+        );
+    }
+
+    if overrides.is_empty() {
+        return "[]".to_string();
+    }
+
+    let mut merged = Map::new();
+    for value in &overrides {
+        if let Value::Object(obj) = value {
+            deep_merge(&mut merged, obj);
+        }
+    }
+    Value::Array(vec![Value::Object(merged)]).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 与前端共用的测试用例（前端由 scripts/check-pipeline-override.mjs 运行同一份用例）
+    const FIXTURE: &str = include_str!("../../../tests/fixtures/pipeline-override.json");
+
+    #[test]
+    fn matches_frontend_fixture() {
+        let fixture: Value = serde_json::from_str(FIXTURE).unwrap();
+        let pi: ProjectInterface = serde_json::from_value(fixture["interface"].clone()).unwrap();
+        let cases = fixture["cases"].as_array().unwrap();
+        assert!(!cases.is_empty());
+
+        for case in cases {
+            let name = case["name"].as_str().unwrap();
+            let task: SavedTask = serde_json::from_value(case["task"].clone()).unwrap();
+            let generated = generate_task_pipeline_override(
+                &task,
+                &pi,
+                case["controller"].as_str(),
+                case["resource"].as_str(),
+            );
+            let generated: Value = serde_json::from_str(&generated).unwrap();
+            assert_eq!(generated, case["expected"], "{}", name);
+        }
+    }
+}
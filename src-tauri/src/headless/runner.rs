@@ -0,0 +1,437 @@
+//! 无界面运行流程
+//!
+//! 按前端 startTasksForInstance 的顺序执行：连接控制器 → 加载资源 → 启动 Agent 并提交任务 → 等待任务结束
+
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{info, warn};
+use maa_framework::MaaStatus;
+
+use super::config::{AppSettings, SavedDeviceInfo, SavedInstance};
+use super::interface::{compute_resource_paths, ControllerItem, ProjectInterface};
+use super::pipeline_override::generate_task_pipeline_override;
+use super::special_tasks::get_special_task;
+use crate::commands::backend::TaskerBackend;
+use crate::commands::event_bus::{BusEvent, ChannelSink, EventBus, EventSink};
+use crate::commands::maa_agent::start_tasks;
+use crate::commands::maa_core::{
+    connect_controller, create_instance, load_resource, search_adb_devices, search_win32_windows,
+};
+use crate::commands::task_retry::TASK_RETRY_EVENT;
+use crate::commands::types::{
+    ControllerConfig, MaaState, ResumedTask, RunOptions, TaskConfig, TaskStatus,
+};
+
+/// 任务状态轮询间隔
+const TASK_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 单个任务的执行结果
+#[derive(Debug, Clone)]
+pub struct TaskOutcome {
+    /// 任务显示名称（自定义名称或任务名）
+    pub name: String,
+    pub entry: String,
+    /// MaaFramework task_id（提交失败时为 None）
+    pub task_id: Option<i64>,
+    pub status: TaskStatus,
+}
+
+/// 运行期间注册的事件消费者（离开作用域时移除）
+struct SinkGuard<'a> {
+    bus: &'a EventBus,
+    id: u64,
+}
+
+impl<'a> SinkGuard<'a> {
+    fn add(bus: &'a EventBus, sink: Arc<dyn EventSink>) -> Self {
+        let id = bus.add_sink(sink);
+        Self { bus, id }
+    }
+}
+
+impl Drop for SinkGuard<'_> {
+    fn drop(&mut self) {
+        self.bus.remove_sink(self.id);
+    }
+}
+
+/// 实例运行结果
+#[derive(Debug, Clone, Default)]
+pub struct RunSummary {
+    pub tasks: Vec<TaskOutcome>,
+}
+
+impl RunSummary {
+    /// 是否所有任务都执行成功
+    pub fn all_succeeded(&self) -> bool {
+        self.tasks
+            .iter()
+            .all(|t| matches!(t.status, TaskStatus::Succeeded))
+    }
+}
+
+// ============================================================================
+// 控制器配置解析
+// ============================================================================
+
+/// 解析 Win32 截图方法名称（默认 FramePool）
+fn parse_win32_screencap_method(name: &str) -> u64 {
+    match name {
+        "GDI" => 1,
+        "FramePool" => 1 << 1,
+        "DXGI_DesktopDup" => 1 << 2,
+        "DXGI_DesktopDup_Window" => 1 << 3,
+        "PrintWindow" => 1 << 4,
+        "ScreenDC" => 1 << 5,
+        _ => 1 << 1,
+    }
+}
+
+/// 解析 Win32 输入方法名称（默认 Seize）
+fn parse_win32_input_method(name: &str) -> u64 {
+    match name {
+        "Seize" => 1,
+        "SendMessage" => 1 << 1,
+        "PostMessage" => 1 << 2,
+        "LegacyEvent" => 1 << 3,
+        "PostThreadMessage" => 1 << 4,
+        "SendMessageWithCursorPos" => 1 << 5,
+        "PostMessageWithCursorPos" => 1 << 6,
+        "SendMessageWithWindowPos" => 1 << 7,
+        "PostMessageWithWindowPos" => 1 << 8,
+        _ => 1,
+    }
+}
+
+/// 根据保存的设备信息搜索设备，构建控制器配置
+/// 有保存的设备时按名称精确匹配，否则使用搜索到的第一个结果
+pub fn resolve_controller_config(
+    state: &MaaState,
+    controller: &ControllerItem,
+    saved_device: Option<&SavedDeviceInfo>,
+) -> Result<ControllerConfig, String> {
+    let saved = saved_device.cloned().unwrap_or_default();
+    let screenshot_resolution = controller.screenshot_resolution();
+
+    match controller.controller_type.as_str() {
+        "Adb" => {
+            let devices = search_adb_devices(state)?;
+            let device = match &saved.adb_device_name {
+                Some(name) => devices
+                    .into_iter()
+                    .find(|d| &d.name == name)
+                    .ok_or_else(|| format!("未找到设备 {}", name))?,
+                None => devices.into_iter().next().ok_or("未搜索到任何 ADB 设备")?,
+            };
+            info!("Using ADB device: {} ({})", device.name, device.address);
+            Ok(ControllerConfig::Adb {
+                adb_path: device.adb_path,
+                address: device.address,
+                screencap_methods: device.screencap_methods.to_string(),
+                input_methods: device.input_methods.to_string(),
+                config: device.config,
+                screenshot_resolution,
+            })
+        }
+        "Win32" | "Gamepad" => {
+            let win32 = controller.win32.clone().unwrap_or_default();
+            let gamepad = controller.gamepad.clone().unwrap_or_default();
+            let class_regex = win32.class_regex.as_ref().or(gamepad.class_regex.as_ref());
+            let window_regex = win32
+                .window_regex
+                .as_ref()
+                .or(gamepad.window_regex.as_ref());
+
+            let windows = search_win32_windows(
+                state,
+                class_regex.map(|s| s.as_str()),
+                window_regex.map(|s| s.as_str()),
+            )?;
+            let window = match &saved.window_name {
+                Some(name) => windows
+                    .into_iter()
+                    .find(|w| &w.window_name == name)
+                    .ok_or_else(|| format!("未找到窗口 {}", name))?,
+                None => windows.into_iter().next().ok_or("未搜索到任何窗口")?,
+            };
+            info!(
+                "Using window: {} ({})",
+                window.window_name, window.class_name
+            );
+
+            if controller.controller_type == "Win32" {
+                Ok(ControllerConfig::Win32 {
+                    handle: window.handle,
+                    screencap_method: parse_win32_screencap_method(
+                        win32.screencap.as_deref().unwrap_or_default(),
+                    ),
+                    mouse_method: parse_win32_input_method(
+                        win32.mouse.as_deref().unwrap_or_default(),
+                    ),
+                    keyboard_method: parse_win32_input_method(
+                        win32.keyboard.as_deref().unwrap_or_default(),
+                    ),
+                    screenshot_resolution,
+                })
+            } else {
+                Ok(ControllerConfig::Gamepad {
+                    handle: window.handle,
+                    gamepad_type: None,
+                    screencap_method: None,
+                    screenshot_resolution,
+                })
+            }
+        }
+        "PlayCover" => {
+            // PlayCover 没有搜索功能，必须使用保存的地址
+            let address = saved
+                .playcover_address
+                .ok_or("PlayCover 控制器需要先在界面中配置地址")?;
+            Ok(ControllerConfig::PlayCover {
+                address,
+                uuid: controller.playcover.as_ref().and_then(|p| p.uuid.clone()),
+                screenshot_resolution,
+            })
+        }
+        "ImageFolder" => {
+            let path = saved
+                .image_folder_path
+                .ok_or("ImageFolder 控制器需要先在界面中选择图片目录")?;
+            Ok(ControllerConfig::ImageFolder {
+                path,
+                screenshot_resolution,
+            })
+        }
+        other => Err(format!("不支持的控制器类型: {}", other)),
+    }
+}
+
+// ============================================================================
+// 运行流程
+// ============================================================================
+
+/// 运行实例中所有启用的任务，阻塞直到全部任务结束
+pub async fn run_instance(
+    state: &Arc<MaaState>,
+    pi: &ProjectInterface,
+    base_path: &Path,
+    instance: &SavedInstance,
+    settings: &AppSettings,
+) -> Result<RunSummary, String> {
+    // 未保存时使用第一个控制器/资源（与前端默认选择一致）
+    let controller = match &instance.controller_name {
+        Some(name) => pi.controller.iter().find(|c| &c.name == name),
+        None => pi.controller.first(),
+    }
+    .ok_or("未找到控制器定义")?;
+    let resource = match &instance.resource_name {
+        Some(name) => pi.resource.iter().find(|r| &r.name == name),
+        None => pi.resource.first(),
+    }
+    .ok_or("未找到资源定义")?;
+
+    // 构建任务配置列表
+    let mut task_configs = Vec::new();
+    let mut outcomes = Vec::new();
+    for task in instance.tasks.iter().filter(|t| t.enabled) {
+        let entry = match get_special_task(&task.task_name) {
+            Some(special) => special.task_def.entry,
+            None => match pi.task.iter().find(|t| t.name == task.task_name) {
+                Some(task_def) => task_def.entry.clone(),
+                None => {
+                    warn!("Task definition not found, skipping: {}", task.task_name);
+                    continue;
+                }
+            },
+        };
+        task_configs.push(TaskConfig {
+            entry: entry.clone(),
+            pipeline_override: generate_task_pipeline_override(
+                task,
+                pi,
+                Some(&controller.name),
+                Some(&resource.name),
+            ),
+            timeout_ms: settings.task_timeout.task_timeout_ms(),
+            retry: settings.task_retry.policy(task.retry.as_ref()),
+        });
+        outcomes.push(TaskOutcome {
+            name: task
+                .custom_name
+                .clone()
+                .unwrap_or_else(|| task.task_name.clone()),
+            entry,
+            task_id: None,
+            status: TaskStatus::Pending,
+        });
+    }
+
+    if task_configs.is_empty() {
+        return Err("没有可执行的任务".to_string());
+    }
+
+    create_instance(state, &instance.id)?;
+
+    // 1. 连接控制器
+    info!("Connecting controller: {}", controller.name);
+    let mut config = resolve_controller_config(state, controller, instance.saved_device.as_ref())?;
+    if let Some(resolution) = instance.screenshot_resolution {
+        config.set_screenshot_resolution(resolution);
+    }
+    let conn_id = connect_controller(state, &instance.id, &config)?;
+    let ctrl = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let runtime = instances.get(&instance.id).ok_or("Instance not found")?;
+        runtime
+            .controller
+            .clone()
+            .ok_or("Controller not connected")?
+    };
+    if !ctrl.wait(conn_id).succeeded() {
+        return Err("连接设备失败".to_string());
+    }
+
+    // 2. 加载资源
+    let paths = compute_resource_paths(resource, Some(controller), base_path);
+    info!("Loading resource: {} {:?}", resource.name, paths);
+    let res_ids = load_resource(state, &instance.id, &paths)?;
+    let res = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let runtime = instances.get(&instance.id).ok_or("Instance not found")?;
+        runtime.resource.clone().ok_or("Resource not loaded")?
+    };
+    if res_ids.len() != paths.len() || !res_ids.iter().all(|id| res.wait(*id).succeeded()) {
+        return Err("资源加载失败".to_string());
+    }
+
+    // 3. 启动 Agent 并提交任务
+    // 失败重试后按重新提交的 task_id 继续跟踪
+    let (sink, retry_events) = ChannelSink::new();
+    let _sink = SinkGuard::add(&state.event_bus, Arc::new(sink));
+    // 这里按提交时的 task_id 跟踪结果，超时后不重新提交后续任务
+    let run_options = RunOptions {
+        timeout_ms: settings.task_timeout.run_timeout_ms(),
+        continue_on_timeout: false,
+    };
+    let started = start_tasks(
+        state,
+        &instance.id,
+        &task_configs,
+        pi.agent_configs(),
+        &base_path.to_string_lossy(),
+        settings.tcp_compat_mode,
+        &run_options,
+    )
+    .await?;
+    for rejected in &started.rejected {
+        warn!(
+            "Task rejected: {} {:?}",
+            outcomes[rejected.index].name, rejected.errors
+        );
+    }
+    for warned in &started.warnings {
+        warn!(
+            "Task override warnings: {} {:?}",
+            outcomes[warned.index].name, warned.warnings
+        );
+    }
+
+    // task_id 按顺序对应未被拒绝的启用任务（与前端一致），未分配 task_id 的任务视为提交失败
+    let mut task_ids = started.task_ids.iter();
+    for (index, outcome) in outcomes.iter_mut().enumerate() {
+        if started.rejected.iter().any(|r| r.index == index) {
+            continue;
+        }
+        outcome.task_id = task_ids.next().copied();
+    }
+    for outcome in outcomes.iter_mut().filter(|o| o.task_id.is_none()) {
+        outcome.status = TaskStatus::Failed;
+    }
+
+    // 4. 等待任务结束（轮询会阻塞，放到阻塞线程中执行）
+    let tasker = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let runtime = instances.get(&instance.id).ok_or("Instance not found")?;
+        runtime.tasker.clone().ok_or("Tasker not created")?
+    };
+    let state = state.clone();
+    let instance_id = instance.id.clone();
+    let tasks = tauri::async_runtime::spawn_blocking(move || {
+        wait_tasks(
+            &state,
+            &instance_id,
+            tasker.as_ref(),
+            &retry_events,
+            outcomes,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    Ok(RunSummary { tasks })
+}
+
+/// 轮询任务状态直到全部任务结束且没有等待中的重试，按重试事件跟踪重新提交的 task_id
+fn wait_tasks(
+    state: &MaaState,
+    instance_id: &str,
+    tasker: &dyn TaskerBackend,
+    events: &Receiver<BusEvent>,
+    mut outcomes: Vec<TaskOutcome>,
+) -> Result<Vec<TaskOutcome>, String> {
+    loop {
+        // 先取重试状态再读取事件：重试结束时其 resumed 事件已在通道中
+        let retrying = state.task_retry.is_retrying(instance_id);
+        for event in events.try_iter() {
+            if event.name != TASK_RETRY_EVENT || event.instance_id.as_deref() != Some(instance_id) {
+                continue;
+            }
+            if event.payload["stage"].as_str() != Some("resumed") {
+                continue;
+            }
+            let resumed: Vec<ResumedTask> =
+                serde_json::from_value(event.payload["resumed_tasks"].clone()).unwrap_or_default();
+            for task in resumed {
+                if let Some(outcome) = outcomes
+                    .iter_mut()
+                    .find(|o| o.task_id == Some(task.previous_task_id))
+                {
+                    outcome.task_id = task.task_id;
+                    outcome.status = match task.task_id {
+                        Some(_) => TaskStatus::Pending,
+                        None => TaskStatus::Failed,
+                    };
+                }
+            }
+        }
+
+        let mut pending = retrying || tasker.running();
+        for outcome in outcomes.iter_mut() {
+            let Some(task_id) = outcome.task_id else {
+                continue;
+            };
+            if matches!(outcome.status, TaskStatus::Succeeded | TaskStatus::Failed) {
+                continue;
+            }
+
+            let status = tasker.task_status(task_id)?;
+            outcome.status = match status {
+                MaaStatus::PENDING => TaskStatus::Pending,
+                MaaStatus::RUNNING => TaskStatus::Running,
+                MaaStatus::SUCCEEDED => TaskStatus::Succeeded,
+                _ => TaskStatus::Failed,
+            };
+            if matches!(outcome.status, TaskStatus::Pending | TaskStatus::Running) {
+                pending = true;
+            }
+        }
+
+        if !pending {
+            return Ok(outcomes);
+        }
+        std::thread::sleep(TASK_POLL_INTERVAL);
+    }
+}
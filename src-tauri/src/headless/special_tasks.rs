@@ -0,0 +1,184 @@
+//! MXU 内置特殊任务
+//!
+//! 与前端 specialTasks.ts 中的注册表保持一致，特殊任务通过 mxu_actions 中的 Custom Action 实现
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use super::interface::{OptionDefinition, TaskItem};
+
+/// MXU 特殊任务定义
+pub struct SpecialTask {
+    /// 虚拟 TaskItem 定义
+    pub task_def: TaskItem,
+    /// 相关选项定义（键为选项 key）
+    pub option_defs: HashMap<String, OptionDefinition>,
+}
+
+/// 判断是否为 MXU 内置特殊任务
+pub fn is_special_task(task_name: &str) -> bool {
+    get_special_task(task_name).is_some()
+}
+
+/// 获取 MXU 特殊任务定义
+pub fn get_special_task(task_name: &str) -> Option<SpecialTask> {
+    let (task_def, option_defs) = match task_name {
+        "__MXU_SLEEP__" => (
+            entry_task(task_name, "MXU_SLEEP", &["__MXU_SLEEP_OPTION__"]),
+            json!({
+                "__MXU_SLEEP_OPTION__": input_option(
+                    "MXU_SLEEP",
+                    json!([{ "name": "sleep_time", "default": "5", "pipeline_type": "int" }]),
+                    json!({ "sleep_time": "{sleep_time}" }),
+                ),
+            }),
+        ),
+        "__MXU_WAITUNTIL__" => (
+            entry_task(task_name, "MXU_WAITUNTIL", &["__MXU_WAITUNTIL_OPTION__"]),
+            json!({
+                "__MXU_WAITUNTIL_OPTION__": input_option(
+                    "MXU_WAITUNTIL",
+                    json!([{ "name": "target_time", "default": "08:00", "pipeline_type": "string" }]),
+                    json!({ "target_time": "{target_time}" }),
+                ),
+            }),
+        ),
+        "__MXU_LAUNCH__" => (
+            entry_task(
+                task_name,
+                "MXU_LAUNCH",
+                &[
+                    "__MXU_LAUNCH_OPTION__",
+                    "__MXU_LAUNCH_WAIT_OPTION__",
+                    "__MXU_LAUNCH_SKIP_OPTION__",
+                ],
+            ),
+            json!({
+                "__MXU_LAUNCH_OPTION__": input_option(
+                    "MXU_LAUNCH",
+                    json!([
+                        { "name": "program", "default": "", "pipeline_type": "string" },
+                        { "name": "args", "default": "", "pipeline_type": "string" },
+                    ]),
+                    json!({ "program": "{program}", "args": "{args}" }),
+                ),
+                "__MXU_LAUNCH_WAIT_OPTION__": switch_option("MXU_LAUNCH", "wait_for_exit", None),
+                "__MXU_LAUNCH_SKIP_OPTION__": switch_option("MXU_LAUNCH", "skip_if_running", None),
+            }),
+        ),
+        "__MXU_WEBHOOK__" => (
+            entry_task(task_name, "MXU_WEBHOOK", &["__MXU_WEBHOOK_OPTION__"]),
+            json!({
+                "__MXU_WEBHOOK_OPTION__": input_option(
+                    "MXU_WEBHOOK",
+                    json!([{ "name": "url", "default": "", "pipeline_type": "string" }]),
+                    json!({ "url": "{url}" }),
+                ),
+            }),
+        ),
+        "__MXU_NOTIFY__" => (
+            entry_task(task_name, "MXU_NOTIFY", &["__MXU_NOTIFY_OPTION__"]),
+            json!({
+                "__MXU_NOTIFY_OPTION__": input_option(
+                    "MXU_NOTIFY",
+                    json!([
+                        { "name": "title", "default": "MXU", "pipeline_type": "string" },
+                        { "name": "body", "default": "", "pipeline_type": "string" },
+                    ]),
+                    json!({ "title": "{title}", "body": "{body}" }),
+                ),
+            }),
+        ),
+        "__MXU_KILLPROC__" => {
+            let mut self_option = switch_option(
+                "MXU_KILLPROC",
+                "kill_self",
+                Some("__MXU_KILLPROC_NAME_OPTION__"),
+            );
+            self_option["default_case"] = json!("Yes");
+            (
+                entry_task(task_name, "MXU_KILLPROC", &["__MXU_KILLPROC_SELF_OPTION__"]),
+                json!({
+                    "__MXU_KILLPROC_SELF_OPTION__": self_option,
+                    "__MXU_KILLPROC_NAME_OPTION__": input_option(
+                        "MXU_KILLPROC",
+                        json!([{ "name": "process_name", "default": "", "pipeline_type": "string" }]),
+                        json!({ "process_name": "{process_name}" }),
+                    ),
+                }),
+            )
+        }
+        "__MXU_POWER__" => {
+            let cases: Vec<Value> = ["shutdown", "restart", "screenoff", "sleep"]
+                .iter()
+                .map(|action| {
+                    json!({
+                        "name": action,
+                        "pipeline_override": {
+                            "MXU_POWER": { "custom_action_param": { "power_action": action } }
+                        },
+                    })
+                })
+                .collect();
+            (
+                entry_task(task_name, "MXU_POWER", &["__MXU_POWER_OPTION__"]),
+                json!({
+                    "__MXU_POWER_OPTION__": {
+                        "type": "select",
+                        "cases": cases,
+                        "default_case": "shutdown",
+                    },
+                }),
+            )
+        }
+        _ => return None,
+    };
+
+    Some(SpecialTask {
+        task_def: serde_json::from_value(task_def).ok()?,
+        option_defs: serde_json::from_value(option_defs).ok()?,
+    })
+}
+
+/// 构建特殊任务的虚拟 TaskItem（entry 节点执行同名 Custom Action）
+fn entry_task(task_name: &str, entry: &str, options: &[&str]) -> Value {
+    json!({
+        "name": task_name,
+        "entry": entry,
+        "option": options,
+        "pipeline_override": {
+            entry: { "action": "Custom", "custom_action": format!("{}_ACTION", entry) }
+        },
+    })
+}
+
+/// 构建输入类型选项，输入值写入 entry 节点的 custom_action_param
+fn input_option(entry: &str, inputs: Value, param: Value) -> Value {
+    json!({
+        "type": "input",
+        "inputs": inputs,
+        "pipeline_override": { entry: { "custom_action_param": param } },
+    })
+}
+
+/// 构建开关类型选项（Yes/No 分别写入 true/false），默认 No
+fn switch_option(entry: &str, param_key: &str, no_case_option: Option<&str>) -> Value {
+    let case = |name: &str, value: bool| {
+        json!({
+            "name": name,
+            "pipeline_override": { entry: { "custom_action_param": { param_key: value } } },
+        })
+    };
+
+    let mut no_case = case("No", false);
+    if let Some(option) = no_case_option {
+        no_case["option"] = json!([option]);
+    }
+
+    json!({
+        "type": "switch",
+        "cases": [case("Yes", true), no_case],
+        "default_case": "No",
+    })
+}
@@ -0,0 +1,353 @@
+//! 运行历史
+//!
+//! 记录每次运行（一次 maa_start_tasks，或未在运行中时单独提交的 maa_run_task）中各任务的结果：
+//! 入口、Pipeline 覆盖哈希、开始/结束时间、最终状态、失败节点以及 Agent 退出码。
+//! 任务状态取自事件总线上的 MaaFramework 回调，运行结束后追加写入
+//! 数据目录/history/runs-{年}-{月}.jsonl，不受前端日志条数限制
+//!
+//! 运行结束的判定：
+//! - 带 Agent 的运行：Agent 子进程全部退出后（stop_agents）
+//! - 不带 Agent 的运行：任务全部提交且全部结束后
+//! - 实例被销毁或同一实例开始新的运行时，未结束的运行按当前状态写入
+//!
+//! 模块结构：
+//! - `store`: JSON Lines 存储与查询
+//! - `stats`: 成功率、耗时与失败节点统计
+//! - `timings`: 任务、节点、识别与动作的执行时间（用于任务详情）
+
+pub mod stats;
+pub mod store;
+pub mod timings;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commands::error::{MxuError, MxuResult};
+use crate::commands::event_bus::{BusEvent, EventSink, MAA_CALLBACK_EVENT};
+use crate::commands::types::TaskStatus;
+use store::RunQuery;
+
+/// MaaFramework 内部停止任务的入口名（不计入运行历史）
+pub(crate) const STOP_TASK_ENTRY: &str = "MaaTaskerPostStop";
+
+// ============================================================================
+// 记录类型
+// ============================================================================
+
+/// 一次运行的记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// 运行 ID（实例 ID + 开始时间）
+    pub run_id: String,
+    pub instance_id: String,
+    /// 开始时间（毫秒时间戳）
+    pub started_at: i64,
+    /// 结束时间（毫秒时间戳）
+    pub ended_at: i64,
+    /// 各任务结果（按提交顺序）
+    pub tasks: Vec<TaskRecord>,
+    /// Agent 子进程退出码（被信号终止等没有退出码时为 None）
+    #[serde(default)]
+    pub agent_exit_codes: Vec<Option<i32>>,
+}
+
+/// 单个任务的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRecord {
+    pub task_id: i64,
+    pub entry: String,
+    /// Pipeline 覆盖的哈希（用于区分同一入口的不同选项组合）
+    pub pipeline_override_hash: Option<String>,
+    /// 资源哈希（取自 Tasker.Task 回调，用于区分资源版本）
+    #[serde(default)]
+    pub resource_hash: Option<String>,
+    /// 开始时间（毫秒时间戳），任务未开始执行时为 None
+    pub started_at: Option<i64>,
+    /// 结束时间（毫秒时间戳），任务未执行完时为 None
+    pub ended_at: Option<i64>,
+    /// 最终状态（运行被中断时为中断时的状态）
+    pub status: TaskStatus,
+    /// 最后一个失败的节点
+    pub failed_node: Option<String>,
+}
+
+impl TaskRecord {
+    fn new(task_id: i64, entry: &str) -> Self {
+        Self {
+            task_id,
+            entry: entry.to_string(),
+            pipeline_override_hash: None,
+            resource_hash: None,
+            started_at: None,
+            ended_at: None,
+            status: TaskStatus::Pending,
+            failed_node: None,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.status, TaskStatus::Succeeded | TaskStatus::Failed)
+    }
+}
+
+/// Pipeline 覆盖的哈希（FNV-1a 64 位，十六进制；不同版本与平台间保持一致）
+pub fn pipeline_override_hash(pipeline_override: &str) -> String {
+    let hash = pipeline_override
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+// ============================================================================
+// 记录器
+// ============================================================================
+
+/// 进行中的运行
+struct OpenRun {
+    record: RunRecord,
+    /// 本次运行启动的 Agent 数量
+    agent_count: usize,
+    /// 任务是否已全部提交
+    sealed: bool,
+    /// Agent 是否已全部退出
+    agents_exited: bool,
+}
+
+impl OpenRun {
+    fn new(instance_id: &str, agent_count: usize, sealed: bool) -> Self {
+        let started_at = chrono::Local::now().timestamp_millis();
+        Self {
+            record: RunRecord {
+                run_id: format!("{}-{}", instance_id, started_at),
+                instance_id: instance_id.to_string(),
+                started_at,
+                ended_at: started_at,
+                tasks: Vec::new(),
+                agent_exit_codes: Vec::new(),
+            },
+            agent_count,
+            sealed,
+            agents_exited: false,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        if self.agent_count > 0 {
+            self.agents_exited
+        } else {
+            self.sealed && self.record.tasks.iter().all(TaskRecord::is_finished)
+        }
+    }
+
+    fn task_mut(&mut self, task_id: i64, entry: &str) -> &mut TaskRecord {
+        let index = match self.record.tasks.iter().position(|t| t.task_id == task_id) {
+            Some(index) => index,
+            None => {
+                self.record.tasks.push(TaskRecord::new(task_id, entry));
+                self.record.tasks.len() - 1
+            }
+        };
+        &mut self.record.tasks[index]
+    }
+}
+
+/// 运行历史记录器（注册为事件总线消费者，接收任务回调）
+#[derive(Default)]
+pub struct RunHistory {
+    /// 存储目录（未设置时只跟踪不写入，如测试与 mock 环境）
+    dir: Mutex<Option<PathBuf>>,
+    /// 各实例进行中的运行
+    runs: Mutex<HashMap<String, OpenRun>>,
+}
+
+impl RunHistory {
+    /// 设置存储目录
+    pub fn set_dir(&self, dir: PathBuf) {
+        if let Ok(mut guard) = self.dir.lock() {
+            *guard = Some(dir);
+        }
+    }
+
+    /// 开始新的运行（同一实例未结束的运行先按当前状态写入）
+    pub fn begin_run(&self, instance_id: &str, agent_count: usize) {
+        let previous = match self.runs.lock() {
+            Ok(mut runs) => runs.insert(
+                instance_id.to_string(),
+                OpenRun::new(instance_id, agent_count, false),
+            ),
+            Err(_) => return,
+        };
+        if let Some(previous) = previous {
+            self.write(previous.record);
+        }
+    }
+
+    /// 记录已提交的任务（没有进行中的运行时，视为单独提交的任务开始新的运行）
+    pub fn task_posted(
+        &self,
+        instance_id: &str,
+        task_id: i64,
+        entry: &str,
+        pipeline_override: &str,
+    ) {
+        let Ok(mut runs) = self.runs.lock() else {
+            return;
+        };
+        let run = runs
+            .entry(instance_id.to_string())
+            .or_insert_with(|| OpenRun::new(instance_id, 0, true));
+        run.task_mut(task_id, entry).pipeline_override_hash =
+            Some(pipeline_override_hash(pipeline_override));
+    }
+
+    /// 任务已全部提交
+    pub fn seal_run(&self, instance_id: &str) {
+        self.update(instance_id, |run| run.sealed = true);
+    }
+
+    /// 记录 Agent 子进程退出码
+    pub fn agents_exited(&self, instance_id: &str, exit_codes: Vec<Option<i32>>) {
+        self.update(instance_id, |run| {
+            run.record.agent_exit_codes = exit_codes;
+            run.agents_exited = true;
+        });
+    }
+
+    /// 结束实例进行中的运行（实例被销毁时调用）
+    pub fn finish_run(&self, instance_id: &str) {
+        let run = match self.runs.lock() {
+            Ok(mut runs) => runs.remove(instance_id),
+            Err(_) => return,
+        };
+        if let Some(run) = run {
+            self.write(run.record);
+        }
+    }
+
+    /// 查询已写入的运行记录
+    pub fn query(&self, query: &RunQuery) -> MxuResult<Vec<RunRecord>> {
+        let dir = self.dir.lock()?.clone();
+        match dir {
+            Some(dir) => store::query(&dir, query),
+            None => Err(MxuError::internal("Run history directory not set")),
+        }
+    }
+
+    /// 修改进行中的运行，完成时写入
+    fn update(&self, instance_id: &str, f: impl FnOnce(&mut OpenRun)) {
+        let finished = {
+            let Ok(mut runs) = self.runs.lock() else {
+                return;
+            };
+            let Some(run) = runs.get_mut(instance_id) else {
+                return;
+            };
+            f(run);
+            if run.is_complete() {
+                runs.remove(instance_id)
+            } else {
+                None
+            }
+        };
+        if let Some(run) = finished {
+            self.write(run.record);
+        }
+    }
+
+    fn write(&self, mut record: RunRecord) {
+        if record.tasks.is_empty() && record.agent_exit_codes.is_empty() {
+            debug!("Run {} has no tasks, not recorded", record.run_id);
+            return;
+        }
+        record.ended_at = chrono::Local::now().timestamp_millis();
+
+        let Some(dir) = self.dir.lock().ok().and_then(|d| d.clone()) else {
+            return;
+        };
+        if let Err(e) = store::append(&dir, &record) {
+            warn!("Failed to record run {}: {}", record.run_id, e);
+        }
+    }
+
+    /// 处理任务与节点回调
+    fn handle_callback(&self, instance_id: &str, message: &str, details: &Value, timestamp: i64) {
+        let task_id = details["task_id"].as_i64().unwrap_or_default();
+        let entry = details["entry"].as_str().unwrap_or_default();
+
+        match message {
+            "Tasker.Task.Starting" => {
+                if entry == STOP_TASK_ENTRY {
+                    return;
+                }
+                // 回调可能早于 task_posted 到达，此时先按单独提交的任务开始运行
+                if let Ok(mut runs) = self.runs.lock() {
+                    let run = runs
+                        .entry(instance_id.to_string())
+                        .or_insert_with(|| OpenRun::new(instance_id, 0, true));
+                    let task = run.task_mut(task_id, entry);
+                    task.status = TaskStatus::Running;
+                    task.started_at = Some(timestamp);
+                    task.resource_hash = details["hash"]
+                        .as_str()
+                        .filter(|h| !h.is_empty())
+                        .map(str::to_string);
+                }
+            }
+            "Tasker.Task.Succeeded" | "Tasker.Task.Failed" => {
+                if entry == STOP_TASK_ENTRY {
+                    return;
+                }
+                let status = if message == "Tasker.Task.Succeeded" {
+                    TaskStatus::Succeeded
+                } else {
+                    TaskStatus::Failed
+                };
+                self.update(instance_id, |run| {
+                    let task = run.task_mut(task_id, entry);
+                    task.status = status;
+                    task.ended_at = Some(timestamp);
+                });
+            }
+            "Node.PipelineNode.Failed" | "Node.NextList.Failed" => {
+                let Some(name) = details["name"].as_str() else {
+                    return;
+                };
+                self.update(instance_id, |run| {
+                    if let Some(task) = run.record.tasks.iter_mut().find(|t| t.task_id == task_id) {
+                        task.failed_node = Some(name.to_string());
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+impl EventSink for RunHistory {
+    fn handle(&self, event: &BusEvent) {
+        if event.name != MAA_CALLBACK_EVENT {
+            return;
+        }
+        let Some(instance_id) = event.instance_id.as_deref() else {
+            return;
+        };
+        let Some(message) = event.payload["message"].as_str() else {
+            return;
+        };
+        if !message.starts_with("Tasker.Task.") && !message.starts_with("Node.") {
+            return;
+        }
+        let details = event.payload["details"]
+            .as_str()
+            .and_then(|d| serde_json::from_str::<Value>(d).ok())
+            .unwrap_or(Value::Null);
+        self.handle_callback(instance_id, message, &details, event.timestamp);
+    }
+}
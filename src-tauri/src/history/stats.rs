@@ -0,0 +1,429 @@
+//! 运行历史统计
+//!
+//! 按任务入口汇总成功率、耗时分布与失败节点，按实例汇总每日运行次数。
+//! 可按资源哈希拆分入口统计，用于对比资源更新前后同一任务的表现
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+
+use super::RunRecord;
+use crate::commands::types::TaskStatus;
+
+/// 每个入口保留的失败节点数量
+const TOP_NODES_PER_ENTRY: usize = 5;
+
+/// 全局失败节点排行保留的数量
+const TOP_FAILING_NODES: usize = 20;
+
+/// 统计结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RunStats {
+    /// 参与统计的运行数
+    pub total_runs: usize,
+    /// 各任务入口的统计（按执行次数从多到少）
+    pub entries: Vec<EntryStats>,
+    /// 最常见的失败节点（按次数从多到少）
+    pub failing_nodes: Vec<FailingNode>,
+    /// 各实例每日运行次数（按日期、实例排序）
+    pub daily: Vec<DailyRuns>,
+}
+
+/// 任务入口统计
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryStats {
+    pub entry: String,
+    /// 资源哈希（仅按资源拆分统计时设置）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_hash: Option<String>,
+    /// 执行结束的次数（成功 + 失败）
+    pub finished: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// 成功率（0-1），没有结束的执行时为 None
+    pub success_rate: Option<f64>,
+    /// 成功执行耗时的中位数（毫秒）
+    pub median_duration_ms: Option<i64>,
+    /// 成功执行耗时的 95 分位数（毫秒）
+    pub p95_duration_ms: Option<i64>,
+    /// 该入口最常见的失败节点
+    pub failing_nodes: Vec<NodeCount>,
+    /// 最近一次执行的开始时间（毫秒时间戳）
+    pub last_run_at: Option<i64>,
+}
+
+/// 节点失败次数
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeCount {
+    pub node: String,
+    pub count: usize,
+}
+
+/// 失败节点（全局排行）
+#[derive(Debug, Clone, Serialize)]
+pub struct FailingNode {
+    pub entry: String,
+    pub node: String,
+    pub count: usize,
+}
+
+/// 实例单日运行次数
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyRuns {
+    /// 本地日期（YYYY-MM-DD）
+    pub date: String,
+    pub instance_id: String,
+    pub runs: usize,
+    /// 存在失败任务的运行数
+    pub failed_runs: usize,
+}
+
+/// 入口统计的累加器
+#[derive(Default)]
+struct EntryAccumulator {
+    succeeded: usize,
+    failed: usize,
+    durations: Vec<i64>,
+    nodes: HashMap<String, usize>,
+    last_run_at: Option<i64>,
+}
+
+/// 汇总运行记录（by_resource 为 true 时按 入口 + 资源哈希 分组）
+pub fn compute(records: &[RunRecord], by_resource: bool) -> RunStats {
+    let mut entries: HashMap<(String, Option<String>), EntryAccumulator> = HashMap::new();
+    let mut daily: BTreeMap<(String, String), DailyRuns> = BTreeMap::new();
+
+    for record in records {
+        let date = Local
+            .timestamp_millis_opt(record.started_at)
+            .earliest()
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let day = daily
+            .entry((date.clone(), record.instance_id.clone()))
+            .or_insert_with(|| DailyRuns {
+                date,
+                instance_id: record.instance_id.clone(),
+                runs: 0,
+                failed_runs: 0,
+            });
+        day.runs += 1;
+        if record.tasks.iter().any(|t| t.status == TaskStatus::Failed) {
+            day.failed_runs += 1;
+        }
+
+        for task in &record.tasks {
+            let resource_hash = if by_resource {
+                task.resource_hash.clone()
+            } else {
+                None
+            };
+            let acc = entries
+                .entry((task.entry.clone(), resource_hash))
+                .or_default();
+
+            match task.status {
+                TaskStatus::Succeeded => {
+                    acc.succeeded += 1;
+                    if let (Some(start), Some(end)) = (task.started_at, task.ended_at) {
+                        acc.durations.push(end - start);
+                    }
+                }
+                TaskStatus::Failed => acc.failed += 1,
+                // 被中断的执行不计入成功率
+                TaskStatus::Pending | TaskStatus::Running => {}
+            }
+            if let Some(node) = &task.failed_node {
+                *acc.nodes.entry(node.clone()).or_default() += 1;
+            }
+            let started_at = task.started_at.unwrap_or(record.started_at);
+            acc.last_run_at = acc.last_run_at.max(Some(started_at));
+        }
+    }
+
+    // 按资源拆分时同一节点会分散在多个分组，全局排行按 入口 + 节点 合并计数
+    let mut node_counts: HashMap<(String, String), usize> = HashMap::new();
+    let mut entries: Vec<EntryStats> = entries
+        .into_iter()
+        .map(|((entry, resource_hash), mut acc)| {
+            for (node, count) in &acc.nodes {
+                *node_counts
+                    .entry((entry.clone(), node.clone()))
+                    .or_default() += count;
+            }
+
+            let finished = acc.succeeded + acc.failed;
+            acc.durations.sort_unstable();
+            EntryStats {
+                finished,
+                succeeded: acc.succeeded,
+                failed: acc.failed,
+                success_rate: (finished > 0).then(|| acc.succeeded as f64 / finished as f64),
+                median_duration_ms: percentile(&acc.durations, 50),
+                p95_duration_ms: percentile(&acc.durations, 95),
+                failing_nodes: top_nodes(acc.nodes, TOP_NODES_PER_ENTRY),
+                last_run_at: acc.last_run_at,
+                entry,
+                resource_hash,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.finished
+            .cmp(&a.finished)
+            .then_with(|| a.entry.cmp(&b.entry))
+            .then_with(|| b.last_run_at.cmp(&a.last_run_at))
+    });
+
+    let mut failing_nodes: Vec<FailingNode> = node_counts
+        .into_iter()
+        .map(|((entry, node), count)| FailingNode { entry, node, count })
+        .collect();
+    failing_nodes.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.entry.cmp(&b.entry))
+            .then_with(|| a.node.cmp(&b.node))
+    });
+    failing_nodes.truncate(TOP_FAILING_NODES);
+
+    RunStats {
+        total_runs: records.len(),
+        entries,
+        failing_nodes,
+        daily: daily.into_values().collect(),
+    }
+}
+
+/// 最近秩法分位数（sorted 需已升序排列）
+fn percentile(sorted: &[i64], pct: usize) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+fn top_nodes(nodes: HashMap<String, usize>, limit: usize) -> Vec<NodeCount> {
+    let mut nodes: Vec<NodeCount> = nodes
+        .into_iter()
+        .map(|(node, count)| NodeCount { node, count })
+        .collect();
+    nodes.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.node.cmp(&b.node)));
+    nodes.truncate(limit);
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::TaskRecord;
+
+    fn task(entry: &str, status: TaskStatus, duration_ms: Option<i64>) -> TaskRecord {
+        TaskRecord {
+            task_id: 1,
+            entry: entry.to_string(),
+            pipeline_override_hash: None,
+            resource_hash: None,
+            started_at: duration_ms.map(|_| 1_000),
+            ended_at: duration_ms.map(|ms| 1_000 + ms),
+            status,
+            failed_node: None,
+        }
+    }
+
+    fn record(instance_id: &str, started_at: i64, tasks: Vec<TaskRecord>) -> RunRecord {
+        RunRecord {
+            run_id: format!("{}-{}", instance_id, started_at),
+            instance_id: instance_id.to_string(),
+            started_at,
+            ended_at: started_at,
+            tasks,
+            agent_exit_codes: Vec::new(),
+        }
+    }
+
+    /// 本地时间的毫秒时间戳
+    fn local_ms(date: (i32, u32, u32), hour: u32, min: u32) -> i64 {
+        Local
+            .with_ymd_and_hms(date.0, date.1, date.2, hour, min, 0)
+            .earliest()
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        // (样本数, 分位, 期望的秩（从 1 开始）)
+        let cases = [
+            (1, 50, 1),
+            (1, 95, 1),
+            (2, 50, 1),
+            (2, 95, 2),
+            (3, 50, 2),
+            (10, 50, 5),
+            (10, 95, 10),
+            (20, 50, 10),
+            (20, 95, 19),
+            (100, 50, 50),
+            (100, 95, 95),
+            (101, 95, 96),
+            (10, 0, 1),
+            (10, 100, 10),
+        ];
+        for (len, pct, rank) in cases {
+            let sorted: Vec<i64> = (1..=len).collect();
+            assert_eq!(
+                percentile(&sorted, pct),
+                Some(rank),
+                "len {} pct {}",
+                len,
+                pct
+            );
+        }
+        assert_eq!(percentile(&[], 50), None);
+        assert_eq!(percentile(&[], 95), None);
+    }
+
+    #[test]
+    fn compute_empty_records() {
+        let stats = compute(&[], false);
+        assert_eq!(stats.total_runs, 0);
+        assert!(stats.entries.is_empty());
+        assert!(stats.failing_nodes.is_empty());
+        assert!(stats.daily.is_empty());
+    }
+
+    #[test]
+    fn entry_without_finished_runs_has_no_rate_or_durations() {
+        let records = [record(
+            "a",
+            0,
+            vec![
+                task("Start", TaskStatus::Pending, None),
+                task("Start", TaskStatus::Running, Some(500)),
+            ],
+        )];
+        let stats = compute(&records, false);
+        let entry = &stats.entries[0];
+        assert_eq!(entry.finished, 0);
+        assert_eq!(entry.success_rate, None);
+        assert_eq!(entry.median_duration_ms, None);
+        assert_eq!(entry.p95_duration_ms, None);
+    }
+
+    #[test]
+    fn entry_rates_and_durations() {
+        // 只有成功且有起止时间的执行计入耗时
+        let mut tasks: Vec<TaskRecord> = (1..=19)
+            .map(|i| task("Start", TaskStatus::Succeeded, Some(i * 100)))
+            .collect();
+        tasks.push(task("Start", TaskStatus::Succeeded, None));
+        tasks.push(task("Start", TaskStatus::Failed, Some(99_999)));
+        tasks.push(task("Start", TaskStatus::Failed, None));
+        tasks.push(task("Start", TaskStatus::Running, Some(99_999)));
+        let stats = compute(&[record("a", 0, tasks)], false);
+
+        assert_eq!(stats.entries.len(), 1);
+        let entry = &stats.entries[0];
+        assert_eq!(entry.finished, 22);
+        assert_eq!(entry.succeeded, 20);
+        assert_eq!(entry.failed, 2);
+        assert_eq!(entry.success_rate, Some(20.0 / 22.0));
+        // 19 个耗时样本：中位数为第 10 个，95 分位为第 19 个
+        assert_eq!(entry.median_duration_ms, Some(1_000));
+        assert_eq!(entry.p95_duration_ms, Some(1_900));
+    }
+
+    #[test]
+    fn failing_nodes_merge_across_resources() {
+        let failed = |resource: &str, node: &str| TaskRecord {
+            resource_hash: Some(resource.to_string()),
+            failed_node: Some(node.to_string()),
+            ..task("Start", TaskStatus::Failed, None)
+        };
+        let records = [record(
+            "a",
+            0,
+            vec![
+                failed("r1", "Click"),
+                failed("r2", "Click"),
+                failed("r2", "Swipe"),
+                TaskRecord {
+                    resource_hash: Some("r1".to_string()),
+                    ..task("Start", TaskStatus::Succeeded, Some(100))
+                },
+            ],
+        )];
+
+        let merged = compute(&records, false);
+        assert_eq!(merged.entries.len(), 1);
+        assert_eq!(merged.entries[0].resource_hash, None);
+
+        let split = compute(&records, true);
+        let groups: Vec<(Option<&str>, usize, usize)> = split
+            .entries
+            .iter()
+            .map(|e| (e.resource_hash.as_deref(), e.succeeded, e.failed))
+            .collect();
+        assert_eq!(groups, [(Some("r1"), 1, 1), (Some("r2"), 0, 2)]);
+
+        for stats in [merged, split] {
+            let nodes: Vec<(&str, usize)> = stats
+                .failing_nodes
+                .iter()
+                .map(|n| (n.node.as_str(), n.count))
+                .collect();
+            assert_eq!(nodes, [("Click", 2), ("Swipe", 1)]);
+        }
+    }
+
+    #[test]
+    fn daily_runs_bucket_by_local_date() {
+        let day1 = (2026, 5, 1);
+        let day2 = (2026, 5, 2);
+        // (实例, 开始时间, 是否有失败任务)
+        let runs = [
+            ("b", local_ms(day1, 0, 0), false),
+            ("a", local_ms(day1, 12, 0), true),
+            ("a", local_ms(day1, 23, 59), false),
+            ("a", local_ms(day2, 0, 0), false),
+            ("a", local_ms(day2, 8, 30), true),
+        ];
+        let records: Vec<RunRecord> = runs
+            .iter()
+            .map(|&(instance, started_at, failed)| {
+                let status = if failed {
+                    TaskStatus::Failed
+                } else {
+                    TaskStatus::Succeeded
+                };
+                record(instance, started_at, vec![task("Start", status, None)])
+            })
+            .collect();
+
+        let stats = compute(&records, false);
+        assert_eq!(stats.total_runs, 5);
+        let daily: Vec<(&str, &str, usize, usize)> = stats
+            .daily
+            .iter()
+            .map(|d| {
+                (
+                    d.date.as_str(),
+                    d.instance_id.as_str(),
+                    d.runs,
+                    d.failed_runs,
+                )
+            })
+            .collect();
+        assert_eq!(
+            daily,
+            [
+                ("2026-05-01", "a", 2, 1),
+                ("2026-05-01", "b", 1, 0),
+                ("2026-05-02", "a", 2, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! 运行历史存储
+//!
+//! 每月一个 JSON Lines 文件（runs-2026-01.jsonl，按运行开始时间的本地日期归档），只追加不修改
+
+use std::cmp::Reverse;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, TimeZone};
+use log::warn;
+
+use super::RunRecord;
+use crate::commands::error::{MxuError, MxuResult, Subsystem};
+
+const FILE_PREFIX: &str = "runs-";
+const FILE_EXTENSION: &str = ".jsonl";
+
+/// 查询条件
+#[derive(Debug, Clone, Default)]
+pub struct RunQuery {
+    /// 只返回指定实例的运行
+    pub instance_id: Option<String>,
+    /// 开始时间下限（毫秒时间戳，包含）
+    pub since: Option<i64>,
+    /// 开始时间上限（毫秒时间戳，不包含）
+    pub until: Option<i64>,
+    /// 最多返回的条数（按开始时间从新到旧）
+    pub limit: Option<usize>,
+}
+
+impl RunQuery {
+    fn matches(&self, record: &RunRecord) -> bool {
+        self.instance_id
+            .as_ref()
+            .is_none_or(|id| &record.instance_id == id)
+            && self.since.is_none_or(|since| record.started_at >= since)
+            && self.until.is_none_or(|until| record.started_at < until)
+    }
+}
+
+/// 时间戳所在月份（本地时区，如 2026-01）
+fn month_key(timestamp: i64) -> String {
+    Local
+        .timestamp_millis_opt(timestamp)
+        .earliest()
+        .map(|t| t.format("%Y-%m").to_string())
+        .unwrap_or_default()
+}
+
+fn month_file(dir: &Path, timestamp: i64) -> PathBuf {
+    dir.join(format!(
+        "{}{}{}",
+        FILE_PREFIX,
+        month_key(timestamp),
+        FILE_EXTENSION
+    ))
+}
+
+/// 追加一条运行记录
+pub fn append(dir: &Path, record: &RunRecord) -> MxuResult<()> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        MxuError::io(
+            Subsystem::History,
+            dir,
+            format!("Failed to create history directory: {}", e),
+        )
+    })?;
+
+    let path = month_file(dir, record.started_at);
+    let line = serde_json::to_string(record).map_err(|e| MxuError::internal(e.to_string()))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| MxuError::io(Subsystem::History, &path, e.to_string()))?;
+    writeln!(file, "{}", line).map_err(|e| MxuError::io(Subsystem::History, &path, e.to_string()))
+}
+
+/// 按条件查询运行记录（按开始时间从新到旧）
+pub fn query(dir: &Path, query: &RunQuery) -> MxuResult<Vec<RunRecord>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        // 尚未产生任何记录
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(MxuError::io(Subsystem::History, dir, e.to_string())),
+    };
+
+    // 只读取与时间范围有交集的月份文件
+    let first_month = query.since.map(month_key);
+    let last_month = query.until.map(|until| month_key(until - 1));
+    let mut files: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let month = name
+                .strip_prefix(FILE_PREFIX)?
+                .strip_suffix(FILE_EXTENSION)?
+                .to_string();
+            Some((month, entry.path()))
+        })
+        .filter(|(month, _)| {
+            first_month.as_ref().is_none_or(|first| month >= first)
+                && last_month.as_ref().is_none_or(|last| month <= last)
+        })
+        .collect();
+    files.sort();
+
+    let mut records = Vec::new();
+    for (_, path) in files {
+        read_file(&path, query, &mut records)?;
+    }
+
+    records.sort_by_key(|record| Reverse(record.started_at));
+    if let Some(limit) = query.limit {
+        records.truncate(limit);
+    }
+    Ok(records)
+}
+
+fn read_file(path: &Path, query: &RunQuery, records: &mut Vec<RunRecord>) -> MxuResult<()> {
+    let file =
+        File::open(path).map_err(|e| MxuError::io(Subsystem::History, path, e.to_string()))?;
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| MxuError::io(Subsystem::History, path, e.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // 写入中断等原因产生的损坏行跳过，不影响其他记录
+        match serde_json::from_str::<RunRecord>(&line) {
+            Ok(record) if query.matches(&record) => records.push(record),
+            Ok(_) => {}
+            Err(e) => warn!(
+                "Skipping invalid history line {}:{}: {}",
+                path.display(),
+                index + 1,
+                e
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local_ms(date: (i32, u32, u32), hour: u32) -> i64 {
+        Local
+            .with_ymd_and_hms(date.0, date.1, date.2, hour, 0, 0)
+            .earliest()
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn record(instance_id: &str, started_at: i64) -> RunRecord {
+        RunRecord {
+            run_id: format!("{}-{}", instance_id, started_at),
+            instance_id: instance_id.to_string(),
+            started_at,
+            ended_at: started_at,
+            tasks: Vec::new(),
+            agent_exit_codes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn query_filters_by_date_range_across_months() {
+        let dir = std::env::temp_dir().join(format!("mxu_history_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let jan_31 = local_ms((2026, 1, 31), 23);
+        let feb_1 = local_ms((2026, 2, 1), 0);
+        let feb_15 = local_ms((2026, 2, 15), 12);
+        let mar_1 = local_ms((2026, 3, 1), 0);
+        for (instance_id, started_at) in [("a", jan_31), ("b", feb_1), ("a", feb_15), ("a", mar_1)]
+        {
+            append(&dir, &record(instance_id, started_at)).unwrap();
+        }
+        // 按开始时间的本地月份归档
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                "runs-2026-01.jsonl",
+                "runs-2026-02.jsonl",
+                "runs-2026-03.jsonl"
+            ]
+        );
+
+        // (实例, since, until, limit, 期望的开始时间（从新到旧）)
+        let cases = [
+            (None, None, None, None, vec![mar_1, feb_15, feb_1, jan_31]),
+            // since 包含，until 不包含
+            (None, Some(feb_1), Some(mar_1), None, vec![feb_15, feb_1]),
+            (
+                None,
+                Some(jan_31 + 1),
+                Some(mar_1 + 1),
+                None,
+                vec![mar_1, feb_15, feb_1],
+            ),
+            (None, None, Some(feb_1), None, vec![jan_31]),
+            (Some("a"), Some(feb_1), None, None, vec![mar_1, feb_15]),
+            (None, None, None, Some(2), vec![mar_1, feb_15]),
+            // 空范围
+            (None, Some(feb_15), Some(feb_15), None, vec![]),
+            (None, Some(mar_1 + 1), None, None, vec![]),
+        ];
+        for (instance_id, since, until, limit, expected) in cases {
+            let query = RunQuery {
+                instance_id: instance_id.map(str::to_string),
+                since,
+                until,
+                limit,
+            };
+            let started: Vec<i64> = super::query(&dir, &query)
+                .unwrap()
+                .iter()
+                .map(|r| r.started_at)
+                .collect();
+            assert_eq!(started, expected, "{:?}", query);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn query_missing_dir_is_empty() {
+        let dir = std::env::temp_dir().join(format!("mxu_history_missing_{}", std::process::id()));
+        assert!(query(&dir, &RunQuery::default()).unwrap().is_empty());
+    }
+}
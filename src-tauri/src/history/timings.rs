@@ -0,0 +1,167 @@
+//! 任务执行计时
+//!
+//! MaaFramework 的任务详情不含时间信息，这里从事件总线上的任务与节点回调中记录各任务、节点、
+//! 识别和动作的开始/结束时间，查询任务详情时填入。每个实例只保留最近若干个任务
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use super::STOP_TASK_ENTRY;
+use crate::commands::event_bus::{BusEvent, EventSink, MAA_CALLBACK_EVENT};
+use crate::commands::types::{TaskDetail, Timing};
+
+/// 每个实例保留计时的任务数
+const MAX_TASKS_PER_INSTANCE: usize = 64;
+
+/// 回调对应的计时对象
+enum Target {
+    Task,
+    Node(i64),
+    Recognition(i64),
+    Action(i64),
+}
+
+/// 单个任务的计时
+#[derive(Default)]
+struct TaskTiming {
+    task: Timing,
+    nodes: HashMap<i64, Timing>,
+    recognitions: HashMap<i64, Timing>,
+    actions: HashMap<i64, Timing>,
+}
+
+#[derive(Default)]
+struct InstanceTimings {
+    tasks: HashMap<i64, TaskTiming>,
+    /// 任务 ID（按首次收到回调的顺序，用于淘汰最早的任务）
+    order: VecDeque<i64>,
+}
+
+impl InstanceTimings {
+    fn task_mut(&mut self, task_id: i64) -> &mut TaskTiming {
+        if !self.tasks.contains_key(&task_id) {
+            if self.order.len() >= MAX_TASKS_PER_INSTANCE {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.tasks.remove(&oldest);
+                }
+            }
+            self.order.push_back(task_id);
+        }
+        self.tasks.entry(task_id).or_default()
+    }
+}
+
+/// 任务执行计时（注册为事件总线消费者）
+#[derive(Default)]
+pub struct TaskTimings {
+    instances: Mutex<HashMap<String, InstanceTimings>>,
+}
+
+impl TaskTimings {
+    /// 实例被销毁时丢弃计时
+    pub fn remove_instance(&self, instance_id: &str) {
+        if let Ok(mut instances) = self.instances.lock() {
+            instances.remove(instance_id);
+        }
+    }
+
+    /// 将记录的时间填入任务详情
+    pub fn apply(&self, instance_id: &str, detail: &mut TaskDetail) {
+        let Ok(instances) = self.instances.lock() else {
+            return;
+        };
+        let Some(timing) = instances
+            .get(instance_id)
+            .and_then(|instance| instance.tasks.get(&detail.task_id))
+        else {
+            return;
+        };
+
+        detail.timing = timing.task;
+        for node in &mut detail.nodes {
+            if let Some(node_timing) = timing.nodes.get(&node.node_id) {
+                node.timing = *node_timing;
+            }
+            if let Some(reco) = node.recognition.as_mut() {
+                if let Some(reco_timing) = timing.recognitions.get(&reco.reco_id) {
+                    reco.timing = *reco_timing;
+                }
+            }
+            if let Some(action) = node.action.as_mut() {
+                if let Some(action_timing) = timing.actions.get(&action.action_id) {
+                    action.timing = *action_timing;
+                }
+            }
+        }
+    }
+
+    fn handle_callback(&self, instance_id: &str, message: &str, details: &Value, timestamp: i64) {
+        let Some(task_id) = details["task_id"].as_i64() else {
+            return;
+        };
+        // 回调格式：Tasker.Task.{阶段} 或 Node.{PipelineNode,Recognition,Action}.{阶段}
+        let Some((kind, phase)) = message.rsplit_once('.') else {
+            return;
+        };
+        let id = |field: &str| details[field].as_i64();
+        let target = match kind {
+            "Tasker.Task" if details["entry"].as_str() != Some(STOP_TASK_ENTRY) => Target::Task,
+            "Node.PipelineNode" => match id("node_id") {
+                Some(id) => Target::Node(id),
+                None => return,
+            },
+            "Node.Recognition" => match id("reco_id") {
+                Some(id) => Target::Recognition(id),
+                None => return,
+            },
+            "Node.Action" => match id("action_id") {
+                Some(id) => Target::Action(id),
+                None => return,
+            },
+            _ => return,
+        };
+
+        let Ok(mut instances) = self.instances.lock() else {
+            return;
+        };
+        let task = instances
+            .entry(instance_id.to_string())
+            .or_default()
+            .task_mut(task_id);
+        let timing = match target {
+            Target::Task => &mut task.task,
+            Target::Node(id) => task.nodes.entry(id).or_default(),
+            Target::Recognition(id) => task.recognitions.entry(id).or_default(),
+            Target::Action(id) => task.actions.entry(id).or_default(),
+        };
+        match phase {
+            "Starting" => timing.started_at = Some(timestamp),
+            "Succeeded" | "Failed" => timing.ended_at = Some(timestamp),
+            _ => {}
+        }
+    }
+}
+
+impl EventSink for TaskTimings {
+    fn handle(&self, event: &BusEvent) {
+        if event.name != MAA_CALLBACK_EVENT {
+            return;
+        }
+        let Some(instance_id) = event.instance_id.as_deref() else {
+            return;
+        };
+        let Some(message) = event.payload["message"].as_str() else {
+            return;
+        };
+        if !message.starts_with("Tasker.Task.") && !message.starts_with("Node.") {
+            return;
+        }
+        let details = event.payload["details"]
+            .as_str()
+            .and_then(|d| serde_json::from_str::<Value>(d).ok())
+            .unwrap_or(Value::Null);
+        self.handle_callback(instance_id, message, &details, event.timestamp);
+    }
+}
@@ -1,7 +1,16 @@
 pub mod commands;
+pub mod device_monitor;
+pub mod headless;
+pub mod history;
 mod mxu_actions;
+pub mod recorder;
+pub mod remote_api;
+pub mod scheduler;
+pub mod screen_stream;
 mod tray;
+pub mod watchdog;
 
+use commands::event_bus::TauriSink;
 use commands::MaaState;
 use std::sync::Arc;
 use tauri::Manager;
@@ -49,7 +58,42 @@ pub fn run() {
         .setup(|app| {
             // 创建 MaaState 并注册为 Tauri 管理状态
             let maa_state = Arc::new(MaaState::default());
-            app.manage(maa_state);
+            // MaaFramework 回调经事件总线转发到前端
+            maa_state
+                .event_bus
+                .add_sink(Arc::new(TauriSink::new(app.handle().clone())));
+            // 运行历史写入 数据目录/history，会话录制写入 数据目录/debug/sessions，
+            // 最近画面导出到 数据目录/debug/frames
+            match commands::utils::get_app_data_dir() {
+                Ok(data_dir) => {
+                    maa_state.history.set_dir(data_dir.join("history"));
+                    maa_state
+                        .recorder
+                        .set_dir(data_dir.join("debug").join("sessions"));
+                    maa_state
+                        .frame_buffer
+                        .set_dir(data_dir.join("debug").join("frames"));
+                }
+                Err(e) => log::warn!("Run history, session recording and frame export disabled: {}", e),
+            }
+            app.manage(maa_state.clone());
+
+            // 启动定时调度（不依赖前端计时器）
+            let scheduler = scheduler::Scheduler::new(maa_state.clone(), Default::default());
+            scheduler.start();
+            app.manage(scheduler.clone());
+
+            // 实时截图推流（各查看者共享同一个截图循环）
+            app.manage(screen_stream::ScreenStreamer::new(maa_state.clone()));
+
+            // 控制器掉线重连（由前端按设置启用）
+            app.manage(watchdog::ControllerWatchdog::new(maa_state.clone()));
+
+            // ADB 设备热插拔监听（由前端按设置启用）
+            app.manage(device_monitor::AdbDeviceMonitor::new(maa_state.clone()));
+
+            // 本地控制接口（由前端按设置启动）
+            app.manage(Arc::new(remote_api::RemoteApi::new(maa_state, scheduler)));
 
             // Windows 下移除系统标题栏（使用自定义标题栏）
             // macOS/Linux 保留完整的原生标题栏
@@ -130,16 +174,42 @@ pub fn run() {
             commands::maa_core::maa_destroy_instance,
             commands::maa_core::maa_connect_controller,
             commands::maa_core::maa_get_connection_status,
+            commands::maa_core::maa_set_screenshot_resolution,
             commands::maa_core::maa_load_resource,
             commands::maa_core::maa_is_resource_loaded,
             commands::maa_core::maa_destroy_resource,
             commands::maa_core::maa_run_task,
             commands::maa_core::maa_get_task_status,
+            commands::maa_core::maa_get_task_detail,
+            commands::pipeline_check::maa_validate_tasks,
+            commands::maa_core::maa_get_recognition_images,
+            commands::maa_core::maa_get_recognition_image_raw,
             commands::maa_core::maa_stop_task,
+            commands::task_pause::maa_pause_tasks,
+            commands::task_pause::maa_resume_tasks,
+            commands::task_queue::maa_get_task_queue,
+            commands::task_queue::maa_append_task,
+            commands::task_queue::maa_insert_task,
+            commands::task_queue::maa_cancel_task,
+            commands::task_queue::maa_move_task,
             commands::maa_core::maa_override_pipeline,
             commands::maa_core::maa_is_running,
             commands::maa_core::maa_post_screencap,
             commands::maa_core::maa_get_cached_image,
+            commands::maa_core::maa_get_cached_image_raw,
+            // 控制器输入命令
+            commands::input::maa_post_input,
+            commands::input::maa_wait_input,
+            // 实时截图推流命令
+            commands::screen_stream::maa_screen_stream_start,
+            commands::screen_stream::maa_screen_stream_stop,
+            commands::screen_stream::maa_screen_stream_ack,
+            // 掉线重连命令
+            commands::watchdog::maa_watchdog_configure,
+            commands::watchdog::maa_watchdog_get_options,
+            // 设备监听命令
+            commands::device_monitor::maa_device_monitor_configure,
+            commands::device_monitor::maa_device_monitor_get_options,
             // Agent 命令
             commands::maa_agent::maa_start_tasks,
             commands::maa_agent::maa_stop_agent,
@@ -175,6 +245,7 @@ pub fn run() {
             commands::system::is_autostart,
             commands::system::restart_as_admin,
             commands::system::maa_set_save_draw,
+            commands::system::maa_set_debug_mode,
             commands::system::open_file,
             commands::system::run_and_wait,
             commands::system::run_action,
@@ -193,6 +264,22 @@ pub fn run() {
             commands::tray::get_minimize_to_tray,
             commands::tray::update_tray_icon,
             commands::tray::update_tray_tooltip,
+            // 定时执行命令
+            commands::scheduler::scheduler_get_entries,
+            commands::scheduler::scheduler_reload,
+            commands::scheduler::scheduler_preview,
+            // 本地控制接口命令
+            commands::remote_api::remote_api_start,
+            commands::remote_api::remote_api_stop,
+            commands::remote_api::remote_api_status,
+            // 运行历史命令
+            commands::history::history_query_runs,
+            commands::history::history_stats,
+            // 会话录制命令
+            commands::recorder::recorder_start,
+            commands::recorder::recorder_stop,
+            commands::recorder::recorder_status,
+            commands::recorder::recorder_export_frames,
         ])
         .on_window_event(|window, event| {
             match event {
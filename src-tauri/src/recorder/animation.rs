@@ -0,0 +1,187 @@
+//! 画面帧导出
+//!
+//! 将环形缓冲中的画面导出为 APNG 动画（帧间隔取实际截图间隔，各帧时间写入 tEXt 块）、
+//! GIF 动画（帧间隔同 APNG，GIF 不保存各帧时间），或按序编号的 PNG 序列（文件名带截图时间）
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, TimeZone};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::FilterType;
+use image::{Delay, Frame, GenericImageView, RgbaImage};
+use serde::Deserialize;
+
+use super::frame_buffer::BufferedFrame;
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+
+/// 动画最大宽度（超过时按比例缩小，控制文件大小）
+const ANIMATION_MAX_WIDTH: u32 = 960;
+
+/// 动画单帧最短显示时间（毫秒）
+const MIN_FRAME_DELAY_MS: i64 = 100;
+
+/// 动画单帧最长显示时间（毫秒），截图间隔较长时压缩等待
+const MAX_FRAME_DELAY_MS: i64 = 2000;
+
+/// GIF 颜色量化速度（1-30，越大越快、画质越差）
+const GIF_QUANTIZE_SPEED: i32 = 10;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameExportFormat {
+    /// APNG 动画（.png）
+    #[default]
+    Apng,
+    /// GIF 动画（.gif）
+    Gif,
+    /// PNG 序列（目录）
+    PngSequence,
+}
+
+/// 导出画面帧，base 为不含扩展名的输出路径，返回实际写入的文件或目录
+pub fn export(
+    frames: &[BufferedFrame],
+    format: FrameExportFormat,
+    base: &Path,
+) -> MxuResult<PathBuf> {
+    if frames.is_empty() {
+        return Err(MxuError::new(
+            ErrorCode::NotFound,
+            Subsystem::Recorder,
+            "No frames buffered",
+        ));
+    }
+    match format {
+        FrameExportFormat::Apng => {
+            let path = base.with_extension("png");
+            write_apng(frames, &path)?;
+            Ok(path)
+        }
+        FrameExportFormat::Gif => {
+            let path = base.with_extension("gif");
+            write_gif(frames, &path)?;
+            Ok(path)
+        }
+        FrameExportFormat::PngSequence => {
+            write_sequence(frames, base)?;
+            Ok(base.to_path_buf())
+        }
+    }
+}
+
+fn write_apng(frames: &[BufferedFrame], path: &Path) -> MxuResult<()> {
+    let encode_err = |e: png::EncodingError| MxuError::io(Subsystem::Recorder, path, e.to_string());
+
+    let (out_width, out_height) = output_size(frames)?;
+    let file =
+        File::create(path).map_err(|e| MxuError::io(Subsystem::Recorder, path, e.to_string()))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), out_width, out_height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(encode_err)?;
+    let timestamps: Vec<String> = frames
+        .iter()
+        .map(|f| format_time(f.timestamp, "%Y-%m-%d %H:%M:%S%.3f"))
+        .collect();
+    encoder
+        .add_text_chunk("Timestamps".to_string(), timestamps.join("\n"))
+        .map_err(encode_err)?;
+
+    let mut writer = encoder.write_header().map_err(encode_err)?;
+    for (i, frame) in frames.iter().enumerate() {
+        writer
+            .set_frame_delay(frame_delay(frames, i) as u16, 1000)
+            .map_err(encode_err)?;
+        let rgba = scaled_rgba(frame, out_width, out_height)?;
+        writer.write_image_data(&rgba).map_err(encode_err)?;
+    }
+    writer.finish().map_err(encode_err)
+}
+
+fn write_gif(frames: &[BufferedFrame], path: &Path) -> MxuResult<()> {
+    let encode_err = |e: image::ImageError| MxuError::io(Subsystem::Recorder, path, e.to_string());
+
+    let (out_width, out_height) = output_size(frames)?;
+    let file =
+        File::create(path).map_err(|e| MxuError::io(Subsystem::Recorder, path, e.to_string()))?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), GIF_QUANTIZE_SPEED);
+    encoder.set_repeat(Repeat::Infinite).map_err(encode_err)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let rgba = scaled_rgba(frame, out_width, out_height)?;
+        let delay = Delay::from_numer_denom_ms(frame_delay(frames, i) as u32, 1);
+        encoder
+            .encode_frame(Frame::from_parts(rgba, 0, 0, delay))
+            .map_err(encode_err)?;
+    }
+    Ok(())
+}
+
+/// 动画尺寸：所有帧统一缩放到首帧（缩小后）的尺寸
+fn output_size(frames: &[BufferedFrame]) -> MxuResult<(u32, u32)> {
+    let (width, height) = decode(&frames[0])?.dimensions();
+    let out_width = width.min(ANIMATION_MAX_WIDTH);
+    let out_height = (u64::from(height) * u64::from(out_width) / u64::from(width)).max(1) as u32;
+    Ok((out_width, out_height))
+}
+
+/// 第 i 帧的显示时间（毫秒，取到下一帧的截图间隔）
+fn frame_delay(frames: &[BufferedFrame], i: usize) -> i64 {
+    frames
+        .get(i + 1)
+        .map_or(MAX_FRAME_DELAY_MS, |next| {
+            next.timestamp - frames[i].timestamp
+        })
+        .clamp(MIN_FRAME_DELAY_MS, MAX_FRAME_DELAY_MS)
+}
+
+/// 解码并缩放一帧（逐帧解码，避免同时持有全部位图）
+fn scaled_rgba(frame: &BufferedFrame, width: u32, height: u32) -> MxuResult<RgbaImage> {
+    let image = decode(frame)?;
+    Ok(if image.dimensions() == (width, height) {
+        image.to_rgba8()
+    } else {
+        image
+            .resize_exact(width, height, FilterType::Triangle)
+            .to_rgba8()
+    })
+}
+
+fn write_sequence(frames: &[BufferedFrame], dir: &Path) -> MxuResult<()> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| MxuError::io(Subsystem::Recorder, dir, e.to_string()))?;
+    for (i, frame) in frames.iter().enumerate() {
+        let name = format!(
+            "{:03}_{}.png",
+            i + 1,
+            format_time(frame.timestamp, "%H%M%S-%3f")
+        );
+        let path = dir.join(name);
+        std::fs::write(&path, &*frame.png)
+            .map_err(|e| MxuError::io(Subsystem::Recorder, &path, e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn decode(frame: &BufferedFrame) -> MxuResult<image::DynamicImage> {
+    image::load_from_memory(&frame.png).map_err(|e| {
+        MxuError::new(
+            ErrorCode::ParseFailed,
+            Subsystem::Recorder,
+            format!("Failed to decode frame: {}", e),
+        )
+    })
+}
+
+/// 格式化毫秒时间戳（本地时区）
+fn format_time(timestamp: i64, format: &str) -> String {
+    Local
+        .timestamp_millis_opt(timestamp)
+        .single()
+        .map(|t| t.format(format).to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
@@ -0,0 +1,126 @@
+//! 会话包
+//!
+//! 录制目录打包为 zip，以及回放时从会话包中按顺序读取画面帧
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{FRAMES_DIR, MANIFEST_FILE};
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+
+/// 路径是否为会话包（zip 文件）
+pub fn is_bundle(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+/// 将录制目录打包为会话包（画面帧已是压缩格式，直接存储）
+pub fn pack(dir: &Path, zip_path: &Path) -> MxuResult<()> {
+    let archive_err = |e: &dyn std::fmt::Display| {
+        MxuError::io(
+            Subsystem::Recorder,
+            zip_path,
+            format!("Failed to write session bundle: {}", e),
+        )
+    };
+
+    let file = File::create(zip_path).map_err(|e| archive_err(&e))?;
+    let mut zip = ZipWriter::new(file);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+    for (name, path) in files {
+        let options = if name.starts_with(FRAMES_DIR) {
+            stored
+        } else {
+            deflated
+        };
+        let content = std::fs::read(&path)
+            .map_err(|e| MxuError::io(Subsystem::Recorder, &path, e.to_string()))?;
+        zip.start_file(name, options).map_err(|e| archive_err(&e))?;
+        zip.write_all(&content).map_err(|e| archive_err(&e))?;
+    }
+
+    zip.finish().map_err(|e| archive_err(&e))?;
+    Ok(())
+}
+
+/// 递归收集目录中的文件（会话包内路径统一使用 `/` 分隔）
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) -> MxuResult<()> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| MxuError::io(Subsystem::Recorder, dir, e.to_string()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((name, path));
+        }
+    }
+    Ok(())
+}
+
+/// 按录制顺序读取会话包中的画面帧
+pub fn read_frames(zip_path: &Path) -> MxuResult<Vec<Vec<u8>>> {
+    let invalid = |message: String| {
+        MxuError::new(ErrorCode::ArchiveInvalid, Subsystem::Recorder, message).with_path(zip_path)
+    };
+
+    let file = File::open(zip_path)
+        .map_err(|e| MxuError::io(Subsystem::Recorder, zip_path, e.to_string()))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| invalid(format!("Invalid session bundle: {}", e)))?;
+    if archive.index_for_name(MANIFEST_FILE).is_none() {
+        return Err(invalid(format!("{} not found in bundle", MANIFEST_FILE)));
+    }
+
+    let prefix = format!("{}/", FRAMES_DIR);
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.starts_with(&prefix) && name.len() > prefix.len())
+        .map(str::to_string)
+        .collect();
+    names.sort();
+
+    let mut frames = Vec::with_capacity(names.len());
+    for name in names {
+        let mut entry = archive
+            .by_name(&name)
+            .map_err(|e| invalid(format!("Failed to read {}: {}", name, e)))?;
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry
+            .read_to_end(&mut data)
+            .map_err(|e| invalid(format!("Failed to read {}: {}", name, e)))?;
+        frames.push(data);
+    }
+    Ok(frames)
+}
+
+/// 目录中最近的会话包（按修改时间从新到旧）
+pub fn recent(dir: &Path, limit: usize) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut bundles: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_bundle(path))
+        .collect();
+    bundles.sort_by_key(|path| std::cmp::Reverse(path.metadata().and_then(|m| m.modified()).ok()));
+    bundles.truncate(limit);
+    bundles
+}
@@ -0,0 +1,352 @@
+//! 画面环形缓冲
+//!
+//! 为每个已连接的实例在内存中保留最近若干帧截图（画面未变化时不重复保存）。任务失败时自动
+//! 导出为 APNG，夜间无人值守运行失败后也能查看失败前的画面；也可随时通过命令手动导出
+//!
+//! 回调线程中只记录截图请求，读取画面与导出在后台线程中进行。停止、暂停、超时、重试等主动停止
+//! 任务导致的失败不导出
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use log::{debug, info, warn};
+use serde_json::Value;
+
+use super::animation::{self, FrameExportFormat};
+use super::sanitize;
+use crate::commands::backend::ControllerBackend;
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::event_bus::{BusEvent, EventSink, MAA_CALLBACK_EVENT};
+use crate::history::STOP_TASK_ENTRY;
+
+/// 每个实例保留的帧数
+pub const DEFAULT_CAPACITY: usize = 30;
+
+/// 任务失败时自动导出的文件名前缀
+const FAILURE_PREFIX: &str = "failure";
+
+/// 手动导出的文件名前缀
+const EXPORT_PREFIX: &str = "frames";
+
+/// 缓冲中的一帧
+#[derive(Clone)]
+pub struct BufferedFrame {
+    /// 截图时间（毫秒时间戳）
+    pub timestamp: i64,
+    /// PNG 编码的画面
+    pub png: Arc<Vec<u8>>,
+}
+
+#[derive(Default)]
+struct InstanceFrames {
+    /// 截图来源（实例重新连接时更新）
+    controller: Option<Arc<dyn ControllerBackend>>,
+    frames: VecDeque<BufferedFrame>,
+    /// 最近一帧的哈希（画面未变化时不重复保存）
+    last_hash: Option<u64>,
+    /// 上次自动导出后是否有新画面（同一次失败的多个回调只导出一次）
+    dirty: bool,
+}
+
+/// 后台线程处理的请求（按回调顺序）
+enum Job {
+    /// 保存控制器当前缓存的画面（实例 ID 与截图时间）
+    Capture(String, i64),
+    /// 导出任务失败前的画面
+    ExportFailure(String),
+}
+
+/// 缓存的画面（与后台线程共享）
+struct Frames {
+    capacity: usize,
+    /// 导出目录（debug/frames）
+    dir: Mutex<Option<PathBuf>>,
+    instances: Mutex<HashMap<String, InstanceFrames>>,
+}
+
+/// 画面环形缓冲（注册为事件总线消费者）
+pub struct FrameBuffer {
+    frames: Arc<Frames>,
+    /// 后台线程的请求通道（首次截图时启动线程）
+    worker: Mutex<Option<Sender<Job>>>,
+    /// 主动停止了任务的实例（被中止的任务的失败不导出，下一个任务开始时清除）
+    stopping: Mutex<HashSet<String>>,
+}
+
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        Self {
+            frames: Arc::new(Frames {
+                capacity: DEFAULT_CAPACITY,
+                dir: Mutex::new(None),
+                instances: Mutex::new(HashMap::new()),
+            }),
+            worker: Mutex::new(None),
+            stopping: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl FrameBuffer {
+    /// 设置导出目录
+    pub fn set_dir(&self, dir: PathBuf) {
+        if let Ok(mut guard) = self.frames.dir.lock() {
+            *guard = Some(dir);
+        }
+    }
+
+    /// 实例连接控制器后开始缓存（重新连接时保留已缓存的画面）
+    pub fn set_controller(&self, instance_id: &str, controller: &Arc<dyn ControllerBackend>) {
+        if let Ok(mut instances) = self.frames.instances.lock() {
+            instances
+                .entry(instance_id.to_string())
+                .or_default()
+                .controller = Some(controller.clone());
+        }
+    }
+
+    /// 实例被销毁时丢弃缓存
+    pub fn remove_instance(&self, instance_id: &str) {
+        if let Ok(mut instances) = self.frames.instances.lock() {
+            instances.remove(instance_id);
+        }
+        if let Ok(mut stopping) = self.stopping.lock() {
+            stopping.remove(instance_id);
+        }
+    }
+
+    /// 主动停止实例的任务前调用，之后被中止的任务的失败不再导出画面
+    pub fn ignore_failures(&self, instance_id: &str) {
+        if let Ok(mut stopping) = self.stopping.lock() {
+            stopping.insert(instance_id.to_string());
+        }
+    }
+
+    /// 实例缓存的画面（按时间从旧到新）
+    pub fn frames(&self, instance_id: &str) -> Vec<BufferedFrame> {
+        self.frames.frames(instance_id)
+    }
+
+    /// 导出实例缓存的画面，返回写入的文件或目录
+    pub fn export(&self, instance_id: &str, format: FrameExportFormat) -> MxuResult<PathBuf> {
+        let frames = self.frames(instance_id);
+        let base = self.frames.output_base(instance_id, EXPORT_PREFIX)?;
+        let path = animation::export(&frames, format, &base)?;
+        info!(
+            "[FrameBuffer] Exported {} frame(s) of {} to {}",
+            frames.len(),
+            instance_id,
+            path.display()
+        );
+        Ok(path)
+    }
+
+    /// 交给后台线程处理
+    fn send(&self, job: Job) {
+        let Ok(mut worker) = self.worker.lock() else {
+            return;
+        };
+        let sender = worker.get_or_insert_with(|| {
+            let (sender, jobs) = mpsc::channel();
+            let frames = self.frames.clone();
+            thread::spawn(move || frames.run(&jobs));
+            sender
+        });
+        if sender.send(job).is_err() {
+            *worker = None;
+        }
+    }
+}
+
+impl Frames {
+    /// 后台线程：按顺序处理请求，直到缓冲被释放
+    fn run(&self, jobs: &Receiver<Job>) {
+        while let Ok(job) = jobs.recv() {
+            let batch: Vec<Job> = std::iter::once(job).chain(jobs.try_iter()).collect();
+            for (i, job) in batch.iter().enumerate() {
+                match job {
+                    // 积压时只读取一次最新画面（之后同一实例还有截图且中间没有导出）
+                    Job::Capture(instance_id, timestamp) => {
+                        let superseded = batch[i + 1..]
+                            .iter()
+                            .map_while(|next| match next {
+                                Job::ExportFailure(id) if id == instance_id => None,
+                                next => Some(next),
+                            })
+                            .any(|next| matches!(next, Job::Capture(id, _) if id == instance_id));
+                        if !superseded {
+                            self.capture(instance_id, *timestamp);
+                        }
+                    }
+                    Job::ExportFailure(instance_id) => self.export_failure(instance_id),
+                }
+            }
+        }
+    }
+
+    fn frames(&self, instance_id: &str) -> Vec<BufferedFrame> {
+        self.instances
+            .lock()
+            .ok()
+            .and_then(|instances| {
+                instances
+                    .get(instance_id)
+                    .map(|entry| entry.frames.iter().cloned().collect())
+            })
+            .unwrap_or_default()
+    }
+
+    /// 导出路径（不含扩展名）
+    fn output_base(&self, instance_id: &str, prefix: &str) -> MxuResult<PathBuf> {
+        let root = self.dir.lock()?.clone().ok_or_else(|| {
+            MxuError::new(
+                ErrorCode::NotFound,
+                Subsystem::Recorder,
+                "Frame export directory not set",
+            )
+        })?;
+        std::fs::create_dir_all(&root)
+            .map_err(|e| MxuError::io(Subsystem::Recorder, &root, e.to_string()))?;
+        Ok(root.join(format!(
+            "{}-{}-{}",
+            prefix,
+            sanitize(instance_id),
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )))
+    }
+
+    /// 保存控制器当前缓存的画面
+    fn capture(&self, instance_id: &str, timestamp: i64) {
+        let controller = match self.instances.lock() {
+            Ok(instances) => instances
+                .get(instance_id)
+                .and_then(|entry| entry.controller.clone()),
+            Err(_) => return,
+        };
+        let Some(controller) = controller else {
+            return;
+        };
+        // 读取画面时不持有锁
+        let png = match controller.cached_image() {
+            Ok(png) if !png.is_empty() => png,
+            Ok(_) => return,
+            Err(e) => {
+                debug!("[FrameBuffer] Failed to get cached image: {}", e);
+                return;
+            }
+        };
+        let mut hasher = DefaultHasher::new();
+        png.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let Ok(mut instances) = self.instances.lock() else {
+            return;
+        };
+        let Some(entry) = instances.get_mut(instance_id) else {
+            return;
+        };
+        if entry.last_hash == Some(hash) {
+            return;
+        }
+        entry.last_hash = Some(hash);
+        if entry.frames.len() >= self.capacity {
+            entry.frames.pop_front();
+        }
+        entry.frames.push_back(BufferedFrame {
+            timestamp,
+            png: Arc::new(png),
+        });
+        entry.dirty = true;
+    }
+
+    /// 任务失败时导出 APNG（编码较慢，在单独的线程中进行，不阻塞截图）
+    fn export_failure(&self, instance_id: &str) {
+        let frames: Vec<BufferedFrame> = {
+            let Ok(mut instances) = self.instances.lock() else {
+                return;
+            };
+            let Some(entry) = instances.get_mut(instance_id) else {
+                return;
+            };
+            if !entry.dirty || entry.frames.is_empty() {
+                return;
+            }
+            entry.dirty = false;
+            entry.frames.iter().cloned().collect()
+        };
+        let base = match self.output_base(instance_id, FAILURE_PREFIX) {
+            Ok(base) => base,
+            Err(e) => {
+                warn!(
+                    "[FrameBuffer] Failure frames of {} not saved: {}",
+                    instance_id, e
+                );
+                return;
+            }
+        };
+
+        let instance_id = instance_id.to_string();
+        thread::spawn(
+            move || match animation::export(&frames, FrameExportFormat::Apng, &base) {
+                Ok(path) => info!(
+                    "[FrameBuffer] Task of {} failed, last {} frame(s) saved to {}",
+                    instance_id,
+                    frames.len(),
+                    path.display()
+                ),
+                Err(e) => warn!(
+                    "[FrameBuffer] Failed to save failure frames of {}: {}",
+                    instance_id, e
+                ),
+            },
+        );
+    }
+}
+
+impl EventSink for FrameBuffer {
+    fn handle(&self, event: &BusEvent) {
+        if event.name != MAA_CALLBACK_EVENT {
+            return;
+        }
+        let Some(instance_id) = event.instance_id.as_deref() else {
+            return;
+        };
+        let message = event.payload["message"].as_str().unwrap_or_default();
+        if !matches!(
+            message,
+            "Controller.Action.Succeeded" | "Tasker.Task.Starting" | "Tasker.Task.Failed"
+        ) {
+            return;
+        }
+        let details = event.payload["details"]
+            .as_str()
+            .and_then(|d| serde_json::from_str::<Value>(d).ok())
+            .unwrap_or(Value::Null);
+
+        match message {
+            "Controller.Action.Succeeded" if details["action"] == "screencap" => {
+                self.send(Job::Capture(instance_id.to_string(), event.timestamp))
+            }
+            "Tasker.Task.Starting" if details["entry"] != STOP_TASK_ENTRY => {
+                if let Ok(mut stopping) = self.stopping.lock() {
+                    stopping.remove(instance_id);
+                }
+            }
+            "Tasker.Task.Failed" if details["entry"] != STOP_TASK_ENTRY => {
+                let stopped = self
+                    .stopping
+                    .lock()
+                    .is_ok_and(|stopping| stopping.contains(instance_id));
+                if !stopped {
+                    self.send(Job::ExportFailure(instance_id.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! 会话录制
+//!
+//! 录制实例在真实控制器上的一段会话：每次截图完成后的画面、任务执行器发出的点击/滑动/按键等
+//! 控制器动作以及完整的回调事件时间线。录制期间写入 debug/sessions/{会话名}/，停止后打包为
+//! 同名 zip（会话包），可附加到问题反馈中，也可直接作为 ImageFolder 控制器的输入离线回放
+//!
+//! 会话包结构：
+//! - `manifest.json`: 会话信息（实例、起止时间、帧数、动作数）
+//! - `events.jsonl`: 事件时间线（事件总线格式，截图完成事件附带对应的帧文件名）
+//! - `frames/000001.png`: 按截图顺序编号的画面
+//!
+//! 模块结构：
+//! - `bundle`: 会话包打包与读取
+//! - `frame_buffer`: 最近画面的环形缓冲（任务失败时自动导出）
+//! - `animation`: 画面帧导出（APNG 动画与 PNG 序列）
+
+pub mod animation;
+pub mod bundle;
+pub mod frame_buffer;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commands::backend::ControllerBackend;
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::event_bus::{BusEvent, EventSink, MAA_CALLBACK_EVENT};
+use crate::screen_stream::SCREEN_FRAME_EVENT;
+
+/// 会话包内的帧目录
+pub const FRAMES_DIR: &str = "frames";
+/// 会话包内的事件时间线文件
+pub const EVENTS_FILE: &str = "events.jsonl";
+/// 会话包内的会话信息文件
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// 会话包格式版本
+const MANIFEST_VERSION: u32 = 1;
+
+// ============================================================================
+// 会话包类型
+// ============================================================================
+
+/// 会话信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionManifest {
+    /// 会话包格式版本
+    pub version: u32,
+    pub instance_id: String,
+    /// 开始时间（毫秒时间戳）
+    pub started_at: i64,
+    /// 结束时间（毫秒时间戳）
+    pub ended_at: i64,
+    /// 画面帧数
+    pub frame_count: usize,
+    /// 控制器动作数（不含截图与连接）
+    pub action_count: usize,
+    /// 时间线事件数
+    pub event_count: usize,
+}
+
+/// 事件时间线中的一行
+#[derive(Serialize)]
+struct TimelineEntry<'a> {
+    #[serde(flatten)]
+    event: &'a BusEvent,
+    /// 截图完成事件对应的帧（会话包内的相对路径）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frame: Option<String>,
+}
+
+/// 录制状态
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingStatus {
+    pub instance_id: String,
+    /// 录制目录（停止后打包为同名 zip）
+    pub path: String,
+    /// 开始时间（毫秒时间戳）
+    pub started_at: i64,
+    pub frame_count: usize,
+    pub action_count: usize,
+}
+
+// ============================================================================
+// 录制器
+// ============================================================================
+
+/// 进行中的录制
+struct Recording {
+    dir: PathBuf,
+    /// 截图来源（实例重新连接时更新）
+    controller: Arc<dyn ControllerBackend>,
+    events: BufWriter<File>,
+    manifest: SessionManifest,
+}
+
+impl Recording {
+    fn status(&self) -> RecordingStatus {
+        RecordingStatus {
+            instance_id: self.manifest.instance_id.clone(),
+            path: self.dir.to_string_lossy().to_string(),
+            started_at: self.manifest.started_at,
+            frame_count: self.manifest.frame_count,
+            action_count: self.manifest.action_count,
+        }
+    }
+
+    /// 保存控制器当前缓存的画面，返回帧文件名
+    fn capture_frame(&mut self) -> Option<String> {
+        let image = match self.controller.cached_image() {
+            Ok(image) => image,
+            Err(e) => {
+                warn!("[Recorder] Failed to get cached image: {}", e);
+                return None;
+            }
+        };
+        let name = format!("{}/{:06}.png", FRAMES_DIR, self.manifest.frame_count + 1);
+        let path = self.dir.join(&name);
+        if let Err(e) = std::fs::write(&path, image) {
+            warn!("[Recorder] Failed to write {}: {}", path.display(), e);
+            return None;
+        }
+        self.manifest.frame_count += 1;
+        Some(name)
+    }
+
+    fn record(&mut self, event: &BusEvent) {
+        let mut frame = None;
+        if event.name == MAA_CALLBACK_EVENT {
+            let message = event.payload["message"].as_str().unwrap_or_default();
+            if message.starts_with("Controller.Action.") {
+                let details = event.payload["details"]
+                    .as_str()
+                    .and_then(|d| serde_json::from_str::<Value>(d).ok())
+                    .unwrap_or(Value::Null);
+                match (message, details["action"].as_str().unwrap_or_default()) {
+                    ("Controller.Action.Succeeded", "screencap") => frame = self.capture_frame(),
+                    (_, "screencap" | "connect") => {}
+                    ("Controller.Action.Starting", _) => self.manifest.action_count += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let entry = TimelineEntry { event, frame };
+        match serde_json::to_string(&entry) {
+            Ok(line) => {
+                if let Err(e) = writeln!(self.events, "{}", line) {
+                    warn!("[Recorder] Failed to write event: {}", e);
+                    return;
+                }
+                self.manifest.event_count += 1;
+            }
+            Err(e) => warn!("[Recorder] Failed to serialize event: {}", e),
+        }
+    }
+
+    /// 写入会话信息并打包，返回会话包路径
+    fn finish(mut self) -> MxuResult<PathBuf> {
+        self.events
+            .flush()
+            .map_err(|e| MxuError::io(Subsystem::Recorder, &self.dir, e.to_string()))?;
+        drop(self.events);
+
+        self.manifest.ended_at = chrono::Local::now().timestamp_millis();
+        let manifest_path = self.dir.join(MANIFEST_FILE);
+        let manifest = serde_json::to_string_pretty(&self.manifest)
+            .map_err(|e| MxuError::internal(e.to_string()))?;
+        std::fs::write(&manifest_path, manifest)
+            .map_err(|e| MxuError::io(Subsystem::Recorder, &manifest_path, e.to_string()))?;
+
+        let zip_path = self.dir.with_extension("zip");
+        bundle::pack(&self.dir, &zip_path)?;
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            warn!("[Recorder] Failed to remove {}: {}", self.dir.display(), e);
+        }
+        info!(
+            "[Recorder] Session saved: {} ({} frame(s), {} action(s))",
+            zip_path.display(),
+            self.manifest.frame_count,
+            self.manifest.action_count
+        );
+        Ok(zip_path)
+    }
+}
+
+/// 会话录制器（注册为事件总线消费者，只处理正在录制的实例）
+#[derive(Default)]
+pub struct SessionRecorder {
+    /// 会话存储目录（debug/sessions）
+    dir: Mutex<Option<PathBuf>>,
+    /// 各实例进行中的录制
+    recordings: Mutex<HashMap<String, Recording>>,
+}
+
+impl SessionRecorder {
+    /// 设置会话存储目录
+    pub fn set_dir(&self, dir: PathBuf) {
+        if let Ok(mut guard) = self.dir.lock() {
+            *guard = Some(dir);
+        }
+    }
+
+    /// 会话存储目录
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir.lock().ok().and_then(|d| d.clone())
+    }
+
+    /// 开始录制（实例已在录制时返回当前状态）
+    pub fn start(
+        &self,
+        instance_id: &str,
+        controller: Arc<dyn ControllerBackend>,
+    ) -> MxuResult<RecordingStatus> {
+        let mut recordings = self.recordings.lock()?;
+        if let Some(recording) = recordings.get(instance_id) {
+            return Ok(recording.status());
+        }
+
+        let root = self.dir().ok_or_else(|| {
+            MxuError::new(
+                ErrorCode::NotFound,
+                Subsystem::Recorder,
+                "Session directory not set",
+            )
+        })?;
+        let started_at = chrono::Local::now();
+        let dir = root.join(format!(
+            "session-{}-{}",
+            sanitize(instance_id),
+            started_at.format("%Y%m%d-%H%M%S")
+        ));
+        let frames_dir = dir.join(FRAMES_DIR);
+        std::fs::create_dir_all(&frames_dir).map_err(|e| {
+            MxuError::io(
+                Subsystem::Recorder,
+                &frames_dir,
+                format!("Failed to create session directory: {}", e),
+            )
+        })?;
+        let events_path = dir.join(EVENTS_FILE);
+        let events = File::create(&events_path)
+            .map_err(|e| MxuError::io(Subsystem::Recorder, &events_path, e.to_string()))?;
+
+        let recording = Recording {
+            controller,
+            events: BufWriter::new(events),
+            manifest: SessionManifest {
+                version: MANIFEST_VERSION,
+                instance_id: instance_id.to_string(),
+                started_at: started_at.timestamp_millis(),
+                ended_at: started_at.timestamp_millis(),
+                frame_count: 0,
+                action_count: 0,
+                event_count: 0,
+            },
+            dir,
+        };
+        let status = recording.status();
+        info!("[Recorder] Recording {} to {}", instance_id, status.path);
+        recordings.insert(instance_id.to_string(), recording);
+        Ok(status)
+    }
+
+    /// 停止录制并打包，返回会话包路径
+    pub fn stop(&self, instance_id: &str) -> MxuResult<PathBuf> {
+        let recording = self.recordings.lock()?.remove(instance_id).ok_or_else(|| {
+            MxuError::new(
+                ErrorCode::NotFound,
+                Subsystem::Recorder,
+                "Instance is not being recorded",
+            )
+            .with_instance(instance_id)
+        })?;
+        recording.finish()
+    }
+
+    /// 实例被销毁时结束录制（未在录制时忽略）
+    pub fn finish_instance(&self, instance_id: &str) {
+        let recording = match self.recordings.lock() {
+            Ok(mut recordings) => recordings.remove(instance_id),
+            Err(_) => return,
+        };
+        if let Some(Err(e)) = recording.map(Recording::finish) {
+            warn!(
+                "[Recorder] Failed to save session of {}: {}",
+                instance_id, e
+            );
+        }
+    }
+
+    /// 实例重新连接后更新截图来源
+    pub fn set_controller(&self, instance_id: &str, controller: &Arc<dyn ControllerBackend>) {
+        if let Ok(mut recordings) = self.recordings.lock() {
+            if let Some(recording) = recordings.get_mut(instance_id) {
+                debug!("[Recorder] Controller of {} replaced", instance_id);
+                recording.controller = controller.clone();
+            }
+        }
+    }
+
+    /// 查询录制状态（未在录制时为 None）
+    pub fn status(&self, instance_id: &str) -> Option<RecordingStatus> {
+        let recordings = self.recordings.lock().ok()?;
+        recordings.get(instance_id).map(Recording::status)
+    }
+}
+
+impl EventSink for SessionRecorder {
+    fn handle(&self, event: &BusEvent) {
+        // 截图帧已按截图完成事件保存，不重复写入时间线
+        if event.name == SCREEN_FRAME_EVENT {
+            return;
+        }
+        let Some(instance_id) = event.instance_id.as_deref() else {
+            return;
+        };
+        let Ok(mut recordings) = self.recordings.lock() else {
+            return;
+        };
+        if let Some(recording) = recordings.get_mut(instance_id) {
+            recording.record(event);
+        }
+    }
+}
+
+/// 将实例 ID 转为可用作文件名的形式
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
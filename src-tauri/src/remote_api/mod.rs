@@ -0,0 +1,183 @@
+//! 本地控制接口
+//!
+//! 可选启用的 HTTP / WebSocket 服务，仅监听 127.0.0.1，供自动化与监控脚本查询实例状态、
+//! 启动/停止任务、获取截图，并订阅 maa-callback / maa-agent-output 事件。
+//! 所有请求需携带访问令牌（`Authorization: Bearer <token>` 或 `?token=<token>`）
+//!
+//! 启动/停止任务与托盘菜单一样交给前端执行（remote-start-tasks / remote-stop-tasks 事件），
+//! 复用前端的自动连接、前置动作与 Agent 启动流程
+//!
+//! 模块结构：
+//! - `routes`: HTTP 路由与 WebSocket 事件流
+
+mod routes;
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+
+use log::{error, info};
+use serde::Serialize;
+use tokio::sync::{broadcast, watch};
+
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::event_bus::{BusEvent, EventSink, AGENT_OUTPUT_EVENT, MAA_CALLBACK_EVENT};
+use crate::commands::types::MaaState;
+use crate::scheduler::Scheduler;
+use crate::watchdog::WATCHDOG_EVENT;
+
+/// 远程启动任务事件名（前端收到后按托盘启动流程执行）
+pub const REMOTE_START_TASKS_EVENT: &str = "remote-start-tasks";
+
+/// 远程停止任务事件名
+pub const REMOTE_STOP_TASKS_EVENT: &str = "remote-stop-tasks";
+
+/// WebSocket 事件缓冲容量（客户端处理过慢时丢弃最旧的事件）
+const EVENT_BUFFER_CAPACITY: usize = 1024;
+
+/// 通过 WebSocket 推送的事件（其余事件如截图帧不进入广播通道）
+const STREAMED_EVENTS: &[&str] = &[MAA_CALLBACK_EVENT, AGENT_OUTPUT_EVENT, WATCHDOG_EVENT];
+
+/// 服务运行状态
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteApiStatus {
+    pub running: bool,
+    /// 监听地址（如 127.0.0.1:8765）
+    pub address: Option<String>,
+}
+
+/// 远程启动/停止任务事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteTaskRequest {
+    pub instance_id: String,
+}
+
+/// 路由共享的上下文
+struct ApiContext {
+    state: Arc<MaaState>,
+    scheduler: Arc<Scheduler>,
+    token: String,
+    events: broadcast::Sender<BusEvent>,
+    /// 服务停止信号（通知 WebSocket 连接关闭）
+    shutdown: watch::Receiver<bool>,
+}
+
+struct RunningServer {
+    addr: SocketAddr,
+    shutdown: watch::Sender<bool>,
+    sink_id: u64,
+}
+
+/// 本地控制接口服务
+pub struct RemoteApi {
+    state: Arc<MaaState>,
+    scheduler: Arc<Scheduler>,
+    server: Mutex<Option<RunningServer>>,
+}
+
+impl RemoteApi {
+    pub fn new(state: Arc<MaaState>, scheduler: Arc<Scheduler>) -> Self {
+        Self {
+            state,
+            scheduler,
+            server: Mutex::new(None),
+        }
+    }
+
+    /// 启动服务（已启动时按新的端口和令牌重启）
+    pub async fn start(&self, port: u16, token: String) -> MxuResult<RemoteApiStatus> {
+        if token.trim().is_empty() {
+            return Err(MxuError::new(
+                ErrorCode::InvalidArgument,
+                Subsystem::RemoteApi,
+                "Access token must not be empty",
+            ));
+        }
+        self.stop();
+
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .await
+            .map_err(|e| {
+                MxuError::new(
+                    ErrorCode::BindFailed,
+                    Subsystem::RemoteApi,
+                    format!("Failed to listen on 127.0.0.1:{}: {}", port, e),
+                )
+            })?;
+        let addr = listener.local_addr().map_err(|e| {
+            MxuError::new(ErrorCode::BindFailed, Subsystem::RemoteApi, e.to_string())
+        })?;
+
+        let (events, _) = broadcast::channel(EVENT_BUFFER_CAPACITY);
+        let sink_id = self.state.event_bus.add_sink(Arc::new(BroadcastSink {
+            sender: events.clone(),
+        }));
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let context = Arc::new(ApiContext {
+            state: self.state.clone(),
+            scheduler: self.scheduler.clone(),
+            token,
+            events,
+            shutdown: shutdown_rx.clone(),
+        });
+        let app = routes::router(context);
+
+        let mut shutdown = shutdown_rx;
+        tauri::async_runtime::spawn(async move {
+            let result = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown.wait_for(|stop| *stop).await;
+                })
+                .await;
+            match result {
+                Ok(()) => info!("Remote API stopped"),
+                Err(e) => error!("Remote API server error: {}", e),
+            }
+        });
+
+        info!("Remote API listening on {}", addr);
+        *self.server.lock()? = Some(RunningServer {
+            addr,
+            shutdown: shutdown_tx,
+            sink_id,
+        });
+        Ok(self.status())
+    }
+
+    /// 停止服务（未启动时忽略）
+    pub fn stop(&self) {
+        let Some(server) = self.server.lock().ok().and_then(|mut s| s.take()) else {
+            return;
+        };
+        self.state.event_bus.remove_sink(server.sink_id);
+        let _ = server.shutdown.send(true);
+        info!("Remote API on {} stopping", server.addr);
+    }
+
+    pub fn status(&self) -> RemoteApiStatus {
+        let addr = self
+            .server
+            .lock()
+            .ok()
+            .and_then(|s| s.as_ref().map(|s| s.addr));
+        RemoteApiStatus {
+            running: addr.is_some(),
+            address: addr.map(|a| a.to_string()),
+        }
+    }
+}
+
+/// 将需要推送的总线事件转发到 WebSocket 广播通道
+struct BroadcastSink {
+    sender: broadcast::Sender<BusEvent>,
+}
+
+impl EventSink for BroadcastSink {
+    fn handle(&self, event: &BusEvent) {
+        if !STREAMED_EVENTS.contains(&event.name.as_str()) {
+            return;
+        }
+        // 没有 WebSocket 客户端时发送失败，直接忽略
+        let _ = self.sender.send(event.clone());
+    }
+}
@@ -0,0 +1,360 @@
+//! HTTP 路由
+//!
+//! - `GET  /api/instances`：实例列表及运行时状态
+//! - `GET  /api/instances/{id}`：单个实例
+//! - `POST /api/instances/{id}/start`：启动实例的任务列表
+//! - `POST /api/instances/{id}/stop`：停止实例的任务
+//! - `GET  /api/instances/{id}/screenshot`：最近一次截图（默认 PNG，可指定格式、质量与最大宽度）
+//! - `POST /api/instances/{id}/input`：发送点击、滑动、按键等控制器输入（`?wait=true` 时等待完成）
+//! - `GET  /api/events`：WebSocket 事件流（可用 `?instance=<id>` 只订阅单个实例）
+//!
+//! 错误以 MxuError JSON 返回（与 Tauri 命令一致）
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{ApiContext, RemoteTaskRequest, REMOTE_START_TASKS_EVENT, REMOTE_STOP_TASKS_EVENT};
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::event_bus::BusEvent;
+use crate::commands::input::{self, DEFAULT_WAIT_TIMEOUT};
+use crate::commands::screenshot::{cached_screenshot, ScreenshotOptions};
+use crate::commands::types::{ControllerInput, InstanceState, TaskStatus};
+
+pub(super) fn router(context: Arc<ApiContext>) -> Router {
+    Router::new()
+        .route("/api/instances", get(list_instances))
+        .route("/api/instances/{id}", get(get_instance))
+        .route("/api/instances/{id}/start", post(start_tasks))
+        .route("/api/instances/{id}/stop", post(stop_tasks))
+        .route("/api/instances/{id}/screenshot", get(get_screenshot))
+        .route("/api/instances/{id}/input", post(post_input))
+        .route("/api/events", get(stream_events))
+        .layer(middleware::from_fn_with_state(
+            context.clone(),
+            authenticate,
+        ))
+        .with_state(context)
+}
+
+// ============================================================================
+// 鉴权与错误
+// ============================================================================
+
+/// 校验访问令牌：`Authorization: Bearer <token>` 或查询参数 `token`（WebSocket 客户端无法设置请求头时使用）
+async fn authenticate(
+    State(context): State<Arc<ApiContext>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let header_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim().to_string());
+    let query_token = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .and_then(|v| urlencoding::decode(v).ok())
+            .map(|v| v.into_owned())
+    });
+
+    let authorized = header_token
+        .or(query_token)
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), context.token.as_bytes()));
+    if !authorized {
+        warn!(
+            "Remote API rejected unauthorized request: {} {}",
+            request.method(),
+            request.uri().path()
+        );
+        return MxuError::new(
+            ErrorCode::Unauthorized,
+            Subsystem::RemoteApi,
+            "Missing or invalid access token",
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// 比较令牌（耗时与内容无关，避免按响应时间逐字节猜测）
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl IntoResponse for MxuError {
+    fn into_response(self) -> Response {
+        let status = match self.code {
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::InstanceNotFound | ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::InstanceBusy
+            | ErrorCode::ControllerNotConnected
+            | ErrorCode::ResourceNotLoaded
+            | ErrorCode::TaskerNotCreated
+            | ErrorCode::TaskerNotInitialized => StatusCode::CONFLICT,
+            ErrorCode::InvalidArgument => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
+// ============================================================================
+// 实例
+// ============================================================================
+
+/// 实例信息
+#[derive(Serialize)]
+struct InstanceSummary {
+    id: String,
+    /// 实例名称（仅存在于运行时、尚未保存到配置文件的实例为 None）
+    name: Option<String>,
+    /// 运行时状态（前端尚未创建该实例时为 None）
+    state: Option<InstanceState>,
+}
+
+/// 配置文件中的实例与运行时实例的合集
+fn collect_instances(context: &ApiContext) -> MxuResult<Vec<InstanceSummary>> {
+    let saved = context.scheduler.saved_instances().unwrap_or_else(|e| {
+        debug!("Remote API could not read saved instances: {}", e);
+        Vec::new()
+    });
+
+    let mut runtimes = context.state.instances.lock()?;
+    let mut states: HashMap<String, InstanceState> = runtimes
+        .iter_mut()
+        .map(|(id, runtime)| (id.clone(), runtime.snapshot()))
+        .collect();
+
+    let mut summaries: Vec<InstanceSummary> = saved
+        .into_iter()
+        .map(|instance| InstanceSummary {
+            state: states.remove(&instance.id),
+            id: instance.id,
+            name: Some(instance.name),
+        })
+        .collect();
+    let mut unsaved: Vec<InstanceSummary> = states
+        .into_iter()
+        .map(|(id, state)| InstanceSummary {
+            id,
+            name: None,
+            state: Some(state),
+        })
+        .collect();
+    unsaved.sort_by(|a, b| a.id.cmp(&b.id));
+    summaries.extend(unsaved);
+
+    Ok(summaries)
+}
+
+fn find_instance(context: &ApiContext, id: &str) -> MxuResult<InstanceSummary> {
+    collect_instances(context)?
+        .into_iter()
+        .find(|instance| instance.id == id)
+        .ok_or_else(|| MxuError::instance_not_found(id))
+}
+
+async fn list_instances(
+    State(context): State<Arc<ApiContext>>,
+) -> MxuResult<Json<Vec<InstanceSummary>>> {
+    collect_instances(&context).map(Json)
+}
+
+async fn get_instance(
+    State(context): State<Arc<ApiContext>>,
+    Path(id): Path<String>,
+) -> MxuResult<Json<InstanceSummary>> {
+    find_instance(&context, &id).map(Json)
+}
+
+/// 已转交前端执行的请求
+#[derive(Serialize)]
+struct Accepted {
+    accepted: bool,
+}
+
+async fn start_tasks(
+    State(context): State<Arc<ApiContext>>,
+    Path(id): Path<String>,
+) -> MxuResult<(StatusCode, Json<Accepted>)> {
+    let instance = find_instance(&context, &id)?;
+    if instance.state.is_some_and(|s| s.is_running) {
+        return Err(MxuError::new(
+            ErrorCode::InstanceBusy,
+            Subsystem::Instance,
+            "Instance is already running",
+        )
+        .with_instance(&id));
+    }
+
+    info!("Remote API requested start for instance {}", id);
+    emit_task_request(&context, REMOTE_START_TASKS_EVENT, id);
+    Ok((StatusCode::ACCEPTED, Json(Accepted { accepted: true })))
+}
+
+async fn stop_tasks(
+    State(context): State<Arc<ApiContext>>,
+    Path(id): Path<String>,
+) -> MxuResult<(StatusCode, Json<Accepted>)> {
+    find_instance(&context, &id)?;
+
+    info!("Remote API requested stop for instance {}", id);
+    emit_task_request(&context, REMOTE_STOP_TASKS_EVENT, id);
+    Ok((StatusCode::ACCEPTED, Json(Accepted { accepted: true })))
+}
+
+fn emit_task_request(context: &ApiContext, event: &str, instance_id: String) {
+    let request = RemoteTaskRequest { instance_id };
+    context
+        .state
+        .event_bus
+        .emit(BusEvent::new(event, Some(&request.instance_id), &request));
+}
+
+/// 查询参数：`format`（png / jpeg / webp）、`quality`（JPEG 质量）、`max_width`
+async fn get_screenshot(
+    State(context): State<Arc<ApiContext>>,
+    Path(id): Path<String>,
+    Query(options): Query<ScreenshotOptions>,
+) -> MxuResult<Response> {
+    let state = context.state.clone();
+    let image = tokio::task::spawn_blocking(move || cached_screenshot(&state, &id, &options))
+        .await
+        .map_err(|e| MxuError::internal(e.to_string()))??;
+
+    Ok((
+        [(header::CONTENT_TYPE, HeaderValue::from_static(image.mime))],
+        image.data,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+struct InputQuery {
+    /// 等待动作完成后再返回
+    #[serde(default)]
+    wait: bool,
+    /// 等待超时（毫秒，默认 10 秒）
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct InputResult {
+    ctrl_id: i64,
+    /// 动作状态（仅 `wait=true`，超时时为 Pending / Running）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<TaskStatus>,
+}
+
+/// 请求体为 ControllerInput，如 `{"type": "Click", "x": 100, "y": 200}`
+async fn post_input(
+    State(context): State<Arc<ApiContext>>,
+    Path(id): Path<String>,
+    Query(query): Query<InputQuery>,
+    Json(request): Json<ControllerInput>,
+) -> MxuResult<Json<InputResult>> {
+    info!(
+        "Remote API requested {} for instance {}",
+        request.action(),
+        id
+    );
+    let state = context.state.clone();
+    let result = tokio::task::spawn_blocking(move || -> MxuResult<InputResult> {
+        let ctrl_id = input::post_input(&state, &id, &request)?;
+        let status = if query.wait {
+            let timeout = query
+                .timeout_ms
+                .map_or(DEFAULT_WAIT_TIMEOUT, Duration::from_millis);
+            Some(input::wait_input(&state, &id, ctrl_id, timeout)?)
+        } else {
+            None
+        };
+        Ok(InputResult { ctrl_id, status })
+    })
+    .await
+    .map_err(|e| MxuError::internal(e.to_string()))??;
+
+    Ok(Json(result))
+}
+
+// ============================================================================
+// 事件流
+// ============================================================================
+
+#[derive(Deserialize)]
+struct EventFilter {
+    /// 只推送指定实例的事件
+    instance: Option<String>,
+}
+
+async fn stream_events(
+    State(context): State<Arc<ApiContext>>,
+    Query(filter): Query<EventFilter>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade.on_upgrade(move |socket| forward_events(context, filter, socket))
+}
+
+/// 将总线事件以 JSON 文本消息推送给客户端，直到客户端断开或服务停止
+async fn forward_events(context: Arc<ApiContext>, filter: EventFilter, mut socket: WebSocket) {
+    let mut events = context.events.subscribe();
+    let mut shutdown = context.shutdown.clone();
+    debug!("Remote API event stream connected");
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Remote API event stream lagged, {} event(s) dropped", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if filter
+                    .instance
+                    .as_ref()
+                    .is_some_and(|id| event.instance_id.as_ref() != Some(id))
+                {
+                    continue;
+                }
+                let Ok(text) = serde_json::to_string(&event) else {
+                    continue;
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                // 客户端只需接收，收到关闭或连接出错时结束
+                match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+            _ = shutdown.changed() => {
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+        }
+    }
+
+    debug!("Remote API event stream disconnected");
+}
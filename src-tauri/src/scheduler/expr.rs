@@ -0,0 +1,837 @@
+//! 定时表达式
+//!
+//! 解析定时表达式并计算下一次触发时间，支持：
+//! - cron 表达式：`分 时 日 月 周`，支持 `*`、`a-b`、`*/n`、`a,b` 与月份/星期英文缩写，
+//!   以及 `@hourly`、`@daily`、`@weekly`、`@monthly`、`@yearly` 别名
+//! - 固定间隔：`@every 4h`、`@every 1h30m from 2026-10-20 04:00`
+//! - 指定时间（只触发一次）：`@at 2026-10-20 04:00, 2026-11-01 04:00`
+//!
+//! 排除时段内的触发时间会被跳过：
+//! - 时间范围：`2026-10-20 04:00 ~ 2026-10-20 10:00`
+//! - 每日时段（结束早于开始时跨零点，可限定开始的星期）：`04:00 ~ 06:00`、`wed 04:00 ~ 10:00`
+//!
+//! 所有时间按本地时区的墙上时间计算，夏令时切换时：
+//! 不存在的本地时间（时钟拨快）顺延到切换后的第一个有效时刻（如 02:00 拨快到 03:00 时，02:30 在
+//! 03:00 触发），重复的本地时间（时钟拨慢）只在第一次出现时触发
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Timelike,
+};
+
+/// cron 表达式向后查找的最大年数（2 月 29 日这类表达式最长 8 年才出现一次）
+const SEARCH_YEARS: i32 = 9;
+
+/// 跳过重复的本地时间或排除时段时的最大尝试次数
+const MAX_ATTEMPTS: usize = 1000;
+
+/// 固定间隔的最大值（天）
+const MAX_INTERVAL_DAYS: i64 = 366;
+
+/// 不存在的本地时间向后查找有效时刻的最大分钟数（时区调整最多跳过一整天）
+const MAX_GAP_MINUTES: usize = 24 * 60;
+
+/// 支持的日期时间格式
+const DATETIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"];
+
+/// 将本地时间转换为带时区的时间
+///
+/// 重复的本地时间取较早的一次；不存在的本地时间按分钟向后顺延到切换后的第一个有效时刻
+pub fn resolve_local<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> Option<DateTime<Tz>> {
+    let mut candidate = naive;
+    for _ in 0..=MAX_GAP_MINUTES {
+        match tz.from_local_datetime(&candidate) {
+            LocalResult::Single(dt) => return Some(dt),
+            // 两个结果的先后顺序不固定，显式取较早的时刻
+            LocalResult::Ambiguous(a, b) => return Some(a.min(b)),
+            LocalResult::None => {
+                candidate =
+                    candidate
+                        .date()
+                        .and_hms_opt(candidate.hour(), candidate.minute(), 0)?
+                        + Duration::minutes(1);
+            }
+        }
+    }
+    None
+}
+
+// ============================================================================
+// 触发规则
+// ============================================================================
+
+/// 定时表达式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleExpr {
+    Cron(CronSpec),
+    /// 从 `anchor` 开始按墙上时间每隔 `minutes` 分钟触发
+    Every {
+        minutes: i64,
+        anchor: NaiveDateTime,
+    },
+    /// 指定时间（升序、去重）
+    At(Vec<NaiveDateTime>),
+}
+
+impl ScheduleExpr {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Schedule expression is empty".to_string());
+        }
+        if let Some(rest) = strip_keyword(input, "@every") {
+            return parse_every(rest);
+        }
+        if let Some(rest) = strip_keyword(input, "@at") {
+            return parse_at(rest);
+        }
+
+        let cron = match input.to_ascii_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            alias if alias.starts_with('@') => {
+                return Err(format!("Unknown schedule expression '{}'", input));
+            }
+            _ => input,
+        };
+        CronSpec::parse(cron).map(Self::Cron)
+    }
+
+    /// 严格晚于 `after` 的下一次触发时间
+    pub fn next_after<Tz: TimeZone>(&self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let mut naive = after.naive_local();
+        for _ in 0..MAX_ATTEMPTS {
+            let candidate = self.next_naive_after(naive)?;
+            // 时钟拨慢后重复的本地时间会解析到第一次出现的时刻，拨快时多个本地时间会顺延到同一时刻，
+            // 可能不晚于 after，继续向后找
+            if let Some(fire_time) = resolve_local(&tz, candidate) {
+                if fire_time > after {
+                    return Some(fire_time);
+                }
+            }
+            naive = candidate;
+        }
+        None
+    }
+
+    /// 严格晚于 `after` 的下一个匹配的本地时间
+    fn next_naive_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Self::Cron(spec) => spec.next_after(after),
+            Self::Every { minutes, anchor } => {
+                if after < *anchor {
+                    return Some(*anchor);
+                }
+                let steps = (after - *anchor).num_minutes() / minutes + 1;
+                anchor.checked_add_signed(Duration::minutes(steps.checked_mul(*minutes)?))
+            }
+            Self::At(times) => times.iter().find(|t| **t > after).copied(),
+        }
+    }
+}
+
+/// 去掉开头的关键字（不区分大小写，关键字后必须是空白）
+fn strip_keyword<'a>(input: &'a str, keyword: &str) -> Option<&'a str> {
+    let head = input.get(..keyword.len())?;
+    let rest = &input[keyword.len()..];
+    (head.eq_ignore_ascii_case(keyword) && rest.starts_with(char::is_whitespace))
+        .then(|| rest.trim())
+}
+
+/// 解析 `@every <间隔> [from <起点>]`，未指定起点时从 1970-01-01 00:00 开始对齐
+fn parse_every(input: &str) -> Result<ScheduleExpr, String> {
+    // 只转换 ASCII 大小写，字节位置不变
+    let (interval, anchor) = match input.to_ascii_lowercase().find(" from ") {
+        Some(pos) => (&input[..pos], Some(input[pos + 6..].trim())),
+        None => (input, None),
+    };
+
+    let minutes = parse_interval(interval.trim())?;
+    let anchor = match anchor {
+        Some(anchor) => parse_datetime(anchor)?,
+        None => NaiveDateTime::default(),
+    };
+    Ok(ScheduleExpr::Every { minutes, anchor })
+}
+
+/// 解析 `4h`、`30m`、`1d`、`1h30m` 形式的间隔，返回分钟数
+fn parse_interval(input: &str) -> Result<i64, String> {
+    let invalid = || {
+        format!(
+            "Invalid interval '{}', expected e.g. 4h, 30m or 1h30m",
+            input
+        )
+    };
+
+    let mut total: i64 = 0;
+    let mut number = String::new();
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            'd' => 24 * 60,
+            'h' => 60,
+            'm' => 1,
+            _ => return Err(invalid()),
+        };
+        let value: i64 = number.parse().map_err(|_| invalid())?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(invalid)?;
+        number.clear();
+    }
+
+    if !number.is_empty() || total <= 0 {
+        return Err(invalid());
+    }
+    if total > MAX_INTERVAL_DAYS * 24 * 60 {
+        return Err(format!(
+            "Interval '{}' is too long (at most {} days)",
+            input, MAX_INTERVAL_DAYS
+        ));
+    }
+    Ok(total)
+}
+
+/// 解析 `@at <时间>[, <时间>...]`
+fn parse_at(input: &str) -> Result<ScheduleExpr, String> {
+    let mut times = input
+        .split(',')
+        .map(|s| parse_datetime(s.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    times.sort();
+    times.dedup();
+    Ok(ScheduleExpr::At(times))
+}
+
+/// 解析 `YYYY-MM-DD HH:MM`（只有日期时为当天 00:00）
+fn parse_datetime(input: &str) -> Result<NaiveDateTime, String> {
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| format!("Invalid date time '{}', expected YYYY-MM-DD HH:MM", input))
+}
+
+/// 解析 `HH:MM`
+fn parse_time(input: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(input, "%H:%M")
+        .map_err(|_| format!("Invalid time '{}', expected HH:MM", input))
+}
+
+// ============================================================================
+// cron
+// ============================================================================
+
+/// cron 字段定义
+struct CronField {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    /// 英文缩写，按下标对应取值（从 `min` 开始）
+    names: &'static [&'static str],
+}
+
+const MINUTE: CronField = CronField {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+
+const HOUR: CronField = CronField {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+
+const DAY: CronField = CronField {
+    name: "day",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+
+const MONTH: CronField = CronField {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &[
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ],
+};
+
+/// 星期（0 和 7 都表示周日）
+const WEEKDAY: CronField = CronField {
+    name: "weekday",
+    min: 0,
+    max: 7,
+    names: &["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+};
+
+/// 解析后的 cron 表达式，各字段为取值位图
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSpec {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// 日期字段是否为 `*`，日期与星期同时限定时任一命中即可（与标准 cron 一致）
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSpec {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = input.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "Cron expression '{}' must have 5 fields: minute hour day month weekday",
+                input
+            ));
+        };
+
+        Ok(Self {
+            minutes: parse_field(minute, &MINUTE)?,
+            hours: parse_field(hour, &HOUR)?,
+            days: parse_field(day, &DAY)?,
+            months: parse_field(month, &MONTH)?,
+            weekdays: parse_weekdays(weekday)?,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    /// 由星期（0-6，0 = 周日）和整点（0-23）生成，超出范围的值被忽略
+    pub fn from_grid(weekdays: &[u32], hours: &[u32]) -> Self {
+        let mask = |values: &[u32], max: u32| {
+            values
+                .iter()
+                .filter(|v| **v <= max)
+                .fold(0u64, |mask, v| mask | (1 << v))
+        };
+
+        Self {
+            minutes: 1,
+            hours: mask(hours, HOUR.max),
+            days: range_mask(DAY.min, DAY.max),
+            months: range_mask(MONTH.min, MONTH.max),
+            weekdays: mask(weekdays, 6),
+            any_day: true,
+            any_weekday: false,
+        }
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.any_day || self.any_weekday {
+            day && weekday
+        } else {
+            day || weekday
+        }
+    }
+
+    /// 严格晚于 `after` 的下一个匹配的本地时间（精确到分钟）
+    fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        if [
+            self.minutes,
+            self.hours,
+            self.days,
+            self.months,
+            self.weekdays,
+        ]
+        .contains(&0)
+        {
+            return None;
+        }
+
+        let mut t =
+            after.date().and_hms_opt(after.hour(), after.minute(), 0)? + Duration::minutes(1);
+        let last_year = t.year() + SEARCH_YEARS;
+        while t.year() <= last_year {
+            if !has(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.day_matches(t.date()) {
+                t = t.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if !has(self.hours, t.hour()) {
+                t = t.date().and_hms_opt(t.hour(), 0, 0)? + Duration::hours(1);
+            } else if !has(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn range_mask(start: u32, end: u32) -> u64 {
+    (start..=end).fold(0, |mask, v| mask | (1 << v))
+}
+
+/// 解析一个 cron 字段（`*`、`a`、`a-b`、`*/n`、`a-b/n`、`a/n`，逗号分隔）
+fn parse_field(input: &str, field: &CronField) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in input.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("Invalid step '{}' in {} field", step, field.name))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (start, end) = if range == "*" {
+            (field.min, field.max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, field)?, parse_value(end, field)?)
+        } else {
+            let value = parse_value(range, field)?;
+            // `a/n` 表示从 a 开始到最大值每隔 n
+            (value, if step.is_some() { field.max } else { value })
+        };
+        if start > end {
+            return Err(format!("Invalid range '{}' in {} field", range, field.name));
+        }
+
+        mask = (start..=end)
+            .step_by(step.unwrap_or(1))
+            .fold(mask, |mask, v| mask | (1 << v));
+    }
+    Ok(mask)
+}
+
+fn parse_value(input: &str, field: &CronField) -> Result<u32, String> {
+    let value = input.parse::<u32>().ok().or_else(|| {
+        field
+            .names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(input))
+            .map(|i| field.min + i as u32)
+    });
+    value
+        .filter(|v| (field.min..=field.max).contains(v))
+        .ok_or_else(|| {
+            format!(
+                "Invalid {} '{}', expected {}-{}",
+                field.name, input, field.min, field.max
+            )
+        })
+}
+
+/// 解析星期字段，7 归为 0（周日）
+fn parse_weekdays(input: &str) -> Result<u64, String> {
+    let mask = parse_field(input, &WEEKDAY)?;
+    Ok(if has(mask, 7) {
+        (mask & !(1 << 7)) | 1
+    } else {
+        mask
+    })
+}
+
+// ============================================================================
+// 排除时段
+// ============================================================================
+
+/// 排除时段（左闭右开）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exclusion {
+    /// 指定时间范围
+    Range {
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    },
+    /// 每日时段，`end <= start` 时跨零点；`weekdays` 限定时段开始的星期
+    Daily {
+        weekdays: u64,
+        start: NaiveTime,
+        end: NaiveTime,
+    },
+}
+
+impl Exclusion {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (left, right) = input.split_once('~').ok_or_else(|| {
+            format!(
+                "Invalid exclusion window '{}', expected 'start ~ end'",
+                input.trim()
+            )
+        })?;
+        let (left, right) = (left.trim(), right.trim());
+
+        if let (Ok(start), Ok(end)) = (parse_datetime(left), parse_datetime(right)) {
+            if end <= start {
+                return Err(format!(
+                    "Exclusion window '{}' ends before it starts",
+                    input.trim()
+                ));
+            }
+            return Ok(Self::Range { start, end });
+        }
+
+        // 每日时段，可带星期前缀（如 `mon-fri 23:00 ~ 01:00`）
+        let (weekdays, start) = match left.rsplit_once(char::is_whitespace) {
+            Some((days, time)) => (parse_weekdays(days.trim())?, time),
+            None => (range_mask(0, 6), left),
+        };
+        let start = parse_time(start)?;
+        let end = parse_time(right)?;
+        if start == end {
+            return Err(format!("Exclusion window '{}' is empty", input.trim()));
+        }
+        Ok(Self::Daily {
+            weekdays,
+            start,
+            end,
+        })
+    }
+
+    /// 本地时间落在时段内时返回时段的结束时间
+    fn window_end(&self, t: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Self::Range { start, end } => (*start <= t && t < *end).then_some(*end),
+            Self::Daily {
+                weekdays,
+                start,
+                end,
+            } => {
+                let on = |date: NaiveDate| has(*weekdays, date.weekday().num_days_from_sunday());
+                let (date, time) = (t.date(), t.time());
+                if start < end {
+                    (on(date) && *start <= time && time < *end).then(|| date.and_time(*end))
+                } else if time >= *start && on(date) {
+                    Some(date.succ_opt()?.and_time(*end))
+                } else if time < *end && on(date.pred_opt()?) {
+                    Some(date.and_time(*end))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 完整规则
+// ============================================================================
+
+/// 定时表达式与排除时段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    expr: ScheduleExpr,
+    exclusions: Vec<Exclusion>,
+}
+
+impl Schedule {
+    /// 解析表达式与排除时段（空白的排除时段被忽略）
+    pub fn parse(expression: &str, exclusions: &[String]) -> Result<Self, String> {
+        Self::with_exclusions(ScheduleExpr::parse(expression)?, exclusions)
+    }
+
+    pub fn with_exclusions(expr: ScheduleExpr, exclusions: &[String]) -> Result<Self, String> {
+        let exclusions = exclusions
+            .iter()
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| Exclusion::parse(s))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { expr, exclusions })
+    }
+
+    /// 严格晚于 `after` 且不在排除时段内的下一次触发时间
+    pub fn next_after<Tz: TimeZone>(&self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let mut cursor = after;
+        for _ in 0..MAX_ATTEMPTS {
+            let fire_time = self.expr.next_after(cursor.clone())?;
+            let naive = fire_time.naive_local();
+            let Some(end) = self.exclusions.iter().find_map(|e| e.window_end(naive)) else {
+                return Some(fire_time);
+            };
+            // 直接跳到时段结束（结束时刻本身可以触发）
+            cursor = resolve_local(&tz, end)
+                .map(|end| end - Duration::seconds(1))
+                .filter(|t| *t > fire_time)
+                .unwrap_or(fire_time);
+        }
+        None
+    }
+
+    /// 从 `after` 开始的接下来 `count` 次触发时间
+    pub fn preview<Tz: TimeZone>(&self, after: DateTime<Tz>, count: usize) -> Vec<DateTime<Tz>> {
+        std::iter::successors(self.next_after(after), |t| self.next_after(t.clone()))
+            .take(count)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    /// 测试用时区：UTC+1，2026-03-29 01:00 UTC 到 2026-10-25 01:00 UTC 为夏令时 UTC+2
+    /// （本地时间 03-29 02:00 拨快到 03:00，10-25 03:00 拨慢到 02:00）
+    #[derive(Debug, Clone, Copy)]
+    struct DstZone;
+
+    impl DstZone {
+        fn offset_at(utc: NaiveDateTime) -> FixedOffset {
+            let summer = datetime("2026-03-29 01:00")..datetime("2026-10-25 01:00");
+            let hours = if summer.contains(&utc) { 2 } else { 1 };
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for DstZone {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            DstZone
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            // 重复的本地时间按先后顺序返回（夏令时的偏移在前）
+            let offsets: Vec<FixedOffset> = [2, 1]
+                .into_iter()
+                .map(|hours| FixedOffset::east_opt(hours * 3600).unwrap())
+                .filter(|offset| Self::offset_at(*local - *offset) == *offset)
+                .collect();
+            match offsets[..] {
+                [offset] => LocalResult::Single(offset),
+                [a, b] => LocalResult::Ambiguous(a, b),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            Self::offset_at(utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset_at(*utc)
+        }
+    }
+
+    fn datetime(input: &str) -> NaiveDateTime {
+        parse_datetime(input).unwrap()
+    }
+
+    fn at<Tz: TimeZone>(tz: &Tz, input: &str) -> DateTime<Tz> {
+        tz.from_local_datetime(&datetime(input)).earliest().unwrap()
+    }
+
+    /// 从 `after` 开始的触发时间（本地时间，带 UTC 偏移便于区分重复的本地时间）
+    fn fire_times<Tz: TimeZone>(
+        schedule: &Schedule,
+        after: DateTime<Tz>,
+        count: usize,
+    ) -> Vec<String>
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        schedule
+            .preview(after, count)
+            .iter()
+            .map(|t| t.format("%Y-%m-%d %H:%M %:z").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn cron_day_and_weekday_match_either() {
+        // 日期与星期同时限定：每月 1 日或每周一
+        let schedule = Schedule::parse("0 4 1 * mon", &[]).unwrap();
+        assert_eq!(
+            fire_times(&schedule, at(&Utc, "2026-10-24 00:00"), 3),
+            [
+                "2026-10-26 04:00 +00:00",
+                "2026-11-01 04:00 +00:00",
+                "2026-11-02 04:00 +00:00",
+            ]
+        );
+
+        // 其中一个为 `*` 时只按另一个匹配
+        let cases = [
+            ("0 4 * * mon", "2026-10-26 04:00 +00:00"),
+            ("0 4 1 * *", "2026-11-01 04:00 +00:00"),
+            ("0 4 */5 * mon", "2026-10-26 04:00 +00:00"),
+        ];
+        for (expression, expected) in cases {
+            let schedule = Schedule::parse(expression, &[]).unwrap();
+            assert_eq!(
+                fire_times(&schedule, at(&Utc, "2026-10-24 00:00"), 1),
+                [expected],
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn nonexistent_local_time_fires_at_switch() {
+        let schedule = Schedule::parse("30 2 * * *", &[]).unwrap();
+        assert_eq!(
+            fire_times(&schedule, at(&DstZone, "2026-03-28 12:00"), 2),
+            ["2026-03-29 03:00 +02:00", "2026-03-30 02:30 +02:00"]
+        );
+
+        // 落在同一切换时刻的多个本地时间只触发一次
+        let schedule = Schedule::parse("*/20 2-3 * * *", &[]).unwrap();
+        assert_eq!(
+            fire_times(&schedule, at(&DstZone, "2026-03-29 01:50"), 4),
+            [
+                "2026-03-29 03:00 +02:00",
+                "2026-03-29 03:20 +02:00",
+                "2026-03-29 03:40 +02:00",
+                "2026-03-30 02:00 +02:00",
+            ]
+        );
+
+        let every = Schedule::parse("@every 1d from 2026-03-20 02:30", &[]).unwrap();
+        assert_eq!(
+            fire_times(&every, at(&DstZone, "2026-03-28 12:00"), 2),
+            ["2026-03-29 03:00 +02:00", "2026-03-30 02:30 +02:00"]
+        );
+    }
+
+    #[test]
+    fn ambiguous_local_time_fires_once() {
+        let schedule = Schedule::parse("30 2 * * *", &[]).unwrap();
+        assert_eq!(
+            fire_times(&schedule, at(&DstZone, "2026-10-24 12:00"), 2),
+            ["2026-10-25 02:30 +02:00", "2026-10-26 02:30 +01:00"]
+        );
+
+        // 从第一次出现之后开始查找时跳过第二次出现
+        let first = at(&DstZone, "2026-10-25 02:30");
+        assert_eq!(
+            fire_times(&schedule, first + Duration::minutes(10), 1),
+            ["2026-10-26 02:30 +01:00"]
+        );
+
+        // 固定间隔按墙上时间计算，重复的一小时内不重复触发
+        let every = Schedule::parse("@every 30m", &[]).unwrap();
+        assert_eq!(
+            fire_times(&every, at(&DstZone, "2026-10-25 02:00"), 3),
+            [
+                "2026-10-25 02:30 +02:00",
+                "2026-10-25 03:00 +01:00",
+                "2026-10-25 03:30 +01:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn exclusion_window_end_is_not_excluded() {
+        let schedule = Schedule::parse(
+            "0 * * * *",
+            &["2026-10-20 04:00 ~ 2026-10-20 06:00".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            fire_times(&schedule, at(&Utc, "2026-10-20 03:30"), 2),
+            ["2026-10-20 06:00 +00:00", "2026-10-20 07:00 +00:00"]
+        );
+
+        // 跨零点的每日时段，限定开始的星期（2026-10-21 为周三）
+        let schedule = Schedule::parse("0 */2 * * *", &["wed 23:00 ~ 04:00".to_string()]).unwrap();
+        assert_eq!(
+            fire_times(&schedule, at(&Utc, "2026-10-21 21:00"), 3),
+            [
+                "2026-10-21 22:00 +00:00",
+                "2026-10-22 04:00 +00:00",
+                "2026-10-22 06:00 +00:00",
+            ]
+        );
+        assert_eq!(
+            fire_times(&schedule, at(&Utc, "2026-10-22 21:00"), 2),
+            ["2026-10-22 22:00 +00:00", "2026-10-23 00:00 +00:00"]
+        );
+
+        // 排除时段覆盖全部触发时间时没有下一次
+        let once = Schedule::parse(
+            "@at 2026-10-20 05:00",
+            &["2026-10-20 04:00 ~ 2026-10-20 06:00".to_string()],
+        )
+        .unwrap();
+        assert!(once.next_after(at(&Utc, "2026-10-20 00:00")).is_none());
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        let invalid = [
+            "",
+            "@fortnightly",
+            "0 4 * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "0 0 0 * *",
+            "0 0 * 13 *",
+            "0 0 * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "0 0 * * funday",
+            "@every 0m",
+            "@every 5x",
+            "@every 1h30",
+            "@every 400d",
+            "@every 1h from tomorrow",
+            "@at 2026-13-01 04:00",
+            "@at 2026-10-20 04:00, later",
+        ];
+        for expression in invalid {
+            assert!(
+                ScheduleExpr::parse(expression).is_err(),
+                "'{}' should be rejected",
+                expression
+            );
+        }
+
+        let invalid_exclusions = [
+            "04:00 06:00",
+            "2026-10-20 10:00 ~ 2026-10-20 04:00",
+            "04:00 ~ 04:00",
+            "25:00 ~ 04:00",
+            "funday 04:00 ~ 06:00",
+        ];
+        for exclusion in invalid_exclusions {
+            assert!(
+                Exclusion::parse(exclusion).is_err(),
+                "'{}' should be rejected",
+                exclusion
+            );
+        }
+        assert!(Schedule::parse("@daily", &["04:00 ~ 04:00".to_string()]).is_err());
+        assert!(Schedule::parse("@daily", &["  ".to_string()]).is_ok());
+    }
+}
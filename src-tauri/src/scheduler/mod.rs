@@ -0,0 +1,525 @@
+//! 定时执行
+//!
+//! 在 Rust 端执行实例的定时策略，不依赖前端计时器（WebView 刷新、被节流或隐藏到托盘时仍然生效）。
+//! 调度线程读取 interface.json 与 mxu 配置文件（文件修改后自动重新加载），到达触发时间后
+//! 由后端通过 `run_instance`（连接控制器 → 加载资源 → maa_start_tasks）执行实例的任务列表，
+//! 不依赖 WebView 启动任务。schedule-fired / schedule-finished 事件仅用于通知前端同步界面状态，
+//! 实例正在运行、已暂停或错过触发时间时发出 schedule-skipped 事件
+//!
+//! 模块结构：
+//! - `expr`: 定时表达式解析与触发时间计算（按本地时区处理夏令时）
+//! - `policy`: SchedulePolicy 到触发规则的转换
+
+pub mod expr;
+pub mod policy;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local};
+use log::{debug, error, info, warn};
+use serde::Serialize;
+
+use crate::commands::event_bus::BusEvent;
+use crate::commands::types::{MaaState, PauseState};
+use crate::commands::utils::{get_app_data_dir, get_exe_directory};
+use crate::headless::config::{
+    config_file_path, load_config, MxuConfig, SavedInstance, SchedulePolicy,
+};
+use crate::headless::interface::{load_interface, ProjectInterface};
+use crate::headless::runner::run_instance;
+use expr::Schedule;
+
+/// 定时策略触发事件名（仅通知，任务由调度器启动）
+pub const SCHEDULE_FIRED_EVENT: &str = "schedule-fired";
+
+/// 定时策略跳过事件名
+pub const SCHEDULE_SKIPPED_EVENT: &str = "schedule-skipped";
+
+/// 定时执行结束事件名
+pub const SCHEDULE_FINISHED_EVENT: &str = "schedule-finished";
+
+/// 错过触发时间后仍然补执行的宽限期（秒），超过则视为错过（如系统休眠期间）
+const MISFIRE_GRACE_SECS: i64 = 5 * 60;
+
+/// 调度线程最长休眠时间（用于感知配置文件修改与系统时间调整）
+const MAX_SLEEP: Duration = Duration::from_secs(30);
+
+// ============================================================================
+// 事件与查询类型
+// ============================================================================
+
+/// 跳过原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// 实例正在运行任务或已暂停
+    AlreadyRunning,
+    /// 超过宽限期才检查到触发时间
+    Missed,
+}
+
+/// 定时事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleEvent {
+    pub instance_id: String,
+    pub instance_name: String,
+    pub policy_id: String,
+    pub policy_name: String,
+    /// 计划触发时间（RFC 3339）
+    pub fire_time: String,
+    /// 跳过原因（仅 schedule-skipped）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<SkipReason>,
+    /// 是否全部任务成功（仅 schedule-finished）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    /// 执行失败原因（仅 schedule-finished）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ScheduleEvent {
+    fn new(instance: &SavedInstance, policy: &SchedulePolicy, fire_time: DateTime<Local>) -> Self {
+        Self {
+            instance_id: instance.id.clone(),
+            instance_name: instance.name.clone(),
+            policy_id: policy.id.clone(),
+            policy_name: policy.name.clone(),
+            fire_time: fire_time.to_rfc3339(),
+            reason: None,
+            success: None,
+            error: None,
+        }
+    }
+}
+
+/// 调度条目（供前端展示下次触发时间）
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleEntry {
+    pub instance_id: String,
+    pub instance_name: String,
+    pub policy_id: String,
+    pub policy_name: String,
+    /// 下次触发时间（RFC 3339），没有后续触发时间或规则无效时为 None
+    pub next_fire: Option<String>,
+    /// 表达式或排除时段无效时的错误信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// 调度器
+// ============================================================================
+
+/// 配置来源
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleSource {
+    /// interface.json 路径（默认：exe 目录下的 interface.json）
+    pub interface_path: Option<PathBuf>,
+    /// 配置文件路径（默认：数据目录/config/mxu-{项目名}.json）
+    pub config_path: Option<PathBuf>,
+}
+
+/// 已加载的配置
+struct LoadedConfig {
+    pi: ProjectInterface,
+    base_path: PathBuf,
+    config: MxuConfig,
+    interface_path: PathBuf,
+    config_path: PathBuf,
+    interface_mtime: Option<SystemTime>,
+    config_mtime: Option<SystemTime>,
+    /// 各策略解析后的触发规则，键为 (实例 ID, 策略 ID)
+    schedules: HashMap<(String, String), Result<Schedule, String>>,
+}
+
+impl LoadedConfig {
+    /// 文件自加载后是否被修改
+    fn is_stale(&self) -> bool {
+        modified_time(&self.interface_path) != self.interface_mtime
+            || modified_time(&self.config_path) != self.config_mtime
+    }
+
+    fn schedule(
+        &self,
+        instance: &SavedInstance,
+        policy: &SchedulePolicy,
+    ) -> Option<&Result<Schedule, String>> {
+        self.schedules
+            .get(&(instance.id.clone(), policy.id.clone()))
+    }
+}
+
+#[derive(Default)]
+struct SchedulerInner {
+    /// 调度线程是否在运行
+    running: bool,
+    /// 请求停止调度线程
+    stop: bool,
+    /// 请求下次检查时强制重新加载配置
+    reload: bool,
+    loaded: Option<Arc<LoadedConfig>>,
+    /// 上一次检查的时间，触发时间落在 (last_check, now] 内的策略会被执行
+    last_check: Option<DateTime<Local>>,
+    /// 正在执行定时任务的实例
+    active: HashSet<String>,
+}
+
+/// 定时调度器
+pub struct Scheduler {
+    state: Arc<MaaState>,
+    source: ScheduleSource,
+    inner: Mutex<SchedulerInner>,
+    wakeup: Condvar,
+}
+
+impl Scheduler {
+    pub fn new(state: Arc<MaaState>, source: ScheduleSource) -> Arc<Self> {
+        Arc::new(Self {
+            state,
+            source,
+            inner: Mutex::new(SchedulerInner::default()),
+            wakeup: Condvar::new(),
+        })
+    }
+
+    /// 启动调度线程（已启动时忽略）
+    pub fn start(self: &Arc<Self>) {
+        {
+            let Ok(mut inner) = self.inner.lock() else {
+                return;
+            };
+            if inner.running {
+                return;
+            }
+            inner.running = true;
+            inner.stop = false;
+            inner.last_check = None;
+        }
+
+        let scheduler = self.clone();
+        thread::spawn(move || scheduler.run_loop());
+        info!("Scheduler started");
+    }
+
+    /// 停止调度线程（已触发的定时任务继续执行）
+    pub fn stop(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.stop = true;
+        }
+        self.wakeup.notify_all();
+    }
+
+    /// 重新加载配置并立即重新计算触发时间
+    pub fn reload(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.reload = true;
+        }
+        self.wakeup.notify_all();
+    }
+
+    /// 所有实例的调度条目
+    pub fn entries(&self) -> Result<Vec<ScheduleEntry>, String> {
+        let loaded = self.ensure_loaded()?;
+        let now = Local::now();
+
+        let mut entries = Vec::new();
+        for instance in &loaded.config.instances {
+            for policy in instance.schedule_policies.iter().filter(|p| p.enabled) {
+                let (next_fire, error) = match loaded.schedule(instance, policy) {
+                    Some(Ok(schedule)) => (schedule.next_after(now).map(|t| t.to_rfc3339()), None),
+                    Some(Err(e)) => (None, Some(e.clone())),
+                    None => (None, None),
+                };
+                entries.push(ScheduleEntry {
+                    instance_id: instance.id.clone(),
+                    instance_name: instance.name.clone(),
+                    policy_id: policy.id.clone(),
+                    policy_name: policy.name.clone(),
+                    next_fire,
+                    error,
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 配置文件中保存的实例
+    pub fn saved_instances(&self) -> Result<Vec<SavedInstance>, String> {
+        Ok(self.ensure_loaded()?.config.instances.clone())
+    }
+
+    fn run_loop(self: Arc<Self>) {
+        // 加载失败时只在错误变化时输出警告，避免每次检查都刷日志
+        let mut last_error: Option<String> = None;
+        loop {
+            let next_wake = match self.ensure_loaded() {
+                Ok(loaded) => {
+                    last_error = None;
+                    self.tick(&loaded)
+                }
+                Err(e) => {
+                    if last_error.as_ref() != Some(&e) {
+                        warn!("Scheduler failed to load config: {}", e);
+                        last_error = Some(e);
+                    }
+                    None
+                }
+            };
+
+            let sleep = next_wake
+                .and_then(|t| (t - Local::now()).to_std().ok())
+                .map_or(MAX_SLEEP, |d| d.min(MAX_SLEEP));
+
+            let Ok(inner) = self.inner.lock() else {
+                break;
+            };
+            let Ok((mut inner, _)) = self
+                .wakeup
+                .wait_timeout_while(inner, sleep, |i| !i.stop && !i.reload)
+            else {
+                break;
+            };
+            if inner.stop {
+                inner.running = false;
+                info!("Scheduler stopped");
+                break;
+            }
+        }
+    }
+
+    /// 加载配置（首次调用、请求重新加载或文件被修改时重新读取）
+    fn ensure_loaded(&self) -> Result<Arc<LoadedConfig>, String> {
+        {
+            let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+            if let Some(loaded) = &inner.loaded {
+                if !inner.reload && !loaded.is_stale() {
+                    return Ok(loaded.clone());
+                }
+            }
+            inner.reload = false;
+        }
+
+        let loaded = Arc::new(self.load()?);
+        debug!(
+            "Scheduler loaded config: {} instance(s) from {}",
+            loaded.config.instances.len(),
+            loaded.config_path.display()
+        );
+        self.inner.lock().map_err(|e| e.to_string())?.loaded = Some(loaded.clone());
+        Ok(loaded)
+    }
+
+    fn load(&self) -> Result<LoadedConfig, String> {
+        let interface_path = match &self.source.interface_path {
+            Some(path) => path.clone(),
+            None => get_exe_directory()?.join("interface.json"),
+        };
+        let interface_mtime = modified_time(&interface_path);
+        let pi = load_interface(&interface_path)?;
+        let base_path = interface_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let config_path = match &self.source.config_path {
+            Some(path) => path.clone(),
+            None => config_file_path(&get_app_data_dir()?, &pi.name),
+        };
+        let config_mtime = modified_time(&config_path);
+        // 尚未保存过配置时视为没有实例
+        let config = if config_path.exists() {
+            load_config(&config_path)?
+        } else {
+            MxuConfig::default()
+        };
+
+        let mut schedules = HashMap::new();
+        for instance in &config.instances {
+            for policy in &instance.schedule_policies {
+                let schedule = policy.schedule();
+                if let Err(e) = &schedule {
+                    warn!(
+                        "Invalid schedule policy \"{}\" of instance \"{}\": {}",
+                        policy.name, instance.name, e
+                    );
+                }
+                schedules.insert((instance.id.clone(), policy.id.clone()), schedule);
+            }
+        }
+
+        Ok(LoadedConfig {
+            pi,
+            base_path,
+            config,
+            interface_path,
+            config_path,
+            interface_mtime,
+            config_mtime,
+            schedules,
+        })
+    }
+
+    /// 执行到期的策略，返回下一次触发时间
+    fn tick(self: &Arc<Self>, loaded: &Arc<LoadedConfig>) -> Option<DateTime<Local>> {
+        let now = Local::now();
+        let last_check = {
+            let Ok(mut inner) = self.inner.lock() else {
+                return None;
+            };
+            inner.last_check.replace(now).unwrap_or(now)
+        };
+
+        let mut next_wake: Option<DateTime<Local>> = None;
+        for instance in &loaded.config.instances {
+            let policies: Vec<(&SchedulePolicy, &Schedule)> = instance
+                .schedule_policies
+                .iter()
+                .filter(|p| p.enabled)
+                .filter_map(|p| match loaded.schedule(instance, p) {
+                    Some(Ok(schedule)) => Some((p, schedule)),
+                    _ => None,
+                })
+                .collect();
+
+            // 一个实例只执行第一个命中的策略，避免重复启动
+            let due = policies.iter().find_map(|(policy, schedule)| {
+                schedule
+                    .next_after(last_check)
+                    .filter(|t| *t <= now)
+                    .map(|t| (*policy, t))
+            });
+            if let Some((policy, fire_time)) = due {
+                self.fire(loaded, instance, policy, fire_time, now);
+            }
+
+            for (_, schedule) in policies {
+                if let Some(t) = schedule.next_after(now) {
+                    next_wake = Some(next_wake.map_or(t, |n| n.min(t)));
+                }
+            }
+        }
+
+        next_wake
+    }
+
+    /// 触发策略：检查实例状态后在后台线程执行实例任务
+    fn fire(
+        self: &Arc<Self>,
+        loaded: &Arc<LoadedConfig>,
+        instance: &SavedInstance,
+        policy: &SchedulePolicy,
+        fire_time: DateTime<Local>,
+        now: DateTime<Local>,
+    ) {
+        let event = ScheduleEvent::new(instance, policy, fire_time);
+
+        if (now - fire_time).num_seconds() > MISFIRE_GRACE_SECS {
+            warn!(
+                "Schedule missed: instance \"{}\", policy \"{}\" at {}",
+                instance.name, policy.name, event.fire_time
+            );
+            self.emit_skipped(event, SkipReason::Missed);
+            return;
+        }
+
+        if self.is_instance_busy(&instance.id) {
+            info!(
+                "Schedule skipped, instance \"{}\" is already running (policy \"{}\")",
+                instance.name, policy.name
+            );
+            self.emit_skipped(event, SkipReason::AlreadyRunning);
+            return;
+        }
+
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.active.insert(instance.id.clone());
+        }
+        info!(
+            "Schedule fired: instance \"{}\", policy \"{}\"",
+            instance.name, policy.name
+        );
+        self.state.event_bus.emit(BusEvent::new(
+            SCHEDULE_FIRED_EVENT,
+            Some(&instance.id),
+            &event,
+        ));
+
+        let scheduler = self.clone();
+        let loaded = loaded.clone();
+        let instance = instance.clone();
+        thread::spawn(move || {
+            let result = tauri::async_runtime::block_on(run_instance(
+                &scheduler.state,
+                &loaded.pi,
+                &loaded.base_path,
+                &instance,
+                &loaded.config.settings,
+            ));
+
+            let mut event = event;
+            match result {
+                Ok(summary) => {
+                    info!(
+                        "Scheduled run finished: instance \"{}\", all succeeded: {}",
+                        instance.name,
+                        summary.all_succeeded()
+                    );
+                    event.success = Some(summary.all_succeeded());
+                }
+                Err(e) => {
+                    error!(
+                        "Scheduled run failed: instance \"{}\": {}",
+                        instance.name, e
+                    );
+                    event.success = Some(false);
+                    event.error = Some(e);
+                }
+            }
+
+            if let Ok(mut inner) = scheduler.inner.lock() {
+                inner.active.remove(&instance.id);
+            }
+            scheduler.state.event_bus.emit(BusEvent::new(
+                SCHEDULE_FINISHED_EVENT,
+                Some(&instance.id),
+                &event,
+            ));
+        });
+    }
+
+    fn emit_skipped(&self, mut event: ScheduleEvent, reason: SkipReason) {
+        event.reason = Some(reason);
+        let instance_id = event.instance_id.clone();
+        self.state.event_bus.emit(BusEvent::new(
+            SCHEDULE_SKIPPED_EVENT,
+            Some(&instance_id),
+            &event,
+        ));
+    }
+
+    /// 实例是否正在执行任务（定时任务或前端启动的任务）或已暂停
+    fn is_instance_busy(&self, instance_id: &str) -> bool {
+        if self
+            .inner
+            .lock()
+            .is_ok_and(|inner| inner.active.contains(instance_id))
+        {
+            return true;
+        }
+        self.state.instances.lock().is_ok_and(|instances| {
+            instances.get(instance_id).is_some_and(|i| {
+                i.pause_state != PauseState::None || i.tasker.as_ref().is_some_and(|t| t.running())
+            })
+        })
+    }
+}
+
+/// 文件修改时间（文件不存在时为 None）
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,18 @@
+//! 定时策略
+//!
+//! 将前端保存的 SchedulePolicy 转换为触发规则：配置了表达式时使用表达式，
+//! 否则由星期与整点生成等价的 cron 表达式
+
+use super::expr::{CronSpec, Schedule, ScheduleExpr};
+use crate::headless::config::SchedulePolicy;
+
+impl SchedulePolicy {
+    /// 策略的触发规则，表达式或排除时段无效时返回错误
+    pub fn schedule(&self) -> Result<Schedule, String> {
+        let expr = match self.expression.as_deref().map(str::trim) {
+            Some(expression) if !expression.is_empty() => ScheduleExpr::parse(expression)?,
+            _ => ScheduleExpr::Cron(CronSpec::from_grid(&self.weekdays, &self.hours)),
+        };
+        Schedule::with_exclusions(expr, &self.exclusions)
+    }
+}
@@ -0,0 +1,427 @@
+//! 实时截图推流
+//!
+//! 每个实例最多一个截图线程，按订阅者中最短的帧间隔截图（任务运行时直接读取任务执行器更新的
+//! 缓存画面），画面未变化时不推送，编码后经事件总线发出 maa-screen-frame 事件。截图面板、
+//! 中控台等多个查看者共享同一个截图循环，不再各自发起截图；编码选项按查看者分别保存，
+//! 每种编码选项编码一次，事件中带上接收该帧的查看者
+//!
+//! 背压：同一时间只有一帧在途，查看者确认（ack）后才截取下一帧，处理较慢的查看者总是收到
+//! 最新画面而不会积压；超过确认超时视为已确认（兼容不回复确认的查看者）
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use log::{debug, info, warn};
+use serde::Serialize;
+
+use crate::commands::error::{MxuError, MxuResult};
+use crate::commands::event_bus::BusEvent;
+use crate::commands::screenshot::{self, ScreenshotOptions};
+use crate::commands::types::MaaState;
+
+/// 截图帧事件名
+pub const SCREEN_FRAME_EVENT: &str = "maa-screen-frame";
+
+/// 截图流停止事件名（仅在截图线程因断开或失败而退出时发出）
+pub const SCREEN_STREAM_STOPPED_EVENT: &str = "maa-screen-stream-stopped";
+
+/// 等待查看者确认的最长时间，超过后继续推送
+const ACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 两次截图的最小间隔（不限帧率时避免空转）
+const MIN_INTERVAL: Duration = Duration::from_millis(33);
+
+/// 连续截图失败次数上限，达到后停止截图流
+const MAX_CONSECUTIVE_FAILURES: u32 = 20;
+
+// ============================================================================
+// 事件类型
+// ============================================================================
+
+/// 截图帧事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenFrameEvent {
+    pub instance_id: String,
+    /// 帧序号（从 1 开始，确认时回传）
+    pub seq: u64,
+    /// 编码后的画面（data URL）
+    pub data: String,
+    /// 订阅了这一编码选项的查看者
+    pub viewers: Vec<String>,
+    /// 原始画面尺寸（截图坐标系，用于将预览上的位置换算为输入坐标）
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 截图流停止原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// 实例已销毁或控制器已断开
+    Disconnected,
+    /// 截图连续失败
+    CaptureFailed,
+}
+
+/// 截图流停止事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenStreamStoppedEvent {
+    pub instance_id: String,
+    pub reason: StopReason,
+}
+
+// ============================================================================
+// 截图流
+// ============================================================================
+
+/// 查看者的订阅参数
+struct Viewer {
+    /// 期望的帧间隔
+    interval: Duration,
+    options: ScreenshotOptions,
+}
+
+#[derive(Default)]
+struct StreamInner {
+    /// 查看者 ID → 订阅参数
+    viewers: HashMap<String, Viewer>,
+    /// 已推送但尚未确认的帧（序号与推送时间）
+    in_flight: Option<(u64, Instant)>,
+    /// 有新查看者加入，下一帧即使画面未变化也推送
+    force: bool,
+}
+
+/// 下一次截图的参数
+struct Turn {
+    /// 各编码选项及其查看者
+    outputs: Vec<(ScreenshotOptions, Vec<String>)>,
+    interval: Duration,
+    force: bool,
+}
+
+/// 单个实例的截图流
+#[derive(Default)]
+struct Stream {
+    inner: Mutex<StreamInner>,
+    wakeup: Condvar,
+}
+
+impl Stream {
+    /// 等待到截图时间且上一帧已确认（没有查看者时返回 None）
+    fn wait_turn(&self, next_capture: Instant) -> Option<Turn> {
+        let mut inner = self.inner.lock().ok()?;
+        loop {
+            if inner.viewers.is_empty() {
+                return None;
+            }
+
+            let now = Instant::now();
+            if let Some((seq, sent)) = inner.in_flight {
+                if now.duration_since(sent) >= ACK_TIMEOUT {
+                    debug!(
+                        "[ScreenStream] Frame #{} not acknowledged, skipping ack",
+                        seq
+                    );
+                    inner.in_flight = None;
+                }
+            }
+            // 新查看者不等待当前帧间隔
+            let capture_at = if inner.force { now } else { next_capture };
+            let deadline = match inner.in_flight {
+                Some((_, sent)) => capture_at.max(sent + ACK_TIMEOUT),
+                None => capture_at,
+            };
+            if deadline <= now {
+                let interval = inner
+                    .viewers
+                    .values()
+                    .map(|v| v.interval)
+                    .min()
+                    .unwrap_or_default();
+                let mut outputs: Vec<(ScreenshotOptions, Vec<String>)> = Vec::new();
+                for (viewer_id, viewer) in &inner.viewers {
+                    match outputs.iter_mut().find(|(o, _)| *o == viewer.options) {
+                        Some((_, viewers)) => viewers.push(viewer_id.clone()),
+                        None => outputs.push((viewer.options.clone(), vec![viewer_id.clone()])),
+                    }
+                }
+                return Some(Turn {
+                    outputs,
+                    interval: interval.max(MIN_INTERVAL),
+                    force: std::mem::take(&mut inner.force),
+                });
+            }
+            inner = self.wakeup.wait_timeout(inner, deadline - now).ok()?.0;
+        }
+    }
+
+    fn set_in_flight(&self, seq: u64) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.in_flight = Some((seq, Instant::now()));
+        }
+    }
+}
+
+/// 截图失败
+enum CaptureError {
+    /// 实例已销毁或控制器已断开
+    Disconnected,
+    Failed(String),
+}
+
+/// 截图推流器
+pub struct ScreenStreamer {
+    state: Arc<MaaState>,
+    streams: Mutex<HashMap<String, Arc<Stream>>>,
+}
+
+impl ScreenStreamer {
+    pub fn new(state: Arc<MaaState>) -> Arc<Self> {
+        Arc::new(Self {
+            state,
+            streams: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 订阅实例的截图流（实例尚无截图线程时启动；同一查看者重复订阅时更新帧间隔与编码选项）
+    pub fn subscribe(
+        self: &Arc<Self>,
+        instance_id: &str,
+        viewer_id: &str,
+        interval: Duration,
+        options: ScreenshotOptions,
+    ) -> MxuResult<()> {
+        self.ensure_connected(instance_id)?;
+
+        let mut streams = self.streams.lock()?;
+        let (stream, is_new) = match streams.get(instance_id) {
+            Some(stream) => (stream.clone(), false),
+            None => {
+                let stream = Arc::new(Stream::default());
+                streams.insert(instance_id.to_string(), stream.clone());
+                (stream, true)
+            }
+        };
+        {
+            let mut inner = stream.inner.lock()?;
+            inner
+                .viewers
+                .insert(viewer_id.to_string(), Viewer { interval, options });
+            inner.force = true;
+        }
+        stream.wakeup.notify_all();
+
+        if is_new {
+            let streamer = self.clone();
+            let instance_id = instance_id.to_string();
+            thread::spawn(move || streamer.run_loop(instance_id, stream));
+        }
+        debug!(
+            "[ScreenStream] {} subscribed to {} ({:?})",
+            viewer_id, instance_id, interval
+        );
+        Ok(())
+    }
+
+    /// 取消订阅（最后一个查看者离开后截图线程退出）
+    pub fn unsubscribe(&self, instance_id: &str, viewer_id: &str) {
+        let Ok(mut streams) = self.streams.lock() else {
+            return;
+        };
+        let Some(stream) = streams.get(instance_id).cloned() else {
+            return;
+        };
+        let Ok(mut inner) = stream.inner.lock() else {
+            return;
+        };
+        inner.viewers.remove(viewer_id);
+        if inner.viewers.is_empty() {
+            streams.remove(instance_id);
+        }
+        drop(inner);
+        stream.wakeup.notify_all();
+        debug!(
+            "[ScreenStream] {} unsubscribed from {}",
+            viewer_id, instance_id
+        );
+    }
+
+    /// 确认已收到帧，允许推送下一帧
+    pub fn ack(&self, instance_id: &str, seq: u64) {
+        let Some(stream) = self
+            .streams
+            .lock()
+            .ok()
+            .and_then(|streams| streams.get(instance_id).cloned())
+        else {
+            return;
+        };
+        if let Ok(mut inner) = stream.inner.lock() {
+            if inner
+                .in_flight
+                .is_some_and(|(in_flight, _)| seq >= in_flight)
+            {
+                inner.in_flight = None;
+            }
+        }
+        stream.wakeup.notify_all();
+    }
+
+    fn run_loop(self: Arc<Self>, instance_id: String, stream: Arc<Stream>) {
+        info!("[ScreenStream] Streaming {}", instance_id);
+        let mut seq = 0;
+        let mut last_hash = None;
+        let mut failures = 0;
+        let mut next_capture = Instant::now();
+
+        let stop_reason = loop {
+            let Some(turn) = stream.wait_turn(next_capture) else {
+                break None;
+            };
+            next_capture = Instant::now() + turn.interval;
+
+            let png = match self.capture(&instance_id) {
+                Ok(png) => png,
+                Err(CaptureError::Disconnected) => break Some(StopReason::Disconnected),
+                Err(CaptureError::Failed(e)) => {
+                    failures += 1;
+                    warn!(
+                        "[ScreenStream] Capture of {} failed ({}/{}): {}",
+                        instance_id, failures, MAX_CONSECUTIVE_FAILURES, e
+                    );
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        break Some(StopReason::CaptureFailed);
+                    }
+                    continue;
+                }
+            };
+            failures = 0;
+
+            // 画面未变化时不推送（截图编码是确定的，相同画面得到相同的 PNG）
+            let mut hasher = DefaultHasher::new();
+            png.hash(&mut hasher);
+            let hash = hasher.finish();
+            if !turn.force && last_hash == Some(hash) {
+                continue;
+            }
+            last_hash = Some(hash);
+
+            let (width, height) = screenshot::dimensions(&png).unwrap_or_default();
+            let mut frames = Vec::new();
+            for (options, viewers) in turn.outputs {
+                match screenshot::encode(png.clone(), &options) {
+                    Ok(image) => frames.push((image, viewers)),
+                    Err(e) => warn!("[ScreenStream] Failed to encode frame: {}", e),
+                }
+            }
+            if frames.is_empty() {
+                continue;
+            }
+            seq += 1;
+            stream.set_in_flight(seq);
+            for (image, viewers) in frames {
+                let event = ScreenFrameEvent {
+                    instance_id: instance_id.clone(),
+                    seq,
+                    data: format!(
+                        "data:{};base64,{}",
+                        image.mime,
+                        STANDARD.encode(&image.data)
+                    ),
+                    viewers,
+                    width,
+                    height,
+                };
+                self.state.event_bus.emit(BusEvent::new(
+                    SCREEN_FRAME_EVENT,
+                    Some(&instance_id),
+                    &event,
+                ));
+            }
+        };
+
+        // 异常退出时移除自身（期间若已有新的截图流替换，则保留新的）
+        if let Some(reason) = stop_reason {
+            if let Ok(mut streams) = self.streams.lock() {
+                if streams
+                    .get(&instance_id)
+                    .is_some_and(|s| Arc::ptr_eq(s, &stream))
+                {
+                    streams.remove(&instance_id);
+                }
+            }
+            warn!(
+                "[ScreenStream] Stream of {} stopped: {:?}",
+                instance_id, reason
+            );
+            let event = ScreenStreamStoppedEvent {
+                instance_id: instance_id.clone(),
+                reason,
+            };
+            self.state.event_bus.emit(BusEvent::new(
+                SCREEN_STREAM_STOPPED_EVENT,
+                Some(&instance_id),
+                &event,
+            ));
+        } else {
+            info!("[ScreenStream] Stream of {} ended", instance_id);
+        }
+    }
+
+    /// 获取一帧 PNG（任务运行时直接读取缓存画面，否则主动截图）
+    fn capture(&self, instance_id: &str) -> Result<Vec<u8>, CaptureError> {
+        let (controller, running) = {
+            let instances = self
+                .state
+                .instances
+                .lock()
+                .map_err(|e| CaptureError::Failed(e.to_string()))?;
+            let instance = instances
+                .get(instance_id)
+                .ok_or(CaptureError::Disconnected)?;
+            let controller = instance
+                .controller
+                .clone()
+                .ok_or(CaptureError::Disconnected)?;
+            let running = instance.tasker.as_ref().is_some_and(|t| t.running());
+            (controller, running)
+        };
+        if !controller.connected() {
+            return Err(CaptureError::Disconnected);
+        }
+
+        if !running {
+            let id = controller
+                .post_screencap()
+                .map_err(|e| CaptureError::Failed(e.to_string()))?;
+            if !controller.wait(id).succeeded() {
+                return Err(CaptureError::Failed("Screencap failed".to_string()));
+            }
+        }
+        let png = controller
+            .cached_image()
+            .map_err(|e| CaptureError::Failed(e.to_string()))?;
+        if png.is_empty() {
+            return Err(CaptureError::Failed("No image data available".to_string()));
+        }
+        Ok(png)
+    }
+
+    /// 检查实例控制器已连接
+    fn ensure_connected(&self, instance_id: &str) -> MxuResult<()> {
+        let instances = self.state.instances.lock()?;
+        let instance = instances
+            .get(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+        if instance.controller.as_ref().is_some_and(|c| c.connected()) {
+            Ok(())
+        } else {
+            Err(MxuError::controller_not_connected(instance_id))
+        }
+    }
+}
@@ -0,0 +1,605 @@
+//! 控制器掉线重连
+//!
+//! 模拟器重启等情况下控制器会断开，之后提交的任务只会逐个失败。看门狗线程定期检查各实例的
+//! 控制器：确认连接过的控制器变为未连接时，按指数退避用同一控制器配置重新连接（ADB 设备按
+//! 连接时的设备名重新搜索，模拟器重启后地址可能变化），重建任务执行器并改绑 Agent；掉线时
+//! 仍在运行任务的，可在重连后重新提交未完成的任务。每个阶段经事件总线发出 maa-watchdog 事件
+//!
+//! 只处理看门狗确认连接过的控制器：用户销毁实例或手动连接到其他设备时不干预
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, info, warn};
+use maa_framework::MaaStatus;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::backend::{ControllerBackend, TaskerBackend};
+use crate::commands::error::{ErrorCode, MxuError, MxuResult, Subsystem};
+use crate::commands::event_bus::BusEvent;
+use crate::commands::maa_agent::rebind_agent_sinks;
+use crate::commands::maa_core::{connect_controller, create_tasker, search_adb_devices};
+use crate::commands::types::{
+    repost_tasks, resumed_count, stop_tasks, ControllerConfig, MaaState, PostedTask, ResumedTask,
+    ScreenshotResolution,
+};
+
+/// 看门狗事件名
+pub const WATCHDOG_EVENT: &str = "maa-watchdog";
+
+/// 检查控制器连接状态的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// 配置与事件类型
+// ============================================================================
+
+/// 重连配置（字段均可省略）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchdogOptions {
+    /// 是否启用掉线重连
+    pub enabled: bool,
+    /// 最多重连次数
+    pub max_attempts: u32,
+    /// 第一次重连前的等待时间（毫秒），之后每次翻倍
+    pub initial_backoff_ms: u64,
+    /// 两次重连之间的最长等待时间（毫秒）
+    pub max_backoff_ms: u64,
+    /// 重连成功后是否重新提交掉线时未完成的任务
+    pub resume_tasks: bool,
+}
+
+impl Default for WatchdogOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 5,
+            initial_backoff_ms: 3000,
+            max_backoff_ms: 60_000,
+            resume_tasks: true,
+        }
+    }
+}
+
+impl WatchdogOptions {
+    /// 第 attempt 次（从 1 开始）重连前的等待时间
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        let max = self.max_backoff_ms.max(self.initial_backoff_ms);
+        Duration::from_millis(self.initial_backoff_ms.saturating_mul(factor).min(max))
+    }
+}
+
+/// 重连阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchdogStage {
+    /// 检测到控制器掉线
+    Disconnected,
+    /// 开始第 attempt 次重连
+    Reconnecting,
+    /// 第 attempt 次重连失败
+    AttemptFailed,
+    /// 重连成功
+    Reconnected,
+    /// 达到最多重连次数，放弃重连
+    GaveUp,
+}
+
+/// 看门狗事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchdogEvent {
+    pub instance_id: String,
+    pub stage: WatchdogStage,
+    /// 第几次重连（从 1 开始，disconnected 时为 0）
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// 失败原因（仅 attempt_failed）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 距下一次重连的等待时间（毫秒，disconnected / attempt_failed）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_ms: Option<u64>,
+    /// 掉线时待恢复的任务数（仅 disconnected，未启用恢复或没有任务在运行时为 0）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_tasks: Option<usize>,
+    /// 重新提交的任务（仅 reconnected）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resumed_tasks: Vec<ResumedTask>,
+}
+
+impl WatchdogEvent {
+    fn new(instance_id: &str, stage: WatchdogStage, attempt: u32, max_attempts: u32) -> Self {
+        Self {
+            instance_id: instance_id.to_string(),
+            stage,
+            attempt,
+            max_attempts,
+            error: None,
+            retry_in_ms: None,
+            pending_tasks: None,
+            resumed_tasks: Vec::new(),
+        }
+    }
+}
+
+// ============================================================================
+// 看门狗
+// ============================================================================
+
+/// 单个实例的看门狗状态
+#[derive(Default)]
+struct Watched {
+    /// 最近一次确认已连接的控制器（只对它的掉线做重连）
+    controller: Option<Weak<dyn ControllerBackend>>,
+    /// 连接时的 ADB 设备名（重连前按名称重新搜索设备）
+    adb_device_name: Option<String>,
+    /// 是否正在重连
+    recovering: bool,
+}
+
+impl Watched {
+    fn is_watching(&self, controller: &Arc<dyn ControllerBackend>) -> bool {
+        self.controller
+            .as_ref()
+            .is_some_and(|c| c.ptr_eq(&Arc::downgrade(controller)))
+    }
+}
+
+#[derive(Default)]
+struct WatchdogInner {
+    options: WatchdogOptions,
+    /// 检查线程是否在运行
+    running: bool,
+    instances: HashMap<String, Watched>,
+}
+
+/// 控制器掉线重连看门狗
+pub struct ControllerWatchdog {
+    state: Arc<MaaState>,
+    inner: Mutex<WatchdogInner>,
+    wakeup: Condvar,
+}
+
+impl ControllerWatchdog {
+    pub fn new(state: Arc<MaaState>) -> Arc<Self> {
+        Arc::new(Self {
+            state,
+            inner: Mutex::new(WatchdogInner::default()),
+            wakeup: Condvar::new(),
+        })
+    }
+
+    /// 更新重连配置（启用时启动检查线程，禁用后线程退出，进行中的重连在下次等待时中止）
+    pub fn configure(self: &Arc<Self>, options: WatchdogOptions) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        info!("[Watchdog] Options updated: {:?}", options);
+        let start = options.enabled && !inner.running;
+        if start {
+            inner.running = true;
+        }
+        inner.options = options;
+        drop(inner);
+        self.wakeup.notify_all();
+
+        if start {
+            let watchdog = self.clone();
+            thread::spawn(move || watchdog.run_loop());
+        }
+    }
+
+    /// 当前重连配置
+    pub fn options(&self) -> WatchdogOptions {
+        self.inner
+            .lock()
+            .map(|inner| inner.options.clone())
+            .unwrap_or_default()
+    }
+
+    fn run_loop(self: Arc<Self>) {
+        info!("[Watchdog] Started");
+        while self.sleep(CHECK_INTERVAL) {
+            self.check();
+        }
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.running = false;
+            inner.instances.clear();
+        }
+        info!("[Watchdog] Stopped");
+    }
+
+    /// 等待指定时间，期间被禁用时提前返回 false
+    fn sleep(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        let Ok(mut inner) = self.inner.lock() else {
+            return false;
+        };
+        loop {
+            if !inner.options.enabled {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            match self.wakeup.wait_timeout(inner, deadline - now) {
+                Ok((guard, _)) => inner = guard,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    /// 检查所有实例的控制器，发现掉线时在后台线程重连
+    fn check(self: &Arc<Self>) {
+        let controllers: Vec<(String, Arc<dyn ControllerBackend>, Option<ControllerConfig>)> =
+            match self.state.instances.lock() {
+                Ok(instances) => instances
+                    .iter()
+                    .filter_map(|(id, instance)| {
+                        let controller = instance.controller.clone()?;
+                        Some((id.clone(), controller, instance.controller_config.clone()))
+                    })
+                    .collect(),
+                Err(_) => return,
+            };
+
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        // 实例被销毁或控制器被移除时不再跟踪
+        inner
+            .instances
+            .retain(|id, watched| watched.recovering || controllers.iter().any(|(c, ..)| c == id));
+
+        for (instance_id, controller, config) in controllers {
+            let watched = inner.instances.entry(instance_id.clone()).or_default();
+            if watched.recovering {
+                continue;
+            }
+            if controller.connected() {
+                if !watched.is_watching(&controller) {
+                    debug!("[Watchdog] Watching controller of {}", instance_id);
+                    watched.controller = Some(Arc::downgrade(&controller));
+                    watched.adb_device_name = self.adb_device_name(config.as_ref());
+                }
+            } else if watched.is_watching(&controller) {
+                watched.recovering = true;
+                let device_name = watched.adb_device_name.clone();
+                let watchdog = self.clone();
+                thread::spawn(move || watchdog.recover(instance_id, controller, device_name));
+            }
+        }
+    }
+
+    /// 重连掉线的控制器
+    fn recover(
+        self: Arc<Self>,
+        instance_id: String,
+        lost: Arc<dyn ControllerBackend>,
+        device_name: Option<String>,
+    ) {
+        let options = self.options();
+        warn!(
+            "[Watchdog] Controller of {} disconnected, reconnecting",
+            instance_id
+        );
+
+        let tasker = self.running_tasker(&instance_id);
+        let remaining = match &tasker {
+            Some(tasker) if options.resume_tasks => self.unfinished_tasks(&instance_id, tasker),
+            _ => Vec::new(),
+        };
+        // 先通知掉线再停止任务，前端收到随后的任务失败回调时已知道任务将被恢复
+        let mut event = WatchdogEvent::new(
+            &instance_id,
+            WatchdogStage::Disconnected,
+            0,
+            options.max_attempts,
+        );
+        event.retry_in_ms = Some(options.backoff(1).as_millis() as u64);
+        event.pending_tasks = Some(remaining.len());
+        self.emit(event);
+        // 队列调整随恢复的任务生效
+        self.state.task_queue.remove_instance(&instance_id);
+        // 控制器已断开，继续执行只会逐个超时失败
+        if let Some(tasker) = tasker {
+            stop_tasks(&self.state, &instance_id, &tasker);
+        }
+
+        let mut current = lost;
+        for attempt in 1..=options.max_attempts {
+            if !self.sleep(options.backoff(attempt)) {
+                info!(
+                    "[Watchdog] Disabled, reconnection of {} cancelled",
+                    instance_id
+                );
+                self.finish(&instance_id, None);
+                return;
+            }
+            // 用户已销毁实例或手动连接了其他控制器
+            if !self
+                .instance_controller(&instance_id)
+                .is_some_and(|c| Arc::ptr_eq(&c, &current))
+            {
+                info!(
+                    "[Watchdog] Controller of {} replaced, reconnection cancelled",
+                    instance_id
+                );
+                self.finish(&instance_id, None);
+                return;
+            }
+
+            self.emit(WatchdogEvent::new(
+                &instance_id,
+                WatchdogStage::Reconnecting,
+                attempt,
+                options.max_attempts,
+            ));
+            info!(
+                "[Watchdog] Reconnecting {} ({}/{})",
+                instance_id, attempt, options.max_attempts
+            );
+
+            match self.reconnect(&instance_id, device_name.as_deref(), &mut current) {
+                Ok(()) => {
+                    let resumed = match self.rebuild_tasker(&instance_id, remaining) {
+                        Ok(resumed) => resumed,
+                        Err(e) => {
+                            warn!(
+                                "[Watchdog] Failed to resume tasks of {}: {}",
+                                instance_id, e
+                            );
+                            Vec::new()
+                        }
+                    };
+                    info!(
+                        "[Watchdog] {} reconnected, {} task(s) resumed",
+                        instance_id,
+                        resumed_count(&resumed)
+                    );
+                    let mut event = WatchdogEvent::new(
+                        &instance_id,
+                        WatchdogStage::Reconnected,
+                        attempt,
+                        options.max_attempts,
+                    );
+                    event.resumed_tasks = resumed;
+                    self.emit(event);
+                    self.finish(&instance_id, Some(&current));
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        "[Watchdog] Reconnection of {} failed ({}/{}): {}",
+                        instance_id, attempt, options.max_attempts, e
+                    );
+                    let mut event = WatchdogEvent::new(
+                        &instance_id,
+                        WatchdogStage::AttemptFailed,
+                        attempt,
+                        options.max_attempts,
+                    );
+                    event.error = Some(e.to_string());
+                    if attempt < options.max_attempts {
+                        event.retry_in_ms = Some(options.backoff(attempt + 1).as_millis() as u64);
+                    }
+                    self.emit(event);
+                }
+            }
+        }
+
+        warn!(
+            "[Watchdog] Giving up reconnecting {} after {} attempt(s)",
+            instance_id, options.max_attempts
+        );
+        self.emit(WatchdogEvent::new(
+            &instance_id,
+            WatchdogStage::GaveUp,
+            options.max_attempts,
+            options.max_attempts,
+        ));
+        self.finish(&instance_id, None);
+    }
+
+    /// 实例正在运行任务时返回其任务执行器
+    fn running_tasker(&self, instance_id: &str) -> Option<Arc<dyn TaskerBackend>> {
+        let instances = self.state.instances.lock().ok()?;
+        instances
+            .get(instance_id)?
+            .tasker
+            .clone()
+            .filter(|t| t.running())
+    }
+
+    /// 掉线时未完成的任务
+    ///
+    /// 任务按提交顺序执行，第一个等待中或执行中的任务及其后的任务均视为未完成
+    /// （运行中插入、尚未提交的任务同样视为未完成）
+    fn unfinished_tasks(
+        &self,
+        instance_id: &str,
+        tasker: &Arc<dyn TaskerBackend>,
+    ) -> Vec<PostedTask> {
+        let posted = match self.state.instances.lock() {
+            Ok(instances) => match instances.get(instance_id) {
+                Some(instance) => instance.posted_tasks.clone(),
+                None => return Vec::new(),
+            },
+            Err(_) => return Vec::new(),
+        };
+        let resume_from = posted.iter().position(|t| {
+            t.task_id < 0
+                || tasker
+                    .task_status(t.task_id)
+                    .is_ok_and(|s| s == MaaStatus::PENDING || s == MaaStatus::RUNNING)
+        });
+        match resume_from {
+            Some(index) => posted[index..].to_vec(),
+            None => Vec::new(),
+        }
+    }
+
+    /// 用实例保存的控制器配置重新连接（阻塞直到连接结束），current 更新为新创建的控制器
+    fn reconnect(
+        &self,
+        instance_id: &str,
+        device_name: Option<&str>,
+        current: &mut Arc<dyn ControllerBackend>,
+    ) -> MxuResult<()> {
+        let config = {
+            let instances = self.state.instances.lock()?;
+            instances
+                .get(instance_id)
+                .ok_or_else(|| MxuError::instance_not_found(instance_id))?
+                .controller_config
+                .clone()
+                .ok_or_else(|| MxuError::controller_not_connected(instance_id))?
+        };
+        let config = match (config, device_name) {
+            (config @ ControllerConfig::Adb { .. }, Some(name)) => {
+                self.resolve_adb_device(name, config.screenshot_resolution())?
+            }
+            (config, _) => config,
+        };
+
+        let conn_id = connect_controller(&self.state, instance_id, &config)?;
+        *current = self
+            .instance_controller(instance_id)
+            .ok_or_else(|| MxuError::controller_not_connected(instance_id))?;
+        if current.wait(conn_id).succeeded() && current.connected() {
+            Ok(())
+        } else {
+            Err(MxuError::new(
+                ErrorCode::MaaCallFailed,
+                Subsystem::Controller,
+                "Connection failed",
+            )
+            .with_instance(instance_id))
+        }
+    }
+
+    /// 按设备名重新搜索 ADB 设备（沿用原来的截图分辨率）
+    fn resolve_adb_device(
+        &self,
+        name: &str,
+        screenshot_resolution: ScreenshotResolution,
+    ) -> MxuResult<ControllerConfig> {
+        let device = search_adb_devices(&self.state)?
+            .into_iter()
+            .find(|d| d.name == name)
+            .ok_or_else(|| {
+                MxuError::new(
+                    ErrorCode::NotFound,
+                    Subsystem::Controller,
+                    format!("ADB device not found: {}", name),
+                )
+            })?;
+        info!(
+            "[Watchdog] Using ADB device: {} ({})",
+            device.name, device.address
+        );
+        Ok(ControllerConfig::Adb {
+            adb_path: device.adb_path,
+            address: device.address,
+            screencap_methods: device.screencap_methods.to_string(),
+            input_methods: device.input_methods.to_string(),
+            config: device.config,
+            screenshot_resolution,
+        })
+    }
+
+    /// 重连后重建任务执行器、改绑 Agent，并重新提交未完成的任务
+    fn rebuild_tasker(
+        &self,
+        instance_id: &str,
+        remaining: Vec<PostedTask>,
+    ) -> MxuResult<Vec<ResumedTask>> {
+        let mut instances = self.state.instances.lock()?;
+        let instance = instances
+            .get_mut(instance_id)
+            .ok_or_else(|| MxuError::instance_not_found(instance_id))?;
+        // 资源尚未加载时 Tasker 由之后的 maa_start_tasks 创建
+        let (Some(resource), Some(controller)) =
+            (instance.resource.clone(), instance.controller.clone())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let tasker = create_tasker(&self.state, &resource, &controller, instance_id)?;
+        instance.tasker = Some(tasker.clone());
+        if let Err(e) = rebind_agent_sinks(instance) {
+            warn!(
+                "[Watchdog] Failed to rebind agents of {}: {}",
+                instance_id, e
+            );
+        }
+        // 重连期间用户手动停止了任务（已清空提交记录）时不再恢复
+        let remaining: Vec<PostedTask> = remaining
+            .into_iter()
+            .filter(|t| instance.posted_tasks.iter().any(|p| p.task_id == t.task_id))
+            .collect();
+        if !remaining.is_empty() && !tasker.inited() {
+            return Err(MxuError::new(
+                ErrorCode::TaskerNotInitialized,
+                Subsystem::Tasker,
+                "Tasker not initialized",
+            )
+            .with_instance(instance_id));
+        }
+
+        Ok(repost_tasks(&self.state, instance_id, instance, remaining))
+    }
+
+    /// 结束重连，成功时继续跟踪新的控制器
+    fn finish(&self, instance_id: &str, controller: Option<&Arc<dyn ControllerBackend>>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            if let Some(watched) = inner.instances.get_mut(instance_id) {
+                watched.recovering = false;
+                watched.controller = controller.map(Arc::downgrade);
+            }
+        }
+    }
+
+    fn instance_controller(&self, instance_id: &str) -> Option<Arc<dyn ControllerBackend>> {
+        self.state
+            .instances
+            .lock()
+            .ok()?
+            .get(instance_id)?
+            .controller
+            .clone()
+    }
+
+    /// 连接时使用的 ADB 设备名（按地址在设备搜索缓存中查找）
+    fn adb_device_name(&self, config: Option<&ControllerConfig>) -> Option<String> {
+        let Some(ControllerConfig::Adb {
+            adb_path, address, ..
+        }) = config
+        else {
+            return None;
+        };
+        self.state
+            .cached_adb_devices
+            .lock()
+            .ok()?
+            .iter()
+            .find(|d| &d.address == address && &d.adb_path == adb_path)
+            .map(|d| d.name.clone())
+            .filter(|name| !name.is_empty())
+    }
+
+    fn emit(&self, event: WatchdogEvent) {
+        self.state.event_bus.emit(BusEvent::new(
+            WATCHDOG_EVENT,
+            Some(&event.instance_id),
+            &event,
+        ));
+    }
+}
@@ -973,12 +973,14 @@ function App() {
     };
   }, [hotkeys?.globalEnabled, hotkeys?.startTasks, hotkeys?.stopTasks]);
 
-  // 监听托盘菜单事件（开始/停止任务）
+  // 监听托盘菜单与本地控制接口事件（开始/停止任务）
   useEffect(() => {
     if (!isTauri()) return;
 
     let unlistenStart: (() => void) | null = null;
     let unlistenStop: (() => void) | null = null;
+    let unlistenRemoteStart: (() => void) | null = null;
+    let unlistenRemoteStop: (() => void) | null = null;
 
     const setupTrayListeners = async () => {
       try {
@@ -996,6 +998,31 @@ function App() {
           document.dispatchEvent(new CustomEvent('mxu-stop-tasks', { detail: { source: 'tray' } }));
         });
 
+        // 本地控制接口指定了实例 ID，由 Toolbar 按实例启动/停止
+        unlistenRemoteStart = await listen<{ instance_id: string }>(
+          'remote-start-tasks',
+          ({ payload }) => {
+            log.info('收到本地控制接口开始任务事件:', payload.instance_id);
+            document.dispatchEvent(
+              new CustomEvent('mxu-start-tasks', {
+                detail: { source: 'remote', instanceId: payload.instance_id },
+              }),
+            );
+          },
+        );
+
+        unlistenRemoteStop = await listen<{ instance_id: string }>(
+          'remote-stop-tasks',
+          ({ payload }) => {
+            log.info('收到本地控制接口停止任务事件:', payload.instance_id);
+            document.dispatchEvent(
+              new CustomEvent('mxu-stop-tasks', {
+                detail: { source: 'remote', instanceId: payload.instance_id },
+              }),
+            );
+          },
+        );
+
         log.info('托盘事件监听已注册');
       } catch (err) {
         log.warn('注册托盘事件监听失败:', err);
@@ -1007,6 +1034,8 @@ function App() {
     return () => {
       if (unlistenStart) unlistenStart();
       if (unlistenStop) unlistenStop();
+      if (unlistenRemoteStart) unlistenRemoteStart();
+      if (unlistenRemoteStop) unlistenRemoteStop();
     };
   }, []);
 
@@ -15,14 +15,17 @@ import {
   CheckCircle,
   Settings2,
   History,
+  FolderOpen,
 } from 'lucide-react';
 import clsx from 'clsx';
+import { open } from '@tauri-apps/plugin-dialog';
 import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import { resolveI18nText } from '@/services/contentResolver';
 import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
 import type { ControllerItem, ResourceItem } from '@/types/interface';
 import { computeResourcePaths } from '@/utils/resourcePath';
+import { withScreenshotResolution } from '@/utils/screenshotResolution';
 import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
 import { getInterfaceLangKey } from '@/i18n';
 import {
@@ -83,6 +86,10 @@ export function ConnectionPanel() {
   const [playcoverAddress, setPlaycoverAddress] = useState(
     activeInstance?.savedDevice?.playcoverAddress || '127.0.0.1:1717',
   );
+  // 图片目录（调试控制器）从保存的配置初始化
+  const [imageFolderPath, setImageFolderPath] = useState(
+    activeInstance?.savedDevice?.imageFolderPath || '',
+  );
 
   // 资源相关状态
   const [isLoadingResource, setIsLoadingResource] = useState(false);
@@ -260,6 +267,9 @@ export function ConnectionPanel() {
       setPlaycoverAddress('127.0.0.1:1717');
     }
 
+    // 恢复图片目录
+    setImageFolderPath(savedDevice?.imageFolderPath || '');
+
     // 如果已连接但未展开，自动折叠
     if (isInstanceConnected && isInstanceResourceLoaded) {
       setConnectionPanelExpanded(false);
@@ -296,7 +306,7 @@ export function ConnectionPanel() {
     }
   }, [storedResourceLoaded, isLoadingResource, currentResourceName]);
 
-  // 判断是否需要搜索设备（PlayCover 不需要搜索）
+  // 判断是否需要搜索设备（PlayCover、ImageFolder 不需要搜索）
   const needsDeviceSearch =
     controllerType === 'Adb' || controllerType === 'Win32' || controllerType === 'Gamepad';
 
@@ -329,20 +339,88 @@ export function ConnectionPanel() {
       savedDevice &&
       ((controllerType === 'Adb' && savedDevice.adbDeviceName) ||
         ((controllerType === 'Win32' || controllerType === 'Gamepad') && savedDevice.windowName) ||
-        (controllerType === 'PlayCover' && savedDevice.playcoverAddress));
+        (controllerType === 'PlayCover' && savedDevice.playcoverAddress) ||
+        (controllerType === 'ImageFolder' && savedDevice.imageFolderPath));
 
     if (hasHistoricalDevice && needsDeviceSearch) {
       // 标记该实例已尝试过自动重连
       autoReconnectAttempted.add(instanceId);
       // 触发搜索并自动连接（handleSearch 内部已有匹配+自动连接逻辑）
       handleSearch();
-    } else if (hasHistoricalDevice && controllerType === 'PlayCover') {
-      // PlayCover 不需要搜索，直接连接
+    } else if (
+      hasHistoricalDevice &&
+      (controllerType === 'PlayCover' || controllerType === 'ImageFolder')
+    ) {
+      // PlayCover、ImageFolder 不需要搜索，直接连接
       autoReconnectAttempted.add(instanceId);
       handleConnect();
     }
   }, [instanceId, activeInstance, currentController, isConnected, isConnecting, isSearching]); // eslint-disable-line react-hooks/exhaustive-deps
 
+  // 监听 ADB 设备热插拔：同步设备列表，之前使用的设备重新出现时自动连接
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onAdbDeviceChanges(
+        (device) => {
+          const devices = useAppStore.getState().cachedAdbDevices;
+          if (!devices.some((d) => d.address === device.address && d.name === device.name)) {
+            setCachedAdbDevices([...devices, device]);
+          }
+
+          const savedName = activeInstance?.savedDevice?.adbDeviceName;
+          if (
+            controllerType !== 'Adb' ||
+            !savedName ||
+            device.name !== savedName ||
+            activeInstance?.isRunning ||
+            isConnected ||
+            isConnecting ||
+            isSearching
+          ) {
+            return;
+          }
+          if (instanceId) {
+            addLog(instanceId, {
+              type: 'info',
+              message: t('taskList.autoConnect.deviceAppeared', { name: device.name }),
+            });
+          }
+          handleSelectAdbDevice(device);
+        },
+        (device) => {
+          const devices = useAppStore.getState().cachedAdbDevices;
+          setCachedAdbDevices(
+            devices.filter((d) => d.address !== device.address || d.name !== device.name),
+          );
+          if (
+            instanceId &&
+            controllerType === 'Adb' &&
+            device.name === activeInstance?.savedDevice?.adbDeviceName
+          ) {
+            addLog(instanceId, {
+              type: 'warning',
+              message: t('taskList.autoConnect.deviceRemoved', { name: device.name }),
+            });
+          }
+        },
+      )
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [instanceId, activeInstance, controllerType, isConnected, isConnecting, isSearching]); // eslint-disable-line react-hooks/exhaustive-deps
+
   // 初始化 MaaFramework
   const ensureMaaInitialized = async () => {
     try {
@@ -453,7 +531,10 @@ export function ConnectionPanel() {
     deviceName: string,
     targetType: 'device' | 'window',
   ) => {
-    const ctrlId = await maaService.connectController(instanceId, config);
+    const ctrlId = await maaService.connectController(
+      instanceId,
+      withScreenshotResolution(config, currentController, activeInstance?.screenshotResolution),
+    );
 
     // 注册 ctrl_id 与设备/窗口名及类型的映射，用于日志显示
     registerCtrlIdName(ctrlId, deviceName || '', targetType);
@@ -533,6 +614,15 @@ export function ConnectionPanel() {
         };
         deviceName = playcoverAddress;
         targetType = 'device';
+      } else if (controllerType === 'ImageFolder') {
+        // 保存图片目录到实例配置
+        setInstanceSavedDevice(instanceId, { imageFolderPath });
+        config = {
+          type: 'ImageFolder',
+          path: imageFolderPath,
+        };
+        deviceName = imageFolderPath;
+        targetType = 'device';
       } else if (controllerType === 'Gamepad' && selectedWindow) {
         config = {
           type: 'Gamepad',
@@ -673,6 +763,8 @@ export function ConnectionPanel() {
         return <Apple className="w-4 h-4" />;
       case 'Gamepad':
         return <Gamepad2 className="w-4 h-4" />;
+      case 'ImageFolder':
+        return <FolderOpen className="w-4 h-4" />;
       default:
         return <Smartphone className="w-4 h-4" />;
     }
@@ -935,11 +1027,24 @@ export function ConnectionPanel() {
     return [];
   };
 
+  // 选择图片目录
+  const handleBrowseImageFolder = async () => {
+    try {
+      const selected = await open({ directory: true, multiple: false });
+      if (selected && typeof selected === 'string') {
+        setImageFolderPath(selected);
+      }
+    } catch (err) {
+      console.error('Failed to open folder dialog:', err);
+    }
+  };
+
   // 判断是否可以连接
   const canConnect = () => {
     if (controllerType === 'Adb') return !!selectedAdbDevice;
     if (controllerType === 'Win32' || controllerType === 'Gamepad') return !!selectedWindow;
     if (controllerType === 'PlayCover') return playcoverAddress.trim().length > 0;
+    if (controllerType === 'ImageFolder') return imageFolderPath.trim().length > 0;
     return false;
   };
 
@@ -975,6 +1080,9 @@ export function ConnectionPanel() {
       if (savedDevice?.playcoverAddress) {
         return truncateText(savedDevice.playcoverAddress, 6);
       }
+      if (savedDevice?.imageFolderPath) {
+        return truncateText(savedDevice.imageFolderPath, 6);
+      }
       // 没有设备名时回退到控制器名称
       if (currentController) {
         return getControllerDisplayName(currentController);
@@ -987,7 +1095,8 @@ export function ConnectionPanel() {
       activeInstance?.savedDevice &&
       (activeInstance.savedDevice.adbDeviceName ||
         activeInstance.savedDevice.windowName ||
-        activeInstance.savedDevice.playcoverAddress);
+        activeInstance.savedDevice.playcoverAddress ||
+        activeInstance.savedDevice.imageFolderPath);
 
     return (
       <div className="flex items-center gap-2">
@@ -1188,6 +1297,60 @@ export function ConnectionPanel() {
               </div>
             )}
 
+            {/* 图片目录输入和连接按钮（调试控制器） */}
+            {controllerType === 'ImageFolder' && (
+              <div className="flex gap-2">
+                <input
+                  type="text"
+                  value={imageFolderPath}
+                  onChange={(e) => setImageFolderPath(e.target.value)}
+                  placeholder={t('controller.imageFolderPlaceholder')}
+                  disabled={isConnected || isConnecting || isRunning}
+                  className={clsx(
+                    'flex-1 min-w-0 px-2.5 py-1.5 rounded-md border bg-bg-tertiary border-border text-sm',
+                    'text-text-primary placeholder:text-text-muted',
+                    'focus:outline-none focus:border-accent transition-colors',
+                    (isConnected || isRunning) && 'opacity-60 cursor-not-allowed',
+                  )}
+                />
+                <button
+                  onClick={handleBrowseImageFolder}
+                  disabled={isConnected || isConnecting || isRunning}
+                  className={clsx(
+                    'flex items-center justify-center px-3 py-1.5 rounded-md border transition-colors',
+                    'bg-bg-tertiary border-border',
+                    isConnected || isConnecting || isRunning
+                      ? 'opacity-50 cursor-not-allowed'
+                      : 'hover:bg-bg-hover',
+                  )}
+                  title={t('controller.browseImageFolder')}
+                >
+                  <FolderOpen className="w-3.5 h-3.5 text-text-secondary" />
+                </button>
+                <button
+                  onClick={handleConnect}
+                  disabled={isConnecting || isConnected || !canConnect() || isRunning}
+                  className={clsx(
+                    'flex items-center justify-center px-3 py-1.5 rounded-md border transition-colors',
+                    isConnected
+                      ? 'bg-success/20 border-success/50 cursor-not-allowed'
+                      : isConnecting || !canConnect() || isRunning
+                        ? 'bg-bg-tertiary border-border opacity-50 cursor-not-allowed'
+                        : 'bg-accent border-accent text-white hover:bg-accent-hover',
+                  )}
+                  title={t('controller.connect')}
+                >
+                  {isConnecting ? (
+                    <Loader2 className="w-3.5 h-3.5 animate-spin text-text-secondary" />
+                  ) : isConnected ? (
+                    <Check className="w-3.5 h-3.5 text-success" />
+                  ) : (
+                    <Wifi className="w-3.5 h-3.5" />
+                  )}
+                </button>
+              </div>
+            )}
+
             {/* 设备选择（Adb/Win32/Gamepad）- 下拉框和刷新按钮同一行 */}
             {needsDeviceSearch && (
               <div className="flex gap-2">
@@ -24,11 +24,19 @@ import {
 } from 'lucide-react';
 import clsx from 'clsx';
 import { useAppStore } from '@/stores/appStore';
-import { maaService } from '@/services/maaService';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import { maaService, PREVIEW_SCREENSHOT_OPTIONS } from '@/services/maaService';
 import { ContextMenu, useContextMenu, type MenuItem } from './ContextMenu';
 import { FrameRateSelector, getFrameInterval } from './FrameRateSelector';
 import { resolveI18nText } from '@/services/contentResolver';
-import { loggers, generateTaskPipelineOverride } from '@/utils';
+import {
+  loggers,
+  generateTaskPipelineOverride,
+  formatOverrideErrors,
+  getTaskTimeoutMs,
+  getTaskRetryPolicy,
+  toRunOptions,
+} from '@/utils';
 import type { TaskConfig } from '@/types/maa';
 import { normalizeAgentConfigs } from '@/types/interface';
 import { getInterfaceLangKey } from '@/i18n';
@@ -36,6 +44,12 @@ import { getMxuSpecialTask } from '@/types/specialTasks';
 
 const log = loggers.ui;
 
+// 截图流运行期间检查停止条件的间隔
+const STREAM_CHECK_INTERVAL = 200;
+
+// 截图流查看者序号（每次订阅使用独立的查看者 ID）
+let viewerSeq = 0;
+
 interface InstanceCardProps {
   instanceId: string;
   instanceName: string;
@@ -77,6 +91,7 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
     screenshotFrameRate,
     setShowAddTaskPanel,
     tcpCompatMode,
+    addLog,
   } = useAppStore();
 
   const langKey = getInterfaceLangKey(language);
@@ -89,7 +104,6 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
   const [isStarting, setIsStarting] = useState(false);
   const [isStopping, setIsStopping] = useState(false);
   const streamingRef = useRef(false);
-  const lastFrameTimeRef = useRef(0);
   const frameIntervalRef = useRef(getFrameInterval(screenshotFrameRate));
   const runningInstanceIdRef = useRef<string | null>(null);
 
@@ -134,6 +148,8 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
       deviceName = savedDevice.windowName;
     } else if (savedDevice?.playcoverAddress) {
       deviceName = savedDevice.playcoverAddress;
+    } else if (savedDevice?.imageFolderPath) {
+      deviceName = savedDevice.imageFolderPath;
     }
 
     const controllerLabel = currentController
@@ -225,6 +241,7 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           log.info(`[${instanceName}] 开始执行任务, 数量:`, enabledTasks.length);
 
           // 构建任务配置列表
+          const { taskTimeout, taskRetry } = useAppStore.getState();
           const taskConfigs: TaskConfig[] = [];
           for (const selectedTask of enabledTasks) {
             // 先检查是否是 MXU 特殊任务
@@ -242,6 +259,8 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
                 currentControllerName,
                 currentResourceName,
               ),
+              timeout_ms: getTaskTimeoutMs(taskTimeout),
+              retry: getTaskRetryPolicy(taskRetry, selectedTask.retry),
             });
             // MXU 特殊任务的 label 是 MXU i18n key，需要用 t() 翻译
             const taskDisplayName =
@@ -267,42 +286,73 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           setShowAddTaskPanel(false);
 
           // 启动任务
-          const taskIds = await maaService.startTasks(
+          const { task_ids: taskIds, rejected, warnings } = await maaService.startTasks(
             instanceId,
             taskConfigs,
             agentConfigs,
             basePath,
             tcpCompatMode,
+            toRunOptions(taskTimeout),
           );
 
           log.info(`[${instanceName}] 任务已提交, task_ids:`, taskIds);
 
+          // 未通过校验或提交失败的任务不会分配 task_id，其余任务按顺序与 task_id 对应
+          const rejectedIndices = new Set(rejected.map((r) => r.index));
+          const submittedTasks = enabledTasks.filter((_, index) => !rejectedIndices.has(index));
+          for (const r of rejected) {
+            addLog(instanceId, {
+              type: 'warning',
+              message: t('taskList.taskRejected', {
+                name: enabledTasks[r.index]?.customName || r.entry,
+                reason: formatOverrideErrors(r.errors),
+              }),
+            });
+          }
+          for (const w of warnings) {
+            addLog(instanceId, {
+              type: 'warning',
+              message: t('taskList.taskOverrideWarning', {
+                name: enabledTasks[w.index]?.customName || w.entry,
+                reason: formatOverrideErrors(w.warnings),
+              }),
+            });
+          }
+          if (taskIds.length === 0) {
+            throw new Error('所有任务均未提交');
+          }
+
           // 初始化任务运行状态
           const enabledTaskIds = enabledTasks.map((t) => t.id);
           setAllTasksRunStatus(instanceId, enabledTaskIds, 'pending');
+          for (const r of rejected) {
+            if (enabledTasks[r.index]) {
+              setTaskRunStatus(instanceId, enabledTasks[r.index].id, 'failed');
+            }
+          }
 
           // 记录映射关系
           taskIds.forEach((maaTaskId, index) => {
-            if (enabledTasks[index]) {
-              registerMaaTaskMapping(instanceId, maaTaskId, enabledTasks[index].id);
+            if (submittedTasks[index]) {
+              registerMaaTaskMapping(instanceId, maaTaskId, submittedTasks[index].id);
               // MXU 特殊任务的 label 需要用 t() 翻译
-              const specialTask = getMxuSpecialTask(enabledTasks[index].taskName);
+              const specialTask = getMxuSpecialTask(submittedTasks[index].taskName);
               const taskDef =
                 specialTask?.taskDef ||
-                projectInterface?.task.find((t) => t.name === enabledTasks[index].taskName);
+                projectInterface?.task.find((t) => t.name === submittedTasks[index].taskName);
               const taskDisplayName =
-                enabledTasks[index].customName ||
+                submittedTasks[index].customName ||
                 (specialTask && taskDef?.label
                   ? t(taskDef.label)
                   : resolveI18nText(taskDef?.label, translations)) ||
-                enabledTasks[index].taskName;
+                submittedTasks[index].taskName;
               registerTaskIdName(maaTaskId, taskDisplayName);
             }
           });
 
           // 第一个任务设为 running
-          if (enabledTasks.length > 0) {
-            setTaskRunStatus(instanceId, enabledTasks[0].id, 'running');
+          if (submittedTasks.length > 0) {
+            setTaskRunStatus(instanceId, submittedTasks[0].id, 'running');
           }
 
           // 设置任务队列
@@ -353,10 +403,11 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
       setShowAddTaskPanel,
       translations,
       tcpCompatMode,
+      addLog,
     ],
   );
 
-  // 获取截图
+  // 获取单帧截图（强制刷新时使用；预览使用缩小的 JPEG，降低多实例同时刷新时的开销）
   const captureFrame = useCallback(async (): Promise<string | null> => {
     if (!instanceId) return null;
 
@@ -364,7 +415,7 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
       const isRunning = await maaService.isRunning(instanceId);
 
       if (isRunning) {
-        const imageData = await maaService.getCachedImage(instanceId);
+        const imageData = await maaService.getCachedImage(instanceId, PREVIEW_SCREENSHOT_OPTIONS);
         return imageData || null;
       } else {
         const screencapId = await maaService.postScreencap(instanceId);
@@ -374,7 +425,7 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
         const success = await maaService.waitForScreencap(screencapId, 10000);
         if (!success) return null;
 
-        const imageData = await maaService.getCachedImage(instanceId);
+        const imageData = await maaService.getCachedImage(instanceId, PREVIEW_SCREENSHOT_OPTIONS);
         return imageData || null;
       }
     } catch {
@@ -382,44 +433,54 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
     }
   }, [instanceId]);
 
-  // 截图流循环
+  // 截图流：订阅后端截图流（与截图面板共享同一实例的截图循环），直到停止
   const streamLoop = useCallback(async () => {
-    // 初始化下一帧时间
-    let nextFrameTime = Date.now();
-
-    while (streamingRef.current) {
-      // 计算需要等待的时间（sleep until 下一帧时间点）
-      const now = Date.now();
-      const sleepTime = nextFrameTime - now;
-      if (sleepTime > 0) {
-        await new Promise((resolve) => setTimeout(resolve, sleepTime));
-      }
-
-      // 计算下一帧时间
-      const frameInterval = frameIntervalRef.current;
-      if (frameInterval > 0) {
-        nextFrameTime += frameInterval;
-        // 如果已经落后太多，重置到当前时间
-        if (nextFrameTime < Date.now()) {
-          nextFrameTime = Date.now() + frameInterval;
-        }
-      } else {
-        // unlimited 模式，立即执行下一帧
-        nextFrameTime = Date.now();
-      }
-
-      lastFrameTimeRef.current = Date.now();
+    const viewerId = `dashboard-${++viewerSeq}`;
+    const stopped = { current: false };
+    let unlisten: UnlistenFn | null = null;
 
-      try {
-        const imageData = await captureFrame();
-        if (imageData && streamingRef.current) {
-          setScreenshotUrl(imageData);
+    try {
+      unlisten = await maaService.onScreenStream(
+        instanceId,
+        viewerId,
+        (frame) => {
+          if (streamingRef.current) {
+            setScreenshotUrl(frame.data);
+          }
+        },
+        () => {
+          stopped.current = true;
+        },
+      );
+
+      let frameInterval = frameIntervalRef.current;
+      await maaService.startScreenStream(
+        instanceId,
+        viewerId,
+        frameInterval,
+        PREVIEW_SCREENSHOT_OPTIONS,
+      );
+
+      while (streamingRef.current && !stopped.current) {
+        // 帧率配置变化时更新订阅
+        if (frameIntervalRef.current !== frameInterval) {
+          frameInterval = frameIntervalRef.current;
+          await maaService.startScreenStream(
+            instanceId,
+            viewerId,
+            frameInterval,
+            PREVIEW_SCREENSHOT_OPTIONS,
+          );
         }
-      } catch {
-        // 静默处理
+        await new Promise((resolve) => setTimeout(resolve, STREAM_CHECK_INTERVAL));
       }
+    } catch {
+      // 静默处理
+    } finally {
+      unlisten?.();
+      maaService.stopScreenStream(instanceId, viewerId).catch(() => {});
     }
-  }, [instanceId, captureFrame]);
+  }, [instanceId]);
 
   // 组件卸载时停止流
   useEffect(() => {
@@ -496,12 +557,12 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
     };
   }, [isFullscreen]);
 
-  // 保存截图
+  // 保存截图（预览为压缩后的图像，保存原始 PNG）
   const saveScreenshot = useCallback(async () => {
     if (!screenshotUrl) return;
     try {
       const link = document.createElement('a');
-      link.href = screenshotUrl;
+      link.href = (await maaService.getCachedImage(instanceId)) || screenshotUrl;
       link.download = `screenshot_${instanceName}_${Date.now()}.png`;
       document.body.appendChild(link);
       link.click();
@@ -509,19 +570,19 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
     } catch {
       // 静默处理
     }
-  }, [screenshotUrl, instanceName]);
+  }, [screenshotUrl, instanceId, instanceName]);
 
   // 复制截图到剪贴板
   const copyScreenshot = useCallback(async () => {
     if (!screenshotUrl) return;
     try {
-      const response = await fetch(screenshotUrl);
+      const response = await fetch(await maaService.getCachedImage(instanceId));
       const blob = await response.blob();
       await navigator.clipboard.write([new ClipboardItem({ 'image/png': blob })]);
     } catch (err) {
       log.warn('复制截图失败:', err);
     }
-  }, [screenshotUrl]);
+  }, [screenshotUrl, instanceId]);
 
   // 断开连接
   const disconnect = useCallback(async () => {
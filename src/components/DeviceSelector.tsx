@@ -21,6 +21,7 @@ import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
 import type { ControllerItem } from '@/types/interface';
 import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
 import { loggers } from '@/utils/logger';
+import { withScreenshotResolution } from '@/utils/screenshotResolution';
 
 const log = loggers.device;
 
@@ -51,6 +52,10 @@ export function DeviceSelector({
     registerCtrlIdName,
   } = useAppStore();
 
+  // 读取实例的截图分辨率设置（连接时读取最新值）
+  const getInstanceScreenshotResolution = () =>
+    useAppStore.getState().instances.find((i) => i.id === instanceId)?.screenshotResolution;
+
   // 选中的设备（本地状态）
   const [selectedAdbDevice, setSelectedAdbDevice] = useState<AdbDevice | null>(null);
   const [selectedWindow, setSelectedWindow] = useState<Win32Window | null>(null);
@@ -243,7 +248,10 @@ export function DeviceSelector({
         throw new Error('请先选择设备');
       }
 
-      const ctrlId = await maaService.connectController(instanceId, config);
+      const ctrlId = await maaService.connectController(
+        instanceId,
+        withScreenshotResolution(config, controllerDef, getInstanceScreenshotResolution()),
+      );
 
       // 注册 ctrl_id 与设备名/类型的映射
       let deviceName = '';
@@ -320,7 +328,10 @@ export function DeviceSelector({
         config: device.config,
       };
 
-      const ctrlId = await maaService.connectController(instanceId, config);
+      const ctrlId = await maaService.connectController(
+        instanceId,
+        withScreenshotResolution(config, controllerDef, getInstanceScreenshotResolution()),
+      );
 
       // 注册 ctrl_id 与设备名/类型的映射
       registerCtrlIdName(ctrlId, device.name || device.address, 'device');
@@ -369,7 +380,10 @@ export function DeviceSelector({
         };
       }
 
-      const ctrlId = await maaService.connectController(instanceId, config);
+      const ctrlId = await maaService.connectController(
+        instanceId,
+        withScreenshotResolution(config, controllerDef, getInstanceScreenshotResolution()),
+      );
 
       // 注册 ctrl_id 与窗口名/类型的映射
       registerCtrlIdName(ctrlId, win.window_name || win.class_name, 'window');
@@ -20,6 +20,7 @@ import {
 } from '@/services/updateService';
 import { ReleaseNotes, DownloadProgressBar } from './UpdateInfoCard';
 import { loggers } from '@/utils/logger';
+import { getErrorMessage } from '@/utils/errors';
 
 export function InstallConfirmModal() {
   const { t } = useTranslation();
@@ -85,7 +86,7 @@ export function InstallConfirmModal() {
       if (error instanceof FallbackUpdateError) {
         setInstallError(error.message);
       } else {
-        setInstallError(error instanceof Error ? error.message : String(error));
+        setInstallError(getErrorMessage(error));
       }
     }
   }, [downloadSavePath, basePath, updateInfo, setInstallStatus, setInstallError, t]);
@@ -183,7 +184,7 @@ export function InstallConfirmModal() {
           if (error instanceof FallbackUpdateError) {
             setInstallError(error.message);
           } else {
-            setInstallError(error instanceof Error ? error.message : String(error));
+            setInstallError(getErrorMessage(error));
           }
         }
       })();
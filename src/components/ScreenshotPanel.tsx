@@ -10,14 +10,29 @@ import {
   RefreshCw,
   Download,
   Copy,
+  Film,
   Unplug,
+  MousePointerClick,
+  Scan,
 } from 'lucide-react';
 import clsx from 'clsx';
-import { maaService } from '@/services/maaService';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import {
+  maaService,
+  PREVIEW_SCREENSHOT_OPTIONS,
+  type ScreenStreamStopReason,
+} from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
+import type { ControllerInput, FrameExportFormat, ScreenshotResolution } from '@/types/maa';
 import { ContextMenu, useContextMenu, type MenuItem } from './ContextMenu';
 import { getFrameInterval } from './FrameRateSelector';
 import { loggers } from '@/utils/logger';
+import {
+  SCREENSHOT_RESOLUTION_PRESETS,
+  getControllerScreenshotResolution,
+  isSameScreenshotResolution,
+  resolveScreenshotResolution,
+} from '@/utils/screenshotResolution';
 
 const log = loggers.ui;
 
@@ -29,9 +44,56 @@ const withTimeout = <T,>(promise: Promise<T>, ms: number): Promise<T> => {
   ]);
 };
 
-const MAX_CONSECUTIVE_FAILURES = 20;
 const API_TIMEOUT = 30000;
 
+// 截图流运行期间检查停止条件的间隔
+const STREAM_CHECK_INTERVAL = 200;
+
+// 截图流查看者序号（每次订阅使用独立的查看者 ID）
+let viewerSeq = 0;
+
+// 远程操控：按下到抬起的移动距离（屏幕像素）小于该值视为点击，否则为滑动
+const TAP_SLOP = 10;
+const MIN_SWIPE_DURATION = 100;
+const MAX_SWIPE_DURATION = 2000;
+
+interface FramePoint {
+  x: number;
+  y: number;
+}
+
+interface PointerDown {
+  point: FramePoint;
+  clientX: number;
+  clientY: number;
+  time: number;
+}
+
+// 将鼠标位置换算为截图坐标（考虑 object-contain 的留白），落在画面外时返回 null
+const toFramePoint = (
+  e: React.PointerEvent<HTMLImageElement>,
+  frameSize: { width: number; height: number },
+): FramePoint | null => {
+  const rect = e.currentTarget.getBoundingClientRect();
+  const scale = Math.min(rect.width / frameSize.width, rect.height / frameSize.height);
+  if (!scale) return null;
+  const offsetX = (rect.width - frameSize.width * scale) / 2;
+  const offsetY = (rect.height - frameSize.height * scale) / 2;
+  const x = Math.round((e.clientX - rect.left - offsetX) / scale);
+  const y = Math.round((e.clientY - rect.top - offsetY) / scale);
+  if (x < 0 || y < 0 || x >= frameSize.width || y >= frameSize.height) return null;
+  return { x, y };
+};
+
+// 以二进制获取预览帧并生成 blob URL（替换或卸载时释放）
+const fetchPreviewFrame = async (instanceId: string): Promise<string | null> => {
+  const blob = await withTimeout(
+    maaService.getCachedImageBlob(instanceId, PREVIEW_SCREENSHOT_OPTIONS),
+    API_TIMEOUT,
+  );
+  return blob ? URL.createObjectURL(blob) : null;
+};
+
 export function ScreenshotPanel() {
   const { t } = useTranslation();
   const {
@@ -44,19 +106,27 @@ export function ScreenshotPanel() {
     screenshotPanelExpanded,
     setScreenshotPanelExpanded,
     screenshotFrameRate,
+    instances,
+    projectInterface,
+    selectedController,
+    setInstanceScreenshotResolution,
   } = useAppStore();
 
   const [screenshotUrl, setScreenshotUrl] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [isFullscreen, setIsFullscreen] = useState(false);
+  const [remoteControl, setRemoteControl] = useState(false);
 
   const { state: menuState, show: showMenu, hide: hideMenu } = useContextMenu();
 
   // 用于控制截图流的引用
   const streamingRef = useRef(false);
-  const lastFrameTimeRef = useRef(0);
   const frameIntervalRef = useRef(getFrameInterval(screenshotFrameRate));
 
+  // 远程操控：最近一帧的原始尺寸与按下时的位置
+  const frameSizeRef = useRef<{ width: number; height: number } | null>(null);
+  const pointerDownRef = useRef<PointerDown | null>(null);
+
   // 帧率配置变化时更新帧间隔
   useEffect(() => {
     frameIntervalRef.current = getFrameInterval(screenshotFrameRate);
@@ -77,7 +147,7 @@ export function ScreenshotPanel() {
     [instanceId, setInstanceScreenshotStreaming],
   );
 
-  // 获取单帧截图（任务未运行时使用）
+  // 获取单帧截图（强制刷新时使用）
   const captureFrame = useCallback(async (): Promise<string | null> => {
     if (!instanceId) return null;
 
@@ -92,33 +162,28 @@ export function ScreenshotPanel() {
         throw new Error('Screencap failed');
       }
 
-      const imageData = await withTimeout(maaService.getCachedImage(instanceId), API_TIMEOUT);
-      return imageData || null;
+      return await fetchPreviewFrame(instanceId);
     } catch (err) {
       log.warn('截图失败:', err);
       throw err;
     }
   }, [instanceId]);
 
-  // 获取缓存截图（任务运行时直接获取）
-  const getCachedFrame = useCallback(async (): Promise<string | null> => {
-    if (!instanceId) return null;
-
-    try {
-      const imageData = await withTimeout(maaService.getCachedImage(instanceId), API_TIMEOUT);
-      return imageData || null;
-    } catch (err) {
-      log.warn('获取缓存截图失败:', err);
-      throw err;
-    }
-  }, [instanceId]);
-
   // 全屏模式切换
   const toggleFullscreen = (e?: React.MouseEvent) => {
     e?.stopPropagation();
     setIsFullscreen(!isFullscreen);
   };
 
+  // 释放被替换的预览帧
+  useEffect(() => {
+    return () => {
+      if (screenshotUrl?.startsWith('blob:')) {
+        URL.revokeObjectURL(screenshotUrl);
+      }
+    };
+  }, [screenshotUrl]);
+
   // 组件卸载时停止截图流
   useEffect(() => {
     return () => {
@@ -143,87 +208,90 @@ export function ScreenshotPanel() {
     };
   }, [isFullscreen]);
 
-  // 截图流循环
+  // 截图流：订阅后端截图流（由后端按帧率截图并推送），直到停止或切换实例
   const streamLoop = useCallback(async () => {
     // 保存启动时的实例 ID，用于检查是否仍是活动实例
     const loopInstanceId = instanceId;
+    if (!loopInstanceId) return;
 
-    // 初始化下一帧时间
-    let nextFrameTime = Date.now();
-    let consecutiveFailures = 0;
-
-    while (streamingRef.current) {
-      // 检查当前实例是否仍是活动实例，避免非活动 tab 刷新截图
-      const currentActiveId = useAppStore.getState().activeInstanceId;
-      if (loopInstanceId !== currentActiveId) {
-        break;
-      }
-
-      // 检查连接状态
-      const connStatus = useAppStore.getState().instanceConnectionStatus[loopInstanceId];
-      if (connStatus !== 'Connected') {
-        setError('连接已断开');
-        break;
-      }
+    const viewerId = `screenshot-panel-${++viewerSeq}`;
+    const stopped: { reason: ScreenStreamStopReason | null } = { reason: null };
+    let unlisten: UnlistenFn | null = null;
 
-      // 等待下一帧时间
-      const now = Date.now();
-      const sleepTime = nextFrameTime - now;
-      if (sleepTime > 0) {
-        await new Promise((resolve) => setTimeout(resolve, sleepTime));
-      }
-
-      // 计算下一帧时间
-      const frameInterval = frameIntervalRef.current;
-      if (frameInterval > 0) {
-        nextFrameTime += frameInterval;
-        if (nextFrameTime < Date.now()) {
-          nextFrameTime = Date.now() + frameInterval;
+    try {
+      unlisten = await maaService.onScreenStream(
+        loopInstanceId,
+        viewerId,
+        (frame) => {
+          // 避免更新非活动 tab 的截图
+          if (streamingRef.current && loopInstanceId === useAppStore.getState().activeInstanceId) {
+            if (frame.width && frame.height) {
+              frameSizeRef.current = { width: frame.width, height: frame.height };
+            }
+            setScreenshotUrl(frame.data);
+            setError(null);
+          }
+        },
+        (reason) => {
+          stopped.reason = reason;
+        },
+      );
+
+      let frameInterval = frameIntervalRef.current;
+      await maaService.startScreenStream(
+        loopInstanceId,
+        viewerId,
+        frameInterval,
+        PREVIEW_SCREENSHOT_OPTIONS,
+      );
+
+      while (streamingRef.current && !stopped.reason) {
+        // 检查当前实例是否仍是活动实例，避免非活动 tab 占用截图流
+        const currentActiveId = useAppStore.getState().activeInstanceId;
+        if (loopInstanceId !== currentActiveId) {
+          break;
         }
-      } else {
-        nextFrameTime = Date.now();
-      }
-
-      lastFrameTimeRef.current = Date.now();
-
-      try {
-        const isRunning = await withTimeout(maaService.isRunning(loopInstanceId), API_TIMEOUT);
-
-        let imageData: string | null = null;
 
-        if (isRunning) {
-          // 任务运行中，直接获取缓存的截图（任务会自动更新缓存）
-          imageData = await getCachedFrame();
-        } else {
-          // 任务未运行，需要主动发起截图
-          imageData = await captureFrame();
+        // 检查连接状态
+        const connStatus = useAppStore.getState().instanceConnectionStatus[loopInstanceId];
+        if (connStatus !== 'Connected') {
+          setError('连接已断开');
+          break;
         }
 
-        // 再次检查是否仍是活动实例，避免更新非活动 tab 的截图
-        if (
-          imageData &&
-          streamingRef.current &&
-          loopInstanceId === useAppStore.getState().activeInstanceId
-        ) {
-          setScreenshotUrl(imageData);
-          setError(null);
-          consecutiveFailures = 0;
+        // 帧率配置变化时更新订阅
+        if (frameIntervalRef.current !== frameInterval) {
+          frameInterval = frameIntervalRef.current;
+          await maaService.startScreenStream(
+            loopInstanceId,
+            viewerId,
+            frameInterval,
+            PREVIEW_SCREENSHOT_OPTIONS,
+          );
         }
-      } catch (err) {
-        consecutiveFailures++;
-        log.warn(`截图失败 (${consecutiveFailures}/${MAX_CONSECUTIVE_FAILURES}):`, err);
 
-        if (consecutiveFailures >= MAX_CONSECUTIVE_FAILURES) {
-          setError('截图连续失败，已停止');
-          break;
-        }
+        await new Promise((resolve) => setTimeout(resolve, STREAM_CHECK_INTERVAL));
       }
+
+      if (stopped.reason === 'capture_failed') {
+        setError('截图连续失败，已停止');
+      } else if (stopped.reason === 'disconnected') {
+        setError('连接已断开');
+      }
+    } catch (err) {
+      log.warn('截图流启动失败:', err);
+      setError('截图流启动失败');
+    } finally {
+      unlisten?.();
+      maaService.stopScreenStream(loopInstanceId, viewerId).catch((err) => {
+        log.warn('取消订阅截图流失败:', err);
+      });
     }
 
     // 循环结束
     streamingRef.current = false;
     setIsStreaming(false);
-  }, [instanceId, captureFrame, getCachedFrame, setIsStreaming]);
+  }, [instanceId, setIsStreaming]);
 
   // 开始/停止截图流
   const toggleStreaming = useCallback(
@@ -262,6 +330,8 @@ export function ScreenshotPanel() {
     // 清除截图，等待新实例的截图
     setScreenshotUrl(null);
     setError(null);
+    frameSizeRef.current = null;
+    setRemoteControl(false);
 
     // 同步 streamingRef 与新实例的截图流状态
     const newInstanceStreaming = instanceId
@@ -331,14 +401,14 @@ export function ScreenshotPanel() {
     hasAutoStartedRef.current = false;
   }, [instanceId]);
 
-  // 保存截图
+  // 保存截图（预览为压缩后的图像，保存原始 PNG）
   const saveScreenshot = useCallback(async () => {
     if (!screenshotUrl) return;
 
     try {
       // 创建下载链接
       const link = document.createElement('a');
-      link.href = screenshotUrl;
+      link.href = (await maaService.getCachedImage(instanceId)) || screenshotUrl;
       link.download = `screenshot_${Date.now()}.png`;
       document.body.appendChild(link);
       link.click();
@@ -346,20 +416,37 @@ export function ScreenshotPanel() {
     } catch (err) {
       log.warn('保存截图失败:', err);
     }
-  }, [screenshotUrl]);
+  }, [screenshotUrl, instanceId]);
 
   // 复制截图到剪贴板
   const copyScreenshot = useCallback(async () => {
     if (!screenshotUrl) return;
 
     try {
-      const response = await fetch(screenshotUrl);
+      const response = await fetch(await maaService.getCachedImage(instanceId));
       const blob = await response.blob();
       await navigator.clipboard.write([new ClipboardItem({ 'image/png': blob })]);
     } catch (err) {
       log.warn('复制截图失败:', err);
     }
-  }, [screenshotUrl]);
+  }, [screenshotUrl, instanceId]);
+
+  // 导出最近画面（APNG / GIF）并在文件管理器中显示
+  const exportRecentFrames = useCallback(
+    async (format: FrameExportFormat) => {
+      if (!instanceId) return;
+
+      try {
+        const path = await maaService.exportRecentFrames(instanceId, format);
+        log.info('最近画面已导出:', path);
+        const { revealItemInDir } = await import('@tauri-apps/plugin-opener');
+        await revealItemInDir(path);
+      } catch (err) {
+        log.warn('导出最近画面失败:', err);
+      }
+    },
+    [instanceId],
+  );
 
   // 强制刷新截图
   const forceRefresh = useCallback(async () => {
@@ -376,6 +463,47 @@ export function ScreenshotPanel() {
     }
   }, [instanceId, captureFrame]);
 
+  // 当前实例的控制器与截图分辨率设置
+  const instanceResolution = instances.find((i) => i.id === instanceId)?.screenshotResolution;
+  const controllerName = selectedController[instanceId] || projectInterface?.controller[0]?.name;
+  const currentController = projectInterface?.controller.find((c) => c.name === controllerName);
+
+  const formatResolution = useCallback(
+    (resolution: ScreenshotResolution) =>
+      resolution.mode === 'raw'
+        ? t('screenshot.resolutionRaw')
+        : resolution.mode === 'long_side'
+          ? t('screenshot.resolutionLongSide', { value: resolution.value })
+          : t('screenshot.resolutionShortSide', { value: resolution.value }),
+    [t],
+  );
+
+  // 切换截图分辨率（保存到实例，已连接时立即应用到控制器；undefined 表示跟随控制器）
+  const changeScreenshotResolution = useCallback(
+    async (resolution: ScreenshotResolution | undefined) => {
+      if (!instanceId) return;
+      setInstanceScreenshotResolution(instanceId, resolution);
+      if (connectionStatus !== 'Connected') return;
+
+      try {
+        await maaService.setScreenshotResolution(
+          instanceId,
+          resolveScreenshotResolution(currentController, resolution),
+        );
+        await forceRefresh();
+      } catch (err) {
+        log.warn('设置截图分辨率失败:', err);
+      }
+    },
+    [
+      instanceId,
+      connectionStatus,
+      currentController,
+      setInstanceScreenshotResolution,
+      forceRefresh,
+    ],
+  );
+
   // 断开连接（销毁实例）
   const disconnect = useCallback(async () => {
     if (!instanceId) return;
@@ -393,6 +521,72 @@ export function ScreenshotPanel() {
     }
   }, [instanceId, setIsStreaming]);
 
+  // 远程操控：按下时记录位置
+  const handlePointerDown = useCallback(
+    (e: React.PointerEvent<HTMLImageElement>) => {
+      if (!remoteControl || e.button !== 0 || !frameSizeRef.current) return;
+      const point = toFramePoint(e, frameSizeRef.current);
+      if (!point) return;
+      e.preventDefault();
+      e.stopPropagation();
+      e.currentTarget.setPointerCapture(e.pointerId);
+      pointerDownRef.current = {
+        point,
+        clientX: e.clientX,
+        clientY: e.clientY,
+        time: Date.now(),
+      };
+    },
+    [remoteControl],
+  );
+
+  // 远程操控：抬起时按移动距离发送点击或滑动
+  const handlePointerUp = useCallback(
+    (e: React.PointerEvent<HTMLImageElement>) => {
+      const down = pointerDownRef.current;
+      pointerDownRef.current = null;
+      if (!remoteControl || !down || !frameSizeRef.current || !instanceId) return;
+      e.stopPropagation();
+
+      let input: ControllerInput;
+      const moved = Math.hypot(e.clientX - down.clientX, e.clientY - down.clientY);
+      if (moved < TAP_SLOP) {
+        input = { type: 'Click', x: down.point.x, y: down.point.y };
+      } else {
+        // 滑动终点超出画面时不发送
+        const end = toFramePoint(e, frameSizeRef.current);
+        if (!end) return;
+        const duration = Math.min(
+          Math.max(Date.now() - down.time, MIN_SWIPE_DURATION),
+          MAX_SWIPE_DURATION,
+        );
+        input = {
+          type: 'Swipe',
+          x1: down.point.x,
+          y1: down.point.y,
+          x2: end.x,
+          y2: end.y,
+          duration,
+        };
+      }
+
+      maaService.postInput(instanceId, input).catch((err) => {
+        log.warn('远程操控输入失败:', err);
+      });
+    },
+    [remoteControl, instanceId],
+  );
+
+  // 远程操控模式下截图的交互属性
+  const remoteControlProps = remoteControl
+    ? {
+        draggable: false,
+        onPointerDown: handlePointerDown,
+        onPointerUp: handlePointerUp,
+        onClick: (e: React.MouseEvent) => e.stopPropagation(),
+      }
+    : {};
+
   // 右键菜单处理
   const handleContextMenu = useCallback(
     (e: React.MouseEvent) => {
@@ -439,7 +633,44 @@ export function ScreenshotPanel() {
           disabled: !screenshotUrl,
           onClick: copyScreenshot,
         },
+        {
+          id: 'export-frames',
+          label: t('contextMenu.exportRecentFrames'),
+          icon: Film,
+          disabled: !instanceId || !isConnected,
+          onClick: () => exportRecentFrames('apng'),
+        },
+        {
+          id: 'export-frames-gif',
+          label: t('contextMenu.exportRecentFramesGif'),
+          icon: Film,
+          disabled: !instanceId || !isConnected,
+          onClick: () => exportRecentFrames('gif'),
+        },
         { id: 'divider-3', label: '', divider: true },
+        {
+          id: 'resolution',
+          label: t('contextMenu.screenshotResolution'),
+          icon: Scan,
+          disabled: true,
+        },
+        {
+          id: 'resolution-controller',
+          label: t('screenshot.resolutionFollowController', {
+            resolution: formatResolution(getControllerScreenshotResolution(currentController)),
+          }),
+          checked: !instanceResolution,
+          disabled: !instanceId,
+          onClick: () => changeScreenshotResolution(undefined),
+        },
+        ...SCREENSHOT_RESOLUTION_PRESETS.map((preset, index) => ({
+          id: `resolution-${index}`,
+          label: formatResolution(preset),
+          checked: isSameScreenshotResolution(instanceResolution, preset),
+          disabled: !instanceId,
+          onClick: () => changeScreenshotResolution(preset),
+        })),
+        { id: 'divider-4', label: '', divider: true },
         {
           id: 'disconnect',
           label: t('contextMenu.disconnect'),
@@ -462,6 +693,11 @@ export function ScreenshotPanel() {
       forceRefresh,
       saveScreenshot,
       copyScreenshot,
+      exportRecentFrames,
+      instanceResolution,
+      currentController,
+      formatResolution,
+      changeScreenshotResolution,
       disconnect,
       showMenu,
     ],
@@ -476,6 +712,7 @@ export function ScreenshotPanel() {
     // 连接断开时清空截图（如切换控制器、断开连接等场景）
     if (wasConnected && !isConnected) {
       setScreenshotUrl(null);
+      setRemoteControl(false);
       streamingRef.current = false;
       setIsStreaming(false);
     }
@@ -549,6 +786,30 @@ export function ScreenshotPanel() {
             {isStreaming ? <Pause className="w-3.5 h-3.5" /> : <Play className="w-3.5 h-3.5" />}
           </button>
 
+          {/* 远程操控开关按钮 */}
+          <button
+            onClick={(e) => {
+              e.stopPropagation();
+              setRemoteControl(!remoteControl);
+            }}
+            disabled={connectionStatus !== 'Connected'}
+            className={clsx(
+              'p-1 rounded-md transition-colors',
+              connectionStatus !== 'Connected'
+                ? 'text-text-muted cursor-not-allowed'
+                : remoteControl
+                  ? 'text-accent hover:bg-bg-tertiary'
+                  : 'text-text-secondary hover:bg-bg-tertiary hover:text-text-primary',
+            )}
+            title={
+              remoteControl
+                ? t('screenshot.disableRemoteControl')
+                : t('screenshot.enableRemoteControl')
+            }
+          >
+            <MousePointerClick className="w-3.5 h-3.5" />
+          </button>
+
           {/* 全屏按钮 */}
           <button
             onClick={(e) => {
@@ -594,7 +855,11 @@ export function ScreenshotPanel() {
                   <img
                     src={screenshotUrl}
                     alt="Screenshot"
-                    className="w-full h-full object-contain rounded-md"
+                    className={clsx(
+                      'w-full h-full object-contain rounded-md',
+                      remoteControl && 'cursor-crosshair select-none',
+                    )}
+                    {...remoteControlProps}
                   />
                   {/* 流模式指示器 */}
                   {isStreaming && (
@@ -665,7 +930,11 @@ export function ScreenshotPanel() {
               <img
                 src={screenshotUrl}
                 alt="Screenshot"
-                className="max-w-full max-h-[calc(90vh-80px)] object-contain rounded-md"
+                className={clsx(
+                  'max-w-full max-h-[calc(90vh-80px)] object-contain rounded-md',
+                  remoteControl && 'cursor-crosshair select-none',
+                )}
+                {...remoteControlProps}
               />
             </div>
           </div>
@@ -0,0 +1,305 @@
+import { useState, useEffect, useCallback } from 'react';
+import { useTranslation } from 'react-i18next';
+import {
+  ListTree,
+  X,
+  RefreshCw,
+  Check,
+  AlertCircle,
+  ChevronRight,
+  ChevronDown,
+  Image as ImageIcon,
+} from 'lucide-react';
+
+import { maaService } from '@/services/maaService';
+import type { TaskDetail, NodeDetail, RecognitionImages } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+
+const log = loggers.task;
+
+/** 任务未结束时自动刷新的间隔（毫秒） */
+const REFRESH_INTERVAL = 1000;
+
+/** 计算耗时（毫秒），缺少开始或结束时间时返回 null */
+function elapsed(timing: { started_at: number | null; ended_at: number | null }): number | null {
+  if (timing.started_at === null || timing.ended_at === null) return null;
+  return timing.ended_at - timing.started_at;
+}
+
+function formatElapsed(ms: number | null): string {
+  if (ms === null) return '-';
+  return ms < 1000 ? `${ms} ms` : `${(ms / 1000).toFixed(2)} s`;
+}
+
+/** 识别使用的截图与标注图（按需加载，需开启调试模式） */
+function RecognitionImagesView({ instanceId, recoId }: { instanceId: string; recoId: number }) {
+  const { t } = useTranslation();
+  const [images, setImages] = useState<RecognitionImages | null>(null);
+  const [visible, setVisible] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const toggle = async () => {
+    if (visible) {
+      setVisible(false);
+      return;
+    }
+    setVisible(true);
+    if (images) return;
+    setLoading(true);
+    try {
+      setImages(await maaService.getRecognitionImages(instanceId, recoId));
+      setError(null);
+    } catch (err) {
+      log.warn('获取识别图像失败:', err);
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const entries = images
+    ? [
+        ...(images.raw ? [{ label: t('taskDetail.rawImage'), src: images.raw }] : []),
+        ...images.draws.map((src, i) => ({
+          label: t('taskDetail.drawImage', { index: i + 1 }),
+          src,
+        })),
+      ]
+    : [];
+
+  return (
+    <div className="space-y-2">
+      <button
+        type="button"
+        onClick={toggle}
+        className="flex items-center gap-1 text-accent hover:underline"
+      >
+        <ImageIcon className="w-3.5 h-3.5" />
+        {visible ? t('taskDetail.hideImages') : t('taskDetail.showImages')}
+      </button>
+      {visible &&
+        (loading ? (
+          <p className="text-text-muted">{t('taskDetail.loading')}</p>
+        ) : error ? (
+          <p className="text-error">{error}</p>
+        ) : entries.length === 0 ? (
+          <p className="text-text-muted">{t('taskDetail.imagesUnavailable')}</p>
+        ) : (
+          <div className="grid grid-cols-2 gap-2">
+            {entries.map((entry) => (
+              <figure key={entry.label} className="space-y-1">
+                <a href={entry.src} target="_blank" rel="noreferrer">
+                  <img
+                    src={entry.src}
+                    alt={entry.label}
+                    className="w-full rounded border border-border bg-bg-secondary"
+                  />
+                </a>
+                <figcaption className="text-text-muted">{entry.label}</figcaption>
+              </figure>
+            ))}
+          </div>
+        ))}
+    </div>
+  );
+}
+
+/** 单个节点的执行记录（可展开查看识别与动作结果） */
+function NodeRow({
+  instanceId,
+  node,
+  index,
+}: {
+  instanceId: string;
+  node: NodeDetail;
+  index: number;
+}) {
+  const { t } = useTranslation();
+  const [expanded, setExpanded] = useState(false);
+  const reco = node.recognition;
+  const action = node.action;
+
+  return (
+    <div className="border border-border rounded-lg overflow-hidden">
+      <button
+        type="button"
+        onClick={() => setExpanded(!expanded)}
+        className="w-full flex items-center gap-2 px-3 py-2 text-left text-sm hover:bg-bg-hover transition-colors"
+      >
+        {expanded ? (
+          <ChevronDown className="w-4 h-4 text-text-muted flex-shrink-0" />
+        ) : (
+          <ChevronRight className="w-4 h-4 text-text-muted flex-shrink-0" />
+        )}
+        <span className="text-xs text-text-muted w-6 flex-shrink-0">{index + 1}</span>
+        {node.completed ? (
+          <Check className="w-4 h-4 text-success flex-shrink-0" />
+        ) : (
+          <AlertCircle className="w-4 h-4 text-error flex-shrink-0" />
+        )}
+        <span className="flex-1 min-w-0 truncate font-mono text-text-primary">{node.name}</span>
+        {reco && <span className="text-xs text-text-muted flex-shrink-0">{reco.algorithm}</span>}
+        <span className="text-xs text-text-secondary w-16 text-right flex-shrink-0">
+          {formatElapsed(elapsed(node))}
+        </span>
+      </button>
+
+      {expanded && (
+        <div className="px-3 py-2 space-y-3 border-t border-border bg-bg-tertiary/40 text-xs">
+          {reco && (
+            <div className="space-y-1">
+              <div className="flex items-center gap-2 text-text-secondary">
+                <span className="font-medium text-text-primary">{t('taskDetail.recognition')}</span>
+                <span>{reco.algorithm}</span>
+                <span className={reco.hit ? 'text-success' : 'text-error'}>
+                  {reco.hit ? t('taskDetail.hit') : t('taskDetail.miss')}
+                </span>
+                {reco.hit && <span className="font-mono">[{reco.box_rect.join(', ')}]</span>}
+                <span className="ml-auto">{formatElapsed(elapsed(reco))}</span>
+              </div>
+              <pre className="max-h-40 overflow-auto p-2 rounded bg-bg-secondary font-mono text-text-secondary whitespace-pre-wrap break-all">
+                {JSON.stringify(reco.detail, null, 2)}
+              </pre>
+              <RecognitionImagesView instanceId={instanceId} recoId={reco.reco_id} />
+            </div>
+          )}
+          {action ? (
+            <div className="space-y-1">
+              <div className="flex items-center gap-2 text-text-secondary">
+                <span className="font-medium text-text-primary">{t('taskDetail.action')}</span>
+                <span>{action.action}</span>
+                <span className={action.success ? 'text-success' : 'text-error'}>
+                  {action.success ? t('taskDetail.actionSucceeded') : t('taskDetail.actionFailed')}
+                </span>
+                <span className="font-mono">[{action.box_rect.join(', ')}]</span>
+                <span className="ml-auto">{formatElapsed(elapsed(action))}</span>
+              </div>
+              <pre className="max-h-40 overflow-auto p-2 rounded bg-bg-secondary font-mono text-text-secondary whitespace-pre-wrap break-all">
+                {JSON.stringify(action.detail, null, 2)}
+              </pre>
+            </div>
+          ) : (
+            <p className="text-text-muted">{t('taskDetail.noAction')}</p>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+/** 任务执行详情弹窗：按执行顺序列出节点及其识别、动作结果与耗时 */
+export function TaskDetailModal({
+  instanceId,
+  taskId,
+  title,
+  onClose,
+}: {
+  instanceId: string;
+  /** MaaFramework 任务 ID */
+  taskId: number;
+  title: string;
+  onClose: () => void;
+}) {
+  const { t } = useTranslation();
+  const [detail, setDetail] = useState<TaskDetail | null>(null);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setDetail(await maaService.getTaskDetail(instanceId, taskId));
+      setError(null);
+    } catch (err) {
+      log.warn('获取任务详情失败:', err);
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setLoading(false);
+    }
+  }, [instanceId, taskId]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  // 任务未结束时定时刷新
+  const finished = detail?.status === 'Succeeded' || detail?.status === 'Failed';
+  useEffect(() => {
+    if (!detail || finished) return;
+    const timer = setInterval(refresh, REFRESH_INTERVAL);
+    return () => clearInterval(timer);
+  }, [detail, finished, refresh]);
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') onClose();
+    };
+    document.addEventListener('keydown', onKeyDown);
+    return () => document.removeEventListener('keydown', onKeyDown);
+  }, [onClose]);
+
+  return (
+    <div
+      className="fixed inset-0 z-50 flex items-center justify-center bg-black/50 backdrop-blur-sm"
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget) onClose();
+      }}
+    >
+      <div
+        role="dialog"
+        aria-modal="true"
+        aria-label={title}
+        className="w-full max-w-2xl max-h-[85vh] mx-4 bg-bg-secondary rounded-xl shadow-2xl border border-border overflow-hidden flex flex-col"
+      >
+        {/* 标题栏 */}
+        <div className="flex items-center gap-2 px-4 py-3 bg-bg-tertiary border-b border-border flex-shrink-0">
+          <ListTree className="w-5 h-5 text-accent" />
+          <span className="flex-1 min-w-0 truncate text-sm font-medium text-text-primary">
+            {t('taskDetail.title', { name: title })}
+          </span>
+          <button
+            onClick={refresh}
+            className="p-1.5 rounded-md hover:bg-bg-hover text-text-secondary transition-colors"
+            title={t('taskDetail.refresh')}
+          >
+            <RefreshCw className="w-4 h-4" />
+          </button>
+          <button
+            onClick={onClose}
+            className="p-1.5 rounded-md hover:bg-bg-hover text-text-secondary transition-colors"
+            title={t('common.close')}
+          >
+            <X className="w-4 h-4" />
+          </button>
+        </div>
+
+        {/* 概要 */}
+        {detail && (
+          <div className="flex items-center gap-4 px-4 py-2 border-b border-border text-xs text-text-secondary flex-shrink-0">
+            <span className="font-mono">{detail.entry}</span>
+            <span>{t(`taskDetail.status.${detail.status}`)}</span>
+            <span>{t('taskDetail.nodeCount', { count: detail.nodes.length })}</span>
+            <span className="ml-auto">{formatElapsed(elapsed(detail))}</span>
+          </div>
+        )}
+
+        {/* 节点列表 */}
+        <div className="flex-1 min-h-0 overflow-y-auto p-4 space-y-2">
+          {loading ? (
+            <p className="text-sm text-text-muted text-center">{t('taskDetail.loading')}</p>
+          ) : error ? (
+            <p className="text-sm text-error text-center">{error}</p>
+          ) : !detail ? (
+            <p className="text-sm text-text-muted text-center">{t('taskDetail.notFound')}</p>
+          ) : detail.nodes.length === 0 ? (
+            <p className="text-sm text-text-muted text-center">{t('taskDetail.noNodes')}</p>
+          ) : (
+            detail.nodes.map((node, index) => (
+              <NodeRow key={node.node_id} instanceId={instanceId} node={node} index={index} />
+            ))
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -20,6 +20,11 @@ import {
   FileText,
   Link,
   AlertCircle,
+  ListTree,
+  ListPlus,
+  ListStart,
+  ListX,
+  RotateCcw,
 } from 'lucide-react';
 import { useAppStore, type TaskRunStatus } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
@@ -29,11 +34,14 @@ import { OptionEditor, SwitchGrid, switchHasNestedOptions } from './OptionEditor
 import { ContextMenu, useContextMenu, type MenuItem } from './ContextMenu';
 import { Tooltip } from './ui/Tooltip';
 import { ConfirmDialog } from './ConfirmDialog';
+import { TaskDetailModal } from './TaskDetailModal';
+import { TaskRetryModal } from './TaskRetryModal';
 import type { SelectedTask } from '@/types/interface';
 import { isMxuSpecialTask, getMxuSpecialTask, findMxuOptionByKey } from '@/types/specialTasks';
 import { getInterfaceLangKey } from '@/i18n';
 import clsx from 'clsx';
 import { loggers } from '@/utils/logger';
+import { useTaskQueue } from '@/utils/useTaskQueue';
 
 /** 选项预览标签组件 */
 function OptionPreviewTag({
@@ -311,6 +319,9 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
   const [isEditing, setIsEditing] = useState(false);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const [editName, setEditName] = useState('');
+  // 正在查看执行详情的 MaaFramework 任务 ID
+  const [detailTaskId, setDetailTaskId] = useState<number | null>(null);
+  const [showRetrySettings, setShowRetrySettings] = useState(false);
 
   const {
     projectInterface,
@@ -463,6 +474,7 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
   ]);
 
   const { state: menuState, show: showMenu, hide: hideMenu } = useContextMenu();
+  const { queueTask, cancelQueuedTask, runQueuedTaskNext } = useTaskQueue(instanceId);
 
   // 获取翻译表
   const translations = interfaceTranslations[langKey];
@@ -654,6 +666,42 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
       const taskIndex = tasks.findIndex((t) => t.id === task.id);
       const isFirst = taskIndex === 0;
       const isLast = taskIndex === tasks.length - 1;
+      // 本次或上次运行中对应的任务（从未运行过时没有执行详情）
+      const maaTaskId = findMaaTaskIdBySelectedTaskId(instanceId, task.id);
+
+      // 运行中调整任务队列：等待执行的任务可提前或取消，其余任务可加入队列
+      const isQueued = taskRunStatus === 'pending';
+      const canQueue = taskRunStatus !== 'running' && !isIncompatible;
+      const queueMenuItems: MenuItem[] = [
+        { id: 'divider-queue', label: '', divider: true },
+        {
+          id: 'run-next',
+          label: t('contextMenu.runTaskNext'),
+          icon: ListStart,
+          disabled: !isQueued && !canQueue,
+          onClick: () => {
+            if (isQueued) {
+              runQueuedTaskNext(task.id);
+            } else {
+              queueTask(task, displayName, true);
+            }
+          },
+        },
+        isQueued
+          ? {
+              id: 'cancel-queued',
+              label: t('contextMenu.cancelQueuedTask'),
+              icon: ListX,
+              onClick: () => cancelQueuedTask(task.id),
+            }
+          : {
+              id: 'queue',
+              label: t('contextMenu.queueTask'),
+              icon: ListPlus,
+              disabled: !canQueue,
+              onClick: () => queueTask(task, displayName, false),
+            },
+      ];
 
       const menuItems: MenuItem[] = [
         {
@@ -672,6 +720,19 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
             setIsEditing(true);
           },
         },
+        {
+          id: 'detail',
+          label: t('contextMenu.viewTaskDetail'),
+          icon: ListTree,
+          disabled: maaTaskId === null,
+          onClick: () => setDetailTaskId(maaTaskId),
+        },
+        {
+          id: 'retry',
+          label: t('contextMenu.taskRetrySettings'),
+          icon: RotateCcw,
+          onClick: () => setShowRetrySettings(true),
+        },
         { id: 'divider-1', label: '', divider: true },
         {
           id: 'toggle',
@@ -692,6 +753,7 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
               },
             ]
           : []),
+        ...(isInstanceRunning ? queueMenuItems : []),
         { id: 'divider-2', label: '', divider: true },
         {
           id: 'move-up',
@@ -746,6 +808,7 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
       instanceId,
       canExpand,
       getActiveInstance,
+      findMaaTaskIdBySelectedTaskId,
       duplicateTask,
       toggleTaskEnabled,
       toggleTaskExpanded,
@@ -758,6 +821,12 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
       isInstanceRunning,
       canReorder,
       canDelete,
+      taskRunStatus,
+      isIncompatible,
+      displayName,
+      queueTask,
+      cancelQueuedTask,
+      runQueuedTaskNext,
     ],
   );
 
@@ -937,6 +1006,11 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
                 {task.customName && (
                   <span className="flex-shrink-0 text-xs text-text-muted">({originalLabel})</span>
                 )}
+                {task.retry && (
+                  <Tooltip content={t('taskItem.retryOverridden')}>
+                    <RotateCcw className="w-3 h-3 flex-shrink-0 text-text-muted" />
+                  </Tooltip>
+                )}
               </div>
 
               {/* 不带选项的任务：直接显示不兼容警告 */}
@@ -1088,6 +1162,26 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
           removeTaskFromInstance(instanceId, task.id);
         }}
       />
+
+      {/* 执行详情弹窗 */}
+      {detailTaskId !== null && (
+        <TaskDetailModal
+          instanceId={instanceId}
+          taskId={detailTaskId}
+          title={displayName}
+          onClose={() => setDetailTaskId(null)}
+        />
+      )}
+
+      {/* 重试设置弹窗 */}
+      {showRetrySettings && (
+        <TaskRetryModal
+          instanceId={instanceId}
+          task={task}
+          title={displayName}
+          onClose={() => setShowRetrySettings(false)}
+        />
+      )}
     </div>
   );
 }
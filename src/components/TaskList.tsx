@@ -32,6 +32,7 @@ import { TaskItem } from './TaskItem';
 import { ActionItem } from './ActionItem';
 import { ContextMenu, useContextMenu, type MenuItem } from './ContextMenu';
 import type { OptionValue, SelectedTask, PresetItem } from '@/types/interface';
+import type { TaskRetryOverride } from '@/types/config';
 import { ConfirmDialog } from './ConfirmDialog';
 import { getInterfaceLangKey } from '@/i18n';
 import { TaskTransferPreview } from './TaskTransferPreview';
@@ -159,6 +160,7 @@ export function TaskList() {
         customName: t.customName,
         enabled: t.enabled,
         optionValues: t.optionValues,
+        retry: t.retry,
       })),
     };
   }, [instance]);
@@ -260,6 +262,7 @@ export function TaskList() {
         customName: rt.customName ? String(rt.customName) : undefined,
         enabled: rt.enabled !== false,
         optionValues,
+        retry: rt.retry as TaskRetryOverride | undefined,
         expanded: false,
       } satisfies SelectedTask;
     });
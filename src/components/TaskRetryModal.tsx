@@ -0,0 +1,192 @@
+import { useState, useEffect } from 'react';
+import { useTranslation } from 'react-i18next';
+import { RotateCcw, X } from 'lucide-react';
+import clsx from 'clsx';
+
+import { useAppStore } from '@/stores/appStore';
+import { SwitchButton } from '@/components/FormControls';
+import type { SelectedTask } from '@/types/interface';
+
+/** 重试次数上限 */
+const MAX_RETRY_ATTEMPTS = 10;
+
+/** 重试前等待时间上限（秒） */
+const MAX_RETRY_DELAY_SECONDS = 3600;
+
+/** 解析输入的整数，超出范围时返回 null */
+function parseInteger(input: string, min: number, max: number): number | null {
+  const value = Number(input);
+  return Number.isInteger(value) && value >= min && value <= max ? value : null;
+}
+
+/** 单个任务的失败重试设置弹窗（未单独设置时使用全局的任务失败重试设置） */
+export function TaskRetryModal({
+  instanceId,
+  task,
+  title,
+  onClose,
+}: {
+  instanceId: string;
+  task: SelectedTask;
+  title: string;
+  onClose: () => void;
+}) {
+  const { t } = useTranslation();
+  const { taskRetry, setTaskRetryOverride } = useAppStore();
+
+  // 未单独设置时以全局设置作为初始值
+  const initial = task.retry ?? taskRetry;
+  const [override, setOverride] = useState(task.retry !== undefined);
+  const [attemptsInput, setAttemptsInput] = useState(String(initial.attempts));
+  const [delayInput, setDelayInput] = useState(String(initial.delaySeconds));
+  const [retryOnTimeout, setRetryOnTimeout] = useState(initial.retryOnTimeout);
+  const [restartApp, setRestartApp] = useState(task.retry?.restartApp ?? '');
+
+  const attempts = parseInteger(attemptsInput, 0, MAX_RETRY_ATTEMPTS);
+  const delaySeconds = parseInteger(delayInput, 0, MAX_RETRY_DELAY_SECONDS);
+  const invalid = override && (attempts === null || delaySeconds === null);
+
+  const handleSave = () => {
+    if (invalid) return;
+    setTaskRetryOverride(
+      instanceId,
+      task.id,
+      override && attempts !== null && delaySeconds !== null
+        ? {
+            attempts,
+            delaySeconds,
+            retryOnTimeout,
+            restartApp: restartApp.trim() || undefined,
+          }
+        : undefined,
+    );
+    onClose();
+  };
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') onClose();
+    };
+    document.addEventListener('keydown', onKeyDown);
+    return () => document.removeEventListener('keydown', onKeyDown);
+  }, [onClose]);
+
+  const inputClassName =
+    'px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50';
+
+  return (
+    <div
+      className="fixed inset-0 z-50 flex items-center justify-center bg-black/50 backdrop-blur-sm"
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget) onClose();
+      }}
+    >
+      <div
+        role="dialog"
+        aria-modal="true"
+        aria-label={title}
+        className="w-full max-w-md mx-4 bg-bg-secondary rounded-xl shadow-2xl border border-border overflow-hidden flex flex-col"
+      >
+        {/* 标题栏 */}
+        <div className="flex items-center gap-2 px-4 py-3 bg-bg-tertiary border-b border-border">
+          <RotateCcw className="w-5 h-5 text-accent" />
+          <span className="flex-1 min-w-0 truncate text-sm font-medium text-text-primary">
+            {t('taskRetry.title', { name: title })}
+          </span>
+          <button
+            onClick={onClose}
+            className="p-1.5 rounded-md hover:bg-bg-hover text-text-secondary transition-colors"
+            title={t('common.close')}
+          >
+            <X className="w-4 h-4" />
+          </button>
+        </div>
+
+        <div className="p-4 space-y-3">
+          <div className="flex items-center justify-between gap-3">
+            <div>
+              <span className="text-sm text-text-primary">{t('taskRetry.override')}</span>
+              <p className="text-xs text-text-muted mt-0.5">{t('taskRetry.overrideHint')}</p>
+            </div>
+            <SwitchButton value={override} onChange={setOverride} />
+          </div>
+
+          {override && (
+            <div className="pt-3 border-t border-border space-y-3">
+              <div className="flex items-center gap-3">
+                <span className="flex-1 text-sm text-text-secondary">
+                  {t('taskRetry.attempts')}
+                </span>
+                <input
+                  type="number"
+                  min={0}
+                  max={MAX_RETRY_ATTEMPTS}
+                  value={attemptsInput}
+                  onChange={(e) => setAttemptsInput(e.target.value)}
+                  className={clsx(inputClassName, 'w-24')}
+                />
+              </div>
+
+              <div className="flex items-center gap-3">
+                <span className="flex-1 text-sm text-text-secondary">{t('taskRetry.delay')}</span>
+                <input
+                  type="number"
+                  min={0}
+                  max={MAX_RETRY_DELAY_SECONDS}
+                  value={delayInput}
+                  onChange={(e) => setDelayInput(e.target.value)}
+                  className={clsx(inputClassName, 'w-24')}
+                />
+              </div>
+
+              <div className="flex items-center justify-between gap-3">
+                <div>
+                  <span className="text-sm text-text-secondary">{t('taskRetry.onTimeout')}</span>
+                  <p className="text-xs text-text-muted mt-0.5">{t('taskRetry.onTimeoutHint')}</p>
+                </div>
+                <SwitchButton value={retryOnTimeout} onChange={setRetryOnTimeout} />
+              </div>
+
+              <div className="space-y-1.5">
+                <span className="text-sm text-text-secondary">{t('taskRetry.restartApp')}</span>
+                <input
+                  type="text"
+                  value={restartApp}
+                  onChange={(e) => setRestartApp(e.target.value)}
+                  placeholder={t('taskRetry.restartAppPlaceholder')}
+                  className={clsx(inputClassName, 'w-full')}
+                />
+                <p className="text-xs text-text-muted">{t('taskRetry.restartAppHint')}</p>
+              </div>
+
+              {invalid && <p className="text-xs text-error">{t('taskRetry.invalid')}</p>}
+            </div>
+          )}
+        </div>
+
+        <div className="px-4 py-3 flex justify-end gap-2 border-t border-border">
+          <button
+            type="button"
+            onClick={onClose}
+            className="px-4 py-2 rounded-lg text-sm font-medium bg-bg-tertiary hover:bg-bg-hover text-text-secondary transition-colors"
+          >
+            {t('common.cancel')}
+          </button>
+          <button
+            type="button"
+            onClick={handleSave}
+            disabled={invalid}
+            className={clsx(
+              'px-4 py-2 rounded-lg text-sm font-medium text-white transition-colors shadow-sm',
+              invalid
+                ? 'bg-bg-active text-text-muted cursor-not-allowed shadow-none'
+                : 'bg-accent hover:bg-accent-hover',
+            )}
+          >
+            {t('common.save')}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
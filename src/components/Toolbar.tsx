@@ -7,19 +7,40 @@ import {
   ChevronsDownUp,
   Plus,
   Play,
+  Pause,
   StopCircle,
   Loader2,
 } from 'lucide-react';
 import { useAppStore } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
 import clsx from 'clsx';
-import { loggers, generateTaskPipelineOverride, computeResourcePaths } from '@/utils';
+import {
+  loggers,
+  generateTaskPipelineOverride,
+  formatOverrideErrors,
+  computeResourcePaths,
+  getErrorMessage,
+  isTauri,
+  withScreenshotResolution,
+  getTaskTimeoutMs,
+  getTaskRetryPolicy,
+  toRunOptions,
+} from '@/utils';
 import { getMxuSpecialTask } from '@/types/specialTasks';
-import type { TaskConfig, ControllerConfig } from '@/types/maa';
+import type {
+  TaskConfig,
+  ControllerConfig,
+  WatchdogEvent,
+  TaskTimeoutEvent,
+  TaskRetryEvent,
+  TaskQueueEvent,
+  ResumedTask,
+  TaskStatus,
+} from '@/types/maa';
 import { normalizeAgentConfigs } from '@/types/interface';
 import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
 import { SchedulePanel } from './SchedulePanel';
-import type { Instance } from '@/types/interface';
+import type { Instance, ScheduleEvent } from '@/types/interface';
 import { resolveI18nText } from '@/services/contentResolver';
 import { getInterfaceLangKey } from '@/i18n';
 import { PermissionModal } from './toolbar/PermissionModal';
@@ -27,6 +48,12 @@ import { ScheduleButton } from './toolbar/ScheduleButton';
 
 const log = loggers.task;
 
+const PAUSE_SETTLE_TIMEOUT_MS = 10000; // 当前任务结束后等待暂停生效的最长时间
+const PAUSE_POLL_INTERVAL_MS = 100; // 轮询暂停状态的间隔
+
+/** 重新提交结果中成功提交的任务数 */
+const countResumed = (resumed: ResumedTask[]) => resumed.filter((r) => r.task_id !== null).length;
+
 interface ToolbarProps {
   showAddPanel: boolean;
   onToggleAddPanel: () => void;
@@ -49,6 +76,8 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     instanceResourceLoaded,
     setInstanceCurrentTaskId,
     setInstanceTaskStatus,
+    instancePauseState,
+    setInstancePauseState,
     setInstanceConnectionStatus,
     setInstanceResourceLoaded,
     selectedController,
@@ -89,6 +118,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
 
   const [isStarting, setIsStarting] = useState(false);
   const [isStopping, setIsStopping] = useState(false);
+  const [isPausing, setIsPausing] = useState(false);
   const [showSchedulePanel, setShowSchedulePanel] = useState(false);
 
   // 自动连接状态
@@ -114,6 +144,164 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   const pendingTaskIds = instancePendingTaskIds[instanceId] || [];
   const currentTaskIndex = instanceCurrentTaskIndex[instanceId] || 0;
   const runningInstanceIdRef = useRef<string | null>(null);
+  // 控制器掉线后等待重连的运行中实例（期间忽略被中断任务的失败回调）
+  const recoveringInstancesRef = useRef<Set<string>>(new Set());
+  // 队列调整生效中的实例（值为调整前正在执行的任务是否成功，结束前为 null）
+  const applyingQueueRef = useRef<Map<string, boolean | null>>(new Map());
+
+  // 结束本次运行（Failed 时未执行完的任务标记为失败）
+  const finishRun = useCallback(
+    (targetId: string, status: TaskStatus = 'Failed') => {
+      const agentConfigs = normalizeAgentConfigs(projectInterface?.agent);
+      if (agentConfigs && agentConfigs.length > 0) {
+        maaService.stopAgent(targetId).catch((err) => {
+          log.error('停止 Agent 失败:', err);
+        });
+      }
+
+      if (status === 'Failed') {
+        const store = useAppStore.getState();
+        const taskIds = store.instancePendingTaskIds[targetId] || [];
+        const index = store.instanceCurrentTaskIndex[targetId] || 0;
+        taskIds.slice(index).forEach((maaTaskId) => {
+          const selectedTaskId = findSelectedTaskIdByMaaTaskId(targetId, maaTaskId);
+          if (selectedTaskId) {
+            setTaskRunStatus(targetId, selectedTaskId, 'failed');
+          }
+        });
+      }
+
+      setInstanceTaskStatus(targetId, status);
+      setInstancePauseState(targetId, 'none');
+      applyingQueueRef.current.delete(targetId);
+      updateInstance(targetId, { isRunning: false });
+      setInstanceCurrentTaskId(targetId, null);
+      clearPendingTasks(targetId);
+      if (runningInstanceIdRef.current === targetId) {
+        runningInstanceIdRef.current = null;
+      }
+    },
+    [
+      projectInterface?.agent,
+      findSelectedTaskIdByMaaTaskId,
+      setTaskRunStatus,
+      setInstanceTaskStatus,
+      setInstancePauseState,
+      updateInstance,
+      setInstanceCurrentTaskId,
+      clearPendingTasks,
+    ],
+  );
+
+  // 按重新提交后的 task_id 继续跟踪剩余任务（掉线重连或暂停恢复后），提交失败的任务记为失败，
+  // 没有成功提交的任务时运行结束
+  const resumeRun = useCallback(
+    (targetId: string, resumed: ResumedTask[]) => {
+      const taskIdToName = useAppStore.getState().taskIdToName;
+      const taskIds: number[] = [];
+      resumed.forEach(({ previous_task_id, task_id, error }) => {
+        const selectedTaskId = findSelectedTaskIdByMaaTaskId(targetId, previous_task_id);
+        const name = taskIdToName[previous_task_id];
+        if (task_id === null) {
+          if (selectedTaskId) {
+            setTaskRunStatus(targetId, selectedTaskId, 'failed');
+          }
+          addLog(targetId, {
+            type: 'error',
+            message: t('logs.messages.taskRepostFailed', {
+              name: name ?? previous_task_id,
+              error: error ?? '',
+            }),
+          });
+          return;
+        }
+        if (selectedTaskId) {
+          registerMaaTaskMapping(targetId, task_id, selectedTaskId);
+          setTaskRunStatus(targetId, selectedTaskId, taskIds.length === 0 ? 'running' : 'pending');
+        }
+        if (name) {
+          registerTaskIdName(task_id, name);
+        }
+        taskIds.push(task_id);
+      });
+      if (taskIds.length === 0) {
+        finishRun(targetId);
+        return;
+      }
+
+      runningInstanceIdRef.current = targetId;
+      applyingQueueRef.current.delete(targetId);
+      setPendingTaskIds(targetId, taskIds);
+      setCurrentTaskIndexStore(targetId, 0);
+      setInstanceCurrentTaskId(targetId, taskIds[0]);
+      setInstanceTaskStatus(targetId, 'Running');
+    },
+    [
+      finishRun,
+      findSelectedTaskIdByMaaTaskId,
+      registerMaaTaskMapping,
+      registerTaskIdName,
+      setTaskRunStatus,
+      addLog,
+      t,
+      setPendingTaskIds,
+      setCurrentTaskIndexStore,
+      setInstanceCurrentTaskId,
+      setInstanceTaskStatus,
+    ],
+  );
+
+  // 暂停中的任务结束后确认暂停结果：已结束的任务记录结果，保留的任务恢复为等待中
+  const settlePause = useCallback(
+    async (targetId: string, finishedTaskId: number, succeeded: boolean) => {
+      let state = await maaService.getInstanceState(targetId);
+      const deadline = Date.now() + PAUSE_SETTLE_TIMEOUT_MS;
+      while (state?.pauseState === 'pausing' && Date.now() < deadline) {
+        await new Promise((resolve) => setTimeout(resolve, PAUSE_POLL_INTERVAL_MS));
+        state = await maaService.getInstanceState(targetId);
+      }
+      if (!state || state.pauseState === 'pausing') {
+        log.warn(`实例 ${targetId}: 等待暂停超时`);
+        return;
+      }
+
+      const held = state.heldTaskIds;
+      const markTask = (maaTaskId: number, status: 'succeeded' | 'failed' | 'pending') => {
+        const selectedTaskId = findSelectedTaskIdByMaaTaskId(targetId, maaTaskId);
+        if (selectedTaskId) {
+          setTaskRunStatus(targetId, selectedTaskId, status);
+        }
+      };
+      if (!held.includes(finishedTaskId)) {
+        markTask(finishedTaskId, succeeded ? 'succeeded' : 'failed');
+      }
+      held.forEach((maaTaskId) => markTask(maaTaskId, 'pending'));
+
+      if (state.pauseState === 'paused') {
+        log.info(`实例 ${targetId}: 任务已暂停，保留 ${held.length} 个任务`);
+        setInstancePauseState(targetId, 'paused');
+        setInstanceCurrentTaskId(targetId, null);
+        clearPendingTasks(targetId);
+        addLog(targetId, {
+          type: 'info',
+          message: t('logs.messages.tasksPaused', { count: held.length }),
+        });
+      } else {
+        // 暂停生效前最后一个任务已执行完，没有保留的任务
+        finishRun(targetId, succeeded ? 'Succeeded' : 'Failed');
+      }
+    },
+    [
+      findSelectedTaskIdByMaaTaskId,
+      setTaskRunStatus,
+      setInstancePauseState,
+      setInstanceCurrentTaskId,
+      clearPendingTasks,
+      addLog,
+      finishRun,
+      t,
+    ],
+  );
 
   // 检查是否有保存的设备和资源配置（用于权限检查等）
   const currentControllerName =
@@ -140,6 +328,34 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
 
         const runningInstanceId = runningInstanceIdRef.current;
         if (!runningInstanceId) return;
+        if (recoveringInstancesRef.current.has(runningInstanceId)) return;
+
+        // 队列调整生效中：记录当前任务结果，等待按调整后的顺序重新提交
+        const applyingQueue = applyingQueueRef.current;
+        if (applyingQueue.has(runningInstanceId)) {
+          if (message === 'Tasker.Task.Succeeded' || message === 'Tasker.Task.Failed') {
+            const succeeded = message === 'Tasker.Task.Succeeded';
+            const selectedTaskId = findSelectedTaskIdByMaaTaskId(runningInstanceId, currentTaskId);
+            if (selectedTaskId) {
+              setTaskRunStatus(
+                runningInstanceId,
+                selectedTaskId,
+                succeeded ? 'succeeded' : 'failed',
+              );
+            }
+            applyingQueue.set(runningInstanceId, succeeded);
+            recoveringInstancesRef.current.add(runningInstanceId);
+          }
+          return;
+        }
+
+        // 暂停中：当前任务结束后不再推进队列（被中止的后续任务 task_id 不匹配，回调会被忽略）
+        if (useAppStore.getState().instancePauseState[runningInstanceId] === 'pausing') {
+          if (message === 'Tasker.Task.Succeeded' || message === 'Tasker.Task.Failed') {
+            settlePause(runningInstanceId, currentTaskId, message === 'Tasker.Task.Succeeded');
+          }
+          return;
+        }
 
         if (message === 'Tasker.Task.Succeeded') {
           log.info(`任务 ${currentTaskIndex + 1}/${pendingTaskIds.length} 完成`);
@@ -245,6 +461,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     setTaskRunStatus,
     advanceCurrentTaskIndex,
     clearPendingTasks,
+    settlePause,
   ]);
 
   const handleSelectAll = () => {
@@ -313,7 +530,10 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
       // 检查是否有保存的设备配置
       const hasSavedDevice = Boolean(
         savedDevice &&
-        (savedDevice.adbDeviceName || savedDevice.windowName || savedDevice.playcoverAddress),
+        (savedDevice.adbDeviceName ||
+          savedDevice.windowName ||
+          savedDevice.playcoverAddress ||
+          savedDevice.imageFolderPath),
       );
 
       const isTargetConnected = instanceConnectionStatus[targetId] === 'Connected';
@@ -452,7 +672,8 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
                 if (
                   !savedDevice?.windowName &&
                   !savedDevice?.adbDeviceName &&
-                  !savedDevice?.playcoverAddress
+                  !savedDevice?.playcoverAddress &&
+                  !savedDevice?.imageFolderPath
                 ) {
                   // 尝试找出实际匹配到的名称用于提示
                   try {
@@ -510,7 +731,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
             log.error(`实例 ${targetInstance.name}: 前置动作执行失败:`, err);
             addLog(targetId, {
               type: 'error',
-              message: t('action.preActionFailed', { error: String(err) }),
+              message: t('action.preActionFailed', { error: getErrorMessage(err) }),
             });
             // 前置动作失败不阻止任务执行，继续
           }
@@ -587,6 +808,13 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
               };
               deviceName = savedDevice.playcoverAddress;
               targetType = 'device';
+            } else if (controllerType === 'ImageFolder' && savedDevice.imageFolderPath) {
+              config = {
+                type: 'ImageFolder',
+                path: savedDevice.imageFolderPath,
+              };
+              deviceName = savedDevice.imageFolderPath;
+              targetType = 'device';
             }
           } else {
             // 没有保存的设备配置，自动搜索并连接第一个结果
@@ -660,9 +888,11 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
               }
               deviceName = firstWindow.window_name || firstWindow.class_name;
               targetType = 'window';
-            } else if (controllerType === 'PlayCover') {
-              // PlayCover 没有搜索功能，无法自动连接
-              log.warn(`实例 ${targetInstance.name}: PlayCover 控制器需要手动配置地址`);
+            } else if (controllerType === 'PlayCover' || controllerType === 'ImageFolder') {
+              // PlayCover / ImageFolder 没有搜索功能，无法自动连接
+              log.warn(
+                `实例 ${targetInstance.name}: ${controllerType} 控制器需要手动配置地址或目录`,
+              );
               addLog(targetId, {
                 type: 'error',
                 message: t('taskList.autoConnect.needConfig'),
@@ -689,7 +919,10 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
             }
           });
 
-          const ctrlId = await maaService.connectController(targetId, config);
+          const ctrlId = await maaService.connectController(
+            targetId,
+            withScreenshotResolution(config, controller, targetInstance.screenshotResolution),
+          );
 
           // 注册 ctrl_id 与设备名/类型的映射
           registerCtrlIdName(ctrlId, deviceName, targetType);
@@ -805,6 +1038,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         log.info(`实例 ${targetInstance.name}: 开始执行任务, 数量:`, enabledTasks.length);
 
         // 构建任务配置列表，同时预注册 entry -> taskName 映射（解决时序问题）
+        const { taskTimeout, taskRetry } = useAppStore.getState();
         const taskConfigs: TaskConfig[] = [];
         for (const selectedTask of enabledTasks) {
           // 先检查是否是 MXU 特殊任务
@@ -821,6 +1055,8 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
               controllerName,
               resourceName,
             ),
+            timeout_ms: getTaskTimeoutMs(taskTimeout),
+            retry: getTaskRetryPolicy(taskRetry, selectedTask.retry),
           });
           // 预注册 entry -> taskName 映射，确保回调时能找到任务名
           // MXU 特殊任务的 label 是 MXU i18n key（如 'specialTask.sleep.label'），需要用 t() 翻译
@@ -854,50 +1090,82 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         }
 
         // 启动任务
-        const taskIds = await maaService.startTasks(
+        const { task_ids: taskIds, rejected, warnings } = await maaService.startTasks(
           targetId,
           taskConfigs,
           agentConfigs,
           basePath,
           tcpCompatMode,
+          toRunOptions(taskTimeout),
         );
 
         log.info(`实例 ${targetInstance.name}: 任务已提交, task_ids:`, taskIds);
 
+        // 未通过校验或提交失败的任务不会分配 task_id，其余任务按顺序与 task_id 对应
+        const rejectedIndices = new Set(rejected.map((r) => r.index));
+        const submittedTasks = enabledTasks.filter((_, index) => !rejectedIndices.has(index));
+        for (const r of rejected) {
+          addLog(targetId, {
+            type: 'warning',
+            message: t('taskList.taskRejected', {
+              name: enabledTasks[r.index]?.customName || r.entry,
+              reason: formatOverrideErrors(r.errors),
+            }),
+          });
+        }
+        for (const w of warnings) {
+          addLog(targetId, {
+            type: 'warning',
+            message: t('taskList.taskOverrideWarning', {
+              name: enabledTasks[w.index]?.customName || w.entry,
+              reason: formatOverrideErrors(w.warnings),
+            }),
+          });
+        }
+        if (taskIds.length === 0) {
+          throw new Error('所有任务均未提交');
+        }
+
         // 初始化任务运行状态
         const enabledTaskIds = enabledTasks.map((t) => t.id);
         setAllTasksRunStatus(targetId, enabledTaskIds, 'pending');
+        for (const r of rejected) {
+          if (enabledTasks[r.index]) {
+            setTaskRunStatus(targetId, enabledTasks[r.index].id, 'failed');
+          }
+        }
 
         // 开始任务时折叠所有任务
         collapseAllTasks(targetId, false);
 
         // 记录映射关系，并注册 task_id 与任务名的映射用于日志显示
         taskIds.forEach((maaTaskId, index) => {
-          if (enabledTasks[index]) {
-            registerMaaTaskMapping(targetId, maaTaskId, enabledTasks[index].id);
+          if (submittedTasks[index]) {
+            registerMaaTaskMapping(targetId, maaTaskId, submittedTasks[index].id);
             // 注册 task_id 与任务名的映射（使用自定义名称或 label）
             // MXU 特殊任务的 label 需要用 t() 翻译
-            const specialTask = getMxuSpecialTask(enabledTasks[index].taskName);
+            const specialTask = getMxuSpecialTask(submittedTasks[index].taskName);
             const taskDef =
               specialTask?.taskDef ||
-              projectInterface?.task.find((t) => t.name === enabledTasks[index].taskName);
+              projectInterface?.task.find((t) => t.name === submittedTasks[index].taskName);
             const taskDisplayName =
-              enabledTasks[index].customName ||
+              submittedTasks[index].customName ||
               (specialTask && taskDef?.label
                 ? t(taskDef.label)
                 : resolveI18nText(taskDef?.label, translations)) ||
-              enabledTasks[index].taskName;
+              submittedTasks[index].taskName;
             registerTaskIdName(maaTaskId, taskDisplayName);
           }
         });
 
         // 第一个任务设为 running
-        if (enabledTasks.length > 0) {
-          setTaskRunStatus(targetId, enabledTasks[0].id, 'running');
+        if (submittedTasks.length > 0) {
+          setTaskRunStatus(targetId, submittedTasks[0].id, 'running');
         }
 
         // 设置任务队列
         runningInstanceIdRef.current = targetId;
+        recoveringInstancesRef.current.delete(targetId);
         setPendingTaskIds(targetId, taskIds);
         setCurrentTaskIndexStore(targetId, 0);
         setInstanceCurrentTaskId(targetId, taskIds[0]);
@@ -951,97 +1219,398 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     ],
   );
 
-  /**
-   * 检查并执行定时任务
-   * 遍历所有实例的定时策略，如果当前时间匹配则启动任务
-   */
-  const checkAndExecuteScheduledTasks = useCallback(async () => {
-    const now = new Date();
-    const currentWeekday = now.getDay(); // 0-6, 0=周日
-    const currentHour = now.getHours(); // 0-23
-
-    log.info(`定时检查: 周${currentWeekday} ${currentHour}:00`);
-
-    for (const inst of instances) {
-      const policies = inst.schedulePolicies || [];
+  // 监听后端调度器事件：定时策略由 Rust 侧调度器触发，这里只同步界面状态和日志
+  useEffect(() => {
+    if (!isTauri()) return;
 
-      for (const policy of policies) {
-        if (!policy.enabled) continue;
+    let unlistenFired: (() => void) | null = null;
+    let unlistenSkipped: (() => void) | null = null;
+    let unlistenFinished: (() => void) | null = null;
+    let disposed = false;
 
-        // 检查是否匹配当前时间
-        const matchesWeekday = policy.weekdays.includes(currentWeekday);
-        const matchesHour = policy.hours.includes(currentHour);
+    const formatFireTime = (fireTime: string) => {
+      const date = new Date(fireTime);
+      return `${date.getHours().toString().padStart(2, '0')}:${date.getMinutes().toString().padStart(2, '0')}`;
+    };
 
-        if (matchesWeekday && matchesHour) {
-          log.info(`定时策略命中: 实例 "${inst.name}", 策略 "${policy.name}"`);
+    const setupScheduleListeners = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
 
-          // 添加定时执行开始日志
-          const timeStr = `${currentHour.toString().padStart(2, '0')}:00`;
-          addLog(inst.id, {
+        const fired = await listen<ScheduleEvent>('schedule-fired', ({ payload }) => {
+          log.info(`定时策略命中: 实例 "${payload.instance_name}", 策略 "${payload.policy_name}"`);
+          addLog(payload.instance_id, {
             type: 'info',
             message: t('logs.messages.scheduleStarting', {
-              policy: policy.name,
-              time: timeStr,
+              policy: payload.policy_name,
+              time: formatFireTime(payload.fire_time),
             }),
           });
+          setScheduleExecution(payload.instance_id, {
+            policyName: payload.policy_name,
+            startTime: Date.now(),
+          });
+          updateInstance(payload.instance_id, { isRunning: true });
+          setInstanceTaskStatus(payload.instance_id, 'Running');
+        });
 
-          // 启动任务（复用统一入口）
-          const started = await startTasksForInstance(inst, {
-            schedulePolicyName: policy.name,
+        const skipped = await listen<ScheduleEvent>('schedule-skipped', ({ payload }) => {
+          log.warn(
+            `定时策略跳过: 实例 "${payload.instance_name}", 策略 "${payload.policy_name}", 原因 ${payload.reason}`,
+          );
+          const key =
+            payload.reason === 'missed'
+              ? 'logs.messages.scheduleMissed'
+              : 'logs.messages.scheduleSkippedRunning';
+          addLog(payload.instance_id, {
+            type: 'warning',
+            message: t(key, {
+              policy: payload.policy_name,
+              time: formatFireTime(payload.fire_time),
+            }),
           });
-          if (started) {
-            log.info(`定时任务启动成功: 实例 "${inst.name}"`);
+        });
+
+        const finished = await listen<ScheduleEvent>('schedule-finished', ({ payload }) => {
+          updateInstance(payload.instance_id, { isRunning: false });
+          setInstanceTaskStatus(payload.instance_id, payload.success ? 'Succeeded' : 'Failed');
+          clearScheduleExecution(payload.instance_id);
+          if (payload.success) {
+            addLog(payload.instance_id, {
+              type: 'success',
+              message: t('logs.messages.scheduleFinished', { policy: payload.policy_name }),
+            });
           } else {
-            log.warn(`定时任务启动失败或跳过: 实例 "${inst.name}"`);
+            addLog(payload.instance_id, {
+              type: 'error',
+              message: t('logs.messages.scheduleFailed', {
+                policy: payload.policy_name,
+                error: payload.error ?? '',
+              }),
+            });
           }
+        });
 
-          // 一个实例只执行第一个匹配的策略，避免重复启动
-          break;
+        if (disposed) {
+          fired();
+          skipped();
+          finished();
+          return;
         }
+        unlistenFired = fired;
+        unlistenSkipped = skipped;
+        unlistenFinished = finished;
+      } catch (err) {
+        log.warn('注册调度器事件监听失败:', err);
       }
-    }
-  }, [instances, startTasksForInstance, addLog, t]);
+    };
+
+    setupScheduleListeners();
 
-  // 定时任务检查：每个整小时检查一次
+    return () => {
+      disposed = true;
+      if (unlistenFired) unlistenFired();
+      if (unlistenSkipped) unlistenSkipped();
+      if (unlistenFinished) unlistenFinished();
+    };
+  }, [
+    addLog,
+    setScheduleExecution,
+    clearScheduleExecution,
+    updateInstance,
+    setInstanceTaskStatus,
+    t,
+  ]);
+
+  // 监听控制器掉线重连事件：重连成功后按新的 task_id 继续跟踪剩余任务，放弃重连时结束本次运行
   useEffect(() => {
-    // 计算距离下一个整小时的时间
-    const now = new Date();
-    const msUntilNextHour =
-      (60 - now.getMinutes()) * 60 * 1000 - now.getSeconds() * 1000 - now.getMilliseconds();
-
-    // 立即检查一次（仅在整点时）
-    if (now.getMinutes() === 0 && now.getSeconds() < 5) {
-      checkAndExecuteScheduledTasks();
-    }
+    if (!isTauri()) return;
 
-    // 设置定时器，在下一个整小时执行
-    const initialTimeout = setTimeout(() => {
-      checkAndExecuteScheduledTasks();
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    const recovering = recoveringInstancesRef.current;
 
-      // 然后每小时执行一次
-      const hourlyInterval = setInterval(
-        () => {
-          checkAndExecuteScheduledTasks();
-        },
-        60 * 60 * 1000,
-      );
+    const setupWatchdogListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        const fn = await listen<WatchdogEvent>('maa-watchdog', ({ payload }) => {
+          const targetId = payload.instance_id;
+          const seconds = Math.round((payload.retry_in_ms ?? 0) / 1000);
+
+          switch (payload.stage) {
+            case 'disconnected': {
+              log.warn(`实例 ${targetId}: 控制器掉线，待恢复任务 ${payload.pending_tasks ?? 0} 个`);
+              const target = useAppStore.getState().instances.find((i) => i.id === targetId);
+              if (target?.isRunning) {
+                recovering.add(targetId);
+              }
+              setInstanceConnectionStatus(targetId, 'Connecting');
+              addLog(targetId, {
+                type: 'warning',
+                message: t('logs.messages.controllerLost', { seconds }),
+              });
+              break;
+            }
+            case 'reconnecting':
+              addLog(targetId, {
+                type: 'info',
+                message: t('logs.messages.reconnecting', {
+                  attempt: payload.attempt,
+                  max: payload.max_attempts,
+                }),
+              });
+              break;
+            case 'attempt_failed':
+              addLog(targetId, {
+                type: 'warning',
+                message: t('logs.messages.reconnectAttemptFailed', {
+                  attempt: payload.attempt,
+                  max: payload.max_attempts,
+                  error: payload.error ?? '',
+                }),
+              });
+              break;
+            case 'reconnected': {
+              const resumed = payload.resumed_tasks ?? [];
+              const count = countResumed(resumed);
+              log.info(`实例 ${targetId}: 控制器重连成功，恢复任务 ${count} 个`);
+              setInstanceConnectionStatus(targetId, 'Connected');
+              addLog(targetId, {
+                type: 'success',
+                message: t('logs.messages.reconnected', { count }),
+              });
+              if (recovering.delete(targetId)) {
+                resumeRun(targetId, resumed);
+              }
+              break;
+            }
+            case 'gave_up':
+              log.error(`实例 ${targetId}: 控制器重连失败，已放弃`);
+              setInstanceConnectionStatus(targetId, 'Disconnected');
+              addLog(targetId, {
+                type: 'error',
+                message: t('logs.messages.reconnectGaveUp', { max: payload.max_attempts }),
+              });
+              if (recovering.delete(targetId)) {
+                finishRun(targetId);
+              }
+              break;
+          }
+        });
 
-      // 保存 interval ID 以便清理
-      (
-        window as unknown as { __scheduleInterval?: ReturnType<typeof setInterval> }
-      ).__scheduleInterval = hourlyInterval;
-    }, msUntilNextHour);
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      } catch (err) {
+        log.warn('注册掉线重连事件监听失败:', err);
+      }
+    };
+
+    setupWatchdogListener();
 
     return () => {
-      clearTimeout(initialTimeout);
-      const hourlyInterval = (
-        window as unknown as { __scheduleInterval?: ReturnType<typeof setInterval> }
-      ).__scheduleInterval;
-      if (hourlyInterval) {
-        clearInterval(hourlyInterval);
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [finishRun, resumeRun, setInstanceConnectionStatus, addLog, t]);
+
+  // 监听任务超时事件：停止期间忽略被中止任务的回调，停止后继续跟踪重新提交的任务或结束本次运行
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    const recovering = recoveringInstancesRef.current;
+
+    const setupTimeoutListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        const fn = await listen<TaskTimeoutEvent>('maa-task-timeout', ({ payload }) => {
+          const targetId = payload.instance_id;
+
+          if (payload.stage === 'timed_out') {
+            const target = useAppStore.getState().instances.find((i) => i.id === targetId);
+            if (!target?.isRunning) return;
+            log.warn(`实例 ${targetId}: 任务 ${payload.entry} 超时 (${payload.scope})`);
+            recovering.add(targetId);
+
+            const selectedTaskId = findSelectedTaskIdByMaaTaskId(targetId, payload.task_id);
+            if (selectedTaskId) {
+              setTaskRunStatus(targetId, selectedTaskId, 'failed');
+            }
+            const name = useAppStore.getState().taskIdToName[payload.task_id] || payload.entry;
+            const seconds = Math.round(payload.elapsed_ms / 1000);
+            addLog(targetId, {
+              type: 'warning',
+              message:
+                payload.scope === 'run'
+                  ? t('logs.messages.runTimedOut', { seconds })
+                  : t('logs.messages.taskTimedOut', { name, seconds }),
+            });
+            return;
+          }
+
+          if (!recovering.delete(targetId)) return;
+          const resumed = payload.resumed_tasks ?? [];
+          const count = countResumed(resumed);
+          if (count > 0) {
+            addLog(targetId, {
+              type: 'info',
+              message: t('logs.messages.timeoutContinued', { count }),
+            });
+          }
+          resumeRun(targetId, resumed);
+        });
+
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      } catch (err) {
+        log.warn('注册任务超时事件监听失败:', err);
       }
     };
-  }, [checkAndExecuteScheduledTasks]);
+
+    setupTimeoutListener();
+
+    return () => {
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [resumeRun, findSelectedTaskIdByMaaTaskId, setTaskRunStatus, addLog, t]);
+
+  // 监听任务重试事件：失败重试期间忽略被中止任务的回调，重新提交后继续跟踪
+  // （超时触发的重试由超时事件处理，这里只记录日志）
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    const recovering = recoveringInstancesRef.current;
+
+    const setupRetryListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        const fn = await listen<TaskRetryEvent>('maa-task-retry', ({ payload }) => {
+          const targetId = payload.instance_id;
+          const name = useAppStore.getState().taskIdToName[payload.task_id] || payload.entry;
+
+          switch (payload.stage) {
+            case 'retrying': {
+              const target = useAppStore.getState().instances.find((i) => i.id === targetId);
+              if (!target?.isRunning) return;
+              log.warn(
+                `实例 ${targetId}: 任务 ${payload.entry} 第 ${payload.attempt}/${payload.max_attempts} 次重试 (${payload.condition})`,
+              );
+              if (payload.condition === 'failed') {
+                recovering.add(targetId);
+              }
+              addLog(targetId, {
+                type: 'warning',
+                message: t('logs.messages.taskRetrying', {
+                  name,
+                  attempt: payload.attempt,
+                  max: payload.max_attempts,
+                }),
+              });
+              break;
+            }
+            case 'resumed':
+            case 'aborted':
+              if (payload.condition !== 'failed' || !recovering.delete(targetId)) return;
+              resumeRun(targetId, payload.resumed_tasks ?? []);
+              break;
+            case 'exhausted':
+              addLog(targetId, {
+                type: 'error',
+                message: t('logs.messages.taskRetryExhausted', { name, count: payload.attempt }),
+              });
+              break;
+          }
+        });
+
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      } catch (err) {
+        log.warn('注册任务重试事件监听失败:', err);
+      }
+    };
+
+    setupRetryListener();
+
+    return () => {
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [resumeRun, addLog, t]);
+
+  // 监听任务队列调整生效事件：调整前的任务结束后忽略被中止任务的回调，重新提交后继续跟踪
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    const recovering = recoveringInstancesRef.current;
+    const applyingQueue = applyingQueueRef.current;
+
+    const setupQueueListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        const fn = await listen<TaskQueueEvent>('maa-task-queue', ({ payload }) => {
+          const targetId = payload.instance_id;
+
+          if (payload.stage === 'applying') {
+            const target = useAppStore.getState().instances.find((i) => i.id === targetId);
+            if (!target?.isRunning) return;
+            applyingQueue.set(targetId, null);
+            return;
+          }
+
+          if (!applyingQueue.has(targetId)) return;
+          const succeeded = applyingQueue.get(targetId);
+          recovering.delete(targetId);
+          const resumed = payload.resumed_tasks ?? [];
+          if (resumed.length > 0) {
+            const count = countResumed(resumed);
+            log.info(`实例 ${targetId}: 任务队列已调整，剩余 ${count} 个任务`);
+            addLog(targetId, {
+              type: 'info',
+              message: t('logs.messages.taskQueueApplied', { count }),
+            });
+            resumeRun(targetId, resumed);
+          } else {
+            log.info(`实例 ${targetId}: 任务队列已调整，没有剩余任务`);
+            finishRun(targetId, succeeded === false ? 'Failed' : 'Succeeded');
+          }
+        });
+
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      } catch (err) {
+        log.warn('注册任务队列事件监听失败:', err);
+      }
+    };
+
+    setupQueueListener();
+
+    return () => {
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [finishRun, resumeRun, addLog, t]);
 
   /**
    * 检查当前控制器是否需要管理员权限
@@ -1124,11 +1693,14 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
       }
       updateInstance(targetInstanceId, { isRunning: false });
       setInstanceTaskStatus(targetInstanceId, null);
+      setInstancePauseState(targetInstanceId, 'none');
       setInstanceCurrentTaskId(targetInstanceId, null);
       clearTaskRunStatus(targetInstanceId);
       clearPendingTasks(targetInstanceId);
       clearScheduleExecution(targetInstanceId);
       runningInstanceIdRef.current = null;
+      recoveringInstancesRef.current.delete(targetInstanceId);
+      applyingQueueRef.current.delete(targetInstanceId);
     } finally {
       setIsStopping(false);
     }
@@ -1172,7 +1744,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         }
       } catch (err) {
         log.error('任务启动异常:', err);
-        setAutoConnectError(err instanceof Error ? err.message : String(err));
+        setAutoConnectError(getErrorMessage(err));
         setAutoConnectPhase('idle');
       } finally {
         setIsStarting(false);
@@ -1180,18 +1752,55 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     }
   };
 
+  // 暂停：等待当前任务执行完毕后停止，剩余任务保留；已暂停时重新提交剩余任务
+  const handlePauseResume = async () => {
+    if (!instance?.isRunning) return;
+    const targetId = instance.id;
+
+    setIsPausing(true);
+    try {
+      if (instancePauseState[targetId] === 'paused') {
+        const resumed = await maaService.resumeTasks(targetId);
+        setInstancePauseState(targetId, 'none');
+        addLog(targetId, {
+          type: 'info',
+          message: t('logs.messages.tasksResumed', { count: countResumed(resumed) }),
+        });
+        resumeRun(targetId, resumed);
+      } else {
+        const state = await maaService.pauseTasks(targetId);
+        setInstancePauseState(targetId, state);
+        if (state === 'pausing') {
+          addLog(targetId, { type: 'info', message: t('logs.messages.tasksPausing') });
+        }
+      }
+    } catch (err) {
+      log.error('暂停/恢复任务失败:', err);
+      addLog(targetId, {
+        type: 'error',
+        message: t('logs.messages.pauseResumeFailed', { error: getErrorMessage(err) }),
+      });
+    } finally {
+      setIsPausing(false);
+    }
+  };
+
   const hotkeyStartingRef = useRef(false);
 
   // 监听来自 App 的全局快捷键事件：F10 开始任务，F11 结束任务
   useEffect(() => {
     const handleStartTasks = async (evt: Event) => {
       if (hotkeyStartingRef.current) return;
-      const currentInstance = useAppStore.getState().getActiveInstance();
-      if (!currentInstance) return;
-
       const detail = (evt as CustomEvent | undefined)?.detail as
-        | { source?: string; combo?: string }
+        | { source?: string; combo?: string; instanceId?: string }
         | undefined;
+      // 本地控制接口会指定实例，其余来源作用于当前实例
+      const store = useAppStore.getState();
+      const currentInstance = detail?.instanceId
+        ? store.instances.find((i) => i.id === detail.instanceId)
+        : store.getActiveInstance();
+      if (!currentInstance) return;
+
       const combo = detail?.combo || '';
       addLog(currentInstance.id, {
         type: 'info',
@@ -1227,13 +1836,16 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     };
 
     const handleStopTasks = async (evt: Event) => {
-      const runningInstance = useAppStore.getState().instances.find((i) => i.isRunning);
+      const detail = (evt as CustomEvent | undefined)?.detail as
+        | { source?: string; combo?: string; instanceId?: string }
+        | undefined;
+      const targetId = detail?.instanceId;
+      const runningInstance = useAppStore
+        .getState()
+        .instances.find((i) => i.isRunning && (!targetId || i.id === targetId));
       if (!runningInstance) return;
       if (isStopping) return;
 
-      const detail = (evt as CustomEvent | undefined)?.detail as
-        | { source?: string; combo?: string }
-        | undefined;
       const combo = detail?.combo || '';
       addLog(runningInstance.id, {
         type: 'info',
@@ -1269,6 +1881,8 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [instance?.id, instance?.isRunning]);
 
+  const pauseState = instancePauseState[instanceId] ?? 'none';
+
   // canRun 只检查是否有启用的任务；运行中时按钮用于停止，不应禁用
   const isDisabled = (tasks.length === 0 || !canRun) && !instance?.isRunning;
 
@@ -1381,6 +1995,36 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
           onRestart={handleRestartAsAdmin}
         />
 
+        {/* 暂停/继续按钮 */}
+        {instance?.isRunning && !isStopping && (
+          <button
+            onClick={handlePauseResume}
+            disabled={isPausing || pauseState === 'pausing'}
+            className={clsx(
+              'flex items-center gap-2 px-3 py-2 rounded-lg text-sm font-medium transition-colors',
+              isPausing || pauseState === 'pausing'
+                ? 'bg-bg-active text-text-tertiary cursor-not-allowed'
+                : 'bg-bg-tertiary hover:bg-bg-hover text-text-primary',
+            )}
+            title={pauseState === 'paused' ? undefined : t('taskList.pauseTasksHint')}
+          >
+            {isPausing || pauseState === 'pausing' ? (
+              <Loader2 className="w-4 h-4 animate-spin" />
+            ) : pauseState === 'paused' ? (
+              <Play className="w-4 h-4" />
+            ) : (
+              <Pause className="w-4 h-4" />
+            )}
+            <span>
+              {pauseState === 'pausing'
+                ? t('taskList.pausingTasks')
+                : pauseState === 'paused'
+                  ? t('taskList.resumeTasks')
+                  : t('taskList.pauseTasks')}
+            </span>
+          </button>
+        )}
+
         {/* 开始/停止按钮 */}
         <button
           data-role="start-stop-button"
@@ -5,6 +5,7 @@ import { useAppStore } from '@/stores/appStore';
 import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
 import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
 import type { ControllerItem } from '@/types/interface';
+import { withScreenshotResolution } from '@/utils/screenshotResolution';
 import { waitForCtrlResult } from './callbackCache';
 
 interface UseDeviceConnectionProps {
@@ -60,7 +61,10 @@ export function useDeviceConnection({
   // 连接控制器的内部实现
   const connectControllerInternal = useCallback(
     async (config: ControllerConfig, deviceName: string, targetType: 'device' | 'window') => {
-      const ctrlId = await maaService.connectController(instanceId, config);
+      const ctrlId = await maaService.connectController(
+        instanceId,
+        withScreenshotResolution(config, currentController, activeInstance?.screenshotResolution),
+      );
 
       registerCtrlIdName(ctrlId, deviceName || '', targetType);
 
@@ -79,7 +83,14 @@ export function useDeviceConnection({
         return false;
       }
     },
-    [instanceId, registerCtrlIdName, setInstanceConnectionStatus, t],
+    [
+      instanceId,
+      currentController,
+      activeInstance?.screenshotResolution,
+      registerCtrlIdName,
+      setInstanceConnectionStatus,
+      t,
+    ],
   );
 
   // 搜索设备
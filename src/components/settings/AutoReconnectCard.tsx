@@ -0,0 +1,80 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { PlugZap } from 'lucide-react';
+
+import { useAppStore } from '@/stores/appStore';
+import { SwitchButton } from '@/components/FormControls';
+
+/** 最多重连次数上限 */
+const MAX_ATTEMPTS_LIMIT = 20;
+
+/** 控制器掉线自动重连设置卡片 */
+export function AutoReconnectCard() {
+  const { t } = useTranslation();
+  const { autoReconnect, setAutoReconnect } = useAppStore();
+
+  const [attemptsInput, setAttemptsInput] = useState(String(autoReconnect.maxAttempts));
+
+  const handleAttemptsBlur = () => {
+    const maxAttempts = Number(attemptsInput);
+    if (!Number.isInteger(maxAttempts) || maxAttempts < 1 || maxAttempts > MAX_ATTEMPTS_LIMIT) {
+      setAttemptsInput(String(autoReconnect.maxAttempts));
+      return;
+    }
+    if (maxAttempts !== autoReconnect.maxAttempts) {
+      setAutoReconnect({ ...autoReconnect, maxAttempts });
+    }
+  };
+
+  return (
+    <div className="bg-bg-secondary rounded-xl p-4 border border-border">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-3">
+          <PlugZap className="w-5 h-5 text-accent" />
+          <div>
+            <span className="font-medium text-text-primary">{t('settings.autoReconnect')}</span>
+            <p className="text-xs text-text-muted mt-0.5">{t('settings.autoReconnectHint')}</p>
+          </div>
+        </div>
+        <SwitchButton
+          value={autoReconnect.enabled}
+          onChange={(enabled) => setAutoReconnect({ ...autoReconnect, enabled })}
+        />
+      </div>
+
+      {autoReconnect.enabled && (
+        <div className="mt-4 pt-4 border-t border-border space-y-3">
+          <div className="flex items-center gap-3">
+            <span className="flex-1 text-sm text-text-secondary">
+              {t('settings.autoReconnectMaxAttempts')}
+            </span>
+            <input
+              type="number"
+              min={1}
+              max={MAX_ATTEMPTS_LIMIT}
+              value={attemptsInput}
+              onChange={(e) => setAttemptsInput(e.target.value)}
+              onBlur={handleAttemptsBlur}
+              className="w-24 px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50"
+            />
+          </div>
+
+          <div className="flex items-center justify-between gap-3">
+            <div>
+              <span className="text-sm text-text-secondary">
+                {t('settings.autoReconnectResumeTasks')}
+              </span>
+              <p className="text-xs text-text-muted mt-0.5">
+                {t('settings.autoReconnectResumeTasksHint')}
+              </p>
+            </div>
+            <SwitchButton
+              value={autoReconnect.resumeTasks}
+              onChange={(resumeTasks) => setAutoReconnect({ ...autoReconnect, resumeTasks })}
+            />
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
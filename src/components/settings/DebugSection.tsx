@@ -1,6 +1,14 @@
 import { useState, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Bug, RefreshCw, FolderOpen, ScrollText, Network, Archive } from 'lucide-react';
+import {
+  Bug,
+  RefreshCw,
+  FolderOpen,
+  ScrollText,
+  Network,
+  Archive,
+  ScanSearch,
+} from 'lucide-react';
 
 import { useAppStore } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
@@ -9,6 +17,7 @@ import { isTauri, getDebugDir, getConfigDir, openDirectory } from '@/utils/paths
 import { useExportLogs } from '@/utils/useExportLogs';
 import { SwitchButton } from '@/components/FormControls';
 import { ExportLogsModal } from './ExportLogsModal';
+import { SessionRecorderRow } from './SessionRecorderRow';
 
 export function DebugSection() {
   const { t } = useTranslation();
@@ -19,6 +28,8 @@ export function DebugSection() {
     setDevMode,
     saveDraw,
     setSaveDraw,
+    debugMode,
+    setDebugMode,
     tcpCompatMode,
     setTcpCompatMode,
   } = useAppStore();
@@ -263,6 +274,21 @@ export function DebugSection() {
           <SwitchButton value={saveDraw} onChange={(v) => setSaveDraw(v)} />
         </div>
 
+        {/* 调试模式 */}
+        <div className="flex items-center justify-between pt-4 border-t border-border">
+          <div className="flex items-center gap-3">
+            <ScanSearch className="w-5 h-5 text-accent" />
+            <div>
+              <span className="font-medium text-text-primary">{t('debug.debugMode')}</span>
+              <p className="text-xs text-text-muted mt-0.5">{t('debug.debugModeHint')}</p>
+            </div>
+          </div>
+          <SwitchButton value={debugMode} onChange={(v) => setDebugMode(v)} />
+        </div>
+
+        {/* 会话录制 */}
+        {isTauri() && <SessionRecorderRow />}
+
         {/* 通信兼容模式 */}
         <div className="flex items-center justify-between pt-4 border-t border-border">
           <div className="flex items-center gap-3">
@@ -11,6 +11,7 @@ import {
   Rocket,
   ChevronDown,
   Check,
+  Usb,
 } from 'lucide-react';
 
 import { invoke } from '@tauri-apps/api/core';
@@ -19,6 +20,10 @@ import { defaultWindowSize } from '@/types/config';
 import { isTauri } from '@/utils/paths';
 import { SwitchButton } from '@/components/FormControls';
 import { FrameRateSelector } from '../FrameRateSelector';
+import { AutoReconnectCard } from './AutoReconnectCard';
+import { RemoteApiCard } from './RemoteApiCard';
+import { TaskTimeoutCard } from './TaskTimeoutCard';
+import { TaskRetryCard } from './TaskRetryCard';
 
 export function GeneralSection() {
   const { t } = useTranslation();
@@ -29,6 +34,8 @@ export function GeneralSection() {
     setConfirmBeforeDelete,
     minimizeToTray,
     setMinimizeToTray,
+    adbDeviceMonitor,
+    setAdbDeviceMonitor,
     setRightPanelWidth,
     setRightPanelCollapsed,
     instances,
@@ -265,7 +272,39 @@ export function GeneralSection() {
         </div>
       </div>
 
-      {/* ⑤ 显示选项预览 */}
+      {/* ⑤ 本地控制接口 */}
+      {isTauri() && <RemoteApiCard />}
+
+      {/* ⑥ 掉线自动重连 */}
+      {isTauri() && <AutoReconnectCard />}
+
+      {/* ⑦ 任务超时 */}
+      {isTauri() && <TaskTimeoutCard />}
+
+      {/* ⑧ 任务失败重试 */}
+      {isTauri() && <TaskRetryCard />}
+
+      {/* ⑨ ADB 设备热插拔监听 */}
+      {isTauri() && (
+        <div className="bg-bg-secondary rounded-xl p-4 border border-border">
+          <div className="flex items-center justify-between">
+            <div className="flex items-center gap-3">
+              <Usb className="w-5 h-5 text-accent" />
+              <div>
+                <span className="font-medium text-text-primary">
+                  {t('settings.adbDeviceMonitor')}
+                </span>
+                <p className="text-xs text-text-muted mt-0.5">
+                  {t('settings.adbDeviceMonitorHint')}
+                </p>
+              </div>
+            </div>
+            <SwitchButton value={adbDeviceMonitor} onChange={(v) => setAdbDeviceMonitor(v)} />
+          </div>
+        </div>
+      )}
+
+      {/* ⑩ 显示选项预览 */}
       <div className="bg-bg-secondary rounded-xl p-4 border border-border">
         <div className="flex items-center justify-between">
           <div className="flex items-center gap-3">
@@ -283,10 +322,10 @@ export function GeneralSection() {
         </div>
       </div>
 
-      {/* ⑥ 帧率选择器 */}
+      {/* ⑪ 帧率选择器 */}
       <FrameRateSelector />
 
-      {/* ⑦ 删除确认 */}
+      {/* ⑫ 删除确认 */}
       <div className="bg-bg-secondary rounded-xl p-4 border border-border">
         <div className="flex items-center justify-between">
           <div className="flex items-center gap-3">
@@ -304,7 +343,7 @@ export function GeneralSection() {
         </div>
       </div>
 
-      {/* ⑧ 重置窗口布局 */}
+      {/* ⑬ 重置窗口布局 */}
       {isTauri() && (
         <div className="bg-bg-secondary rounded-xl p-4 border border-border">
           <div className="flex items-center justify-between">
@@ -0,0 +1,144 @@
+import { useCallback, useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Network, Eye, EyeOff, RefreshCw } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+
+import { useAppStore } from '@/stores/appStore';
+import type { RemoteApiSettings } from '@/types/config';
+import { getErrorMessage, isTauri } from '@/utils';
+import { SwitchButton } from '@/components/FormControls';
+
+interface RemoteApiStatus {
+  running: boolean;
+  address: string | null;
+}
+
+/** 生成随机访问令牌（32 位十六进制） */
+function generateToken(): string {
+  const bytes = crypto.getRandomValues(new Uint8Array(16));
+  return Array.from(bytes, (b) => b.toString(16).padStart(2, '0')).join('');
+}
+
+/** 本地控制接口设置卡片 */
+export function RemoteApiCard() {
+  const { t } = useTranslation();
+  const { remoteApi, setRemoteApi } = useAppStore();
+
+  const [portInput, setPortInput] = useState(String(remoteApi.port));
+  const [showToken, setShowToken] = useState(false);
+  const [status, setStatus] = useState<RemoteApiStatus | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshStatus = useCallback(() => {
+    if (!isTauri()) return;
+    invoke<RemoteApiStatus>('remote_api_status')
+      .then(setStatus)
+      .catch(() => setStatus(null));
+  }, []);
+
+  useEffect(() => {
+    refreshStatus();
+  }, [refreshStatus]);
+
+  const apply = useCallback(
+    async (settings: RemoteApiSettings) => {
+      setError(null);
+      try {
+        await setRemoteApi(settings);
+      } catch (err) {
+        setError(getErrorMessage(err));
+      }
+      refreshStatus();
+    },
+    [setRemoteApi, refreshStatus],
+  );
+
+  const handleToggle = (enabled: boolean) => {
+    // 首次启用时自动生成令牌
+    const token = remoteApi.token || generateToken();
+    apply({ ...remoteApi, enabled, token });
+  };
+
+  const handlePortBlur = () => {
+    const port = Number(portInput);
+    if (!Number.isInteger(port) || port < 1 || port > 65535) {
+      setPortInput(String(remoteApi.port));
+      return;
+    }
+    if (port !== remoteApi.port) {
+      apply({ ...remoteApi, port });
+    }
+  };
+
+  return (
+    <div className="bg-bg-secondary rounded-xl p-4 border border-border">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-3">
+          <Network className="w-5 h-5 text-accent" />
+          <div>
+            <span className="font-medium text-text-primary">{t('settings.remoteApi')}</span>
+            <p className="text-xs text-text-muted mt-0.5">{t('settings.remoteApiHint')}</p>
+          </div>
+        </div>
+        <SwitchButton value={remoteApi.enabled} onChange={handleToggle} />
+      </div>
+
+      {remoteApi.enabled && (
+        <div className="mt-4 pt-4 border-t border-border space-y-3">
+          <div className="flex items-center gap-3">
+            <span className="w-20 shrink-0 text-sm text-text-secondary">
+              {t('settings.remoteApiPort')}
+            </span>
+            <input
+              type="number"
+              min={1}
+              max={65535}
+              value={portInput}
+              onChange={(e) => setPortInput(e.target.value)}
+              onBlur={handlePortBlur}
+              className="w-32 px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50"
+            />
+          </div>
+
+          <div className="flex items-center gap-3">
+            <span className="w-20 shrink-0 text-sm text-text-secondary">
+              {t('settings.remoteApiToken')}
+            </span>
+            <div className="relative flex-1">
+              <input
+                type={showToken ? 'text' : 'password'}
+                value={remoteApi.token}
+                readOnly
+                className="w-full px-3 py-2 pr-10 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary font-mono focus:outline-none focus:ring-2 focus:ring-accent/50"
+              />
+              <button
+                onClick={() => setShowToken(!showToken)}
+                className="absolute right-2 top-1/2 -translate-y-1/2 p-1.5 text-text-muted hover:text-text-secondary transition-colors"
+              >
+                {showToken ? <EyeOff className="w-4 h-4" /> : <Eye className="w-4 h-4" />}
+              </button>
+            </div>
+            <button
+              onClick={() => apply({ ...remoteApi, token: generateToken() })}
+              title={t('settings.remoteApiRegenerateToken')}
+              className="p-2 rounded-lg bg-bg-tertiary hover:bg-bg-hover text-text-secondary transition-colors"
+            >
+              <RefreshCw className="w-4 h-4" />
+            </button>
+          </div>
+
+          {error ? (
+            <p className="text-xs text-error">{t('settings.remoteApiStartFailed', { error })}</p>
+          ) : (
+            status?.running &&
+            status.address && (
+              <p className="text-xs text-text-muted">
+                {t('settings.remoteApiListening', { address: status.address })}
+              </p>
+            )
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,101 @@
+import { useCallback, useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Video } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+
+import { useAppStore } from '@/stores/appStore';
+import { loggers } from '@/utils/logger';
+import { getErrorMessage } from '@/utils/errors';
+import { isTauri } from '@/utils/paths';
+import { SwitchButton } from '@/components/FormControls';
+
+interface RecordingStatus {
+  instance_id: string;
+  path: string;
+  started_at: number;
+  frame_count: number;
+  action_count: number;
+}
+
+/** 会话录制开关（录制当前实例） */
+export function SessionRecorderRow() {
+  const { t } = useTranslation();
+  const activeInstanceId = useAppStore((state) => state.activeInstanceId);
+
+  const [status, setStatus] = useState<RecordingStatus | null>(null);
+  const [savedPath, setSavedPath] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshStatus = useCallback(() => {
+    if (!isTauri() || !activeInstanceId) return;
+    invoke<RecordingStatus | null>('recorder_status', { instanceId: activeInstanceId })
+      .then(setStatus)
+      .catch(() => setStatus(null));
+  }, [activeInstanceId]);
+
+  useEffect(() => {
+    refreshStatus();
+  }, [refreshStatus]);
+
+  const handleToggle = async (enabled: boolean) => {
+    if (!activeInstanceId) return;
+    setError(null);
+    try {
+      if (enabled) {
+        setSavedPath(null);
+        setStatus(
+          await invoke<RecordingStatus>('recorder_start', { instanceId: activeInstanceId }),
+        );
+      } else {
+        const path = await invoke<string>('recorder_stop', { instanceId: activeInstanceId });
+        loggers.ui.info('会话已保存:', path);
+        setSavedPath(path);
+        setStatus(null);
+      }
+    } catch (err) {
+      loggers.ui.error('会话录制失败:', err);
+      setError(getErrorMessage(err));
+      refreshStatus();
+    }
+  };
+
+  const handleReveal = async () => {
+    if (!savedPath) return;
+    try {
+      const { revealItemInDir } = await import('@tauri-apps/plugin-opener');
+      await revealItemInDir(savedPath);
+    } catch (err) {
+      loggers.ui.error('打开会话包所在目录失败:', err);
+    }
+  };
+
+  return (
+    <div className="pt-4 border-t border-border space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-3">
+          <Video className="w-5 h-5 text-accent" />
+          <div>
+            <span className="font-medium text-text-primary">{t('debug.recordSession')}</span>
+            <p className="text-xs text-text-muted mt-0.5">{t('debug.recordSessionHint')}</p>
+          </div>
+        </div>
+        <SwitchButton value={!!status} onChange={handleToggle} disabled={!activeInstanceId} />
+      </div>
+
+      {error ? (
+        <p className="text-xs text-error">{t('debug.recordSessionFailed', { error })}</p>
+      ) : status ? (
+        <p className="text-xs text-text-muted">{t('debug.recordingSession')}</p>
+      ) : (
+        savedPath && (
+          <button
+            onClick={handleReveal}
+            className="text-xs text-accent hover:underline break-all text-left"
+          >
+            {t('debug.sessionSaved', { path: savedPath })}
+          </button>
+        )
+      )}
+    </div>
+  );
+}
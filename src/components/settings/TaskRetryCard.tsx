@@ -0,0 +1,119 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { RotateCcw } from 'lucide-react';
+
+import { useAppStore } from '@/stores/appStore';
+import { SwitchButton } from '@/components/FormControls';
+
+/** 重试次数上限 */
+const MAX_RETRY_ATTEMPTS = 10;
+
+/** 重试前等待时间上限（秒） */
+const MAX_RETRY_DELAY_SECONDS = 3600;
+
+/** 任务失败重试设置卡片 */
+export function TaskRetryCard() {
+  const { t } = useTranslation();
+  const { taskRetry, setTaskRetry } = useAppStore();
+
+  const [attemptsInput, setAttemptsInput] = useState(String(taskRetry.attempts));
+  const [delayInput, setDelayInput] = useState(String(taskRetry.delaySeconds));
+
+  /** 校验输入的数值，无效时恢复原值 */
+  const handleNumberBlur = (
+    input: string,
+    setInput: (value: string) => void,
+    key: 'attempts' | 'delaySeconds',
+    min: number,
+    max: number,
+  ) => {
+    const value = Number(input);
+    if (!Number.isInteger(value) || value < min || value > max) {
+      setInput(String(taskRetry[key]));
+      return;
+    }
+    if (value !== taskRetry[key]) {
+      setTaskRetry({ ...taskRetry, [key]: value });
+    }
+  };
+
+  const inputClassName =
+    'w-24 px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50';
+
+  return (
+    <div className="bg-bg-secondary rounded-xl p-4 border border-border">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-3">
+          <RotateCcw className="w-5 h-5 text-accent" />
+          <div>
+            <span className="font-medium text-text-primary">{t('settings.taskRetry')}</span>
+            <p className="text-xs text-text-muted mt-0.5">{t('settings.taskRetryHint')}</p>
+          </div>
+        </div>
+        <SwitchButton
+          value={taskRetry.enabled}
+          onChange={(enabled) => setTaskRetry({ ...taskRetry, enabled })}
+        />
+      </div>
+
+      {taskRetry.enabled && (
+        <div className="mt-4 pt-4 border-t border-border space-y-3">
+          <div className="flex items-center gap-3">
+            <span className="flex-1 text-sm text-text-secondary">
+              {t('settings.taskRetryAttempts')}
+            </span>
+            <input
+              type="number"
+              min={1}
+              max={MAX_RETRY_ATTEMPTS}
+              value={attemptsInput}
+              onChange={(e) => setAttemptsInput(e.target.value)}
+              onBlur={() =>
+                handleNumberBlur(attemptsInput, setAttemptsInput, 'attempts', 1, MAX_RETRY_ATTEMPTS)
+              }
+              className={inputClassName}
+            />
+          </div>
+
+          <div className="flex items-center gap-3">
+            <span className="flex-1 text-sm text-text-secondary">
+              {t('settings.taskRetryDelay')}
+            </span>
+            <input
+              type="number"
+              min={0}
+              max={MAX_RETRY_DELAY_SECONDS}
+              value={delayInput}
+              onChange={(e) => setDelayInput(e.target.value)}
+              onBlur={() =>
+                handleNumberBlur(
+                  delayInput,
+                  setDelayInput,
+                  'delaySeconds',
+                  0,
+                  MAX_RETRY_DELAY_SECONDS,
+                )
+              }
+              className={inputClassName}
+            />
+          </div>
+
+          <div className="flex items-center justify-between gap-3">
+            <div>
+              <span className="text-sm text-text-secondary">
+                {t('settings.taskRetryOnTimeout')}
+              </span>
+              <p className="text-xs text-text-muted mt-0.5">
+                {t('settings.taskRetryOnTimeoutHint')}
+              </p>
+            </div>
+            <SwitchButton
+              value={taskRetry.retryOnTimeout}
+              onChange={(retryOnTimeout) => setTaskRetry({ ...taskRetry, retryOnTimeout })}
+            />
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,108 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Timer } from 'lucide-react';
+
+import { useAppStore } from '@/stores/appStore';
+import { SwitchButton } from '@/components/FormControls';
+
+/** 超时时间上限（分钟） */
+const MAX_TIMEOUT_MINUTES = 24 * 60;
+
+/** 任务超时设置卡片 */
+export function TaskTimeoutCard() {
+  const { t } = useTranslation();
+  const { taskTimeout, setTaskTimeout } = useAppStore();
+
+  const [taskInput, setTaskInput] = useState(String(taskTimeout.taskTimeoutMinutes));
+  const [runInput, setRunInput] = useState(String(taskTimeout.runTimeoutMinutes));
+
+  /** 校验输入的分钟数，无效时恢复原值 */
+  const handleMinutesBlur = (
+    input: string,
+    setInput: (value: string) => void,
+    key: 'taskTimeoutMinutes' | 'runTimeoutMinutes',
+  ) => {
+    const minutes = Number(input);
+    if (!Number.isInteger(minutes) || minutes < 0 || minutes > MAX_TIMEOUT_MINUTES) {
+      setInput(String(taskTimeout[key]));
+      return;
+    }
+    if (minutes !== taskTimeout[key]) {
+      setTaskTimeout({ ...taskTimeout, [key]: minutes });
+    }
+  };
+
+  const inputClassName =
+    'w-24 px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50';
+
+  return (
+    <div className="bg-bg-secondary rounded-xl p-4 border border-border">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-3">
+          <Timer className="w-5 h-5 text-accent" />
+          <div>
+            <span className="font-medium text-text-primary">{t('settings.taskTimeout')}</span>
+            <p className="text-xs text-text-muted mt-0.5">{t('settings.taskTimeoutHint')}</p>
+          </div>
+        </div>
+        <SwitchButton
+          value={taskTimeout.enabled}
+          onChange={(enabled) => setTaskTimeout({ ...taskTimeout, enabled })}
+        />
+      </div>
+
+      {taskTimeout.enabled && (
+        <div className="mt-4 pt-4 border-t border-border space-y-3">
+          <div className="flex items-center gap-3">
+            <span className="flex-1 text-sm text-text-secondary">
+              {t('settings.taskTimeoutPerTask')}
+            </span>
+            <input
+              type="number"
+              min={0}
+              max={MAX_TIMEOUT_MINUTES}
+              value={taskInput}
+              onChange={(e) => setTaskInput(e.target.value)}
+              onBlur={() => handleMinutesBlur(taskInput, setTaskInput, 'taskTimeoutMinutes')}
+              className={inputClassName}
+            />
+          </div>
+
+          <div className="flex items-center gap-3">
+            <span className="flex-1 text-sm text-text-secondary">
+              {t('settings.taskTimeoutPerRun')}
+            </span>
+            <input
+              type="number"
+              min={0}
+              max={MAX_TIMEOUT_MINUTES}
+              value={runInput}
+              onChange={(e) => setRunInput(e.target.value)}
+              onBlur={() => handleMinutesBlur(runInput, setRunInput, 'runTimeoutMinutes')}
+              className={inputClassName}
+            />
+          </div>
+
+          <p className="text-xs text-text-muted">{t('settings.taskTimeoutMinutesHint')}</p>
+
+          <div className="flex items-center justify-between gap-3">
+            <div>
+              <span className="text-sm text-text-secondary">
+                {t('settings.taskTimeoutContinue')}
+              </span>
+              <p className="text-xs text-text-muted mt-0.5">
+                {t('settings.taskTimeoutContinueHint')}
+              </p>
+            </div>
+            <SwitchButton
+              value={taskTimeout.continueOnTimeout}
+              onChange={(continueOnTimeout) =>
+                setTaskTimeout({ ...taskTimeout, continueOnTimeout })
+              }
+            />
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -30,6 +30,7 @@ import { createProxySettings, shouldUseProxy } from '@/services/proxyService';
 import { resolveI18nText } from '@/services/contentResolver';
 import { getInterfaceLangKey } from '@/i18n';
 import { loggers } from '@/utils/logger';
+import { getErrorMessage } from '@/utils/errors';
 import { ReleaseNotes, DownloadProgressBar } from '../UpdateInfoCard';
 
 export function UpdateSection() {
@@ -237,7 +238,7 @@ export function UpdateSection() {
             }
           }
         } catch (err) {
-          addDebugLog(`切换下载源失败: ${err}`);
+          addDebugLog(`切换下载源失败: ${getErrorMessage(err)}`);
         } finally {
           setUpdateCheckLoading(false);
         }
@@ -340,7 +341,7 @@ export function UpdateSection() {
         addDebugLog('检查更新失败');
       }
     } catch (err) {
-      addDebugLog(`检查更新出错: ${err}`);
+      addDebugLog(`检查更新出错: ${getErrorMessage(err)}`);
     } finally {
       setUpdateCheckLoading(false);
     }
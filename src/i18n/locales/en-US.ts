@@ -88,6 +88,40 @@ export default {
     hotkeysGlobalOnlyStart: 'only start works in global mode',
     minimizeToTray: 'Minimize to tray on close',
     minimizeToTrayHint: 'Hide to system tray instead of exiting when clicking close button',
+    remoteApi: 'Local Control API',
+    remoteApiHint:
+      'Serve an HTTP / WebSocket API on 127.0.0.1 for scripts to query status, start/stop tasks and fetch screenshots',
+    remoteApiPort: 'Port',
+    remoteApiToken: 'Access token',
+    remoteApiRegenerateToken: 'Regenerate token',
+    remoteApiListening: 'Listening on {{address}}',
+    remoteApiStartFailed: 'Failed to start: {{error}}',
+    autoReconnect: 'Auto Reconnect',
+    autoReconnectHint:
+      'Reconnect automatically when the controller drops (e.g. emulator restart) and continue unfinished tasks',
+    autoReconnectMaxAttempts: 'Max attempts',
+    autoReconnectResumeTasks: 'Resume tasks after reconnecting',
+    autoReconnectResumeTasksHint:
+      'Restart from the tasks that were unfinished when the connection dropped',
+    taskTimeout: 'Task Timeout',
+    taskTimeoutHint:
+      'Stop tasks that run too long so a stuck task does not block later scheduled runs',
+    taskTimeoutPerTask: 'Timeout per task (minutes)',
+    taskTimeoutPerRun: 'Timeout per run (minutes)',
+    taskTimeoutMinutesHint: '0 means no limit',
+    taskTimeoutContinue: 'Continue after a task times out',
+    taskTimeoutContinueHint:
+      'Run the remaining tasks after stopping the timed-out one; a run timeout always stops all tasks',
+    taskRetry: 'Retry Failed Tasks',
+    taskRetryHint: 'Automatically run a failed task again and continue with the remaining tasks',
+    taskRetryAttempts: 'Max retries',
+    taskRetryDelay: 'Wait before retrying (seconds)',
+    taskRetryOnTimeout: 'Also retry after a timeout',
+    taskRetryOnTimeoutHint:
+      'Requires a per-task timeout; the timed-out task is run again from its entry',
+    adbDeviceMonitor: 'ADB Device Hotplug Monitoring',
+    adbDeviceMonitorHint:
+      'Periodically scan for ADB devices, update the device list when devices appear or disappear, and reconnect when the previously used device comes back',
     autoStart: 'Launch at startup',
     autoStartHint: 'Automatically start this application when the system boots',
     autoStartInstance: 'Auto-execute on startup',
@@ -208,6 +242,10 @@ export default {
     stopTasks: 'Stop Tasks',
     startingTasks: 'Starting...',
     stoppingTasks: 'Stopping...',
+    pauseTasks: 'Pause',
+    pausingTasks: 'Pausing...',
+    resumeTasks: 'Resume',
+    pauseTasksHint: 'Pause after the current task finishes',
     // Auto connect
     autoConnect: {
       searching: 'Searching devices...',
@@ -221,6 +259,8 @@ export default {
       noDeviceFound: 'No devices found',
       noWindowFound: 'No windows found',
       connectFailed: 'Auto connect failed',
+      deviceAppeared: 'Device "{{name}}" appeared, connecting automatically',
+      deviceRemoved: 'Device "{{name}}" was removed',
       autoSelectedDevice:
         'No device was previously selected. Automatically matched "{{name}}". To change, select manually in Connection Settings — your choice will be remembered next time.',
       autoSelectedWindow:
@@ -230,6 +270,8 @@ export default {
       needConfig:
         'Please connect device and load resource first, or save device config in connection panel',
     },
+    taskRejected: 'Task "{{name}}" was not submitted: {{reason}}',
+    taskOverrideWarning: 'Task "{{name}}" was submitted with warnings: {{reason}}',
   },
 
   // Task item
@@ -266,6 +308,51 @@ export default {
     incompatibleController: 'Not supported by current controller',
     incompatibleResource: 'Not supported by current resource',
     supportedControllers: 'Supported: {{controllers}}',
+    retryOverridden: 'Custom retry settings',
+  },
+
+  // Task execution detail
+  taskDetail: {
+    title: 'Execution Detail - {{name}}',
+    refresh: 'Refresh',
+    loading: 'Loading...',
+    notFound: 'Task detail unavailable (the task was cleaned up or the instance was recreated)',
+    noNodes: 'No nodes executed yet',
+    nodeCount: '{{count}} node(s)',
+    recognition: 'Recognition',
+    hit: 'Hit',
+    miss: 'Miss',
+    action: 'Action',
+    actionSucceeded: 'Succeeded',
+    actionFailed: 'Failed',
+    noAction: 'No action executed',
+    showImages: 'Show recognition images',
+    hideImages: 'Hide recognition images',
+    rawImage: 'Screenshot',
+    drawImage: 'Annotated {{index}}',
+    imagesUnavailable:
+      'No recognition images available. Enable debug mode in settings and run the task again',
+    status: {
+      Pending: 'Pending',
+      Running: 'Running',
+      Succeeded: 'Succeeded',
+      Failed: 'Failed',
+    },
+  },
+
+  // Task retry settings
+  taskRetry: {
+    title: 'Retry Settings - {{name}}',
+    override: 'Custom retry for this task',
+    overrideHint: 'When off, "Retry Failed Tasks" in Settings is used',
+    attempts: 'Max retry attempts (0 = no retry)',
+    delay: 'Wait before retry (seconds)',
+    onTimeout: 'Also retry on timeout',
+    onTimeoutHint: 'Requires a per-task timeout; timed-out tasks restart from their entry',
+    restartApp: 'Restart app before retrying',
+    restartAppHint: 'Package name or Activity for ADB controllers; leave empty to skip',
+    restartAppPlaceholder: 'com.example.app',
+    invalid: 'Enter valid retry attempts (0-10) and wait time (0-3600 seconds)',
   },
 
   // Options
@@ -352,6 +439,8 @@ export default {
     noDevices: 'No devices found',
     noWindows: 'No windows found',
     playcoverHint: 'Enter PlayCover app listen address',
+    imageFolderPlaceholder: 'Image folder path (debug: replays screenshots offline)',
+    browseImageFolder: 'Browse image folder',
     lastSelected: 'Last selected · Click to search',
     savedDeviceNotFound: 'Previous device not found, please check connection or select another',
     savedWindowNotFound: 'Previous window not found, please check connection or select another',
@@ -390,6 +479,12 @@ export default {
     connectFirst: 'Please connect a device first',
     fullscreen: 'Fullscreen',
     exitFullscreen: 'Exit Fullscreen',
+    enableRemoteControl: 'Enable Remote Control (click or drag on the screenshot)',
+    disableRemoteControl: 'Disable Remote Control',
+    resolutionFollowController: 'Controller Default ({{resolution}})',
+    resolutionShortSide: 'Short Side {{value}}',
+    resolutionLongSide: 'Long Side {{value}}',
+    resolutionRaw: 'Raw Resolution',
     // Frame rate settings
     frameRate: {
       title: 'Screenshot Frame Rate',
@@ -430,6 +525,27 @@ export default {
       stopTask: 'Stop Task',
       // Schedule messages
       scheduleStarting: 'Scheduled execution started [{{policy}}] {{time}}',
+      scheduleSkippedRunning:
+        'Scheduled execution skipped [{{policy}}] {{time}}: instance is already running',
+      scheduleMissed: 'Scheduled execution missed [{{policy}}] {{time}}',
+      scheduleFinished: 'Scheduled execution finished [{{policy}}]',
+      scheduleFailed: 'Scheduled execution failed [{{policy}}]: {{error}}',
+      controllerLost: 'Controller disconnected, reconnecting in {{seconds}}s',
+      reconnecting: 'Reconnecting controller ({{attempt}}/{{max}})...',
+      reconnectAttemptFailed: 'Reconnection failed ({{attempt}}/{{max}}): {{error}}',
+      reconnected: 'Controller reconnected, resuming {{count}} task(s)',
+      reconnectGaveUp: 'Gave up reconnecting after {{max}} attempt(s)',
+      tasksPausing: 'Pausing after the current task finishes',
+      tasksPaused: 'Tasks paused, {{count}} task(s) on hold',
+      tasksResumed: 'Tasks resumed, {{count}} task(s) resubmitted',
+      pauseResumeFailed: 'Failed to pause or resume tasks: {{error}}',
+      taskTimedOut: 'Task "{{name}}" timed out after {{seconds}}s and was stopped',
+      runTimedOut: 'Run timed out after {{seconds}}s, stopping all tasks',
+      timeoutContinued: 'Continuing with the remaining {{count}} task(s)',
+      taskRetrying: 'Task "{{name}}" failed, retrying ({{attempt}}/{{max}})',
+      taskRetryExhausted: 'Task "{{name}}" still failed after {{count}} retries',
+      taskQueueApplied: 'Task queue updated, continuing with the remaining {{count}} task(s)',
+      taskRepostFailed: 'Failed to resubmit task "{{name}}": {{error}}',
       // Agent messages
       agentStarting: 'Agent starting...',
       agentStarted: 'Agent started',
@@ -500,9 +616,18 @@ export default {
     saveDraw: 'Save Debug Images',
     saveDrawHint:
       'Save recognition and action debug images to log directory (auto-disabled on restart)',
+    debugMode: 'Debug Mode',
+    debugModeHint:
+      'Keep recognition screenshots and annotated images for the task execution detail (uses more memory, auto-disabled on restart)',
     tcpCompatMode: 'Communication Compat Mode',
     tcpCompatModeHint:
       'Try enabling this if the app crashes immediately after starting tasks. Only use in this case, as it may reduce performance',
+    recordSession: 'Record session',
+    recordSessionHint:
+      'Record screenshots, controller actions and callback events of the current instance. Saved as a session bundle when stopped, for bug reports or offline replay via the ImageFolder controller',
+    recordingSession: 'Recording...',
+    sessionSaved: 'Session saved: {{path}}',
+    recordSessionFailed: 'Session recording failed: {{error}}',
   },
 
   // Welcome dialog
@@ -713,6 +838,11 @@ export default {
     addTask: 'Add Task',
     duplicateTask: 'Duplicate Task',
     deleteTask: 'Delete Task',
+    viewTaskDetail: 'View Execution Detail',
+    taskRetrySettings: 'Retry Settings',
+    runTaskNext: 'Run Next',
+    queueTask: 'Add to Running Queue',
+    cancelQueuedTask: 'Remove from Queue',
     exportTasks: 'Export task list',
     importTasks: 'Import task list',
     renameTask: 'Rename Task',
@@ -737,6 +867,9 @@ export default {
     fullscreen: 'Fullscreen',
     saveScreenshot: 'Save Screenshot',
     copyScreenshot: 'Copy Screenshot',
+    exportRecentFrames: 'Export Recent Frames (APNG)',
+    exportRecentFramesGif: 'Export Recent Frames (GIF)',
+    screenshotResolution: 'Screenshot Resolution',
 
     // Connection panel context menu
     refreshDevices: 'Refresh Device List',
@@ -88,6 +88,39 @@ export default {
     hotkeysGlobalOnlyStart: 'グローバルモードでは開始のみ有効',
     minimizeToTray: '閉じる時にトレイに最小化',
     minimizeToTrayHint: '閉じるボタンをクリックすると、終了せずにシステムトレイに隠れます',
+    remoteApi: 'ローカル制御 API',
+    remoteApiHint:
+      '127.0.0.1 で HTTP / WebSocket API を公開し、スクリプトから状態確認・タスクの開始/停止・スクリーンショット取得を行えます',
+    remoteApiPort: 'ポート',
+    remoteApiToken: 'アクセストークン',
+    remoteApiRegenerateToken: 'トークンを再生成',
+    remoteApiListening: '{{address}} で待ち受け中',
+    remoteApiStartFailed: '起動に失敗しました：{{error}}',
+    autoReconnect: '切断時に自動再接続',
+    autoReconnectHint:
+      'コントローラーが予期せず切断された場合（エミュレーターの再起動など）に自動で再接続し、未完了のタスクを続行します',
+    autoReconnectMaxAttempts: '最大再接続回数',
+    autoReconnectResumeTasks: '再接続後にタスクを続行',
+    autoReconnectResumeTasksHint: '切断時に未完了だったタスクから再実行します',
+    taskTimeout: 'タスクのタイムアウト',
+    taskTimeoutHint:
+      '長時間実行中のタスクを停止し、止まったタスクが後のスケジュール実行を妨げないようにします',
+    taskTimeoutPerTask: 'タスクごとのタイムアウト（分）',
+    taskTimeoutPerRun: '実行全体のタイムアウト（分）',
+    taskTimeoutMinutesHint: '0 は無制限です',
+    taskTimeoutContinue: 'タイムアウト後も続行',
+    taskTimeoutContinueHint:
+      'タイムアウトしたタスクを停止した後、残りのタスクを実行します。実行全体のタイムアウトではすべて停止します',
+    taskRetry: '失敗したタスクの再試行',
+    taskRetryHint: '失敗したタスクを自動で再実行し、残りのタスクを続行します',
+    taskRetryAttempts: '最大再試行回数',
+    taskRetryDelay: '再試行前の待機時間（秒）',
+    taskRetryOnTimeout: 'タイムアウト時も再試行',
+    taskRetryOnTimeoutHint:
+      'タスクごとのタイムアウトが必要です。タイムアウトしたタスクを最初から再実行します',
+    adbDeviceMonitor: 'ADB デバイスのホットプラグ監視',
+    adbDeviceMonitorHint:
+      'ADB デバイスを定期的に検索し、デバイスの接続・切断時にリストを更新します。以前使用したデバイスが再び現れると自動で接続します',
     autoStart: 'スタートアップ時に起動',
     autoStartHint: 'システム起動時にこのアプリケーションを自動的に起動します',
     autoStartInstance: '起動後に自動実行',
@@ -207,6 +240,10 @@ export default {
     stopTasks: '実行停止',
     startingTasks: '開始中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '一時停止',
+    pausingTasks: '一時停止中...',
+    resumeTasks: '再開',
+    pauseTasksHint: '現在のタスクの完了後に一時停止',
     // 自動接続関連
     autoConnect: {
       searching: 'デバイスを検索中...',
@@ -220,6 +257,8 @@ export default {
       noDeviceFound: 'デバイスが見つかりませんでした',
       noWindowFound: 'ウィンドウが見つかりませんでした',
       connectFailed: '自動接続に失敗しました',
+      deviceAppeared: 'デバイス「{{name}}」が接続されました。自動で接続します',
+      deviceRemoved: 'デバイス「{{name}}」が切断されました',
       autoSelectedDevice:
         'デバイスが未設定のため、「{{name}}」を自動的に選択しました。変更する場合は接続設定で手動選択してください。次回以降は選択内容が保存されます。',
       autoSelectedWindow:
@@ -229,6 +268,9 @@ export default {
       needConfig:
         'まずデバイスを接続してリソースを読み込むか、接続パネルでデバイス設定を保存してください',
     },
+    taskRejected: 'タスク「{{name}}」は送信されませんでした：{{reason}}',
+    taskOverrideWarning:
+      'タスク「{{name}}」は送信されましたが、設定に問題がある可能性があります：{{reason}}',
   },
 
   // タスク項目
@@ -265,6 +307,53 @@ export default {
     incompatibleController: '現在のコントローラーに対応していません',
     incompatibleResource: '現在のリソースに対応していません',
     supportedControllers: '対応コントローラー: {{controllers}}',
+    retryOverridden: '個別のリトライ設定あり',
+  },
+
+  // タスク実行詳細
+  taskDetail: {
+    title: '実行詳細 - {{name}}',
+    refresh: '更新',
+    loading: '読み込み中...',
+    notFound: 'タスク詳細を取得できません（タスクが破棄されたか、インスタンスが再作成されました）',
+    noNodes: 'まだノードが実行されていません',
+    nodeCount: '{{count}} ノード',
+    recognition: '認識',
+    hit: 'ヒット',
+    miss: 'ミス',
+    action: 'アクション',
+    actionSucceeded: '成功',
+    actionFailed: '失敗',
+    noAction: 'アクション未実行',
+    showImages: '認識画像を表示',
+    hideImages: '認識画像を隠す',
+    rawImage: 'スクリーンショット',
+    drawImage: '注釈画像 {{index}}',
+    imagesUnavailable:
+      '利用できる認識画像がありません。設定でデバッグモードをオンにしてから再実行してください',
+    status: {
+      Pending: '待機中',
+      Running: '実行中',
+      Succeeded: '完了',
+      Failed: '失敗',
+    },
+  },
+
+  // タスクのリトライ設定
+  taskRetry: {
+    title: 'リトライ設定 - {{name}}',
+    override: 'このタスクのリトライを個別に設定',
+    overrideHint: 'オフの場合は設定の「失敗したタスクの再試行」を使用します',
+    attempts: '最大リトライ回数（0 はリトライしない）',
+    delay: 'リトライ前の待機（秒）',
+    onTimeout: 'タイムアウト時もリトライ',
+    onTimeoutHint:
+      'タスクごとのタイムアウト設定が必要です。タイムアウトしたタスクはエントリから再実行されます',
+    restartApp: 'リトライ前にアプリを再起動',
+    restartAppHint:
+      'ADB コントローラーではパッケージ名または Activity を入力します。空欄の場合は再起動しません',
+    restartAppPlaceholder: 'com.example.app',
+    invalid: '有効なリトライ回数（0-10）と待機時間（0-3600 秒）を入力してください',
   },
 
   // オプション
@@ -351,6 +440,8 @@ export default {
     noDevices: 'デバイスが見つかりません',
     noWindows: 'ウィンドウが見つかりません',
     playcoverHint: 'PlayCover アプリのリッスンアドレスを入力',
+    imageFolderPlaceholder: '画像フォルダのパス（デバッグ：スクリーンショットをオフライン再生）',
+    browseImageFolder: '画像フォルダを選択',
     lastSelected: '前回の選択 · クリックして検索',
     savedDeviceNotFound:
       '前回のデバイスが見つかりません。接続を確認するか、別のデバイスを選択してください',
@@ -391,6 +482,12 @@ export default {
     connectFirst: '先にデバイスを接続してください',
     fullscreen: '全画面表示',
     exitFullscreen: '全画面を終了',
+    enableRemoteControl: 'リモート操作を有効化（スクリーンショット上でクリックまたはドラッグ）',
+    disableRemoteControl: 'リモート操作を無効化',
+    resolutionFollowController: 'コントローラーの既定値（{{resolution}}）',
+    resolutionShortSide: '短辺 {{value}}',
+    resolutionLongSide: '長辺 {{value}}',
+    resolutionRaw: '元の解像度',
     // フレームレート設定
     frameRate: {
       title: 'スクリーンショットのフレームレート',
@@ -431,6 +528,27 @@ export default {
       stopTask: 'タスクを停止',
       // スケジュールメッセージ
       scheduleStarting: 'スケジュール実行を開始 [{{policy}}] {{time}}',
+      scheduleSkippedRunning:
+        'スケジュール実行をスキップ [{{policy}}] {{time}}：インスタンスは実行中です',
+      scheduleMissed: 'スケジュール実行を逃しました [{{policy}}] {{time}}',
+      scheduleFinished: 'スケジュール実行が完了 [{{policy}}]',
+      scheduleFailed: 'スケジュール実行に失敗 [{{policy}}]: {{error}}',
+      controllerLost: 'コントローラーが切断されました。{{seconds}} 秒後に再接続します',
+      reconnecting: 'コントローラーに再接続中（{{attempt}}/{{max}}）...',
+      reconnectAttemptFailed: '再接続に失敗（{{attempt}}/{{max}}）: {{error}}',
+      reconnected: 'コントローラーに再接続しました。{{count}} 個のタスクを続行します',
+      reconnectGaveUp: '{{max}} 回試行しましたが再接続できませんでした',
+      tasksPausing: '現在のタスクの完了後に一時停止します',
+      tasksPaused: 'タスクを一時停止しました（保留中 {{count}} 件）',
+      tasksResumed: 'タスクを再開しました（再送信 {{count}} 件）',
+      pauseResumeFailed: 'タスクの一時停止/再開に失敗しました: {{error}}',
+      taskTimedOut: 'タスク「{{name}}」が {{seconds}} 秒でタイムアウトしたため停止しました',
+      runTimedOut: '実行が {{seconds}} 秒でタイムアウトしたため、すべてのタスクを停止します',
+      timeoutContinued: '残りの {{count}} 件のタスクを続行します',
+      taskRetrying: 'タスク「{{name}}」が失敗したため再試行します（{{attempt}}/{{max}}）',
+      taskRetryExhausted: 'タスク「{{name}}」は {{count}} 回再試行しても失敗しました',
+      taskQueueApplied: 'タスクキューを変更しました。残りの {{count}} 件のタスクを続行します',
+      taskRepostFailed: 'タスク「{{name}}」の再投入に失敗しました: {{error}}',
       // Agent メッセージ
       agentStarting: 'Agent を起動中...',
       agentStarted: 'Agent が起動しました',
@@ -501,9 +619,18 @@ export default {
     saveDraw: 'デバッグ画像を保存',
     saveDrawHint:
       '認識と操作のデバッグ画像をログフォルダに保存します（再起動後は自動的にオフになります）',
+    debugMode: 'デバッグモード',
+    debugModeHint:
+      '認識に使用したスクリーンショットと注釈画像を保持し、実行詳細で確認できます（メモリ使用量が増えます。再起動後は自動的にオフになります）',
     tcpCompatMode: '通信互換モード',
     tcpCompatModeHint:
       'タスク開始後にアプリがすぐにクラッシュする場合は有効にしてください。この場合のみ使用し、それ以外は性能に影響します',
+    recordSession: 'セッションを録画',
+    recordSessionHint:
+      '現在のインスタンスのスクリーンショット、コントローラー操作、コールバックイベントを録画します。停止するとセッションバンドルとして保存され、不具合報告や ImageFolder コントローラーでのオフライン再生に使用できます',
+    recordingSession: '録画中...',
+    sessionSaved: 'セッションを保存しました：{{path}}',
+    recordSessionFailed: 'セッションの録画に失敗しました：{{error}}',
   },
 
   // ウェルカムダイアログ
@@ -717,6 +844,11 @@ export default {
     addTask: 'タスクを追加',
     duplicateTask: 'タスクを複製',
     deleteTask: 'タスクを削除',
+    viewTaskDetail: '実行詳細を表示',
+    taskRetrySettings: 'リトライ設定',
+    runTaskNext: '次に実行',
+    queueTask: '実行キューに追加',
+    cancelQueuedTask: 'キューから削除',
     exportTasks: 'タスクリストをエクスポート',
     importTasks: 'タスクリストをインポート',
     renameTask: 'タスク名を変更',
@@ -741,6 +873,9 @@ export default {
     fullscreen: '全画面表示',
     saveScreenshot: 'スクリーンショットを保存',
     copyScreenshot: 'スクリーンショットをコピー',
+    exportRecentFrames: '最近の画面をエクスポート (APNG)',
+    exportRecentFramesGif: '最近の画面をエクスポート (GIF)',
+    screenshotResolution: 'スクリーンショット解像度',
 
     // 接続パネルのコンテキストメニュー
     refreshDevices: 'デバイス一覧を更新',
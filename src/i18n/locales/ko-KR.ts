@@ -87,6 +87,39 @@ export default {
     hotkeysGlobalOnlyStart: '전역 모드에서는 시작만 작동',
     minimizeToTray: '닫을 때 트레이로 최소화',
     minimizeToTrayHint: '닫기 버튼을 클릭하면 종료하지 않고 시스템 트레이에 숨깁니다',
+    remoteApi: '로컬 제어 API',
+    remoteApiHint:
+      '127.0.0.1에서 HTTP / WebSocket API를 열어 스크립트가 상태 조회, 작업 시작/중지, 스크린샷 가져오기를 할 수 있습니다',
+    remoteApiPort: '포트',
+    remoteApiToken: '액세스 토큰',
+    remoteApiRegenerateToken: '토큰 다시 생성',
+    remoteApiListening: '{{address}}에서 수신 중',
+    remoteApiStartFailed: '시작 실패: {{error}}',
+    autoReconnect: '연결 끊김 시 자동 재연결',
+    autoReconnectHint:
+      '컨트롤러 연결이 예기치 않게 끊어지면(예: 에뮬레이터 재시작) 자동으로 다시 연결하고 완료되지 않은 작업을 계속합니다',
+    autoReconnectMaxAttempts: '최대 재연결 횟수',
+    autoReconnectResumeTasks: '재연결 후 작업 계속',
+    autoReconnectResumeTasksHint: '연결이 끊겼을 때 완료되지 않은 작업부터 다시 실행합니다',
+    taskTimeout: '작업 시간 제한',
+    taskTimeoutHint:
+      '너무 오래 실행되는 작업을 중지하여 멈춘 작업이 이후 예약 실행을 막지 않도록 합니다',
+    taskTimeoutPerTask: '작업별 시간 제한 (분)',
+    taskTimeoutPerRun: '전체 실행 시간 제한 (분)',
+    taskTimeoutMinutesHint: '0은 제한 없음을 의미합니다',
+    taskTimeoutContinue: '시간 초과 후 계속 실행',
+    taskTimeoutContinueHint:
+      '시간이 초과된 작업을 중지한 후 남은 작업을 실행합니다. 전체 실행 시간 초과 시에는 모두 중지합니다',
+    taskRetry: '실패한 작업 재시도',
+    taskRetryHint: '실패한 작업을 자동으로 다시 실행하고 남은 작업을 계속합니다',
+    taskRetryAttempts: '최대 재시도 횟수',
+    taskRetryDelay: '재시도 전 대기 시간 (초)',
+    taskRetryOnTimeout: '시간 초과 시에도 재시도',
+    taskRetryOnTimeoutHint:
+      '작업별 시간 제한이 필요합니다. 시간이 초과된 작업을 처음부터 다시 실행합니다',
+    adbDeviceMonitor: 'ADB 장치 핫플러그 감시',
+    adbDeviceMonitorHint:
+      'ADB 장치를 주기적으로 검색하여 장치가 연결되거나 분리될 때 목록을 업데이트하고, 이전에 사용한 장치가 다시 나타나면 자동으로 연결합니다',
     autoStart: '시작 시 자동 실행',
     autoStartHint: '시스템 부팅 시 이 애플리케이션을 자동으로 시작합니다',
     autoStartInstance: '시작 후 자동 실행',
@@ -207,6 +240,10 @@ export default {
     stopTasks: '실행 중지',
     startingTasks: '시작 중...',
     stoppingTasks: '중지 중...',
+    pauseTasks: '일시정지',
+    pausingTasks: '일시정지 중...',
+    resumeTasks: '재개',
+    pauseTasksHint: '현재 작업이 끝난 후 일시정지',
     // 자동 연결 관련
     autoConnect: {
       searching: '기기 검색 중...',
@@ -220,6 +257,8 @@ export default {
       noDeviceFound: '기기를 찾을 수 없습니다',
       noWindowFound: '창을 찾을 수 없습니다',
       connectFailed: '자동 연결에 실패했습니다',
+      deviceAppeared: '장치 「{{name}}」이(가) 연결되어 자동으로 연결합니다',
+      deviceRemoved: '장치 「{{name}}」이(가) 분리되었습니다',
       autoSelectedDevice:
         '기기가 설정되지 않아 「{{name}}」을(를) 자동으로 선택했습니다. 변경하려면 연결 설정에서 수동으로 선택하세요. 다음 번에는 선택 내용이 저장됩니다.',
       autoSelectedWindow:
@@ -228,6 +267,9 @@ export default {
       startFailed: '작업 시작에 실패했습니다',
       needConfig: '먼저 기기를 연결하고 리소스를 로드하거나 연결 패널에서 기기 설정을 저장하세요',
     },
+    taskRejected: '작업 「{{name}}」이(가) 제출되지 않았습니다: {{reason}}',
+    taskOverrideWarning:
+      '작업 「{{name}}」이(가) 제출되었지만 설정에 문제가 있을 수 있습니다: {{reason}}',
   },
 
   // 작업 항목
@@ -264,6 +306,52 @@ export default {
     incompatibleController: '현재 컨트롤러에서 지원되지 않음',
     incompatibleResource: '현재 리소스에서 지원되지 않음',
     supportedControllers: '지원 컨트롤러: {{controllers}}',
+    retryOverridden: '개별 재시도 설정됨',
+  },
+
+  // 작업 실행 상세
+  taskDetail: {
+    title: '실행 상세 - {{name}}',
+    refresh: '새로고침',
+    loading: '불러오는 중...',
+    notFound: '작업 상세를 사용할 수 없습니다 (작업이 정리되었거나 인스턴스가 다시 생성됨)',
+    noNodes: '아직 실행된 노드가 없습니다',
+    nodeCount: '노드 {{count}}개',
+    recognition: '인식',
+    hit: '일치',
+    miss: '불일치',
+    action: '동작',
+    actionSucceeded: '성공',
+    actionFailed: '실패',
+    noAction: '실행된 동작 없음',
+    showImages: '인식 이미지 보기',
+    hideImages: '인식 이미지 숨기기',
+    rawImage: '스크린샷',
+    drawImage: '주석 이미지 {{index}}',
+    imagesUnavailable:
+      '사용 가능한 인식 이미지가 없습니다. 설정에서 디버그 모드를 켠 후 작업을 다시 실행하세요',
+    status: {
+      Pending: '대기 중',
+      Running: '실행 중',
+      Succeeded: '완료',
+      Failed: '실패',
+    },
+  },
+
+  // 작업 재시도 설정
+  taskRetry: {
+    title: '재시도 설정 - {{name}}',
+    override: '이 작업의 재시도 개별 설정',
+    overrideHint: '끄면 설정의 "실패한 작업 재시도"를 사용합니다',
+    attempts: '최대 재시도 횟수 (0은 재시도 안 함)',
+    delay: '재시도 전 대기 (초)',
+    onTimeout: '시간 초과 시에도 재시도',
+    onTimeoutHint: '작업별 시간 제한이 필요하며, 시간 초과된 작업은 진입점부터 다시 실행됩니다',
+    restartApp: '재시도 전 앱 재시작',
+    restartAppHint:
+      'ADB 컨트롤러는 패키지 이름 또는 Activity를 입력합니다. 비워 두면 재시작하지 않습니다',
+    restartAppPlaceholder: 'com.example.app',
+    invalid: '유효한 재시도 횟수(0-10)와 대기 시간(0-3600초)을 입력하세요',
   },
 
   // 옵션
@@ -350,6 +438,8 @@ export default {
     noDevices: '기기를 찾을 수 없습니다',
     noWindows: '윈도우를 찾을 수 없습니다',
     playcoverHint: 'PlayCover 앱 리슨 주소를 입력하세요',
+    imageFolderPlaceholder: '이미지 폴더 경로 (디버그: 스크린샷 오프라인 재생)',
+    browseImageFolder: '이미지 폴더 선택',
     lastSelected: '이전 선택 · 클릭하여 검색',
     savedDeviceNotFound: '이전 기기를 찾을 수 없습니다. 연결을 확인하거나 다른 기기를 선택하세요',
     savedWindowNotFound:
@@ -389,6 +479,12 @@ export default {
     connectFirst: '먼저 기기를 연결하세요',
     fullscreen: '전체 화면',
     exitFullscreen: '전체 화면 종료',
+    enableRemoteControl: '원격 조작 켜기 (스크린샷에서 클릭 또는 드래그)',
+    disableRemoteControl: '원격 조작 끄기',
+    resolutionFollowController: '컨트롤러 기본값 ({{resolution}})',
+    resolutionShortSide: '짧은 변 {{value}}',
+    resolutionLongSide: '긴 변 {{value}}',
+    resolutionRaw: '원본 해상도',
     // 프레임률 설정
     frameRate: {
       title: '스크린샷 프레임률',
@@ -429,6 +525,26 @@ export default {
       stopTask: '작업 중지',
       // 예약 메시지
       scheduleStarting: '예약 실행 시작 [{{policy}}] {{time}}',
+      scheduleSkippedRunning: '예약 실행 건너뜀 [{{policy}}] {{time}}: 인스턴스가 실행 중입니다',
+      scheduleMissed: '예약 실행 누락 [{{policy}}] {{time}}',
+      scheduleFinished: '예약 실행 완료 [{{policy}}]',
+      scheduleFailed: '예약 실행 실패 [{{policy}}]: {{error}}',
+      controllerLost: '컨트롤러 연결이 끊어졌습니다. {{seconds}}초 후 재연결합니다',
+      reconnecting: '컨트롤러 재연결 중 ({{attempt}}/{{max}})...',
+      reconnectAttemptFailed: '재연결 실패 ({{attempt}}/{{max}}): {{error}}',
+      reconnected: '컨트롤러가 다시 연결되었습니다. 작업 {{count}}개를 계속합니다',
+      reconnectGaveUp: '{{max}}회 시도 후 재연결을 포기했습니다',
+      tasksPausing: '현재 작업이 끝나면 일시정지합니다',
+      tasksPaused: '작업이 일시정지되었습니다 (보류 {{count}}개)',
+      tasksResumed: '작업을 재개했습니다 (재제출 {{count}}개)',
+      pauseResumeFailed: '작업 일시정지/재개 실패: {{error}}',
+      taskTimedOut: '작업 "{{name}}"이(가) {{seconds}}초 후 시간 초과되어 중지되었습니다',
+      runTimedOut: '실행이 {{seconds}}초 후 시간 초과되어 모든 작업을 중지합니다',
+      timeoutContinued: '남은 작업 {{count}}개를 계속 실행합니다',
+      taskRetrying: '작업 "{{name}}"이(가) 실패하여 재시도합니다 ({{attempt}}/{{max}})',
+      taskRetryExhausted: '작업 "{{name}}"이(가) {{count}}회 재시도 후에도 실패했습니다',
+      taskQueueApplied: '작업 대기열이 변경되었습니다. 남은 작업 {{count}}개를 계속 실행합니다',
+      taskRepostFailed: '작업 "{{name}}" 재제출 실패: {{error}}',
       // Agent 메시지
       agentStarting: 'Agent 시작 중...',
       agentStarted: 'Agent가 시작되었습니다',
@@ -499,9 +615,18 @@ export default {
     saveDraw: '디버그 이미지 저장',
     saveDrawHint:
       '인식 및 작업의 디버그 이미지를 로그 폴더에 저장합니다 (재시작 후 자동으로 비활성화됨)',
+    debugMode: '디버그 모드',
+    debugModeHint:
+      '인식에 사용된 스크린샷과 주석 이미지를 보관하여 실행 상세에서 확인할 수 있습니다 (메모리 사용량 증가, 재시작 후 자동으로 비활성화됨)',
     tcpCompatMode: '통신 호환 모드',
     tcpCompatModeHint:
       '작업 시작 후 앱이 즉시 충돌하면 활성화해 보세요. 이 경우에만 사용하세요, 성능에 영향을 줄 수 있습니다',
+    recordSession: '세션 녹화',
+    recordSessionHint:
+      '현재 인스턴스의 스크린샷, 컨트롤러 동작, 콜백 이벤트를 녹화합니다. 중지하면 세션 번들로 저장되며 버그 보고나 ImageFolder 컨트롤러를 통한 오프라인 재생에 사용할 수 있습니다',
+    recordingSession: '녹화 중...',
+    sessionSaved: '세션이 저장되었습니다: {{path}}',
+    recordSessionFailed: '세션 녹화 실패: {{error}}',
   },
 
   // 환영 대화상자
@@ -712,6 +837,11 @@ export default {
     addTask: '작업 추가',
     duplicateTask: '작업 복제',
     deleteTask: '작업 삭제',
+    viewTaskDetail: '실행 상세 보기',
+    taskRetrySettings: '재시도 설정',
+    runTaskNext: '다음에 실행',
+    queueTask: '실행 대기열에 추가',
+    cancelQueuedTask: '대기열에서 제거',
     exportTasks: '작업 목록 내보내기',
     importTasks: '작업 목록 가져오기',
     renameTask: '작업 이름 변경',
@@ -736,6 +866,9 @@ export default {
     fullscreen: '전체 화면',
     saveScreenshot: '스크린샷 저장',
     copyScreenshot: '스크린샷 복사',
+    exportRecentFrames: '최근 화면 내보내기 (APNG)',
+    exportRecentFramesGif: '최근 화면 내보내기 (GIF)',
+    screenshotResolution: '스크린샷 해상도',
 
     // 연결 패널 컨텍스트 메뉴
     refreshDevices: '기기 목록 새로고침',
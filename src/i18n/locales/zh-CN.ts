@@ -88,6 +88,34 @@ export default {
     hotkeysGlobalOnlyStart: '全局模式下仅开始生效',
     minimizeToTray: '关闭时最小化到托盘',
     minimizeToTrayHint: '点击关闭按钮时隐藏到系统托盘而非退出程序',
+    remoteApi: '本地控制接口',
+    remoteApiHint: '在 127.0.0.1 上开启 HTTP / WebSocket 接口，供脚本查询状态、启停任务和获取截图',
+    remoteApiPort: '端口',
+    remoteApiToken: '访问令牌',
+    remoteApiRegenerateToken: '重新生成令牌',
+    remoteApiListening: '正在监听 {{address}}',
+    remoteApiStartFailed: '启动失败：{{error}}',
+    autoReconnect: '掉线自动重连',
+    autoReconnectHint: '控制器意外断开（如模拟器重启）时自动重新连接，并继续执行未完成的任务',
+    autoReconnectMaxAttempts: '最多重连次数',
+    autoReconnectResumeTasks: '重连后继续任务',
+    autoReconnectResumeTasksHint: '从掉线时未完成的任务开始重新执行',
+    taskTimeout: '任务超时',
+    taskTimeoutHint: '停止运行过久的任务，避免卡住的任务挡住之后的定时执行',
+    taskTimeoutPerTask: '单个任务超时（分钟）',
+    taskTimeoutPerRun: '整次运行超时（分钟）',
+    taskTimeoutMinutesHint: '0 表示不限制',
+    taskTimeoutContinue: '任务超时后继续执行',
+    taskTimeoutContinueHint: '停止超时的任务后继续执行剩余任务；整次运行超时时总是停止所有任务',
+    taskRetry: '任务失败重试',
+    taskRetryHint: '任务失败时自动重新执行，并继续执行剩余任务',
+    taskRetryAttempts: '最多重试次数',
+    taskRetryDelay: '重试前等待（秒）',
+    taskRetryOnTimeout: '超时后也重试',
+    taskRetryOnTimeoutHint: '需同时设置单个任务超时，超时的任务从入口重新执行',
+    adbDeviceMonitor: 'ADB 设备热插拔监听',
+    adbDeviceMonitorHint:
+      '定期搜索 ADB 设备，设备接入或断开时自动更新列表，之前使用的设备重新出现时自动连接',
     autoStart: '开机自启动',
     autoStartHint: '系统启动时自动运行本程序',
     autoStartInstance: '启动后自动执行',
@@ -201,6 +229,10 @@ export default {
     stopTasks: '停止任务',
     startingTasks: '启动中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '暂停',
+    pausingTasks: '暂停中...',
+    resumeTasks: '继续',
+    pauseTasksHint: '当前任务执行完毕后暂停',
     // 自动连接相关
     autoConnect: {
       searching: '搜索设备...',
@@ -214,6 +246,8 @@ export default {
       noDeviceFound: '未搜索到任何设备',
       noWindowFound: '未搜索到任何窗口',
       connectFailed: '自动连接失败',
+      deviceAppeared: '设备「{{name}}」已接入，正在自动连接',
+      deviceRemoved: '设备「{{name}}」已断开',
       autoSelectedDevice:
         '尚未手动选择过设备，已自动匹配到「{{name}}」。如需更换，请在连接设置中手动选择，下次将记住您的选择。',
       autoSelectedWindow:
@@ -222,6 +256,8 @@ export default {
       startFailed: '任务启动失败',
       needConfig: '请先连接设备并加载资源，或在连接面板保存设备配置',
     },
+    taskRejected: '任务「{{name}}」未提交：{{reason}}',
+    taskOverrideWarning: '任务「{{name}}」已提交，但配置可能有误：{{reason}}',
   },
 
   // 任务项
@@ -258,6 +294,50 @@ export default {
     incompatibleController: '不支持当前控制器',
     incompatibleResource: '不支持当前资源',
     supportedControllers: '支持的控制器: {{controllers}}',
+    retryOverridden: '已单独设置重试',
+  },
+
+  // 任务执行详情
+  taskDetail: {
+    title: '执行详情 - {{name}}',
+    refresh: '刷新',
+    loading: '加载中...',
+    notFound: '任务详情不可用（任务已被清理或实例已重建）',
+    noNodes: '任务尚未执行任何节点',
+    nodeCount: '{{count}} 个节点',
+    recognition: '识别',
+    hit: '命中',
+    miss: '未命中',
+    action: '动作',
+    actionSucceeded: '成功',
+    actionFailed: '失败',
+    noAction: '未执行动作',
+    showImages: '查看识别图像',
+    hideImages: '收起识别图像',
+    rawImage: '截图',
+    drawImage: '标注图 {{index}}',
+    imagesUnavailable: '没有可用的识别图像，请在设置中开启调试模式后重新运行任务',
+    status: {
+      Pending: '等待中',
+      Running: '运行中',
+      Succeeded: '已完成',
+      Failed: '失败',
+    },
+  },
+
+  // 任务重试设置
+  taskRetry: {
+    title: '重试设置 - {{name}}',
+    override: '单独设置重试',
+    overrideHint: '关闭时使用设置中的「任务失败重试」',
+    attempts: '最多重试次数（0 为不重试）',
+    delay: '重试前等待（秒）',
+    onTimeout: '超时后也重试',
+    onTimeoutHint: '需同时设置单个任务超时，超时的任务从入口重新执行',
+    restartApp: '重试前重启应用',
+    restartAppHint: 'ADB 控制器填写包名或 Activity，为空时不重启',
+    restartAppPlaceholder: 'com.example.app',
+    invalid: '请输入有效的重试次数（0-10）和等待时间（0-3600 秒）',
   },
 
   // 选项
@@ -343,6 +423,8 @@ export default {
     noDevices: '未找到设备',
     noWindows: '未找到窗口',
     playcoverHint: '输入 PlayCover 应用监听地址',
+    imageFolderPlaceholder: '图片目录路径（调试：离线回放截图）',
+    browseImageFolder: '选择图片目录',
     lastSelected: '上次选择 · 点击搜索',
     savedDeviceNotFound: '未找到上次的设备，请检查连接或重新选择',
     savedWindowNotFound: '未找到上次的窗口，请检查连接或重新选择',
@@ -381,6 +463,12 @@ export default {
     connectFirst: '请先连接设备',
     fullscreen: '全屏显示',
     exitFullscreen: '退出全屏',
+    enableRemoteControl: '开启远程操控（在截图上点击或拖动）',
+    disableRemoteControl: '关闭远程操控',
+    resolutionFollowController: '跟随控制器（{{resolution}}）',
+    resolutionShortSide: '短边 {{value}}',
+    resolutionLongSide: '长边 {{value}}',
+    resolutionRaw: '原始分辨率',
     // 帧率设置
     frameRate: {
       title: '实时截图帧率',
@@ -421,6 +509,26 @@ export default {
       stopTask: '停止任务',
       // 定时任务消息
       scheduleStarting: '定时执行开始 [{{policy}}] {{time}}',
+      scheduleSkippedRunning: '定时执行跳过 [{{policy}}] {{time}}：实例正在运行',
+      scheduleMissed: '定时执行错过 [{{policy}}] {{time}}',
+      scheduleFinished: '定时执行完成 [{{policy}}]',
+      scheduleFailed: '定时执行失败 [{{policy}}]: {{error}}',
+      controllerLost: '控制器连接已断开，{{seconds}} 秒后尝试重连',
+      reconnecting: '正在重连控制器（{{attempt}}/{{max}}）...',
+      reconnectAttemptFailed: '重连失败（{{attempt}}/{{max}}）: {{error}}',
+      reconnected: '控制器已重新连接，继续执行 {{count}} 个任务',
+      reconnectGaveUp: '已重试 {{max}} 次，放弃重连',
+      tasksPausing: '将在当前任务执行完毕后暂停',
+      tasksPaused: '任务已暂停，保留 {{count}} 个任务',
+      tasksResumed: '任务已继续，重新提交 {{count}} 个任务',
+      pauseResumeFailed: '暂停/继续任务失败: {{error}}',
+      taskTimedOut: '任务「{{name}}」运行 {{seconds}} 秒后超时，已停止',
+      runTimedOut: '本次运行 {{seconds}} 秒后超时，停止所有任务',
+      timeoutContinued: '继续执行剩余的 {{count}} 个任务',
+      taskRetrying: '任务「{{name}}」失败，正在重试（{{attempt}}/{{max}}）',
+      taskRetryExhausted: '任务「{{name}}」重试 {{count}} 次后仍然失败',
+      taskQueueApplied: '任务队列已调整，继续执行剩余的 {{count}} 个任务',
+      taskRepostFailed: '任务「{{name}}」重新提交失败: {{error}}',
       // Agent 消息
       agentStarting: 'Agent 正在启动...',
       agentStarted: 'Agent 已启动',
@@ -490,8 +598,17 @@ export default {
     devModeHint: '启用后允许按 F5 刷新 UI',
     saveDraw: '保存调试图像',
     saveDrawHint: '保存识别和操作的调试图像到日志目录（重启软件后自动关闭）',
+    debugMode: '调试模式',
+    debugModeHint:
+      '保留识别使用的截图与标注图，可在任务执行详情中查看（会增加内存占用，重启软件后自动关闭）',
     tcpCompatMode: '通信兼容模式',
     tcpCompatModeHint: '若启动任务后软件立即闪退，可尝试开启。仅限此情况使用，否则会影响运行效率',
+    recordSession: '录制会话',
+    recordSessionHint:
+      '录制当前实例的截图、控制器动作与回调事件，停止后保存为会话包，可用于问题反馈或通过 ImageFolder 控制器离线回放',
+    recordingSession: '正在录制...',
+    sessionSaved: '会话已保存：{{path}}',
+    recordSessionFailed: '会话录制失败：{{error}}',
   },
 
   // 欢迎弹窗
@@ -712,6 +829,11 @@ export default {
     addTask: '添加任务',
     duplicateTask: '复制任务',
     deleteTask: '删除任务',
+    viewTaskDetail: '查看执行详情',
+    taskRetrySettings: '重试设置',
+    runTaskNext: '下一个执行',
+    queueTask: '加入运行队列',
+    cancelQueuedTask: '取消排队',
     exportTasks: '导出任务列表',
     importTasks: '导入任务列表',
     renameTask: '重命名任务',
@@ -736,6 +858,9 @@ export default {
     fullscreen: '全屏显示',
     saveScreenshot: '保存截图',
     copyScreenshot: '复制截图',
+    exportRecentFrames: '导出最近画面 (APNG)',
+    exportRecentFramesGif: '导出最近画面 (GIF)',
+    screenshotResolution: '截图分辨率',
 
     // 连接面板右键菜单
     refreshDevices: '刷新设备列表',
@@ -87,6 +87,34 @@ export default {
     hotkeysGlobalOnlyStart: '全域模式下僅開始生效',
     minimizeToTray: '關閉時最小化到托盤',
     minimizeToTrayHint: '點選關閉按鈕時隱藏到系統托盤而非退出程式',
+    remoteApi: '本機控制介面',
+    remoteApiHint: '在 127.0.0.1 上開啟 HTTP / WebSocket 介面，供腳本查詢狀態、啟停任務和取得截圖',
+    remoteApiPort: '連接埠',
+    remoteApiToken: '存取權杖',
+    remoteApiRegenerateToken: '重新產生權杖',
+    remoteApiListening: '正在監聽 {{address}}',
+    remoteApiStartFailed: '啟動失敗：{{error}}',
+    autoReconnect: '斷線自動重連',
+    autoReconnectHint: '控制器意外斷開（如模擬器重啟）時自動重新連接，並繼續執行未完成的任務',
+    autoReconnectMaxAttempts: '最多重連次數',
+    autoReconnectResumeTasks: '重連後繼續任務',
+    autoReconnectResumeTasksHint: '從斷線時未完成的任務開始重新執行',
+    taskTimeout: '任務逾時',
+    taskTimeoutHint: '停止執行過久的任務，避免卡住的任務擋住之後的定時執行',
+    taskTimeoutPerTask: '單個任務逾時（分鐘）',
+    taskTimeoutPerRun: '整次執行逾時（分鐘）',
+    taskTimeoutMinutesHint: '0 表示不限制',
+    taskTimeoutContinue: '任務逾時後繼續執行',
+    taskTimeoutContinueHint: '停止逾時的任務後繼續執行剩餘任務；整次執行逾時時總是停止所有任務',
+    taskRetry: '任務失敗重試',
+    taskRetryHint: '任務失敗時自動重新執行，並繼續執行剩餘任務',
+    taskRetryAttempts: '最多重試次數',
+    taskRetryDelay: '重試前等待（秒）',
+    taskRetryOnTimeout: '逾時後也重試',
+    taskRetryOnTimeoutHint: '需同時設定單個任務逾時，逾時的任務從入口重新執行',
+    adbDeviceMonitor: 'ADB 裝置熱插拔監聽',
+    adbDeviceMonitorHint:
+      '定期搜尋 ADB 裝置，裝置接入或斷開時自動更新列表，之前使用的裝置重新出現時自動連接',
     autoStart: '開機自啟動',
     autoStartHint: '系統啟動時自動執行本程式',
     autoStartInstance: '啟動後自動執行',
@@ -201,6 +229,10 @@ export default {
     stopTasks: '停止任務',
     startingTasks: '啟動中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '暫停',
+    pausingTasks: '暫停中...',
+    resumeTasks: '繼續',
+    pauseTasksHint: '目前任務執行完畢後暫停',
     // 自動連接相关
     autoConnect: {
       searching: '搜尋裝置...',
@@ -214,6 +246,8 @@ export default {
       noDeviceFound: '未搜尋到任何裝置',
       noWindowFound: '未搜尋到任何視窗',
       connectFailed: '自動連接失敗',
+      deviceAppeared: '裝置「{{name}}」已接入，正在自動連接',
+      deviceRemoved: '裝置「{{name}}」已斷開',
       autoSelectedDevice:
         '尚未手動選擇過裝置，已自動匹配到「{{name}}」。如需更換，請在連接設定中手動選擇，下次將記住您的選擇。',
       autoSelectedWindow:
@@ -222,6 +256,8 @@ export default {
       startFailed: '任務啟動失敗',
       needConfig: '請先連接裝置並載入資源，或在連接面板儲存裝置設定',
     },
+    taskRejected: '任務「{{name}}」未提交：{{reason}}',
+    taskOverrideWarning: '任務「{{name}}」已提交，但設定可能有誤：{{reason}}',
   },
 
   // 任務项
@@ -258,6 +294,50 @@ export default {
     incompatibleController: '不支援目前控制器',
     incompatibleResource: '不支援目前資源',
     supportedControllers: '支援的控制器: {{controllers}}',
+    retryOverridden: '已單獨設定重試',
+  },
+
+  // 任務執行詳情
+  taskDetail: {
+    title: '執行詳情 - {{name}}',
+    refresh: '重新整理',
+    loading: '載入中...',
+    notFound: '任務詳情不可用（任務已被清理或實例已重建）',
+    noNodes: '任務尚未執行任何節點',
+    nodeCount: '{{count}} 個節點',
+    recognition: '辨識',
+    hit: '命中',
+    miss: '未命中',
+    action: '動作',
+    actionSucceeded: '成功',
+    actionFailed: '失敗',
+    noAction: '未執行動作',
+    showImages: '查看辨識圖像',
+    hideImages: '收起辨識圖像',
+    rawImage: '截圖',
+    drawImage: '標註圖 {{index}}',
+    imagesUnavailable: '沒有可用的辨識圖像，請在設定中開啟除錯模式後重新執行任務',
+    status: {
+      Pending: '等待中',
+      Running: '執行中',
+      Succeeded: '已完成',
+      Failed: '失敗',
+    },
+  },
+
+  // 任務重試設定
+  taskRetry: {
+    title: '重試設定 - {{name}}',
+    override: '單獨設定重試',
+    overrideHint: '關閉時使用設定中的「任務失敗重試」',
+    attempts: '最多重試次數（0 為不重試）',
+    delay: '重試前等待（秒）',
+    onTimeout: '逾時後也重試',
+    onTimeoutHint: '需同時設定單個任務逾時，逾時的任務從入口重新執行',
+    restartApp: '重試前重新啟動應用程式',
+    restartAppHint: 'ADB 控制器填寫套件名稱或 Activity，留空時不重新啟動',
+    restartAppPlaceholder: 'com.example.app',
+    invalid: '請輸入有效的重試次數（0-10）和等待時間（0-3600 秒）',
   },
 
   // 選項
@@ -343,6 +423,8 @@ export default {
     noDevices: '未找到裝置',
     noWindows: '未找到視窗',
     playcoverHint: '輸入 PlayCover 應用程式監聽位址',
+    imageFolderPlaceholder: '圖片目錄路徑（偵錯：離線回放截圖）',
+    browseImageFolder: '選擇圖片目錄',
     lastSelected: '上次選擇 · 點擊搜尋',
     savedDeviceNotFound: '未找到上次的裝置，請檢查連接或重新選擇',
     savedWindowNotFound: '未找到上次的視窗，請檢查連接或重新選擇',
@@ -381,6 +463,12 @@ export default {
     connectFirst: '請先連接裝置',
     fullscreen: '全螢幕顯示',
     exitFullscreen: '退出全螢幕',
+    enableRemoteControl: '開啟遠端操控（在截圖上點擊或拖曳）',
+    disableRemoteControl: '關閉遠端操控',
+    resolutionFollowController: '跟隨控制器（{{resolution}}）',
+    resolutionShortSide: '短邊 {{value}}',
+    resolutionLongSide: '長邊 {{value}}',
+    resolutionRaw: '原始解析度',
     // 幀率設定
     frameRate: {
       title: '即時截圖幀率',
@@ -421,6 +509,26 @@ export default {
       stopTask: '停止任務',
       // 定時任務訊息
       scheduleStarting: '定時執行開始 [{{policy}}] {{time}}',
+      scheduleSkippedRunning: '定時執行略過 [{{policy}}] {{time}}：實例正在執行',
+      scheduleMissed: '定時執行錯過 [{{policy}}] {{time}}',
+      scheduleFinished: '定時執行完成 [{{policy}}]',
+      scheduleFailed: '定時執行失敗 [{{policy}}]: {{error}}',
+      controllerLost: '控制器連接已斷開，{{seconds}} 秒後嘗試重連',
+      reconnecting: '正在重連控制器（{{attempt}}/{{max}}）...',
+      reconnectAttemptFailed: '重連失敗（{{attempt}}/{{max}}）: {{error}}',
+      reconnected: '控制器已重新連接，繼續執行 {{count}} 個任務',
+      reconnectGaveUp: '已重試 {{max}} 次，放棄重連',
+      tasksPausing: '將在目前任務執行完畢後暫停',
+      tasksPaused: '任務已暫停，保留 {{count}} 個任務',
+      tasksResumed: '任務已繼續，重新提交 {{count}} 個任務',
+      pauseResumeFailed: '暫停/繼續任務失敗: {{error}}',
+      taskTimedOut: '任務「{{name}}」執行 {{seconds}} 秒後逾時，已停止',
+      runTimedOut: '本次執行 {{seconds}} 秒後逾時，停止所有任務',
+      timeoutContinued: '繼續執行剩餘的 {{count}} 個任務',
+      taskRetrying: '任務「{{name}}」失敗，正在重試（{{attempt}}/{{max}}）',
+      taskRetryExhausted: '任務「{{name}}」重試 {{count}} 次後仍然失敗',
+      taskQueueApplied: '任務佇列已調整，繼續執行剩餘的 {{count}} 個任務',
+      taskRepostFailed: '任務「{{name}}」重新提交失敗: {{error}}',
       // Agent 訊息
       agentStarting: 'Agent 正在啟動...',
       agentStarted: 'Agent 已啟動',
@@ -490,8 +598,17 @@ export default {
     devModeHint: '啟用後允許按 F5 重新整理 UI',
     saveDraw: '儲存除錯圖像',
     saveDrawHint: '儲存識別和操作的除錯圖像到日誌目錄（重啟軟體後自動關閉）',
+    debugMode: '除錯模式',
+    debugModeHint:
+      '保留辨識使用的截圖與標註圖，可在任務執行詳情中查看（會增加記憶體占用，重啟軟體後自動關閉）',
     tcpCompatMode: '通訊相容模式',
     tcpCompatModeHint: '若啟動任務後軟體立即閃退，可嘗試開啟。僅限此情況使用，否則會影響運行效率',
+    recordSession: '錄製工作階段',
+    recordSessionHint:
+      '錄製目前實例的截圖、控制器動作與回呼事件，停止後儲存為工作階段包，可用於問題回報或透過 ImageFolder 控制器離線回放',
+    recordingSession: '正在錄製...',
+    sessionSaved: '工作階段已儲存：{{path}}',
+    recordSessionFailed: '工作階段錄製失敗：{{error}}',
   },
 
   // 欢迎彈窗
@@ -701,6 +818,11 @@ export default {
     addTask: '新增任務',
     duplicateTask: '複製任務',
     deleteTask: '刪除任務',
+    viewTaskDetail: '檢視執行詳情',
+    taskRetrySettings: '重試設定',
+    runTaskNext: '下一個執行',
+    queueTask: '加入執行佇列',
+    cancelQueuedTask: '取消排隊',
     exportTasks: '匯出任務列表',
     importTasks: '匯入任務列表',
     renameTask: '重新命名任務',
@@ -725,6 +847,9 @@ export default {
     fullscreen: '全螢幕顯示',
     saveScreenshot: '儲存截圖',
     copyScreenshot: '複製截圖',
+    exportRecentFrames: '匯出最近畫面 (APNG)',
+    exportRecentFramesGif: '匯出最近畫面 (GIF)',
+    screenshotResolution: '截圖解析度',
 
     // 連接面板右鍵選單
     refreshDevices: '重新整理裝置列表',
@@ -9,6 +9,7 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import { loggers } from '@/utils/logger';
+import { getErrorMessage } from '@/utils/errors';
 import { marked } from 'marked';
 import DOMPurify from 'dompurify';
 import { cachedFetch } from './cacheService';
@@ -307,7 +308,7 @@ export async function resolveDescriptionContent(
 
     return { content: loadedContent, type, loaded: true };
   } catch (err) {
-    const errorMsg = err instanceof Error ? err.message : String(err);
+    const errorMsg = getErrorMessage(err);
     log.warn(`加载描述内容失败 [${type}: ${resolved}]:`, err);
     // 加载失败时返回原始文本，并附带错误信息
     return { content: resolved, type, loaded: false, error: errorMsg };
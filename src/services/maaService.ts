@@ -9,15 +9,34 @@ import type {
   ControllerConfig,
   ConnectionStatus,
   TaskStatus,
+  TaskDetail,
+  RecognitionImages,
   AgentConfig,
   TaskConfig,
+  TaskValidation,
+  StartTasksResult,
   InstanceRuntimeInfo,
+  PauseState,
+  ResumedTask,
+  FrameExportFormat,
+  TaskQueueState,
+  RunOptions,
+  ScreenshotOptions,
+  ScreenshotResolution,
+  ControllerInput,
 } from '@/types/maa';
 import { loggers } from '@/utils/logger';
 import { isTauri } from '@/utils/paths';
 
 const log = loggers.maa;
 
+/** 实时预览使用的截图编码（缩小的 JPEG；保存、复制时另取原始 PNG） */
+export const PREVIEW_SCREENSHOT_OPTIONS: ScreenshotOptions = {
+  format: 'jpeg',
+  quality: 80,
+  max_width: 1280,
+};
+
 /** MaaFramework 回调事件载荷 */
 export interface MaaCallbackEvent {
   /** 消息类型，如 "Resource.Loading.Succeeded", "Controller.Action.Succeeded", "Tasker.Task.Succeeded" */
@@ -26,6 +45,30 @@ export interface MaaCallbackEvent {
   details: string;
 }
 
+/** 截图帧事件载荷（后端截图流推送） */
+export interface ScreenFrameEvent {
+  instance_id: string;
+  /** 帧序号（确认时回传） */
+  seq: number;
+  /** 编码后的画面 data URL */
+  data: string;
+  /** 订阅了这一编码选项的查看者 ID */
+  viewers: string[];
+  /** 原始画面宽度（截图坐标系，缩放前） */
+  width: number;
+  /** 原始画面高度 */
+  height: number;
+}
+
+/** 截图流停止原因 */
+export type ScreenStreamStopReason = 'disconnected' | 'capture_failed';
+
+/** 截图流停止事件载荷 */
+export interface ScreenStreamStoppedEvent {
+  instance_id: string;
+  reason: ScreenStreamStopReason;
+}
+
 /** 回调消息详情（通用字段） */
 export interface MaaCallbackDetails {
   res_id?: number;
@@ -190,6 +233,20 @@ export const maaService = {
     return status;
   },
 
+  /**
+   * 修改已连接控制器的截图分辨率（下一次截图生效）
+   * @param instanceId 实例 ID
+   * @param resolution 截图目标分辨率
+   */
+  async setScreenshotResolution(
+    instanceId: string,
+    resolution: ScreenshotResolution,
+  ): Promise<void> {
+    log.info('设置截图分辨率, 实例:', instanceId, resolution);
+    if (!isTauri()) return;
+    await invoke('maa_set_screenshot_resolution', { instanceId, resolution });
+  },
+
   /**
    * 加载资源（异步，通过回调通知完成状态）
    * @param instanceId 实例 ID
@@ -277,6 +334,39 @@ export const maaService = {
     return status;
   },
 
+  /**
+   * 获取任务详情（已执行的节点、识别与动作结果及各自的开始/结束时间）
+   * @param instanceId 实例 ID
+   * @param taskId 任务 ID
+   * @returns 未知任务返回 null
+   */
+  async getTaskDetail(instanceId: string, taskId: number): Promise<TaskDetail | null> {
+    if (!isTauri()) return null;
+    log.debug('获取任务详情, 实例:', instanceId, ', taskId:', taskId);
+    return await invoke<TaskDetail | null>('maa_get_task_detail', { instanceId, taskId });
+  },
+
+  /**
+   * 获取识别使用的截图与标注图（需开启调试模式）
+   * @param instanceId 实例 ID
+   * @param recoId 识别 ID
+   * @param options 图像编码选项
+   * @returns data URL 格式的截图与标注图，未开启调试模式时 raw 为 null、draws 为空
+   */
+  async getRecognitionImages(
+    instanceId: string,
+    recoId: number,
+    options?: ScreenshotOptions,
+  ): Promise<RecognitionImages> {
+    if (!isTauri()) return { raw: null, draws: [] };
+    log.debug('获取识别图像, 实例:', instanceId, ', recoId:', recoId);
+    return await invoke<RecognitionImages>('maa_get_recognition_images', {
+      instanceId,
+      recoId,
+      options,
+    });
+  },
+
   /**
    * 停止任务
    * @param instanceId 实例 ID
@@ -288,6 +378,91 @@ export const maaService = {
     log.info('停止任务请求已发送');
   },
 
+  /**
+   * 暂停任务队列，剩余任务保留在实例中，可通过 resumeTasks 继续
+   * @param instanceId 实例 ID
+   * @param immediate 为 true 时立即停止，当前任务随剩余任务一起保留；否则等待当前任务执行完毕
+   * @returns 暂停状态
+   */
+  async pauseTasks(instanceId: string, immediate = false): Promise<PauseState> {
+    log.info('暂停任务, 实例:', instanceId, 'immediate:', immediate);
+    if (!isTauri()) return 'paused';
+    return await invoke<PauseState>('maa_pause_tasks', { instanceId, immediate });
+  },
+
+  /**
+   * 恢复暂停的任务队列
+   * @param instanceId 实例 ID
+   * @returns 重新提交的任务（原 task_id → 新 task_id）
+   */
+  async resumeTasks(instanceId: string): Promise<ResumedTask[]> {
+    log.info('恢复任务, 实例:', instanceId);
+    if (!isTauri()) return [];
+    const resumed = await invoke<ResumedTask[]>('maa_resume_tasks', { instanceId });
+    log.info('任务已恢复, 数量:', resumed.length);
+    return resumed;
+  },
+
+  /**
+   * 获取运行中的任务队列
+   * @param instanceId 实例 ID
+   * @returns 正在执行与等待执行的任务（已暂停时为保留的任务）
+   */
+  async getTaskQueue(instanceId: string): Promise<TaskQueueState> {
+    if (!isTauri()) return { running: null, pending: [], deferred: false };
+    return await invoke<TaskQueueState>('maa_get_task_queue', { instanceId });
+  },
+
+  /**
+   * 追加任务到运行中的队列末尾
+   * @param instanceId 实例 ID
+   * @param task 任务配置
+   * @returns 调整后的队列（新任务位于 pending 末尾）
+   */
+  async appendTask(instanceId: string, task: TaskConfig): Promise<TaskQueueState> {
+    log.info('追加任务, 实例:', instanceId, ', entry:', task.entry);
+    if (!isTauri()) return { running: null, pending: [], deferred: false };
+    return await invoke<TaskQueueState>('maa_append_task', { instanceId, task });
+  },
+
+  /**
+   * 插入任务到运行中的队列，在当前任务结束后生效
+   * @param instanceId 实例 ID
+   * @param index 在等待中的任务中的位置（0 为下一个执行）
+   * @param task 任务配置
+   * @returns 调整后的队列（新任务位于 pending[index]，超出范围时位于末尾）
+   */
+  async insertTask(instanceId: string, index: number, task: TaskConfig): Promise<TaskQueueState> {
+    log.info('插入任务, 实例:', instanceId, ', index:', index, ', entry:', task.entry);
+    if (!isTauri()) return { running: null, pending: [], deferred: false };
+    return await invoke<TaskQueueState>('maa_insert_task', { instanceId, index, task });
+  },
+
+  /**
+   * 取消尚未执行的任务
+   * @param instanceId 实例 ID
+   * @param taskId MAA 任务 ID（可为临时 ID）
+   * @returns 调整后的队列
+   */
+  async cancelTask(instanceId: string, taskId: number): Promise<TaskQueueState> {
+    log.info('取消任务, 实例:', instanceId, ', taskId:', taskId);
+    if (!isTauri()) return { running: null, pending: [], deferred: false };
+    return await invoke<TaskQueueState>('maa_cancel_task', { instanceId, taskId });
+  },
+
+  /**
+   * 调整尚未执行的任务的顺序
+   * @param instanceId 实例 ID
+   * @param taskId MAA 任务 ID（可为临时 ID）
+   * @param index 在等待中的任务中的新位置
+   * @returns 调整后的队列
+   */
+  async moveTask(instanceId: string, taskId: number, index: number): Promise<TaskQueueState> {
+    log.info('调整任务顺序, 实例:', instanceId, ', taskId:', taskId, ', index:', index);
+    if (!isTauri()) return { running: null, pending: [], deferred: false };
+    return await invoke<TaskQueueState>('maa_move_task', { instanceId, taskId, index });
+  },
+
   /**
    * 覆盖已提交任务的 Pipeline 配置（用于运行中修改尚未执行的任务选项）
    * @param instanceId 实例 ID
@@ -345,11 +520,151 @@ export const maaService = {
   /**
    * 获取缓存的截图
    * @param instanceId 实例 ID
+   * @param options 编码选项（格式、质量、最大宽度），省略时返回原始 PNG
    * @returns base64 编码的图像 data URL
    */
-  async getCachedImage(instanceId: string): Promise<string> {
+  async getCachedImage(instanceId: string, options?: ScreenshotOptions): Promise<string> {
     if (!isTauri()) return '';
-    return await invoke<string>('maa_get_cached_image', { instanceId });
+    return await invoke<string>('maa_get_cached_image', { instanceId, options });
+  },
+
+  /**
+   * 获取缓存的截图（二进制传输，省去 base64 编解码，适合高帧率预览）
+   * @param instanceId 实例 ID
+   * @param options 编码选项（格式、质量、最大宽度），省略时返回原始 PNG
+   * @returns 图像 Blob
+   */
+  async getCachedImageBlob(instanceId: string, options?: ScreenshotOptions): Promise<Blob | null> {
+    if (!isTauri()) return null;
+    const data = await invoke<ArrayBuffer>('maa_get_cached_image_raw', { instanceId, options });
+    return new Blob([data], { type: `image/${options?.format ?? 'png'}` });
+  },
+
+  /**
+   * 订阅后端截图流（同一实例的多个查看者共享一个截图循环）
+   * 同一查看者重复订阅时更新帧间隔与编码选项
+   * @param instanceId 实例 ID
+   * @param viewerId 查看者 ID（取消订阅时使用）
+   * @param intervalMs 期望的帧间隔（毫秒），0 表示不限帧率
+   * @param options 编码选项（格式、质量、最大宽度），省略时推送原始 PNG
+   */
+  async startScreenStream(
+    instanceId: string,
+    viewerId: string,
+    intervalMs: number,
+    options?: ScreenshotOptions,
+  ): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_screen_stream_start', { instanceId, viewerId, intervalMs, options });
+  },
+
+  /**
+   * 取消订阅后端截图流（最后一个查看者离开后停止截图）
+   * @param instanceId 实例 ID
+   * @param viewerId 查看者 ID
+   */
+  async stopScreenStream(instanceId: string, viewerId: string): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_screen_stream_stop', { instanceId, viewerId });
+  },
+
+  /**
+   * 监听实例的截图流，每收到一帧回复确认（未确认前后端不会推送下一帧）
+   * @param instanceId 实例 ID
+   * @param viewerId 查看者 ID（只接收按该查看者编码选项编码的帧）
+   * @param onFrame 收到新画面
+   * @param onStopped 截图流因断开连接或截图连续失败而停止
+   * @returns 取消监听的函数
+   */
+  async onScreenStream(
+    instanceId: string,
+    viewerId: string,
+    onFrame: (frame: ScreenFrameEvent) => void,
+    onStopped: (reason: ScreenStreamStopReason) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+
+    const unlistenFrame = await listen<ScreenFrameEvent>('maa-screen-frame', (event) => {
+      const { instance_id, seq, viewers } = event.payload;
+      if (instance_id !== instanceId || !viewers.includes(viewerId)) return;
+      onFrame(event.payload);
+      invoke('maa_screen_stream_ack', { instanceId, seq }).catch((err) => {
+        log.warn('截图帧确认失败:', err);
+      });
+    });
+    const unlistenStopped = await listen<ScreenStreamStoppedEvent>(
+      'maa-screen-stream-stopped',
+      (event) => {
+        if (event.payload.instance_id === instanceId) {
+          onStopped(event.payload.reason);
+        }
+      },
+    );
+    return () => {
+      unlistenFrame();
+      unlistenStopped();
+    };
+  },
+
+  /**
+   * 监听 ADB 设备热插拔（需在设置中启用设备监听）
+   * @param onAdded 新设备出现
+   * @param onRemoved 设备消失
+   * @returns 取消监听的函数
+   */
+  async onAdbDeviceChanges(
+    onAdded: (device: AdbDevice) => void,
+    onRemoved: (device: AdbDevice) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+
+    const unlistenAdded = await listen<AdbDevice>('adb-device-added', (event) => {
+      onAdded(event.payload);
+    });
+    const unlistenRemoved = await listen<AdbDevice>('adb-device-removed', (event) => {
+      onRemoved(event.payload);
+    });
+    return () => {
+      unlistenAdded();
+      unlistenRemoved();
+    };
+  },
+
+  /**
+   * 向控制器发送输入动作（点击、滑动、按键、输入文本、启动/停止应用）
+   * @param instanceId 实例 ID
+   * @param input 输入动作，坐标为截图坐标系
+   * @returns 控制器请求 ID
+   */
+  async postInput(instanceId: string, input: ControllerInput): Promise<number> {
+    log.debug('发送控制器输入, 实例:', instanceId, ', 动作:', input);
+    if (!isTauri()) return -1;
+    return await invoke<number>('maa_post_input', { instanceId, input });
+  },
+
+  /**
+   * 等待输入动作完成
+   * @param instanceId 实例 ID
+   * @param ctrlId 控制器请求 ID
+   * @param timeoutMs 超时时间（毫秒），默认 10 秒；超时返回当前状态
+   * @returns 动作状态
+   */
+  async waitInput(instanceId: string, ctrlId: number, timeoutMs?: number): Promise<TaskStatus> {
+    if (!isTauri()) return 'Succeeded';
+    return await invoke<TaskStatus>('maa_wait_input', { instanceId, ctrlId, timeoutMs });
+  },
+
+  /**
+   * 导出实例最近缓存的画面（任务失败时也会自动导出到 debug/frames）
+   * @param instanceId 实例 ID
+   * @param format 导出格式：APNG 动画、GIF 动画或 PNG 序列，默认 APNG
+   * @returns 写入的文件或目录路径
+   */
+  async exportRecentFrames(
+    instanceId: string,
+    format: FrameExportFormat = 'apng',
+  ): Promise<string> {
+    return await invoke<string>('recorder_export_frames', { instanceId, format });
   },
 
   /**
@@ -359,7 +674,8 @@ export const maaService = {
    * @param agentConfigs Agent 配置列表（可选，支持多个 Agent）
    * @param cwd 工作目录（Agent 子进程的 CWD）
    * @param tcpCompatMode 通信兼容模式（强制使用 TCP）
-   * @returns 任务 ID 列表
+   * @param runOptions 运行选项（整次运行的超时等）
+   * @returns 成功提交的任务 ID 列表，以及未通过 Pipeline 覆盖校验或提交失败的任务
    */
   async startTasks(
     instanceId: string,
@@ -367,7 +683,8 @@ export const maaService = {
     agentConfigs?: AgentConfig[],
     cwd?: string,
     tcpCompatMode?: boolean,
-  ): Promise<number[]> {
+    runOptions?: RunOptions,
+  ): Promise<StartTasksResult> {
     log.info('启动任务, 实例:', instanceId, ', 任务数:', tasks.length, ', cwd:', cwd || '.');
     tasks.forEach((task, i) => {
       log.debug(`  任务[${i}]: entry=${task.entry}, pipelineOverride=${task.pipeline_override}`);
@@ -383,17 +700,37 @@ export const maaService = {
       );
     }
     if (!isTauri()) {
-      return tasks.map((_, i) => i + 1);
+      return { task_ids: tasks.map((_, i) => i + 1), rejected: [], warnings: [] };
     }
-    const taskIds = await invoke<number[]>('maa_start_tasks', {
+    const result = await invoke<StartTasksResult>('maa_start_tasks', {
       instanceId,
       tasks,
       agentConfigs: agentConfigs && agentConfigs.length > 0 ? agentConfigs : null,
       cwd: cwd || '.',
       tcpCompatMode: tcpCompatMode || false,
+      runOptions: runOptions ?? null,
     });
-    log.info('任务已提交, taskIds:', taskIds);
-    return taskIds;
+    log.info('任务已提交, taskIds:', result.task_ids);
+    if (result.rejected.length > 0) {
+      log.warn('未提交的任务:', JSON.stringify(result.rejected));
+    }
+    if (result.warnings.length > 0) {
+      log.warn('覆盖有警告的任务:', JSON.stringify(result.warnings));
+    }
+    return result;
+  },
+
+  /**
+   * 校验任务的 Pipeline 覆盖（JSON 格式、引用节点是否存在、常用字段类型），需已加载资源
+   * @param instanceId 实例 ID
+   * @param tasks 任务列表
+   * @returns 每个任务一项校验结果，errors 为空表示通过，warnings 为不影响提交的问题
+   */
+  async validateTasks(instanceId: string, tasks: TaskConfig[]): Promise<TaskValidation[]> {
+    if (!isTauri()) {
+      return tasks.map((task, index) => ({ index, entry: task.entry, errors: [], warnings: [] }));
+    }
+    return await invoke<TaskValidation[]>('maa_validate_tasks', { instanceId, tasks });
   },
 
   /**
@@ -501,6 +838,8 @@ export const maaService = {
         tasker_inited: boolean;
         is_running: boolean;
         task_ids: number[];
+        pause_state: PauseState;
+        held_task_ids: number[];
       }>('maa_get_instance_state', { instanceId });
       return {
         connectionStatus: state.connected ? 'Connected' : 'Disconnected',
@@ -508,6 +847,8 @@ export const maaService = {
         isRunning: state.is_running,
         currentTaskId: null,
         taskIds: state.task_ids,
+        pauseState: state.pause_state,
+        heldTaskIds: state.held_task_ids,
       };
     } catch {
       return null;
@@ -628,6 +969,23 @@ export const maaService = {
     await invoke('restart_as_admin');
   },
 
+  /**
+   * 设置调试模式（保留识别使用的截图与标注图）
+   * @param enabled 是否启用
+   */
+  async setDebugMode(enabled: boolean): Promise<boolean> {
+    if (!isTauri()) return false;
+    log.info('设置调试模式:', enabled);
+    try {
+      const result = await invoke<boolean>('maa_set_debug_mode', { enabled });
+      log.info('设置调试模式成功:', enabled);
+      return result;
+    } catch (err) {
+      log.error('设置调试模式失败:', err);
+      throw err;
+    }
+  },
+
   /**
    * 设置保存调试图像
    * @param enabled 是否启用
@@ -10,10 +10,14 @@ import {
   resolveThemeMode,
   unregisterCustomAccent,
 } from '@/themes';
-import type { MxuConfig, RecentlyClosedInstance } from '@/types/config';
+import type { AutoReconnectSettings, MxuConfig, RecentlyClosedInstance } from '@/types/config';
 import {
+  defaultAutoReconnectSettings,
   defaultMirrorChyanSettings,
+  defaultRemoteApiSettings,
   defaultScreenshotFrameRate,
+  defaultTaskRetrySettings,
+  defaultTaskTimeoutSettings,
   defaultWindowSize,
 } from '@/types/config';
 import type {
@@ -23,7 +27,7 @@ import type {
   OptionValue,
   SelectedTask,
 } from '@/types/interface';
-import type { ConnectionStatus, TaskStatus } from '@/types/maa';
+import type { ConnectionStatus, ScreenshotResolution, TaskStatus } from '@/types/maa';
 import { getMxuSpecialTask, isMxuSpecialTask, MXU_SPECIAL_TASKS } from '@/types/specialTasks';
 import { loggers } from '@/utils/logger';
 import { findSwitchCase } from '@/utils/optionHelpers';
@@ -53,6 +57,15 @@ export type {
 // 最近关闭列表最大条目数
 const MAX_RECENTLY_CLOSED = 30;
 
+/** 转换为后端掉线重连选项（退避间隔使用后端默认值） */
+function toWatchdogOptions(settings: AutoReconnectSettings) {
+  return {
+    enabled: settings.enabled,
+    max_attempts: settings.maxAttempts,
+    resume_tasks: settings.resumeTasks,
+  };
+}
+
 export const useAppStore = create<AppState>()(
   subscribeWithSelector((set, get) => ({
     // 主题和语言
@@ -162,6 +175,15 @@ export const useAppStore = create<AppState>()(
         loggers.app.error('设置保存调试图像失败:', err);
       }
     },
+    debugMode: false,
+    setDebugMode: async (enabled) => {
+      set({ debugMode: enabled });
+      try {
+        await maaService.setDebugMode(enabled);
+      } catch (err) {
+        loggers.app.error('设置调试模式失败:', err);
+      }
+    },
 
     // Interface 数据
     projectInterface: null,
@@ -293,9 +315,11 @@ export const useAppStore = create<AppState>()(
               customName: t.customName,
               enabled: t.enabled,
               optionValues: t.optionValues,
+              retry: t.retry,
             })),
             schedulePolicies: instanceToClose.schedulePolicies,
             preAction: instanceToClose.preAction,
+            screenshotResolution: instanceToClose.screenshotResolution,
           };
           // 添加到列表头部，并限制最大条目数
           newRecentlyClosed = [closedRecord, ...state.recentlyClosed].slice(0, MAX_RECENTLY_CLOSED);
@@ -636,6 +660,18 @@ export const useAppStore = create<AppState>()(
         ),
       })),
 
+    setTaskRetryOverride: (instanceId, taskId, retry) =>
+      set((state) => ({
+        instances: state.instances.map((i) =>
+          i.id === instanceId
+            ? {
+                ...i,
+                selectedTasks: i.selectedTasks.map((t) => (t.id === taskId ? { ...t, retry } : t)),
+              }
+            : i,
+        ),
+      })),
+
     // 复制任务
     duplicateTask: (instanceId, taskId) => {
       const state = get();
@@ -764,6 +800,7 @@ export const useAppStore = create<AppState>()(
         })),
         isRunning: false,
         preAction: sourceInstance.preAction ? { ...sourceInstance.preAction } : undefined,
+        screenshotResolution: sourceInstance.screenshotResolution,
       };
 
       // 复制源实例的控制器和资源选择
@@ -928,6 +965,7 @@ export const useAppStore = create<AppState>()(
                 customName: t.customName,
                 enabled: t.enabled,
                 optionValues: t.optionValues,
+                retry: t.retry,
                 expanded: false,
               };
             }
@@ -950,6 +988,7 @@ export const useAppStore = create<AppState>()(
               customName: t.customName,
               enabled: t.enabled,
               optionValues: mergedValues,
+              retry: t.retry,
               expanded: false,
             };
           });
@@ -966,6 +1005,7 @@ export const useAppStore = create<AppState>()(
           isRunning: false,
           schedulePolicies: inst.schedulePolicies,
           preAction: inst.preAction,
+          screenshotResolution: inst.screenshotResolution,
         };
       });
 
@@ -1041,6 +1081,11 @@ export const useAppStore = create<AppState>()(
         autoRunOnLaunch: config.settings.autoRunOnLaunch ?? false,
         autoStartRemovedInstanceName: config.settings.autoStartRemovedInstanceName,
         minimizeToTray: config.settings.minimizeToTray ?? false,
+        remoteApi: config.settings.remoteApi ?? defaultRemoteApiSettings,
+        autoReconnect: config.settings.autoReconnect ?? defaultAutoReconnectSettings,
+        taskTimeout: config.settings.taskTimeout ?? defaultTaskTimeoutSettings,
+        taskRetry: config.settings.taskRetry ?? defaultTaskRetrySettings,
+        adbDeviceMonitor: config.settings.adbDeviceMonitor ?? false,
         onboardingCompleted: config.settings.onboardingCompleted ?? false,
         preActionConnectDelaySec: config.settings.preActionConnectDelaySec ?? 5,
         hotkeys: config.settings.hotkeys ?? {
@@ -1071,6 +1116,39 @@ export const useAppStore = create<AppState>()(
           });
         });
       }
+
+      // 启动本地控制接口
+      const remoteApi = config.settings.remoteApi;
+      if (remoteApi?.enabled && remoteApi.token) {
+        import('@tauri-apps/api/core').then(({ invoke }) => {
+          invoke('remote_api_start', { port: remoteApi.port, token: remoteApi.token }).catch(
+            (err) => {
+              loggers.app.error('启动本地控制接口失败:', err);
+            },
+          );
+        });
+      }
+
+      // 同步掉线自动重连设置到后端
+      const autoReconnect = config.settings.autoReconnect;
+      if (autoReconnect?.enabled) {
+        import('@tauri-apps/api/core').then(({ invoke }) => {
+          invoke('maa_watchdog_configure', {
+            options: toWatchdogOptions(autoReconnect),
+          }).catch((err) => {
+            loggers.app.error('同步掉线重连设置失败:', err);
+          });
+        });
+      }
+
+      // 启动 ADB 设备热插拔监听
+      if (config.settings.adbDeviceMonitor) {
+        import('@tauri-apps/api/core').then(({ invoke }) => {
+          invoke('maa_device_monitor_configure', { options: { enabled: true } }).catch((err) => {
+            loggers.app.error('启动设备监听失败:', err);
+          });
+        });
+      }
     },
 
     // MaaFramework 状态
@@ -1087,6 +1165,7 @@ export const useAppStore = create<AppState>()(
     instanceResourceLoaded: {},
     instanceCurrentTaskId: {},
     instanceTaskStatus: {},
+    instancePauseState: {},
 
     setInstanceConnectionStatus: (instanceId, status) =>
       set((state) => ({
@@ -1120,6 +1199,14 @@ export const useAppStore = create<AppState>()(
         },
       })),
 
+    setInstancePauseState: (instanceId, pauseState) =>
+      set((state) => ({
+        instancePauseState: {
+          ...state.instancePauseState,
+          [instanceId]: pauseState,
+        },
+      })),
+
     // 选中的控制器和资源
     selectedController: {},
     selectedResource: {},
@@ -1199,6 +1286,16 @@ export const useAppStore = create<AppState>()(
         ),
       })),
 
+    setInstanceScreenshotResolution: (
+      instanceId: string,
+      resolution: ScreenshotResolution | undefined,
+    ) =>
+      set((state) => ({
+        instances: state.instances.map((i) =>
+          i.id === instanceId ? { ...i, screenshotResolution: resolution } : i,
+        ),
+      })),
+
     // 设备列表缓存
     cachedAdbDevices: [],
     cachedWin32Windows: [],
@@ -1353,6 +1450,50 @@ export const useAppStore = create<AppState>()(
       }
     },
 
+    // 本地控制接口设置（启用时按新设置重启服务，失败时抛出错误供界面提示）
+    remoteApi: defaultRemoteApiSettings,
+    setRemoteApi: async (settings) => {
+      set({ remoteApi: settings });
+      const { invoke } = await import('@tauri-apps/api/core');
+      if (settings.enabled && settings.token) {
+        await invoke('remote_api_start', { port: settings.port, token: settings.token });
+      } else {
+        await invoke('remote_api_stop');
+      }
+    },
+
+    // 控制器掉线自动重连设置
+    autoReconnect: defaultAutoReconnectSettings,
+    setAutoReconnect: async (settings) => {
+      set({ autoReconnect: settings });
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        await invoke('maa_watchdog_configure', { options: toWatchdogOptions(settings) });
+      } catch (err) {
+        loggers.app.error('设置掉线重连选项失败:', err);
+      }
+    },
+
+    // 任务超时设置（提交任务时传给后端）
+    taskTimeout: defaultTaskTimeoutSettings,
+    setTaskTimeout: (settings) => set({ taskTimeout: settings }),
+
+    // 任务失败重试设置（提交任务时传给后端）
+    taskRetry: defaultTaskRetrySettings,
+    setTaskRetry: (settings) => set({ taskRetry: settings }),
+
+    // ADB 设备热插拔监听
+    adbDeviceMonitor: false,
+    setAdbDeviceMonitor: async (enabled) => {
+      set({ adbDeviceMonitor: enabled });
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        await invoke('maa_device_monitor_configure', { options: { enabled } });
+      } catch (err) {
+        loggers.app.error('设置设备监听失败:', err);
+      }
+    },
+
     // 新用户引导
     onboardingCompleted: false,
     setOnboardingCompleted: (completed) => set({ onboardingCompleted: completed }),
@@ -1442,11 +1583,13 @@ export const useAppStore = create<AppState>()(
           customName: t.customName,
           enabled: t.enabled,
           optionValues: cleanOptionValues(t.optionValues),
+          retry: t.retry,
           expanded: false,
         })),
         isRunning: false,
         schedulePolicies: closedInstance.schedulePolicies,
         preAction: closedInstance.preAction,
+        screenshotResolution: closedInstance.screenshotResolution,
       };
 
       // 恢复选中的控制器和资源状态
@@ -1720,9 +1863,11 @@ function generateConfig(): MxuConfig {
         customName: t.customName,
         enabled: t.enabled,
         optionValues: t.optionValues,
+        retry: t.retry,
       })),
       schedulePolicies: inst.schedulePolicies,
       preAction: inst.preAction,
+      screenshotResolution: inst.screenshotResolution,
     })),
     settings: {
       theme: state.theme,
@@ -1748,6 +1893,11 @@ function generateConfig(): MxuConfig {
       autoRunOnLaunch: state.autoRunOnLaunch,
       autoStartRemovedInstanceName: state.autoStartRemovedInstanceName,
       minimizeToTray: state.minimizeToTray,
+      remoteApi: state.remoteApi,
+      autoReconnect: state.autoReconnect,
+      taskTimeout: state.taskTimeout,
+      taskRetry: state.taskRetry,
+      adbDeviceMonitor: state.adbDeviceMonitor,
       onboardingCompleted: state.onboardingCompleted,
       preActionConnectDelaySec: state.preActionConnectDelaySec,
       hotkeys: state.hotkeys,
@@ -1809,6 +1959,11 @@ useAppStore.subscribe(
     autoRunOnLaunch: state.autoRunOnLaunch,
     autoStartRemovedInstanceName: state.autoStartRemovedInstanceName,
     minimizeToTray: state.minimizeToTray,
+    remoteApi: state.remoteApi,
+    autoReconnect: state.autoReconnect,
+    taskTimeout: state.taskTimeout,
+    taskRetry: state.taskRetry,
+    adbDeviceMonitor: state.adbDeviceMonitor,
     onboardingCompleted: state.onboardingCompleted,
     hotkeys: state.hotkeys,
     recentlyClosed: state.recentlyClosed,
@@ -15,8 +15,20 @@ import type {
   RecentlyClosedInstance,
   ScreenshotFrameRate,
   HotkeySettings,
+  RemoteApiSettings,
+  AutoReconnectSettings,
+  TaskTimeoutSettings,
+  TaskRetrySettings,
+  TaskRetryOverride,
 } from '@/types/config';
-import type { ConnectionStatus, TaskStatus, AdbDevice, Win32Window } from '@/types/maa';
+import type {
+  ConnectionStatus,
+  TaskStatus,
+  PauseState,
+  AdbDevice,
+  Win32Window,
+  ScreenshotResolution,
+} from '@/types/maa';
 import type { AccentColor, CustomAccent } from '@/themes';
 
 /** 单个任务的运行状态 */
@@ -110,6 +122,8 @@ export interface AppState {
   // 调试选项（不落盘，每次启动默认关闭）
   saveDraw: boolean;
   setSaveDraw: (enabled: boolean) => void;
+  debugMode: boolean;
+  setDebugMode: (enabled: boolean) => void;
 
   // Interface 数据
   projectInterface: ProjectInterface | null;
@@ -170,6 +184,11 @@ export interface AppState {
   selectAllTasks: (instanceId: string, enabled: boolean) => void;
   collapseAllTasks: (instanceId: string, expanded: boolean) => void;
   renameTask: (instanceId: string, taskId: string, newName: string) => void;
+  setTaskRetryOverride: (
+    instanceId: string,
+    taskId: string,
+    retry: TaskRetryOverride | undefined,
+  ) => void;
 
   // 任务右键菜单操作
   duplicateTask: (instanceId: string, taskId: string) => void;
@@ -221,11 +240,13 @@ export interface AppState {
   instanceResourceLoaded: Record<string, boolean>;
   instanceCurrentTaskId: Record<string, number | null>;
   instanceTaskStatus: Record<string, TaskStatus | null>;
+  instancePauseState: Record<string, PauseState>;
 
   setInstanceConnectionStatus: (instanceId: string, status: ConnectionStatus) => void;
   setInstanceResourceLoaded: (instanceId: string, loaded: boolean) => void;
   setInstanceCurrentTaskId: (instanceId: string, taskId: number | null) => void;
   setInstanceTaskStatus: (instanceId: string, status: TaskStatus | null) => void;
+  setInstancePauseState: (instanceId: string, state: PauseState) => void;
 
   // 选中的控制器和资源
   selectedController: Record<string, string>;
@@ -237,6 +258,10 @@ export interface AppState {
   setInstanceSavedDevice: (instanceId: string, savedDevice: SavedDeviceInfo) => void;
 
   setInstancePreAction: (instanceId: string, action: ActionConfig | undefined) => void;
+  setInstanceScreenshotResolution: (
+    instanceId: string,
+    resolution: ScreenshotResolution | undefined,
+  ) => void;
 
   // 设备列表缓存
   cachedAdbDevices: AdbDevice[];
@@ -331,6 +356,26 @@ export interface AppState {
   minimizeToTray: boolean;
   setMinimizeToTray: (enabled: boolean) => void;
 
+  // 本地控制接口设置
+  remoteApi: RemoteApiSettings;
+  setRemoteApi: (settings: RemoteApiSettings) => Promise<void>;
+
+  // 控制器掉线自动重连设置
+  autoReconnect: AutoReconnectSettings;
+  setAutoReconnect: (settings: AutoReconnectSettings) => Promise<void>;
+
+  // 任务超时设置
+  taskTimeout: TaskTimeoutSettings;
+  setTaskTimeout: (settings: TaskTimeoutSettings) => void;
+
+  // 任务失败重试设置
+  taskRetry: TaskRetrySettings;
+  setTaskRetry: (settings: TaskRetrySettings) => void;
+
+  // ADB 设备热插拔监听
+  adbDeviceMonitor: boolean;
+  setAdbDeviceMonitor: (enabled: boolean) => Promise<void>;
+
   // 启动后自动执行的实例 ID
   autoStartInstanceId: string | undefined;
   setAutoStartInstanceId: (id: string | undefined) => void;
@@ -2,6 +2,7 @@
 
 import type { OptionValue, ActionConfig } from './interface';
 import type { AccentColor, CustomAccent } from '@/themes/types';
+import type { ScreenshotResolution } from './maa';
 
 // 定时执行策略
 export interface SchedulePolicy {
@@ -19,6 +20,7 @@ export interface SavedTask {
   customName?: string; // 用户自定义名称
   enabled: boolean;
   optionValues: Record<string, OptionValue>;
+  retry?: TaskRetryOverride; // 单独设置的失败重试（未设置时使用全局设置）
 }
 
 // 保存的设备信息
@@ -29,6 +31,8 @@ export interface SavedDeviceInfo {
   windowName?: string;
   // PlayCover：保存地址
   playcoverAddress?: string;
+  // ImageFolder：保存图片目录
+  imageFolderPath?: string;
 }
 
 // 保存的实例配置
@@ -46,6 +50,8 @@ export interface SavedInstance {
   // 定时执行策略列表
   schedulePolicies?: SchedulePolicy[];
   preAction?: ActionConfig;
+  // 截图分辨率（未设置时使用控制器声明的 display_* 字段）
+  screenshotResolution?: ScreenshotResolution;
 }
 
 // 窗口大小配置
@@ -73,6 +79,7 @@ export interface RecentlyClosedInstance {
   tasks: SavedTask[]; // 保存的任务配置
   schedulePolicies?: SchedulePolicy[]; // 定时执行策略
   preAction?: ActionConfig;
+  screenshotResolution?: ScreenshotResolution;
 }
 
 // MirrorChyan 更新频道
@@ -103,6 +110,57 @@ export interface HotkeySettings {
   globalEnabled?: boolean;
 }
 
+// 本地控制接口设置（HTTP / WebSocket，仅监听 127.0.0.1）
+export interface RemoteApiSettings {
+  enabled: boolean;
+  port: number;
+  /** 访问令牌，请求需携带 Authorization: Bearer <token> 或 ?token=<token> */
+  token: string;
+}
+
+// 控制器掉线自动重连设置
+export interface AutoReconnectSettings {
+  enabled: boolean;
+  /** 最多重连次数（每次间隔按指数退避增长） */
+  maxAttempts: number;
+  /** 重连成功后是否重新提交未完成的任务 */
+  resumeTasks: boolean;
+}
+
+// 任务超时设置
+export interface TaskTimeoutSettings {
+  enabled: boolean;
+  /** 单个任务的超时时间（分钟，0 表示不限制） */
+  taskTimeoutMinutes: number;
+  /** 整次运行的超时时间（分钟，0 表示不限制） */
+  runTimeoutMinutes: number;
+  /** 单个任务超时后是否继续执行后续任务 */
+  continueOnTimeout: boolean;
+}
+
+// 任务失败重试设置
+export interface TaskRetrySettings {
+  enabled: boolean;
+  /** 最多重试次数 */
+  attempts: number;
+  /** 重试前的等待时间（秒） */
+  delaySeconds: number;
+  /** 任务超时后是否也重试 */
+  retryOnTimeout: boolean;
+}
+
+// 单个任务的失败重试设置（覆盖全局设置）
+export interface TaskRetryOverride {
+  /** 最多重试次数（0 表示该任务不重试） */
+  attempts: number;
+  /** 重试前的等待时间（秒） */
+  delaySeconds: number;
+  /** 任务超时后是否也重试 */
+  retryOnTimeout: boolean;
+  /** 重试前重新启动的应用（ADB 为包名或 Activity，为空时不重启） */
+  restartApp?: string;
+}
+
 // 应用设置
 export interface AppSettings {
   theme: 'light' | 'dark' | 'system';
@@ -129,6 +187,11 @@ export interface AppSettings {
   hotkeys?: HotkeySettings; // 快捷键设置
   tcpCompatMode?: boolean; // 通信兼容模式，强制使用 TCP 而非 IPC
   minimizeToTray?: boolean; // 关闭时最小化到托盘（默认 false）
+  remoteApi?: RemoteApiSettings; // 本地控制接口
+  autoReconnect?: AutoReconnectSettings; // 控制器掉线自动重连
+  taskTimeout?: TaskTimeoutSettings; // 任务超时
+  taskRetry?: TaskRetrySettings; // 任务失败重试
+  adbDeviceMonitor?: boolean; // 定期搜索 ADB 设备，设备插拔时更新列表并自动连接（默认 false）
   autoStartInstanceId?: string; // 启动后自动执行的实例 ID（为空或 undefined 表示不自动执行）
   autoRunOnLaunch?: boolean; // 非开机自启动的手动启动场景下，是否也自动执行选定的实例（默认 false）
   autoStartRemovedInstanceName?: string; // 被删除的自动执行配置名称（用于提示用户）
@@ -176,6 +239,36 @@ export const defaultHotkeySettings: HotkeySettings = {
   stopTasks: 'F11',
 };
 
+// 默认本地控制接口设置
+export const defaultRemoteApiSettings: RemoteApiSettings = {
+  enabled: false,
+  port: 8765,
+  token: '',
+};
+
+// 默认掉线自动重连设置
+export const defaultAutoReconnectSettings: AutoReconnectSettings = {
+  enabled: false,
+  maxAttempts: 5,
+  resumeTasks: true,
+};
+
+// 默认任务超时设置
+export const defaultTaskTimeoutSettings: TaskTimeoutSettings = {
+  enabled: false,
+  taskTimeoutMinutes: 30,
+  runTimeoutMinutes: 0,
+  continueOnTimeout: true,
+};
+
+// 默认任务失败重试设置
+export const defaultTaskRetrySettings: TaskRetrySettings = {
+  enabled: false,
+  attempts: 1,
+  delaySeconds: 5,
+  retryOnTimeout: false,
+};
+
 // 默认配置
 export const defaultConfig: MxuConfig = {
   version: '1.0',
@@ -1,5 +1,8 @@
 // MaaFramework ProjectInterface V2 协议类型定义
 
+import type { ScreenshotResolution } from './maa';
+import type { TaskRetryOverride } from './config';
+
 export interface ProjectInterface {
   interface_version: 2;
   languages?: Record<string, string>;
@@ -47,7 +50,7 @@ export function normalizeAgentConfigs(
   return Array.isArray(agent) ? agent : [agent];
 }
 
-export type ControllerType = 'Adb' | 'Win32' | 'PlayCover' | 'Gamepad';
+export type ControllerType = 'Adb' | 'Win32' | 'PlayCover' | 'Gamepad' | 'ImageFolder';
 
 export interface ControllerItem {
   name: string;
@@ -192,6 +195,7 @@ export interface SelectedTask {
   customName?: string; // 用户自定义名称
   enabled: boolean;
   optionValues: Record<string, OptionValue>;
+  retry?: TaskRetryOverride; // 单独设置的失败重试（未设置时使用全局设置）
   expanded: boolean;
 }
 
@@ -218,6 +222,7 @@ export interface SavedDeviceInfo {
   adbDeviceName?: string;
   windowName?: string;
   playcoverAddress?: string;
+  imageFolderPath?: string;
 }
 
 // 定时执行策略
@@ -227,6 +232,20 @@ export interface SchedulePolicy {
   enabled: boolean; // 是否启用
   weekdays: number[]; // 重复日期 (0-6, 0=周日)
   hours: number[]; // 开始时间 (0-23)
+  expression?: string; // 定时表达式（cron / @every / @at），设置后代替 weekdays 与 hours
+  exclusions?: string[]; // 排除时段，如 "wed 04:00 ~ 10:00"
+}
+
+// 后端调度器事件（schedule-fired / schedule-skipped / schedule-finished）
+export interface ScheduleEvent {
+  instance_id: string;
+  instance_name: string;
+  policy_id: string;
+  policy_name: string;
+  fire_time: string; // RFC 3339
+  reason?: 'already_running' | 'missed';
+  success?: boolean;
+  error?: string;
 }
 
 // pre-action config
@@ -254,6 +273,8 @@ export interface Instance {
   // 定时执行策略列表
   schedulePolicies?: SchedulePolicy[];
   preAction?: ActionConfig;
+  // 截图分辨率（未设置时使用控制器声明的 display_* 字段）
+  screenshotResolution?: ScreenshotResolution;
 }
 
 /** v2.3.0: 预设中的任务配置 */
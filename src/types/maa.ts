@@ -17,8 +17,19 @@ export interface Win32Window {
   window_name: string;
 }
 
+/** 截图目标分辨率（短边 / 长边缩放到指定像素，或使用原始尺寸） */
+export type ScreenshotResolution =
+  | { mode: 'short_side'; value: number }
+  | { mode: 'long_side'; value: number }
+  | { mode: 'raw' };
+
+/** 各控制器共有的配置（省略截图分辨率时默认短边 720） */
+interface ControllerConfigBase {
+  screenshot_resolution?: ScreenshotResolution;
+}
+
 /** ADB 控制器配置 */
-export interface AdbControllerConfig {
+export interface AdbControllerConfig extends ControllerConfigBase {
   type: 'Adb';
   adb_path: string;
   address: string;
@@ -28,7 +39,7 @@ export interface AdbControllerConfig {
 }
 
 /** Win32 控制器配置 */
-export interface Win32ControllerConfig {
+export interface Win32ControllerConfig extends ControllerConfigBase {
   type: 'Win32';
   handle: number;
   screencap_method: number;
@@ -37,14 +48,20 @@ export interface Win32ControllerConfig {
 }
 
 /** PlayCover 控制器配置 (macOS) */
-export interface PlayCoverControllerConfig {
+export interface PlayCoverControllerConfig extends ControllerConfigBase {
   type: 'PlayCover';
   address: string;
   uuid?: string;
 }
 
+/** 图片目录调试控制器配置（截图依次返回目录或会话包中的图片，输入只记录日志） */
+export interface ImageFolderControllerConfig extends ControllerConfigBase {
+  type: 'ImageFolder';
+  path: string;
+}
+
 /** Gamepad 控制器配置 */
-export interface GamepadControllerConfig {
+export interface GamepadControllerConfig extends ControllerConfigBase {
   type: 'Gamepad';
   handle: number;
 }
@@ -54,14 +71,122 @@ export type ControllerConfig =
   | AdbControllerConfig
   | Win32ControllerConfig
   | PlayCoverControllerConfig
-  | GamepadControllerConfig;
+  | GamepadControllerConfig
+  | ImageFolderControllerConfig;
+
+/** 截图输出格式（webp 为无损编码） */
+export type ScreenshotFormat = 'png' | 'jpeg' | 'webp';
+
+/** 截图编码选项（省略时返回原始尺寸的 PNG） */
+export interface ScreenshotOptions {
+  format?: ScreenshotFormat;
+  /** JPEG 质量（1-100，默认 80） */
+  quality?: number;
+  /** 最大宽度（超过时按比例缩小） */
+  max_width?: number;
+}
+
+/** 控制器输入动作（坐标为截图坐标系） */
+export type ControllerInput =
+  | { type: 'Click'; x: number; y: number }
+  | { type: 'Swipe'; x1: number; y1: number; x2: number; y2: number; duration: number }
+  | { type: 'ClickKey'; keycode: number }
+  | { type: 'InputText'; text: string }
+  | { type: 'StartApp'; intent: string }
+  | { type: 'StopApp'; intent: string };
 
 /** 连接状态 */
 export type ConnectionStatus = 'Disconnected' | 'Connecting' | 'Connected' | { Failed: string };
 
+/** 最近画面的导出格式 */
+export type FrameExportFormat = 'apng' | 'gif' | 'png_sequence';
+
+/** 重新提交的任务（原 task_id → 新 task_id） */
+export interface ResumedTask {
+  previous_task_id: number;
+  /** 提交失败时为 null */
+  task_id: number | null;
+  /** 提交失败的原因 */
+  error?: string;
+}
+
+/** 控制器掉线重连事件（maa-watchdog） */
+export interface WatchdogEvent {
+  instance_id: string;
+  stage: 'disconnected' | 'reconnecting' | 'attempt_failed' | 'reconnected' | 'gave_up';
+  /** 第几次重连（从 1 开始，disconnected 时为 0） */
+  attempt: number;
+  max_attempts: number;
+  error?: string;
+  /** 距下一次重连的等待时间（毫秒） */
+  retry_in_ms?: number;
+  /** 掉线时待恢复的任务数 */
+  pending_tasks?: number;
+  /** 重新提交的任务（掉线前的 task_id → 新 task_id） */
+  resumed_tasks?: ResumedTask[];
+}
+
+/** 任务队列暂停状态：pausing 表示等待当前任务结束 */
+export type PauseState = 'none' | 'pausing' | 'paused';
+
 /** 任务状态 */
 export type TaskStatus = 'Pending' | 'Running' | 'Succeeded' | 'Failed';
 
+/** 开始与结束时间（毫秒时间戳，未收到对应回调时为 null） */
+interface Timing {
+  started_at: number | null;
+  ended_at: number | null;
+}
+
+/** 识别结果 */
+export interface RecognitionDetail extends Timing {
+  reco_id: number;
+  name: string;
+  /** 识别算法（TemplateMatch、OCR 等） */
+  algorithm: string;
+  hit: boolean;
+  /** 命中区域 [x, y, w, h] */
+  box_rect: [number, number, number, number];
+  /** 算法相关的原始结果 */
+  detail: unknown;
+  /** And / Or 组合识别的子识别结果 */
+  sub_details: RecognitionDetail[];
+}
+
+/** 动作结果 */
+export interface ActionDetail extends Timing {
+  action_id: number;
+  name: string;
+  /** 动作类型（Click、Swipe 等） */
+  action: string;
+  success: boolean;
+  box_rect: [number, number, number, number];
+  detail: unknown;
+}
+
+/** 已执行的节点 */
+export interface NodeDetail extends Timing {
+  node_id: number;
+  name: string;
+  completed: boolean;
+  recognition: RecognitionDetail | null;
+  action: ActionDetail | null;
+}
+
+/** 任务详情（maa_get_task_detail） */
+export interface TaskDetail extends Timing {
+  task_id: number;
+  entry: string;
+  status: TaskStatus;
+  nodes: NodeDetail[];
+}
+
+/** 识别使用的截图与标注图（data URL，仅在调试模式下可用） */
+export interface RecognitionImages {
+  raw: string | null;
+  draws: string[];
+}
+
 /** MaaFramework 初始化状态 */
 export interface MaaInitState {
   initialized: boolean;
@@ -77,6 +202,9 @@ export interface InstanceRuntimeInfo {
   currentTaskId: number | null;
   /** 当前运行的任务 ID 列表 */
   taskIds: number[];
+  pauseState: PauseState;
+  /** 暂停时保留、恢复后重新提交的任务 ID */
+  heldTaskIds: number[];
 }
 
 /** Win32 截图方法 */
@@ -160,4 +288,140 @@ export interface AgentConfig {
 export interface TaskConfig {
   entry: string;
   pipeline_override: string;
+  /** 任务超时时间（毫秒），超时后停止该任务 */
+  timeout_ms?: number;
+  /** 失败或超时后的重试策略 */
+  retry?: RetryPolicy;
+}
+
+/** 重试条件 */
+export type RetryCondition = 'failed' | 'timeout';
+
+/** 任务重试策略 */
+export interface RetryPolicy {
+  /** 最多重试次数 */
+  attempts: number;
+  /** 重新提交前的等待时间（毫秒，在恢复动作之后） */
+  delay_ms: number;
+  /** 触发重试的条件 */
+  on: RetryCondition[];
+  /** 重新提交前依次执行的控制器动作（如停止并重新启动应用） */
+  recovery?: ControllerInput[];
+}
+
+/** 运行选项 */
+export interface RunOptions {
+  /** 整次运行的超时时间（毫秒，从提交任务开始计时），超时后停止所有任务 */
+  timeout_ms?: number;
+  /** 单个任务超时后是否继续执行后续任务 */
+  continue_on_timeout: boolean;
+}
+
+/** 任务超时事件（maa-task-timeout） */
+export interface TaskTimeoutEvent {
+  instance_id: string;
+  /** timed_out：即将停止任务；stopped：任务已停止（继续执行时已重新提交后续任务） */
+  stage: 'timed_out' | 'stopped';
+  scope: 'task' | 'run';
+  /** 超时时正在执行的任务 */
+  task_id: number;
+  entry: string;
+  /** 已耗时（毫秒） */
+  elapsed_ms: number;
+  continue_run: boolean;
+  /** 重新提交的后续任务（仅 stopped） */
+  resumed_tasks?: ResumedTask[];
+}
+
+/** 任务重试事件（maa-task-retry） */
+export interface TaskRetryEvent {
+  instance_id: string;
+  /**
+   * retrying：开始重试；resumed：已重新提交失败的任务及其后的任务；
+   * aborted：未能重新提交，运行结束；exhausted：重试次数已用完，任务按失败处理
+   */
+  stage: 'retrying' | 'resumed' | 'aborted' | 'exhausted';
+  condition: RetryCondition;
+  /** 失败的任务 */
+  task_id: number;
+  entry: string;
+  /** 第几次重试（exhausted 为已重试次数） */
+  attempt: number;
+  max_attempts: number;
+  /** 重新提交的任务（resumed；aborted 时为提交失败的任务） */
+  resumed_tasks?: ResumedTask[];
+}
+
+/** 运行中队列里的任务 */
+export interface QueuedTask {
+  /** task_id（运行中插入、尚未提交的任务为负数的临时 ID） */
+  task_id: number;
+  entry: string;
+}
+
+/** 实例的任务队列 */
+export interface TaskQueueState {
+  /** 正在执行的任务（已暂停或未在运行时为 null） */
+  running: QueuedTask | null;
+  /** 等待执行的任务（按执行顺序；已暂停时为保留的任务） */
+  pending: QueuedTask[];
+  /** 是否有等待当前任务结束后生效的调整 */
+  deferred: boolean;
+}
+
+/** 任务队列调整生效事件（maa-task-queue） */
+export interface TaskQueueEvent {
+  instance_id: string;
+  /**
+   * applying：调整前正在执行的任务已结束，正在停止任务（之后被中止任务的回调应忽略）；
+   * applied：已按调整后的顺序重新提交剩余任务（没有剩余任务时运行结束）
+   */
+  stage: 'applying' | 'applied';
+  /** 调整前正在执行的任务 */
+  task_id: number;
+  /** 重新提交的任务（仅 applied） */
+  resumed_tasks?: ResumedTask[];
+}
+
+/** Pipeline 覆盖中的一处错误 */
+export interface OverrideError {
+  /** 出错位置（如 `MyNode.timeout`，数组形式的覆盖以 `[序号]` 开头；整体错误为空） */
+  path: string;
+  message: string;
+}
+
+/** 单个任务的校验结果 */
+export interface TaskValidation {
+  /** 任务在提交列表中的序号 */
+  index: number;
+  entry: string;
+  /** 为空表示校验通过 */
+  errors: OverrideError[];
+  /** 不影响提交的问题（常用字段的类型与预期不符） */
+  warnings: OverrideError[];
+}
+
+/** 启动任务结果 */
+export interface StartTasksResult {
+  /** 成功提交的任务 ID（按任务列表顺序） */
+  task_ids: number[];
+  /** 未提交的任务（校验失败或提交失败）及原因 */
+  rejected: TaskValidation[];
+  /** 已提交但校验有警告的任务 */
+  warnings: TaskValidation[];
+}
+
+/** Tauri 命令错误（对应 Rust 端 MxuError） */
+export interface MxuError {
+  /** 稳定错误码，如 INSTANCE_NOT_FOUND、HTTP_STATUS、DOWNLOAD_CANCELLED */
+  code: string;
+  /** 出错的子系统，如 controller、network、update */
+  subsystem: string;
+  message: string;
+  context?: {
+    path?: string;
+    instance_id?: string;
+    http_status?: number;
+    url?: string;
+  };
 }
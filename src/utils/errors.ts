@@ -0,0 +1,28 @@
+/**
+ * 错误处理工具函数
+ * Tauri 命令失败时 reject 的是结构化的 MxuError 对象，而非字符串
+ */
+
+import type { MxuError } from '@/types/maa';
+
+/** 判断是否为 Tauri 命令返回的 MxuError */
+export function isMxuError(err: unknown): err is MxuError {
+  return (
+    typeof err === 'object' &&
+    err !== null &&
+    typeof (err as MxuError).code === 'string' &&
+    typeof (err as MxuError).message === 'string'
+  );
+}
+
+/** 判断错误是否为指定错误码 */
+export function hasErrorCode(err: unknown, code: string): boolean {
+  return isMxuError(err) && err.code === code;
+}
+
+/** 提取可显示的错误消息 */
+export function getErrorMessage(err: unknown): string {
+  if (err instanceof Error) return err.message;
+  if (isMxuError(err)) return err.message;
+  return String(err);
+}
@@ -5,3 +5,7 @@ export * from './jsonc';
 export * from './optionHelpers';
 export * from './resourcePath';
 export * from './paths';
+export * from './errors';
+export * from './screenshotResolution';
+export * from './taskTimeout';
+export * from './taskRetry';
@@ -10,6 +10,7 @@ import type {
   OptionValue,
   OptionDefinition,
 } from '@/types/interface';
+import type { OverrideError } from '@/types/maa';
 import { isMxuSpecialTask, getMxuSpecialTask } from '@/types/specialTasks';
 import { loggers } from './logger';
 import { findSwitchCase } from './optionHelpers';
@@ -89,7 +90,9 @@ const collectOptionOverrides = (
         overrideStr = overrideStr.replace(new RegExp(`"${escapedPlaceholder}"`, 'g'), boolVal);
         overrideStr = overrideStr.replace(placeholderRegex, boolVal);
       } else {
-        overrideStr = overrideStr.replace(placeholderRegex, inputVal || '');
+        // 转义为合法的 JSON 字符串内容（如 Windows 路径中的反斜杠）
+        const escaped = JSON.stringify(inputVal || '').slice(1, -1);
+        overrideStr = overrideStr.replace(placeholderRegex, () => escaped);
       }
     }
 
@@ -189,6 +192,12 @@ export const generateTaskPipelineOverride = (
   return JSON.stringify(overrides);
 };
 
+/**
+ * 将任务校验错误格式化为单行文本（用于日志显示）
+ */
+export const formatOverrideErrors = (errors: OverrideError[]): string =>
+  errors.map((e) => (e.path ? `${e.path}: ${e.message}` : e.message)).join('; ');
+
 /**
  * 深合并多个对象（递归合并嵌套对象，非对象值后者覆盖前者）
  * 用于在前端侧合并多个 pipeline_override，避免 MaaFramework 的浅替换导致字段丢失
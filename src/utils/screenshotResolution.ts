@@ -0,0 +1,68 @@
+/**
+ * 截图分辨率工具
+ * 根据 interface 控制器的 display_* 字段与实例设置确定连接控制器时使用的截图分辨率
+ */
+
+import type { ControllerItem } from '@/types/interface';
+import type { ControllerConfig, ScreenshotResolution } from '@/types/maa';
+
+/** 控制器未声明时使用的分辨率（与后端默认值一致） */
+export const DEFAULT_SCREENSHOT_RESOLUTION: ScreenshotResolution = {
+  mode: 'short_side',
+  value: 720,
+};
+
+/** 截图面板中可切换的分辨率预设 */
+export const SCREENSHOT_RESOLUTION_PRESETS: ScreenshotResolution[] = [
+  { mode: 'short_side', value: 720 },
+  { mode: 'short_side', value: 1080 },
+  { mode: 'long_side', value: 1280 },
+  { mode: 'long_side', value: 1920 },
+  { mode: 'raw' },
+];
+
+/**
+ * 读取 interface 控制器声明的截图分辨率
+ * 优先级：display_raw > display_long_side > display_short_side > 默认短边 720
+ */
+export function getControllerScreenshotResolution(
+  controller: ControllerItem | undefined,
+): ScreenshotResolution {
+  if (controller?.display_raw) {
+    return { mode: 'raw' };
+  }
+  if (controller?.display_long_side) {
+    return { mode: 'long_side', value: controller.display_long_side };
+  }
+  if (controller?.display_short_side) {
+    return { mode: 'short_side', value: controller.display_short_side };
+  }
+  return DEFAULT_SCREENSHOT_RESOLUTION;
+}
+
+/** 确定实际使用的截图分辨率（实例设置优先于控制器声明） */
+export function resolveScreenshotResolution(
+  controller: ControllerItem | undefined,
+  override?: ScreenshotResolution,
+): ScreenshotResolution {
+  return override ?? getControllerScreenshotResolution(controller);
+}
+
+/** 为控制器配置填入截图分辨率 */
+export function withScreenshotResolution(
+  config: ControllerConfig,
+  controller: ControllerItem | undefined,
+  override?: ScreenshotResolution,
+): ControllerConfig {
+  return { ...config, screenshot_resolution: resolveScreenshotResolution(controller, override) };
+}
+
+/** 比较两个截图分辨率是否相同 */
+export function isSameScreenshotResolution(
+  a: ScreenshotResolution | undefined,
+  b: ScreenshotResolution | undefined,
+): boolean {
+  if (!a || !b) return a === b;
+  if (a.mode === 'raw' || b.mode === 'raw') return a.mode === b.mode;
+  return a.mode === b.mode && a.value === b.value;
+}
@@ -0,0 +1,32 @@
+/**
+ * 任务失败重试工具
+ * 将设置转换为提交任务时使用的重试策略
+ */
+
+import type { TaskRetryOverride, TaskRetrySettings } from '@/types/config';
+import type { ControllerInput, RetryCondition, RetryPolicy } from '@/types/maa';
+
+/**
+ * 任务的重试策略：任务单独设置了重试时以其为准，否则使用全局设置（未启用时不重试）
+ */
+export function getTaskRetryPolicy(
+  settings: TaskRetrySettings,
+  override?: TaskRetryOverride,
+): RetryPolicy | undefined {
+  const retry = override ?? (settings.enabled ? settings : undefined);
+  if (!retry || retry.attempts <= 0) return undefined;
+  const on: RetryCondition[] = retry.retryOnTimeout ? ['failed', 'timeout'] : ['failed'];
+  const restartApp = override?.restartApp?.trim();
+  const recovery: ControllerInput[] = restartApp
+    ? [
+        { type: 'StopApp', intent: restartApp },
+        { type: 'StartApp', intent: restartApp },
+      ]
+    : [];
+  return {
+    attempts: retry.attempts,
+    delay_ms: retry.delaySeconds * 1000,
+    on,
+    recovery,
+  };
+}
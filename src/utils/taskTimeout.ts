@@ -0,0 +1,24 @@
+/**
+ * 任务超时工具
+ * 将设置中的超时时间（分钟）转换为提交任务时使用的毫秒数与运行选项
+ */
+
+import type { TaskTimeoutSettings } from '@/types/config';
+import type { RunOptions } from '@/types/maa';
+
+function minutesToMs(settings: TaskTimeoutSettings, minutes: number): number | undefined {
+  return settings.enabled && minutes > 0 ? minutes * 60_000 : undefined;
+}
+
+/** 单个任务的超时时间（毫秒，未启用或为 0 时不限制） */
+export function getTaskTimeoutMs(settings: TaskTimeoutSettings): number | undefined {
+  return minutesToMs(settings, settings.taskTimeoutMinutes);
+}
+
+/** 提交任务时的运行选项 */
+export function toRunOptions(settings: TaskTimeoutSettings): RunOptions {
+  return {
+    timeout_ms: minutesToMs(settings, settings.runTimeoutMinutes),
+    continue_on_timeout: settings.continueOnTimeout,
+  };
+}
@@ -0,0 +1,118 @@
+import { useCallback } from 'react';
+import { useAppStore } from '@/stores/appStore';
+import { maaService } from '@/services/maaService';
+import { getMxuSpecialTask } from '@/types/specialTasks';
+import type { SelectedTask } from '@/types/interface';
+import type { TaskConfig, TaskQueueState } from '@/types/maa';
+import { generateTaskPipelineOverride } from '@/utils/pipelineOverride';
+import { getTaskTimeoutMs } from '@/utils/taskTimeout';
+import { getTaskRetryPolicy } from '@/utils/taskRetry';
+import { loggers } from '@/utils/logger';
+
+const log = loggers.task;
+
+/**
+ * 运行中调整任务队列
+ * 追加的任务直接提交；插入、取消与调整顺序在当前任务结束后生效，期间插入的任务使用负数的临时
+ * task_id，生效后由 Toolbar 按 maa-task-queue 事件中的对应关系继续跟踪
+ */
+export function useTaskQueue(instanceId: string) {
+  /** 按调整后的队列更新前端跟踪的任务（已暂停时恢复后再跟踪） */
+  const syncQueue = useCallback(
+    (queue: TaskQueueState) => {
+      if (!queue.running) return;
+      const { setPendingTaskIds, setCurrentTaskIndex } = useAppStore.getState();
+      setPendingTaskIds(instanceId, [
+        queue.running.task_id,
+        ...queue.pending.map((t) => t.task_id),
+      ]);
+      setCurrentTaskIndex(instanceId, 0);
+    },
+    [instanceId],
+  );
+
+  /** 等待执行的任务中对应该任务的 task_id */
+  const findQueuedTaskId = useCallback(
+    async (selectedTaskId: string) => {
+      const queue = await maaService.getTaskQueue(instanceId);
+      const { findSelectedTaskIdByMaaTaskId } = useAppStore.getState();
+      const queued = queue.pending.find(
+        (t) => findSelectedTaskIdByMaaTaskId(instanceId, t.task_id) === selectedTaskId,
+      );
+      return queued?.task_id ?? null;
+    },
+    [instanceId],
+  );
+
+  /** 将任务加入队列（next 为 true 时下一个执行，否则追加到末尾） */
+  const queueTask = useCallback(
+    async (task: SelectedTask, displayName: string, next: boolean) => {
+      const store = useAppStore.getState();
+      const { projectInterface, selectedController, selectedResource } = store;
+      const taskDef =
+        getMxuSpecialTask(task.taskName)?.taskDef ||
+        projectInterface?.task.find((t) => t.name === task.taskName);
+      if (!taskDef) return;
+
+      const config: TaskConfig = {
+        entry: taskDef.entry,
+        pipeline_override: generateTaskPipelineOverride(
+          task,
+          projectInterface,
+          selectedController[instanceId] || projectInterface?.controller[0]?.name,
+          selectedResource[instanceId] || projectInterface?.resource[0]?.name,
+        ),
+        timeout_ms: getTaskTimeoutMs(store.taskTimeout),
+        retry: getTaskRetryPolicy(store.taskRetry, task.retry),
+      };
+      try {
+        const queue = next
+          ? await maaService.insertTask(instanceId, 0, config)
+          : await maaService.appendTask(instanceId, config);
+        const queued = next ? queue.pending[0] : queue.pending[queue.pending.length - 1];
+        if (!queued) return;
+
+        store.registerMaaTaskMapping(instanceId, queued.task_id, task.id);
+        store.registerTaskIdName(queued.task_id, displayName);
+        store.registerEntryTaskName(config.entry, displayName);
+        store.setTaskRunStatus(instanceId, task.id, 'pending');
+        syncQueue(queue);
+      } catch (err) {
+        log.error('加入任务队列失败:', err);
+      }
+    },
+    [instanceId, syncQueue],
+  );
+
+  /** 取消尚未执行的任务 */
+  const cancelQueuedTask = useCallback(
+    async (selectedTaskId: string) => {
+      try {
+        const taskId = await findQueuedTaskId(selectedTaskId);
+        if (taskId === null) return;
+        const queue = await maaService.cancelTask(instanceId, taskId);
+        useAppStore.getState().setTaskRunStatus(instanceId, selectedTaskId, 'idle');
+        syncQueue(queue);
+      } catch (err) {
+        log.error('取消任务失败:', err);
+      }
+    },
+    [instanceId, findQueuedTaskId, syncQueue],
+  );
+
+  /** 将尚未执行的任务调整为下一个执行 */
+  const runQueuedTaskNext = useCallback(
+    async (selectedTaskId: string) => {
+      try {
+        const taskId = await findQueuedTaskId(selectedTaskId);
+        if (taskId === null) return;
+        syncQueue(await maaService.moveTask(instanceId, taskId, 0));
+      } catch (err) {
+        log.error('调整任务顺序失败:', err);
+      }
+    },
+    [instanceId, findQueuedTaskId, syncQueue],
+  );
+
+  return { queueTask, cancelQueuedTask, runQueuedTaskNext };
+}